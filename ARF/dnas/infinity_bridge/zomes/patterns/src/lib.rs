@@ -220,6 +220,85 @@ pub struct MixingRequest {
     pub operation: String,
 }
 
+// ============================================================================
+// MODALITY RELATIONSHIP GRAPH
+// ============================================================================
+
+/// A kind of physical relationship that can hold between two sensing
+/// modalities. Each [`ModalityEdge`] declares which kinds apply to its pair,
+/// so adding a new relationship or modality is a data change, not a new
+/// function.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RelationshipKind {
+    /// The modalities share a common physical cause or mechanism.
+    Causal,
+    /// Combining the modalities reveals information not present in either alone.
+    Complementary,
+    /// One modality can predict the other with reasonable accuracy (lead-lag).
+    Predictive,
+    /// The relationship between the modalities is stable over time (physical law).
+    TemporallyStable,
+    /// The joint distribution compresses better than the modalities separately.
+    Compressible,
+}
+
+/// An edge in the modality relationship graph: an undirected pair of
+/// modalities plus the set of relationship kinds known to hold between them.
+#[derive(Clone, Copy, Debug)]
+pub struct ModalityEdge {
+    pub a: &'static str,
+    pub b: &'static str,
+    pub kinds: &'static [RelationshipKind],
+}
+
+use RelationshipKind::*;
+
+/// The built-in modality relationship graph. This is the single source of
+/// truth for `check_*` below instead of five separate, overlapping pair
+/// tables — extending coverage (a new modality, a new relationship) means
+/// adding or editing one row here.
+pub const MODALITY_GRAPH: &[ModalityEdge] = &[
+    ModalityEdge { a: "vibration", b: "acoustic", kinds: &[Causal, Complementary, Predictive, TemporallyStable, Compressible] },
+    ModalityEdge { a: "electromagnetic", b: "magnetic", kinds: &[Causal] },
+    ModalityEdge { a: "temperature", b: "pressure", kinds: &[Causal, Complementary, Predictive, TemporallyStable] },
+    ModalityEdge { a: "electrical", b: "magnetic", kinds: &[Causal, Complementary, Predictive, TemporallyStable, Compressible] },
+    ModalityEdge { a: "seismic", b: "acoustic", kinds: &[Causal, Complementary, Predictive, TemporallyStable, Compressible] },
+    ModalityEdge { a: "optical", b: "temperature", kinds: &[Causal] },
+    ModalityEdge { a: "chemical", b: "temperature", kinds: &[Causal, Predictive] },
+    ModalityEdge { a: "radiation", b: "ionization", kinds: &[Causal] },
+    ModalityEdge { a: "optical", b: "infrared", kinds: &[Complementary] },
+    ModalityEdge { a: "chemical", b: "optical", kinds: &[Complementary] },
+    ModalityEdge { a: "radio", b: "optical", kinds: &[Complementary] },
+    ModalityEdge { a: "ultrasonic", b: "acoustic", kinds: &[Complementary, TemporallyStable] },
+    ModalityEdge { a: "electromagnetic", b: "acoustic", kinds: &[Complementary] },
+    ModalityEdge { a: "vibration", b: "temperature", kinds: &[Complementary] },
+    ModalityEdge { a: "pressure", b: "acoustic", kinds: &[Predictive, Compressible] },
+    ModalityEdge { a: "strain", b: "vibration", kinds: &[Predictive, Compressible] },
+    ModalityEdge { a: "electromagnetic", b: "ionization", kinds: &[Predictive] },
+    ModalityEdge { a: "optical", b: "chemical", kinds: &[Predictive] },
+    ModalityEdge { a: "temperature", b: "infrared", kinds: &[TemporallyStable] },
+    ModalityEdge { a: "optical", b: "electromagnetic", kinds: &[TemporallyStable, Compressible] },
+    ModalityEdge { a: "strain", b: "stress", kinds: &[TemporallyStable] },
+    ModalityEdge { a: "chemical", b: "spectroscopic", kinds: &[TemporallyStable, Compressible] },
+    ModalityEdge { a: "magnetic", b: "electromagnetic", kinds: &[TemporallyStable] },
+    ModalityEdge { a: "temperature", b: "infrared", kinds: &[Compressible] },
+    ModalityEdge { a: "radio", b: "electromagnetic", kinds: &[Compressible] },
+    ModalityEdge { a: "ultrasonic", b: "vibration", kinds: &[Compressible] },
+];
+
+/// Does any edge of `kind` connect a modality named in `signal_a` with one
+/// named in `signal_b`? Modality names are matched by substring, matching
+/// the original heuristic (signal identifiers like "acoustic_sensor_1"
+/// still match the modality "acoustic").
+fn has_relationship(signal_a: &str, signal_b: &str, kind: RelationshipKind) -> bool {
+    MODALITY_GRAPH.iter().any(|edge| {
+        edge.kinds.contains(&kind) && (
+            (signal_a.contains(edge.a) && signal_b.contains(edge.b)) ||
+            (signal_a.contains(edge.b) && signal_b.contains(edge.a))
+        )
+    })
+}
+
 // ============================================================================
 // VALIDATION CRITERIA IMPLEMENTATION
 // ============================================================================
@@ -227,130 +306,31 @@ pub struct MixingRequest {
 /// Criterion 1: Physical Causation
 /// Do the signals share a common physical cause or mechanism?
 pub fn check_physical_causation(signal_a: &str, signal_b: &str) -> bool {
-    // Known causal relationships
-    let causal_pairs = vec![
-        // Mechanical vibrations cause acoustic emissions
-        ("vibration", "acoustic"),
-        ("acoustic", "vibration"),
-        // Electromagnetic fields affect each other
-        ("electromagnetic", "magnetic"),
-        ("magnetic", "electromagnetic"),
-        // Thermal changes affect pressure
-        ("temperature", "pressure"),
-        ("pressure", "temperature"),
-        // Electrical and magnetic coupling
-        ("electrical", "magnetic"),
-        ("magnetic", "electrical"),
-        // Seismic and acoustic coupling
-        ("seismic", "acoustic"),
-        ("acoustic", "seismic"),
-        // Optical and thermal
-        ("optical", "temperature"),
-        ("temperature", "optical"),
-        // Chemical and thermal
-        ("chemical", "temperature"),
-        ("temperature", "chemical"),
-        // Radiation and ionization
-        ("radiation", "ionization"),
-        ("ionization", "radiation"),
-    ];
-
-    causal_pairs.iter().any(|(a, b)| {
-        (signal_a.contains(a) && signal_b.contains(b)) ||
-        (signal_a.contains(b) && signal_b.contains(a))
-    })
+    has_relationship(signal_a, signal_b, RelationshipKind::Causal)
 }
 
 /// Criterion 2: Information Gain
 /// Does combining the signals reveal information not present in either alone?
 pub fn check_information_gain(signal_a: &str, signal_b: &str) -> bool {
-    // Different modalities often provide complementary information
-    let complementary_modalities = vec![
-        ("acoustic", "vibration"),      // Different frequency ranges
-        ("optical", "infrared"),        // Visible vs thermal
-        ("electrical", "magnetic"),     // E and M fields
-        ("seismic", "acoustic"),        // Ground vs air propagation
-        ("pressure", "temperature"),    // Thermodynamic state
-        ("chemical", "optical"),        // Spectroscopy
-        ("radio", "optical"),           // Different EM bands
-        ("ultrasonic", "acoustic"),     // Different frequency ranges
-        ("electromagnetic", "acoustic"), // Cross-domain sensing
-        ("vibration", "temperature"),   // Mechanical-thermal coupling
-    ];
-
-    complementary_modalities.iter().any(|(a, b)| {
-        (signal_a.contains(a) && signal_b.contains(b)) ||
-        (signal_a.contains(b) && signal_b.contains(a))
-    })
+    has_relationship(signal_a, signal_b, RelationshipKind::Complementary)
 }
 
 /// Criterion 3: Predictive Power
 /// Can one signal predict the other with reasonable accuracy?
 pub fn check_predictive_power(signal_a: &str, signal_b: &str) -> bool {
-    // Signals with temporal correlation or lead-lag relationships
-    let predictive_pairs = vec![
-        ("vibration", "acoustic"),      // Vibration often precedes sound
-        ("seismic", "acoustic"),        // Seismic waves travel faster
-        ("temperature", "pressure"),    // Thermodynamic relationships
-        ("electrical", "magnetic"),     // Maxwell's equations
-        ("chemical", "temperature"),    // Reaction kinetics
-        ("pressure", "acoustic"),       // Pressure waves = sound
-        ("strain", "vibration"),        // Structural mechanics
-        ("electromagnetic", "ionization"), // EM ionizes matter
-        ("optical", "chemical"),        // Photochemistry
-        ("magnetic", "electrical"),     // Induction
-    ];
-
-    predictive_pairs.iter().any(|(a, b)| {
-        (signal_a.contains(a) && signal_b.contains(b)) ||
-        (signal_a.contains(b) && signal_b.contains(a))
-    })
+    has_relationship(signal_a, signal_b, RelationshipKind::Predictive)
 }
 
 /// Criterion 4: Temporal Stability
 /// Is the relationship between signals stable over time?
 pub fn check_temporal_stability(signal_a: &str, signal_b: &str) -> bool {
-    // Physical laws provide stable relationships
-    let stable_relationships = vec![
-        ("acoustic", "vibration"),      // Always coupled
-        ("electrical", "magnetic"),     // Maxwell's laws
-        ("temperature", "infrared"),    // Blackbody radiation
-        ("pressure", "temperature"),    // Ideal gas law
-        ("seismic", "acoustic"),        // Wave propagation
-        ("optical", "electromagnetic"), // Light is EM
-        ("strain", "stress"),           // Material properties
-        ("chemical", "spectroscopic"),  // Molecular spectra
-        ("magnetic", "electromagnetic"), // EM spectrum
-        ("ultrasonic", "acoustic"),     // Same phenomenon, different freq
-    ];
-
-    stable_relationships.iter().any(|(a, b)| {
-        (signal_a.contains(a) && signal_b.contains(b)) ||
-        (signal_a.contains(b) && signal_b.contains(a))
-    })
+    has_relationship(signal_a, signal_b, RelationshipKind::TemporallyStable)
 }
 
 /// Criterion 5: Compressibility
 /// Can the joint distribution be compressed more than separate signals?
 pub fn check_compressibility(signal_a: &str, signal_b: &str) -> bool {
-    // Correlated signals compress better together (mutual information > 0)
-    let compressible_pairs = vec![
-        ("acoustic", "vibration"),      // Highly correlated
-        ("electrical", "magnetic"),     // Coupled by Maxwell
-        ("temperature", "infrared"),    // Direct relationship
-        ("seismic", "acoustic"),        // Common source
-        ("optical", "electromagnetic"), // Same phenomenon
-        ("pressure", "acoustic"),       // Pressure waves
-        ("strain", "vibration"),        // Mechanical coupling
-        ("chemical", "spectroscopic"),  // Spectral signatures
-        ("radio", "electromagnetic"),   // EM spectrum
-        ("ultrasonic", "vibration"),    // Mechanical waves
-    ];
-
-    compressible_pairs.iter().any(|(a, b)| {
-        (signal_a.contains(a) && signal_b.contains(b)) ||
-        (signal_a.contains(b) && signal_b.contains(a))
-    })
+    has_relationship(signal_a, signal_b, RelationshipKind::Compressible)
 }
 
 // ============================================================================
@@ -1,5 +1,9 @@
 use hdk::prelude::*;
 
+/// Registrations more than this far from `sys_time()` (in either direction)
+/// are rejected as stale or clock-skewed, bounding the replay window.
+const REGISTRATION_WINDOW_MICROS: i64 = 5 * 60 * 1_000_000; // 5 minutes
+
 /// Bridge registration entry
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
@@ -8,10 +12,88 @@ pub struct BridgeRegistration {
     pub capabilities: Vec<String>,  // ["acoustic_20hz_20khz", "fft_1024"]
     pub transport: Vec<String>,     // ["usb_hid", "tcp"]
     pub endpoint: String,
-    pub signature: Vec<u8>,         // Cryptographic signature
+    /// Ed25519 public key the registration is signed with, so `validate` can
+    /// check the signature without trusting the bridge's claimed identity.
+    pub public_key: AgentPubKey,
+    pub signature: Vec<u8>,         // Ed25519 signature over the canonical payload
     pub timestamp: Timestamp,
 }
 
+/// The canonical `(bridge_id, capabilities, transport, endpoint, timestamp)`
+/// payload a `BridgeRegistration`'s `signature` is computed over.
+#[derive(Clone, Serialize, Deserialize)]
+struct BridgeRegistrationPayload {
+    bridge_id: String,
+    capabilities: Vec<String>,
+    transport: Vec<String>,
+    endpoint: String,
+    timestamp: Timestamp,
+}
+
+impl From<&BridgeRegistration> for BridgeRegistrationPayload {
+    fn from(registration: &BridgeRegistration) -> Self {
+        Self {
+            bridge_id: registration.bridge_id.clone(),
+            capabilities: registration.capabilities.clone(),
+            transport: registration.transport.clone(),
+            endpoint: registration.endpoint.clone(),
+            timestamp: registration.timestamp,
+        }
+    }
+}
+
+/// Append `field`'s length (as a 4-byte little-endian count) followed by
+/// `field` itself. Used by [`canonical_registration_bytes`] so that e.g.
+/// `["ab", "c"]` and `["a", "bc"]` never collide on the wire — raw
+/// delimiter-less concatenation can't tell those apart, which would let an
+/// attacker shift a byte across a field boundary without invalidating the
+/// signature.
+fn encode_field(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(field);
+}
+
+/// The exact bytes a `BridgeRegistration`'s `signature` is computed over.
+/// This is the single source of truth both the firmware (see
+/// `registration_signing_payload` in the acoustic-esp32 bridge) and this
+/// zome must produce identically: every variable-length field is
+/// length-prefixed rather than concatenated raw, and the timestamp is
+/// encoded as microseconds (matching [`Timestamp`]'s own unit) rather than
+/// the bridge's local nanosecond clock reading.
+fn canonical_registration_bytes(
+    bridge_id: &str,
+    capabilities: &[String],
+    transport: &[String],
+    endpoint: &str,
+    timestamp_micros: i64,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    encode_field(&mut bytes, bridge_id.as_bytes());
+    bytes.extend_from_slice(&(capabilities.len() as u32).to_le_bytes());
+    for capability in capabilities {
+        encode_field(&mut bytes, capability.as_bytes());
+    }
+    bytes.extend_from_slice(&(transport.len() as u32).to_le_bytes());
+    for t in transport {
+        encode_field(&mut bytes, t.as_bytes());
+    }
+    encode_field(&mut bytes, endpoint.as_bytes());
+    bytes.extend_from_slice(&timestamp_micros.to_le_bytes());
+    bytes
+}
+
+impl BridgeRegistrationPayload {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        canonical_registration_bytes(
+            &self.bridge_id,
+            &self.capabilities,
+            &self.transport,
+            &self.endpoint,
+            self.timestamp.as_micros(),
+        )
+    }
+}
+
 /// Stream metadata
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
@@ -40,12 +122,8 @@ pub enum EntryTypes {
 /// Register a new bridge
 #[hdk_extern]
 pub fn register_bridge(registration: BridgeRegistration) -> ExternResult<ActionHash> {
-    // Validate signature (simplified for now)
-    if registration.signature.is_empty() {
-        return Err(wasm_error!(WasmErrorInner::Guest(
-            "Invalid signature".to_string()
-        )));
-    }
+    // Signature, replay-window and identity checks happen in `validate`
+    // below, which runs on every `StoreEntry` op for this entry type.
 
     // Create entry
     let hash = create_entry(&EntryTypes::BridgeRegistration(registration.clone()))?;
@@ -206,9 +284,61 @@ fn validate_bridge_registration(registration: &BridgeRegistration) -> ExternResu
         return Ok(ValidateCallbackResult::Invalid("Endpoint cannot be empty".to_string()));
     }
 
+    // Reject timestamps too far from now in either direction, bounding how
+    // long a captured registration stays replayable.
+    let now_micros = sys_time()?.as_micros();
+    let age = now_micros - registration.timestamp.as_micros();
+    if age.abs() > REGISTRATION_WINDOW_MICROS {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Registration timestamp outside the {}us replay window",
+            REGISTRATION_WINDOW_MICROS
+        )));
+    }
+
+    // Reject registrations older than the latest one already stored for this
+    // bridge_id, so a captured earlier registration can't be replayed to
+    // roll the bridge's endpoint/capabilities back.
+    if let Some(latest) = latest_stored_registration(&registration.bridge_id)? {
+        if registration.timestamp <= latest.timestamp {
+            return Ok(ValidateCallbackResult::Invalid(
+                "Registration timestamp is not newer than the latest stored registration".to_string(),
+            ));
+        }
+    }
+
+    // Verify the signature covers the canonical payload and was produced by
+    // the public key the registration itself carries.
+    let payload = BridgeRegistrationPayload::from(registration);
+    let payload_bytes = payload.canonical_bytes();
+    let signature = Signature::try_from(registration.signature.as_slice())
+        .map_err(|_| wasm_error!(WasmErrorInner::Guest("Malformed signature".to_string())))?;
+    if !verify_signature_raw(registration.public_key.clone(), signature, payload_bytes.into())? {
+        return Ok(ValidateCallbackResult::Invalid("Signature verification failed".to_string()));
+    }
+
     Ok(ValidateCallbackResult::Valid)
 }
 
+/// The most recently timestamped `BridgeRegistration` already stored for
+/// `bridge_id`, used to reject stale/replayed registrations.
+fn latest_stored_registration(bridge_id: &str) -> ExternResult<Option<BridgeRegistration>> {
+    let path = Path::from("all_bridges");
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllBridges)?.build())?;
+
+    let mut latest: Option<BridgeRegistration> = None;
+    for link in links {
+        if String::from_utf8_lossy(link.tag.as_ref()) != bridge_id {
+            continue;
+        }
+        if let Some(registration) = get_bridge_by_hash(link.target.into())? {
+            if latest.as_ref().map_or(true, |l| registration.timestamp > l.timestamp) {
+                latest = Some(registration);
+            }
+        }
+    }
+    Ok(latest)
+}
+
 fn validate_stream_metadata(stream: &StreamMetadata) -> ExternResult<ValidateCallbackResult> {
     // Sample rate must be reasonable
     if stream.sample_rate_hz == 0 || stream.sample_rate_hz > 1_000_000 {
@@ -222,3 +352,40 @@ fn validate_stream_metadata(stream: &StreamMetadata) -> ExternResult<ValidateCal
 
     Ok(ValidateCallbackResult::Valid)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+
+    /// `verify_signature_raw` is a host function and isn't callable outside
+    /// a running conductor, so this exercises the same Ed25519 sign/verify
+    /// the host performs, directly against `canonical_registration_bytes` —
+    /// the part of the signing contract this zome actually controls, and
+    /// the part that drifted out of sync with the firmware's encoding.
+    #[test]
+    fn canonical_bytes_round_trip_through_signature() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let bytes = canonical_registration_bytes(
+            "bridge-1",
+            &["acoustic_20hz_20khz".to_string(), "fft_1024".to_string()],
+            &["usb_hid".to_string(), "tcp".to_string()],
+            "tcp://192.168.1.101:9999",
+            1_700_000_000_000_000,
+        );
+
+        let signature = signing_key.sign(&bytes);
+        assert!(signing_key.verifying_key().verify(&bytes, &signature).is_ok());
+    }
+
+    /// Shifting a byte across a field boundary (`["ab", "c"]` vs
+    /// `["a", "bc"]`) must not produce identical bytes — the whole reason
+    /// for length-prefixing instead of raw concatenation.
+    #[test]
+    fn length_prefixing_prevents_field_boundary_collisions() {
+        let a = canonical_registration_bytes("id", &["ab".to_string(), "c".to_string()], &[], "ep", 0);
+        let b = canonical_registration_bytes("id", &["a".to_string(), "bc".to_string()], &[], "ep", 0);
+        assert_ne!(a, b);
+    }
+}
@@ -1,286 +1,394 @@
 /// Integration tests for memory_coordinator zome
 ///
 /// These tests verify the transmit/recall/compose cycle works correctly
-/// with proper validation and DHT operations.
+/// with proper validation and DHT operations, against a real conductor
+/// rather than as documentation-only stubs.
+#[cfg(test)]
+mod test_utils {
+    use hdk::prelude::*;
+    use holochain::sweettest::{SweetConductor, SweetConductorBatch, SweetDnaFile, SweetCell};
+
+    /// The zomes every `memory_coordinator` DNA bundle needs: the
+    /// integrity zome backing its entry/link defs, and the ontology
+    /// integrity zome `validate_triple` depends on.
+    const ZOMES: &[&str] = &["memory_coordinator", "rose_forest_integrity", "ontology_integrity"];
+
+    /// One conductor, one agent, one installed cell — enough for every
+    /// single-agent scenario below.
+    pub async fn setup_conductor() -> (SweetConductor, AgentPubKey, SweetCell) {
+        let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+            ZOMES.iter().map(|z| z.to_string()).collect(),
+            ZOMES.iter().map(|_| ()).collect(),
+        )
+        .await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("memory", &[dna]).await.unwrap();
+        let cell = app.cells()[0].clone();
+        let agent = cell.agent_pubkey().clone();
+
+        (conductor, agent, cell)
+    }
+
+    /// Two conductors, each with its own agent and cell on the same DNA —
+    /// for the cross-agent composition and duplicate-detection scenarios,
+    /// which need real gossip between independent conductors rather than
+    /// two cells sharing one.
+    pub async fn setup_two_agents() -> (SweetConductorBatch, [AgentPubKey; 2], [SweetCell; 2]) {
+        let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+            ZOMES.iter().map(|z| z.to_string()).collect(),
+            ZOMES.iter().map(|_| ()).collect(),
+        )
+        .await;
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let apps = conductors.setup_app("memory", &[dna]).await.unwrap();
+
+        let cell0 = apps[0].cells()[0].clone();
+        let cell1 = apps[1].cells()[0].clone();
+        let agent0 = cell0.agent_pubkey().clone();
+        let agent1 = cell1.agent_pubkey().clone();
+
+        conductors.exchange_peer_info().await;
+
+        (conductors, [agent0, agent1], [cell0, cell1])
+    }
+
+    /// Two agents, two cells on the same DNA, both installed as separate
+    /// apps on *one* conductor — what `federate_memories` actually bridges
+    /// between via the HDK `call` host function, which only reaches other
+    /// cells on the calling cell's own conductor, unlike `setup_two_agents`'
+    /// independent conductors (for scenarios that need real network gossip
+    /// instead of a same-conductor bridge call).
+    pub async fn setup_two_apps_one_conductor() -> (SweetConductor, [AgentPubKey; 2], [SweetCell; 2]) {
+        let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+            ZOMES.iter().map(|z| z.to_string()).collect(),
+            ZOMES.iter().map(|_| ()).collect(),
+        )
+        .await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app1 = conductor.setup_app("memory1", &[dna.clone()]).await.unwrap();
+        let app2 = conductor.setup_app("memory2", &[dna]).await.unwrap();
+
+        let cell1 = app1.cells()[0].clone();
+        let cell2 = app2.cells()[0].clone();
+        let agent1 = cell1.agent_pubkey().clone();
+        let agent2 = cell2.agent_pubkey().clone();
+
+        (conductor, [agent1, agent2], [cell1, cell2])
+    }
+
+    /// Transmit `content` on `cell` and return the new `Understanding`'s
+    /// `ActionHash`.
+    pub async fn transmit(conductor: &SweetConductor, cell: &SweetCell, content: &str) -> ActionHash {
+        conductor
+            .call(
+                &cell.zome("memory_coordinator"),
+                "transmit_understanding",
+                memory_coordinator::UnderstandingInput { content: content.to_string(), context: None },
+            )
+            .await
+    }
+}
 
 #[cfg(test)]
 mod memory_tests {
-    // Note: Full Holochain conductor tests require the holochain test framework
-    // These are test stubs that show the intended test structure
-    //
-    // To run these tests, you would need:
-    // 1. holochain = "0.4" in dev-dependencies
-    // 2. A proper conductor setup with the DNA
-    //
-    // For now, these serve as documentation of the test plan
-
-    #[test]
-    fn test_structure_documented() {
-        // This test documents the intended test structure
-        // In production, these would be full integration tests using holochain test utils
-        assert!(true, "Test structure documented");
-    }
+    use hdk::prelude::*;
+    use memory_coordinator::{
+        AwaitConsistencyInput, FederateMemoriesInput, GrantResult, GrantSpec, MemoryComposition,
+        MemoryFunction, RecallPage, RecallQuery, ValidationStats, ADR,
+    };
+    use super::test_utils::{setup_conductor, setup_two_agents, setup_two_apps_one_conductor, transmit};
 
     // Test 1: Basic transmit and recall
-    // #[tokio::test]
-    // async fn test_transmit_and_recall() {
-    //     let (conductor, agent, cell) = setup_conductor().await;
-    //
-    //     // Transmit understanding
-    //     let input = UnderstandingInput {
-    //         content: "GPT-4 is a LLM".to_string(),
-    //         context: None,
-    //     };
-    //
-    //     let hash: ActionHash = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "transmit_understanding",
-    //         input,
-    //     ).await.unwrap();
-    //
-    //     assert!(hash.len() > 0, "Should return valid hash");
-    //
-    //     // Recall understandings
-    //     let query = RecallQuery {
-    //         agent: Some(agent.clone()),
-    //         content_contains: Some("GPT-4".to_string()),
-    //         after_timestamp: None,
-    //         limit: None,
-    //     };
-    //
-    //     let results: Vec<Understanding> = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "recall_understandings",
-    //         query,
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(results.len(), 1, "Should find one understanding");
-    //     assert_eq!(results[0].content, "GPT-4 is a LLM");
-    //     assert_eq!(results[0].triple.predicate, "is_a");
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transmit_and_recall() {
+        let (conductor, agent, cell) = setup_conductor().await;
+
+        let _hash = transmit(&conductor, &cell, "GPT-4 is a LLM").await;
+
+        let query = RecallQuery {
+            agent: Some(agent.clone()),
+            content_contains: Some("GPT-4".to_string()),
+            after_timestamp: None,
+            limit: None,
+            after_cursor: None,
+            min_trust: None,
+            await_consistency_ms: None,
+        };
+
+        let results: Vec<_> = conductor
+            .call::<_, RecallPage>(&cell.zome("memory_coordinator"), "recall_understandings", query)
+            .await
+            .items;
+
+        assert_eq!(results.len(), 1, "Should find one understanding");
+        assert_eq!(results[0].content, "GPT-4 is a LLM");
+        assert_eq!(results[0].triple.predicate, "is_a");
+    }
 
     // Test 2: Validation integration
-    // #[tokio::test]
-    // async fn test_validation_rejects_invalid_triple() {
-    //     let (conductor, _agent, cell) = setup_conductor().await;
-    //
-    //     // Try to transmit with invalid predicate
-    //     let input = UnderstandingInput {
-    //         content: "Test invalid_relation Target".to_string(),
-    //         context: None,
-    //     };
-    //
-    //     let result = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "transmit_understanding",
-    //         input,
-    //     ).await;
-    //
-    //     // Should fail validation (or default to "stated" predicate)
-    //     // Depending on implementation, this might succeed with fallback
-    //     assert!(result.is_ok() || result.is_err());
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validation_rejects_invalid_triple() {
+        let (conductor, _agent, cell) = setup_conductor().await;
+
+        let result: Result<ActionHash, _> = conductor
+            .call_fallible(
+                &cell.zome("memory_coordinator"),
+                "transmit_understanding",
+                memory_coordinator::UnderstandingInput {
+                    content: "Test invalid_relation Target".to_string(),
+                    context: None,
+                },
+            )
+            .await;
+
+        // No extraction pattern matches, so the content falls back to a
+        // "stated" triple; "stated" is not a known ontology relation, so
+        // validate_triple rejects it and the transmit fails.
+        assert!(result.is_err(), "a triple with an unknown relation should be rejected");
+    }
 
     // Test 3: Memory composition
-    // #[tokio::test]
-    // async fn test_compose_memories() {
-    //     let (conductor, agent1, cell1) = setup_conductor().await;
-    //     let (_, agent2, cell2) = setup_conductor().await;
-    //
-    //     // Agent 1 transmits understanding
-    //     let input1 = UnderstandingInput {
-    //         content: "Claude-4.5 is a LLM".to_string(),
-    //         context: None,
-    //     };
-    //     conductor.call_zome(cell1.clone(), "memory_coordinator", "transmit_understanding", input1).await.unwrap();
-    //
-    //     // Agent 2 transmits different understanding
-    //     let input2 = UnderstandingInput {
-    //         content: "GPT-4 is a LLM".to_string(),
-    //         context: None,
-    //     };
-    //     conductor.call_zome(cell2.clone(), "memory_coordinator", "transmit_understanding", input2).await.unwrap();
-    //
-    //     // Agent 1 composes with Agent 2
-    //     let composition: MemoryComposition = conductor.call_zome(
-    //         cell1.clone(),
-    //         "memory_coordinator",
-    //         "compose_memories",
-    //         agent2.clone(),
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(composition.stats.new_understandings, 1, "Should add 1 new understanding");
-    //     assert_eq!(composition.stats.duplicate_skipped, 0, "Should have no duplicates");
-    //
-    //     // Verify agent 1 now has both understandings
-    //     let results: Vec<Understanding> = conductor.call_zome(
-    //         cell1.clone(),
-    //         "memory_coordinator",
-    //         "recall_understandings",
-    //         RecallQuery {
-    //             agent: Some(agent1.clone()),
-    //             content_contains: None,
-    //             after_timestamp: None,
-    //             limit: None,
-    //         },
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(results.len(), 2, "Agent 1 should now have 2 understandings");
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_compose_memories() {
+        let (conductors, [agent1, agent2], [cell1, cell2]) = setup_two_agents().await;
+        let conductor1 = &conductors[0];
+        let conductor2 = &conductors[1];
+
+        transmit(conductor1, &cell1, "Claude-4.5 is a LLM").await;
+        transmit(conductor2, &cell2, "GPT-4 is a LLM").await;
+
+        // Wait for agent2's understanding to have actually propagated
+        // before composing, rather than racing the gossip loop.
+        conductor1
+            .call(
+                &cell1.zome("memory_coordinator"),
+                "await_consistency",
+                AwaitConsistencyInput { agent: agent2.clone(), timeout_ms: 10_000 },
+            )
+            .await;
+
+        let composition: MemoryComposition = conductor1
+            .call(&cell1.zome("memory_coordinator"), "compose_memories", agent2.clone())
+            .await;
+
+        assert_eq!(composition.stats.new_understandings, 1, "Should add 1 new understanding");
+        assert_eq!(composition.stats.duplicate_skipped, 0, "Should have no duplicates");
+
+        let results: Vec<_> = conductor1
+            .call::<_, RecallPage>(
+                &cell1.zome("memory_coordinator"),
+                "recall_understandings",
+                RecallQuery {
+                    agent: Some(agent1.clone()),
+                    content_contains: None,
+                    after_timestamp: None,
+                    limit: None,
+                    after_cursor: None,
+                    min_trust: None,
+                    await_consistency_ms: None,
+                },
+            )
+            .await
+            .items;
+
+        assert_eq!(results.len(), 2, "Agent 1 should now have 2 understandings");
+    }
 
     // Test 4: Duplicate detection
-    // #[tokio::test]
-    // async fn test_duplicate_detection() {
-    //     let (conductor, agent1, cell1) = setup_conductor().await;
-    //     let (_, agent2, cell2) = setup_conductor().await;
-    //
-    //     // Both agents transmit same content
-    //     let input = UnderstandingInput {
-    //         content: "GPT-4 is a LLM".to_string(),
-    //         context: None,
-    //     };
-    //
-    //     conductor.call_zome(cell1.clone(), "memory_coordinator", "transmit_understanding", input.clone()).await.unwrap();
-    //     conductor.call_zome(cell2.clone(), "memory_coordinator", "transmit_understanding", input).await.unwrap();
-    //
-    //     // Agent 1 composes with Agent 2
-    //     let composition: MemoryComposition = conductor.call_zome(
-    //         cell1.clone(),
-    //         "memory_coordinator",
-    //         "compose_memories",
-    //         agent2,
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(composition.stats.duplicate_skipped, 1, "Should skip 1 duplicate");
-    //     assert_eq!(composition.stats.new_understandings, 0, "Should add 0 new understandings");
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_duplicate_detection() {
+        let (conductors, [_agent1, agent2], [cell1, cell2]) = setup_two_agents().await;
+        let conductor1 = &conductors[0];
+        let conductor2 = &conductors[1];
+
+        transmit(conductor1, &cell1, "GPT-4 is a LLM").await;
+        transmit(conductor2, &cell2, "GPT-4 is a LLM").await;
+
+        conductor1
+            .call(
+                &cell1.zome("memory_coordinator"),
+                "await_consistency",
+                AwaitConsistencyInput { agent: agent2.clone(), timeout_ms: 10_000 },
+            )
+            .await;
+
+        let composition: MemoryComposition = conductor1
+            .call(&cell1.zome("memory_coordinator"), "compose_memories", agent2)
+            .await;
+
+        assert_eq!(composition.stats.duplicate_skipped, 1, "Should skip 1 duplicate");
+        assert_eq!(composition.stats.new_understandings, 0, "Should add 0 new understandings");
+    }
 
     // Test 5: Query filtering
-    // #[tokio::test]
-    // async fn test_query_filtering() {
-    //     let (conductor, agent, cell) = setup_conductor().await;
-    //
-    //     // Transmit multiple understandings
-    //     let inputs = vec![
-    //         "GPT-4 is a LLM",
-    //         "Claude-4.5 is a LLM",
-    //         "Llama is a LLM",
-    //     ];
-    //
-    //     for content in inputs {
-    //         conductor.call_zome(
-    //             cell.clone(),
-    //             "memory_coordinator",
-    //             "transmit_understanding",
-    //             UnderstandingInput { content: content.to_string(), context: None },
-    //         ).await.unwrap();
-    //     }
-    //
-    //     // Query with content filter
-    //     let query = RecallQuery {
-    //         agent: Some(agent.clone()),
-    //         content_contains: Some("GPT".to_string()),
-    //         after_timestamp: None,
-    //         limit: None,
-    //     };
-    //
-    //     let results: Vec<Understanding> = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "recall_understandings",
-    //         query,
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(results.len(), 1, "Should find only GPT-4");
-    //     assert!(results[0].content.contains("GPT-4"));
-    //
-    //     // Query with limit
-    //     let query_limited = RecallQuery {
-    //         agent: Some(agent.clone()),
-    //         content_contains: None,
-    //         after_timestamp: None,
-    //         limit: Some(2),
-    //     };
-    //
-    //     let results_limited: Vec<Understanding> = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "recall_understandings",
-    //         query_limited,
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(results_limited.len(), 2, "Should return max 2 results");
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_query_filtering() {
+        let (conductor, agent, cell) = setup_conductor().await;
+
+        for content in ["GPT-4 is a LLM", "Claude-4.5 is a LLM", "Llama is a LLM"] {
+            transmit(&conductor, &cell, content).await;
+        }
+
+        let query = RecallQuery {
+            agent: Some(agent.clone()),
+            content_contains: Some("GPT".to_string()),
+            after_timestamp: None,
+            limit: None,
+            after_cursor: None,
+            min_trust: None,
+            await_consistency_ms: None,
+        };
+
+        let results: Vec<_> = conductor
+            .call::<_, RecallPage>(&cell.zome("memory_coordinator"), "recall_understandings", query)
+            .await
+            .items;
+
+        assert_eq!(results.len(), 1, "Should find only GPT-4");
+        assert!(results[0].content.contains("GPT-4"));
+
+        let query_limited = RecallQuery {
+            agent: Some(agent.clone()),
+            content_contains: None,
+            after_timestamp: None,
+            limit: Some(2),
+            after_cursor: None,
+            min_trust: None,
+            await_consistency_ms: None,
+        };
+
+        let results_limited: Vec<_> = conductor
+            .call::<_, RecallPage>(&cell.zome("memory_coordinator"), "recall_understandings", query_limited)
+            .await
+            .items;
+
+        assert_eq!(results_limited.len(), 2, "Should return max 2 results");
+    }
 
     // Test 6: Validation statistics
-    // #[tokio::test]
-    // async fn test_validation_stats() {
-    //     let (conductor, _agent, cell) = setup_conductor().await;
-    //
-    //     // Transmit several understandings
-    //     for i in 0..5 {
-    //         conductor.call_zome(
-    //             cell.clone(),
-    //             "memory_coordinator",
-    //             "transmit_understanding",
-    //             UnderstandingInput {
-    //                 content: format!("Model-{} is a LLM", i),
-    //                 context: None,
-    //             },
-    //         ).await.unwrap();
-    //     }
-    //
-    //     // Get stats
-    //     let stats: ValidationStats = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "get_validation_stats",
-    //         (),
-    //     ).await.unwrap();
-    //
-    //     assert_eq!(stats.total_understandings, 5);
-    //     assert_eq!(stats.valid_triples, 5);
-    //     assert_eq!(stats.invalid_triples, 0);
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validation_stats() {
+        let (conductor, _agent, cell) = setup_conductor().await;
+
+        for i in 0..5 {
+            transmit(&conductor, &cell, &format!("Model-{i} is a LLM")).await;
+        }
+
+        let stats: ValidationStats = conductor
+            .call(&cell.zome("memory_coordinator"), "get_validation_stats", ())
+            .await;
+
+        assert_eq!(stats.total_understandings, 5);
+        assert_eq!(stats.valid_triples, 5);
+        assert_eq!(stats.invalid_triples, 0);
+    }
 
     // Test 7: ADR creation and retrieval
-    // #[tokio::test]
-    // async fn test_adr_lifecycle() {
-    //     let (conductor, agent, cell) = setup_conductor().await;
-    //
-    //     // Create ADR
-    //     let adr = ADR {
-    //         id: "ADR-001".to_string(),
-    //         title: "Use Holochain for memory storage".to_string(),
-    //         content: "We have decided to use Holochain...".to_string(),
-    //         status: "accepted".to_string(),
-    //         decided_at: Timestamp::now(),
-    //         decided_by: agent.clone(),
-    //     };
-    //
-    //     let adr_hash: ActionHash = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "create_adr",
-    //         adr.clone(),
-    //     ).await.unwrap();
-    //
-    //     // Retrieve ADR
-    //     let retrieved: Option<ADR> = conductor.call_zome(
-    //         cell.clone(),
-    //         "memory_coordinator",
-    //         "get_adr",
-    //         adr_hash,
-    //     ).await.unwrap();
-    //
-    //     assert!(retrieved.is_some());
-    //     let retrieved_adr = retrieved.unwrap();
-    //     assert_eq!(retrieved_adr.id, "ADR-001");
-    //     assert_eq!(retrieved_adr.status, "accepted");
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_adr_lifecycle() {
+        let (conductor, agent, cell) = setup_conductor().await;
+
+        let adr = ADR {
+            id: "ADR-001".to_string(),
+            title: "Use Holochain for memory storage".to_string(),
+            content: "We have decided to use Holochain...".to_string(),
+            status: "accepted".to_string(),
+            decided_at: Timestamp::now(),
+            decided_by: agent.clone(),
+        };
+
+        let adr_hash: ActionHash = conductor
+            .call(&cell.zome("memory_coordinator"), "create_adr", adr.clone())
+            .await;
+
+        let retrieved: Option<ADR> = conductor
+            .call(&cell.zome("memory_coordinator"), "get_adr", adr_hash)
+            .await;
+
+        assert!(retrieved.is_some());
+        let retrieved_adr = retrieved.unwrap();
+        assert_eq!(retrieved_adr.id, "ADR-001");
+        assert_eq!(retrieved_adr.status, "accepted");
+    }
+
+    // Test 8: Federated composition across a bridged cell, gated by a real
+    // capability grant (not just a unit test of `require_access` in
+    // isolation) — exercises the actual conductor-level dispatch of
+    // `export_understandings_for_federation` under the secret
+    // `grant_memory_access` issued, which only succeeds if that secret's
+    // grant lists the bridged function name the conductor sees `call()`
+    // invoke, not just the name `require_access` checks in-wasm.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_federate_memories_with_grant() {
+        let (conductor, [agent1, agent2], [cell1, cell2]) = setup_two_apps_one_conductor().await;
+
+        transmit(&conductor, &cell2, "Claude-4.5 is a LLM").await;
+
+        let GrantResult { cap_secret, .. } = conductor
+            .call(
+                &cell2.zome("memory_coordinator"),
+                "grant_memory_access",
+                GrantSpec { function: MemoryFunction::Recall, assignees: Some(vec![agent1.clone()]) },
+            )
+            .await;
+
+        let composition: MemoryComposition = conductor
+            .call(
+                &cell1.zome("memory_coordinator"),
+                "federate_memories",
+                FederateMemoriesInput {
+                    to_cell: Some(cell2.cell_id().clone()),
+                    other_agent: agent2.clone(),
+                    cap_secret: Some(cap_secret),
+                },
+            )
+            .await;
+
+        assert_eq!(composition.stats.new_understandings, 1, "Should federate 1 new understanding");
+
+        let results: Vec<_> = conductor
+            .call::<_, RecallPage>(
+                &cell1.zome("memory_coordinator"),
+                "recall_understandings",
+                RecallQuery {
+                    agent: Some(agent1.clone()),
+                    content_contains: None,
+                    after_timestamp: None,
+                    limit: None,
+                    after_cursor: None,
+                    min_trust: None,
+                    await_consistency_ms: None,
+                },
+            )
+            .await
+            .items;
+
+        assert_eq!(results.len(), 1, "Agent 1 should now have the federated understanding");
+        assert_eq!(results[0].content, "Claude-4.5 is a LLM");
+    }
+
+    // Test 9: Without a grant, the conductor itself rejects the bridged
+    // `export_understandings_for_federation` call before it ever reaches
+    // `require_access` — the capability-level gate, not just the in-wasm one.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_federate_memories_without_grant_is_rejected() {
+        let (conductor, [_agent1, agent2], [cell1, cell2]) = setup_two_apps_one_conductor().await;
+
+        transmit(&conductor, &cell2, "GPT-4 is a LLM").await;
+
+        let result: Result<MemoryComposition, _> = conductor
+            .call_fallible(
+                &cell1.zome("memory_coordinator"),
+                "federate_memories",
+                FederateMemoriesInput { to_cell: Some(cell2.cell_id().clone()), other_agent: agent2, cap_secret: None },
+            )
+            .await;
+
+        assert!(result.is_err(), "federate_memories should fail without a valid grant");
+    }
 }
 
 /// Test plan documentation
@@ -300,7 +408,8 @@ mod memory_tests {
 ///    - Fallback to "stated" predicate for unparseable content
 ///
 /// 3. **Memory composition**
-///    - Multiple agents can compose memories
+///    - Multiple agents can compose memories, after `await_consistency`
+///      confirms the peer's understandings have actually propagated
 ///    - Deduplication works correctly
 ///    - Statistics are accurate
 ///
@@ -313,9 +422,9 @@ mod memory_tests {
 /// 5. **DHT operations**
 ///    - Links are created correctly
 ///    - Queries use links efficiently
-///    - Cross-agent queries work
+///    - Cross-agent queries work, deterministically, under real gossip
 ///
 /// To run these tests:
-/// 1. Install Holochain test framework
+/// 1. Enable `holochain`'s `sweettest` feature in dev-dependencies
 /// 2. Build the DNA
 /// 3. Run: cargo test --test memory_test
@@ -1,144 +1,489 @@
 use hdk::prelude::*;
-use rose_forest_integrity::BudgetEntry;
-
-// Bio-aware budget parameters based on the manifesto
-// Represents a unit of cognitive output, calibrated to the idea of ~3 major cognitive pulses per day
-pub const COST_ADD_KNOWLEDGE: f32 = 33.0;
-// Represents a unit of cognitive linking, a less intensive action
-pub const COST_LINK_EDGE: f32 = 3.0;
-// Represents the cost of creating a significant thoughtform
-pub const COST_CREATE_THOUGHT_CREDENTIAL: f32 = 10.0;
-
-// Memory operation costs (VVS spec requirements)
-// Cost to transmit an understanding to the DHT
-pub const COST_TRANSMIT_UNDERSTANDING: f32 = 1.0;
-// Cost per result when recalling understandings
-pub const COST_RECALL_UNDERSTANDINGS: f32 = 0.1;
-// Cost to compose memories from another agent
-pub const COST_COMPOSE_MEMORIES: f32 = 5.0;
-// Cost to validate a knowledge triple
-pub const COST_VALIDATE_TRIPLE: f32 = 2.0;
-
-// Total cognitive budget per window, reflecting the idea of a daily cognitive capacity
-pub const MAX_RU_PER_WINDOW: f32 = 100.0;
+use rose_forest_integrity::DimensionalBudgetEntry;
+use std::collections::HashMap;
+
+/// Independent budget dimensions tracked per agent. At minimum a compute
+/// dimension (CPU/FLOPs-bound work) and a memory dimension (DHT storage
+/// footprint), tuned and exhausted separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BudgetDimension {
+    Compute,
+    Memory,
+}
+
+impl BudgetDimension {
+    fn as_str(self) -> &'static str {
+        match self {
+            BudgetDimension::Compute => "compute",
+            BudgetDimension::Memory => "memory",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "compute" => Some(BudgetDimension::Compute),
+            "memory" => Some(BudgetDimension::Memory),
+            _ => None,
+        }
+    }
+}
+
+/// Operations BudgetEngine charges for, keyed into `cost_model_table`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CostType {
+    AddKnowledge,
+    LinkEdge,
+    CreateThoughtCredential,
+}
+
+impl CostType {
+    fn as_str(self) -> &'static str {
+        match self {
+            CostType::AddKnowledge => "add_knowledge",
+            CostType::LinkEdge => "link_edge",
+            CostType::CreateThoughtCredential => "create_thought_credential",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "add_knowledge" => Some(CostType::AddKnowledge),
+            "link_edge" => Some(CostType::LinkEdge),
+            "create_thought_credential" => Some(CostType::CreateThoughtCredential),
+            _ => None,
+        }
+    }
+}
+
+/// `cost = const_term + linear_term * input_size`, e.g. embedding dimension
+/// count for `AddKnowledge`, or result count for a recall-style operation.
+#[derive(Clone, Copy, Debug)]
+pub struct CostModel {
+    pub dimension: BudgetDimension,
+    pub const_term: u64,
+    pub linear_term: u64,
+}
+
+/// Calibrated linear cost models per operation. Each operation may charge
+/// more than one dimension, e.g. `AddKnowledge` costs both compute (to index
+/// the embedding) and memory (to store it), each scaling differently with
+/// `input_size`.
+fn cost_model_table() -> HashMap<CostType, Vec<CostModel>> {
+    let mut table = HashMap::new();
+    table.insert(
+        CostType::AddKnowledge,
+        vec![
+            CostModel { dimension: BudgetDimension::Compute, const_term: 10, linear_term: 1 },
+            CostModel { dimension: BudgetDimension::Memory, const_term: 5, linear_term: 1 },
+        ],
+    );
+    table.insert(
+        CostType::LinkEdge,
+        vec![
+            CostModel { dimension: BudgetDimension::Compute, const_term: 3, linear_term: 0 },
+            CostModel { dimension: BudgetDimension::Memory, const_term: 1, linear_term: 0 },
+        ],
+    );
+    table.insert(
+        CostType::CreateThoughtCredential,
+        vec![
+            CostModel { dimension: BudgetDimension::Compute, const_term: 5, linear_term: 1 },
+            CostModel { dimension: BudgetDimension::Memory, const_term: 3, linear_term: 1 },
+        ],
+    );
+    table
+}
+
+/// Look up the declared cost model for `cost_type` on `dimension`, summing
+/// if more than one model is registered for the pair (there normally isn't).
+pub fn cost_model_for(cost_type: CostType, dimension: BudgetDimension) -> ExternResult<CostModel> {
+    let table = cost_model_table();
+    let models = table.get(&cost_type).ok_or_else(|| {
+        wasm_error!(WasmErrorInner::Guest(format!("E_UNKNOWN_COST_TYPE: {:?}", cost_type)))
+    })?;
+    let const_term = models.iter().filter(|m| m.dimension == dimension).map(|m| m.const_term).sum();
+    let linear_term = models.iter().filter(|m| m.dimension == dimension).map(|m| m.linear_term).sum();
+    Ok(CostModel { dimension, const_term, linear_term })
+}
+
+/// Structured detail for a budget-exceeded denial: which operation was
+/// charged, which dimension ran out, how much was requested, and how much
+/// remained. Carries enough detail for the caller to decide whether to
+/// retry after replenishment, rather than parsing an opaque error string.
+#[derive(Clone, Debug)]
+pub struct BudgetExceededDetail {
+    pub operation: CostType,
+    pub dimension: BudgetDimension,
+    pub requested: u64,
+    pub remaining: u64,
+}
+
+/// Render `detail` as the `E_BUDGET_EXCEEDED` message body: `key=value`
+/// fields, not a `{:?}` dump, so [`parse_budget_exceeded_error`] can recover
+/// `detail` exactly rather than a caller having to scrape Debug-formatted
+/// text.
+fn format_budget_exceeded(detail: &BudgetExceededDetail) -> String {
+    format!(
+        "E_BUDGET_EXCEEDED: operation={} dimension={} requested={} remaining={}",
+        detail.operation.as_str(), detail.dimension.as_str(), detail.requested, detail.remaining
+    )
+}
+
+/// Render `detail` as a `WasmError` carrying the `E_BUDGET_EXCEEDED` code
+/// the tests match on.
+fn budget_exceeded_error(detail: BudgetExceededDetail) -> WasmError {
+    wasm_error!(WasmErrorInner::Guest(format_budget_exceeded(&detail)))
+}
+
+/// Recover the [`BudgetExceededDetail`] a `WasmError` message was rendered
+/// from by [`budget_exceeded_error`]. `ExternResult` errors only carry a
+/// string across the wasm boundary, so this is how a caller (e.g. a client
+/// bridging into this zome) turns that string back into structured fields
+/// instead of pattern-matching on message text. Returns `None` if `message`
+/// isn't an `E_BUDGET_EXCEEDED` error, or is malformed.
+pub fn parse_budget_exceeded_error(message: &str) -> Option<BudgetExceededDetail> {
+    let rest = message.strip_prefix("E_BUDGET_EXCEEDED: ")?;
+
+    let mut operation = None;
+    let mut dimension = None;
+    let mut requested = None;
+    let mut remaining = None;
+
+    for field in rest.split_whitespace() {
+        let (key, value) = field.split_once('=')?;
+        match key {
+            "operation" => operation = CostType::parse(value),
+            "dimension" => dimension = BudgetDimension::parse(value),
+            "requested" => requested = value.parse::<u64>().ok(),
+            "remaining" => remaining = value.parse::<u64>().ok(),
+            _ => {}
+        }
+    }
+
+    Some(BudgetExceededDetail {
+        operation: operation?,
+        dimension: dimension?,
+        requested: requested?,
+        remaining: remaining?,
+    })
+}
+
+/// Sum of `const_term + linear_term * input_size` across every cost model
+/// registered for `cost_type` and `dimension`.
+fn cost_for_dimension(cost_type: CostType, dimension: BudgetDimension, input_size: u64) -> ExternResult<u64> {
+    let table = cost_model_table();
+    let models = table.get(&cost_type).ok_or_else(|| {
+        wasm_error!(WasmErrorInner::Guest(format!("E_UNKNOWN_COST_TYPE: {:?}", cost_type)))
+    })?;
+    Ok(models
+        .iter()
+        .filter(|m| m.dimension == dimension)
+        .map(|m| m.const_term + m.linear_term * input_size)
+        .sum())
+}
+
+// Total per-dimension budget per window.
+pub const MAX_COMPUTE_RU_PER_WINDOW: u64 = 100;
+pub const MAX_MEMORY_RU_PER_WINDOW: u64 = 100;
 // A 24-hour window for budget replenishment, aligning with natural human cycles
 pub const BUDGET_WINDOW_SECONDS: u64 = 86400;
 
-pub fn consume_budget(agent: &AgentPubKey, cost: f32) -> ExternResult<()> {
-    let mut budget_state = get_budget_state(agent)?;
+pub struct BudgetState {
+    pub agent: AgentPubKey,
+    pub remaining_compute_ru: u64,
+    pub remaining_memory_ru: u64,
+    pub window_start: Timestamp,
+    pub seq: u64,
+    pub prev: Option<ActionHash>,
+}
 
-    if budget_state.remaining_ru < cost {
-        return Err(wasm_error!(WasmErrorInner::Guest("E_BUDGET_EXCEEDED: Agent budget exceeded.".into())));
+/// Pure arithmetic core of `consume_budget`: given the current remaining
+/// balances and the two dimension costs, either the post-deduction
+/// balances or which dimension was insufficient. Factored out of
+/// `consume_budget` so it has no `ExternResult`/DHT dependency and can be
+/// driven directly by a fuzz target — unlike `u64` subtraction guarded
+/// only by a debug-mode overflow check, this makes the "never go
+/// negative" invariant checkable without a conductor.
+pub fn try_consume(
+    remaining_compute_ru: u64,
+    remaining_memory_ru: u64,
+    compute_cost: u64,
+    memory_cost: u64,
+) -> Result<(u64, u64), BudgetDimension> {
+    if remaining_compute_ru < compute_cost {
+        return Err(BudgetDimension::Compute);
     }
+    if remaining_memory_ru < memory_cost {
+        return Err(BudgetDimension::Memory);
+    }
+    Ok((remaining_compute_ru - compute_cost, remaining_memory_ru - memory_cost))
+}
+
+/// Pure arithmetic core of `allocate_budget`: add `amount` to `remaining`
+/// and cap at `2 * max_per_window`, saturating instead of wrapping so a
+/// pathological `remaining + amount` near `u64::MAX` can't silently wrap
+/// past the cap.
+pub fn capped_allocate(remaining: u64, amount: u64, max_per_window: u64) -> u64 {
+    remaining.saturating_add(amount).min(max_per_window * 2)
+}
+
+/// Charge `cost_type` against `agent`'s budget, scaled by `input_size`.
+/// Checks compute and memory independently and fails with whichever
+/// dimension is exhausted first.
+pub fn consume_budget(agent: &AgentPubKey, cost_type: CostType, input_size: u64) -> ExternResult<()> {
+    let budget_state = get_budget_state(agent)?;
+
+    let compute_cost = cost_for_dimension(cost_type, BudgetDimension::Compute, input_size)?;
+    let memory_cost = cost_for_dimension(cost_type, BudgetDimension::Memory, input_size)?;
 
-    budget_state.remaining_ru -= cost;
-    update_budget_entry(agent, budget_state.remaining_ru, budget_state.window_start)?; // Update the budget entry
+    let (remaining_compute_ru, remaining_memory_ru) = try_consume(
+        budget_state.remaining_compute_ru,
+        budget_state.remaining_memory_ru,
+        compute_cost,
+        memory_cost,
+    )
+    .map_err(|dimension| {
+        let remaining = match dimension {
+            BudgetDimension::Compute => budget_state.remaining_compute_ru,
+            BudgetDimension::Memory => budget_state.remaining_memory_ru,
+        };
+        let requested = match dimension {
+            BudgetDimension::Compute => compute_cost,
+            BudgetDimension::Memory => memory_cost,
+        };
+        budget_exceeded_error(BudgetExceededDetail { operation: cost_type, dimension, requested, remaining })
+    })?;
+
+    update_budget_entry(agent, remaining_compute_ru, remaining_memory_ru, budget_state.window_start, &budget_state)?;
     Ok(())
 }
 
+/// Get current budget state for an agent — the entry with the highest
+/// `seq`, not merely the one with the latest `window_start` link
+/// timestamp, since two entries can share a `window_start` and only
+/// `seq` tells which actually supersedes the other.
 pub fn get_budget_state(agent: &AgentPubKey) -> ExternResult<BudgetState> {
     let now = sys_time()?;
     let agent_address = agent.clone();
 
-    let path = Path::from(format!("agent_budget.{}", agent_address));
-    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AgentBudget)?.build())?;
+    let path = Path::from(format!("dim_budget.{}", agent_address));
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::DimensionalAgentBudget)?.build())?;
 
-    let mut latest_budget: Option<BudgetEntry> = None;
-    let mut latest_timestamp: Option<Timestamp> = None;
+    let mut latest: Option<(ActionHash, DimensionalBudgetEntry)> = None;
 
     for link in links {
-        if let Some(record) = get(link.target.clone(), GetOptions::default())? {
-            if let Some(budget_entry) = record.entry().to_app_option::<BudgetEntry>()? {
-                if latest_timestamp.is_none() || budget_entry.window_start > latest_timestamp.unwrap() {
-                    latest_budget = Some(budget_entry);
-                    latest_timestamp = Some(budget_entry.window_start);
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(budget_entry) = record.entry().to_app_option::<DimensionalBudgetEntry>()? {
+                if latest.as_ref().is_none_or(|(_, current)| budget_entry.seq > current.seq) {
+                    latest = Some((action_hash, budget_entry));
                 }
             }
         }
     }
 
-    match latest_budget {
-        Some(budget) if (now.as_seconds() - budget.window_start.as_seconds()) < BUDGET_WINDOW_SECONDS => {
-            Ok(BudgetState { agent: agent_address, remaining_ru: budget.remaining_ru, window_start: budget.window_start })
-        },
-        _ => {
-            // Initialize or reset budget
-            let new_budget = BudgetState { agent: agent_address, remaining_ru: MAX_RU_PER_WINDOW, window_start: now };
-            create_budget_entry(agent, new_budget.remaining_ru, new_budget.window_start)?; // Create a new budget entry
-            Ok(new_budget)
+    match latest {
+        Some((hash, budget)) if (now.as_seconds() - budget.window_start.as_seconds()) < BUDGET_WINDOW_SECONDS => {
+            Ok(BudgetState {
+                agent: agent_address,
+                remaining_compute_ru: budget.remaining_compute_ru,
+                remaining_memory_ru: budget.remaining_memory_ru,
+                window_start: budget.window_start,
+                seq: budget.seq,
+                prev: Some(hash),
+            })
+        }
+        Some((hash, budget)) => {
+            // Window elapsed: reset the balance, but keep the chain going
+            // (next `seq`, `prev` pointing at the expired entry) so
+            // `reconstruct_budget` still sees a single unbroken ledger.
+            let hash = create_budget_entry(agent, MAX_COMPUTE_RU_PER_WINDOW, MAX_MEMORY_RU_PER_WINDOW, now, budget.seq + 1, Some(hash))?;
+            Ok(BudgetState {
+                agent: agent_address,
+                remaining_compute_ru: MAX_COMPUTE_RU_PER_WINDOW,
+                remaining_memory_ru: MAX_MEMORY_RU_PER_WINDOW,
+                window_start: now,
+                seq: budget.seq + 1,
+                prev: Some(hash),
+            })
+        }
+        None => {
+            let hash = create_budget_entry(agent, MAX_COMPUTE_RU_PER_WINDOW, MAX_MEMORY_RU_PER_WINDOW, now, 0, None)?;
+            Ok(BudgetState {
+                agent: agent_address,
+                remaining_compute_ru: MAX_COMPUTE_RU_PER_WINDOW,
+                remaining_memory_ru: MAX_MEMORY_RU_PER_WINDOW,
+                window_start: now,
+                seq: 0,
+                prev: Some(hash),
+            })
         }
     }
 }
 
-fn create_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Timestamp) -> ExternResult<ActionHash> {
-    let budget_entry = BudgetEntry { agent: agent.clone(), remaining_ru, window_start };
+fn create_budget_entry(
+    agent: &AgentPubKey,
+    remaining_compute_ru: u64,
+    remaining_memory_ru: u64,
+    window_start: Timestamp,
+    seq: u64,
+    prev: Option<ActionHash>,
+) -> ExternResult<ActionHash> {
+    let budget_entry = DimensionalBudgetEntry { agent: agent.clone(), remaining_compute_ru, remaining_memory_ru, window_start, seq, prev };
     let hash = create_entry(&budget_entry)?;
-    let path = Path::from(format!("agent_budget.{}", agent.clone()));
-    create_link(path.path_entry_hash()?, hash.clone(), LinkTypes::AgentBudget, ())?;
+    let path = Path::from(format!("dim_budget.{}", agent.clone()));
+    create_link(path.path_entry_hash()?, hash.clone(), LinkTypes::DimensionalAgentBudget, ())?;
     Ok(hash)
 }
 
-fn update_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Timestamp) -> ExternResult<ActionHash> {
-    let budget_entry = BudgetEntry { agent: agent.clone(), remaining_ru, window_start };
-    let hash = create_entry(&budget_entry)?;
-    let path = Path::from(format!("agent_budget.{}", agent.clone()));
-    create_link(path.path_entry_hash()?, hash.clone(), LinkTypes::AgentBudget, ())?;
-    Ok(hash)
+/// Compare-and-set the agent's budget to the given balances: re-reads the
+/// chain's current tip and rejects with `E_BUDGET_CONFLICT` if it no
+/// longer matches `based_on` (another writer already advanced `seq` past
+/// what `based_on` saw), rather than blindly writing a new entry and
+/// letting two concurrent deductions silently clobber each other.
+fn update_budget_entry(
+    agent: &AgentPubKey,
+    remaining_compute_ru: u64,
+    remaining_memory_ru: u64,
+    window_start: Timestamp,
+    based_on: &BudgetState,
+) -> ExternResult<ActionHash> {
+    let current_tip = current_seq_and_hash(agent)?;
+    if current_tip != (based_on.seq, based_on.prev.clone()) {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "E_BUDGET_CONFLICT: budget ledger advanced concurrently; re-read and retry".to_string()
+        )));
+    }
+    create_budget_entry(agent, remaining_compute_ru, remaining_memory_ru, window_start, based_on.seq + 1, based_on.prev.clone())
 }
 
-pub struct BudgetState {
-    pub agent: AgentPubKey,
-    pub remaining_ru: f32,
-    pub window_start: Timestamp,
+/// The `(seq, ActionHash)` of the agent's current chain tip, or `(0, None)`
+/// if the agent has no budget entries yet.
+fn current_seq_and_hash(agent: &AgentPubKey) -> ExternResult<(u64, Option<ActionHash>)> {
+    let path = Path::from(format!("dim_budget.{}", agent.clone()));
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::DimensionalAgentBudget)?.build())?;
+
+    let mut latest: Option<(ActionHash, u64)> = None;
+    for link in links {
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(entry) = record.entry().to_app_option::<DimensionalBudgetEntry>()? {
+                if latest.as_ref().is_none_or(|(_, seq)| entry.seq > *seq) {
+                    latest = Some((action_hash, entry.seq));
+                }
+            }
+        }
+    }
+    Ok(match latest {
+        Some((hash, seq)) => (seq, Some(hash)),
+        None => (0, None),
+    })
 }
 
-/// BudgetEngine manages resource units (RU) for autonomous operations
-/// Implements resource-bounded autonomy with graceful degradation
-pub struct BudgetEngine;
+/// Fold `agent`'s `prev`-linked budget chain from its current tip back to
+/// genesis, returning it oldest-first. Detects a fork — two reachable
+/// entries claiming the same `seq` — which the CAS in `update_budget_entry`
+/// should prevent in the common case, but a network partition that lets
+/// two peers both observe a stale tip could still produce one.
+pub fn reconstruct_budget(agent: &AgentPubKey) -> ExternResult<Vec<DimensionalBudgetEntry>> {
+    let path = Path::from(format!("dim_budget.{}", agent.clone()));
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::DimensionalAgentBudget)?.build())?;
 
-impl BudgetEngine {
-    /// Reserve resource units (RU) for an operation
-    /// Returns an error if insufficient budget is available
-    pub fn reserve_ru(agent: &AgentPubKey, amount: f32) -> ExternResult<()> {
-        let budget_state = get_budget_state(agent)?;
+    let mut by_hash: HashMap<ActionHash, DimensionalBudgetEntry> = HashMap::new();
+    let mut tip: Option<(ActionHash, u64)> = None;
+    for link in links {
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(entry) = record.entry().to_app_option::<DimensionalBudgetEntry>()? {
+                if tip.as_ref().is_none_or(|(_, seq)| entry.seq > *seq) {
+                    tip = Some((action_hash.clone(), entry.seq));
+                }
+                by_hash.insert(action_hash, entry);
+            }
+        }
+    }
 
-        if budget_state.remaining_ru >= amount {
-            // Consume the budget by updating the state
-            consume_budget(agent, amount)?;
-            Ok(())
-        } else {
-            Err(wasm_error!(WasmErrorInner::Guest(
-                format!(
-                    "E_INSUFFICIENT_RU: need {:.2} RU, have {:.2} RU. Budget resets at {:?}",
-                    amount,
-                    budget_state.remaining_ru,
-                    budget_state.window_start.as_seconds() + BUDGET_WINDOW_SECONDS
-                )
-            )))
+    let mut chain = Vec::new();
+    let mut seen_seqs = std::collections::HashSet::new();
+    let mut cursor = tip.map(|(hash, _)| hash);
+    while let Some(hash) = cursor {
+        let Some(entry) = by_hash.get(&hash) else { break };
+        if !seen_seqs.insert(entry.seq) {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "E_BUDGET_FORK: duplicate seq {} reachable in {}'s budget chain",
+                entry.seq, agent
+            ))));
         }
+        chain.push(entry.clone());
+        cursor = entry.prev.clone();
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// BudgetEngine manages multi-dimensional resource units (RU) for
+/// autonomous operations. Implements resource-bounded autonomy with
+/// graceful degradation, precise per-dimension over-budget detection.
+pub struct BudgetEngine;
+
+impl BudgetEngine {
+    /// Reserve resource units (RU) for `cost_type` scaled by `input_size`.
+    /// Returns an error naming whichever dimension is insufficient.
+    pub fn reserve_ru(agent: &AgentPubKey, cost_type: CostType, input_size: u64) -> ExternResult<()> {
+        consume_budget(agent, cost_type, input_size)
     }
 
-    /// Allocate additional budget to an agent
-    /// Used for budget replenishment or granting additional resources
-    pub fn allocate_budget(agent: &AgentPubKey, amount: f32) -> ExternResult<()> {
+    /// Allocate additional budget to an agent, independently per dimension.
+    /// Caps at 2x max per dimension to prevent abuse.
+    pub fn allocate_budget(agent: &AgentPubKey, compute_amount: u64, memory_amount: u64) -> ExternResult<()> {
         let budget_state = get_budget_state(agent)?;
-        let new_total = budget_state.remaining_ru + amount;
 
-        // Cap at maximum budget to prevent abuse
-        let capped_total = new_total.min(MAX_RU_PER_WINDOW * 2.0); // Allow 2x max for special cases
+        let capped_compute = capped_allocate(budget_state.remaining_compute_ru, compute_amount, MAX_COMPUTE_RU_PER_WINDOW);
+        let capped_memory = capped_allocate(budget_state.remaining_memory_ru, memory_amount, MAX_MEMORY_RU_PER_WINDOW);
 
-        update_budget_entry(agent, capped_total, budget_state.window_start)?;
+        update_budget_entry(agent, capped_compute, capped_memory, budget_state.window_start, &budget_state)?;
         Ok(())
     }
 
-    /// Get current budget status for an agent
+    /// Get current per-dimension budget status for an agent
     pub fn get_status(agent: &AgentPubKey) -> ExternResult<BudgetState> {
         get_budget_state(agent)
     }
 
-    /// Check if an agent has sufficient budget for an operation
-    pub fn has_budget(agent: &AgentPubKey, amount: f32) -> ExternResult<bool> {
+    /// Check if an agent has sufficient budget, in both dimensions, for
+    /// `cost_type` scaled by `input_size`, without consuming it.
+    pub fn has_budget(agent: &AgentPubKey, cost_type: CostType, input_size: u64) -> ExternResult<bool> {
         let budget_state = get_budget_state(agent)?;
-        Ok(budget_state.remaining_ru >= amount)
+        let compute_cost = cost_for_dimension(cost_type, BudgetDimension::Compute, input_size)?;
+        let memory_cost = cost_for_dimension(cost_type, BudgetDimension::Memory, input_size)?;
+        Ok(budget_state.remaining_compute_ru >= compute_cost && budget_state.remaining_memory_ru >= memory_cost)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_exceeded_error_round_trips_through_its_message() {
+        let detail = BudgetExceededDetail {
+            operation: CostType::AddKnowledge,
+            dimension: BudgetDimension::Memory,
+            requested: 12,
+            remaining: 5,
+        };
+
+        let message = format_budget_exceeded(&detail);
+        let parsed = parse_budget_exceeded_error(&message).expect("should parse its own message");
+
+        assert_eq!(parsed.operation, detail.operation);
+        assert_eq!(parsed.dimension, detail.dimension);
+        assert_eq!(parsed.requested, detail.requested);
+        assert_eq!(parsed.remaining, detail.remaining);
+    }
+
+    #[test]
+    fn parse_rejects_other_errors() {
+        assert!(parse_budget_exceeded_error("E_UNKNOWN_COST_TYPE: LinkEdge").is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use hdk::prelude::*;
+use std::collections::HashMap;
+
+use crate::budget::{CostModel, CostType};
+
+/// One (input_size, measured_cost) observation from a calibration sweep.
+#[derive(Clone, Copy, Debug)]
+pub struct CalibrationSample {
+    pub input_size: u64,
+    pub measured_cost_ns: u64,
+}
+
+/// Fit `cost = const_term + linear_term * input_size` over `samples` via
+/// ordinary least-squares regression, so the declared RU coefficients can be
+/// machine-verified instead of hand-assigned magic numbers.
+pub fn fit_linear_model(samples: &[CalibrationSample]) -> CostModel {
+    let n = samples.len() as f64;
+    if n == 0.0 {
+        return CostModel { dimension: crate::budget::BudgetDimension::Compute, const_term: 0, linear_term: 0 };
+    }
+
+    let mean_x: f64 = samples.iter().map(|s| s.input_size as f64).sum::<f64>() / n;
+    let mean_y: f64 = samples.iter().map(|s| s.measured_cost_ns as f64).sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for s in samples {
+        let dx = s.input_size as f64 - mean_x;
+        let dy = s.measured_cost_ns as f64 - mean_y;
+        numerator += dx * dy;
+        denominator += dx * dx;
+    }
+
+    let linear_term = if denominator.abs() > f64::EPSILON { numerator / denominator } else { 0.0 };
+    let const_term = mean_y - linear_term * mean_x;
+
+    CostModel {
+        dimension: crate::budget::BudgetDimension::Compute,
+        const_term: const_term.max(0.0).round() as u64,
+        linear_term: linear_term.max(0.0).round() as u64,
+    }
+}
+
+/// Run `op` once per `input_size` in `sweep`, timing each call with
+/// `sys_time()` so the measured cost reflects real host/wasm execution
+/// rather than a guessed constant.
+pub fn sweep_cost_type(sweep: &[u64], mut op: impl FnMut(u64) -> ExternResult<()>) -> ExternResult<Vec<CalibrationSample>> {
+    let mut samples = Vec::with_capacity(sweep.len());
+    for &input_size in sweep {
+        let start = sys_time()?;
+        op(input_size)?;
+        let end = sys_time()?;
+        let measured_cost_ns = (end.as_micros() - start.as_micros()).max(0) as u64 * 1000;
+        samples.push(CalibrationSample { input_size, measured_cost_ns });
+    }
+    Ok(samples)
+}
+
+/// Deviation report entry: flags a cost type whose measured cost differs
+/// from the declared `const_term + linear_term * input_size` by more than
+/// `tolerance_factor` at the given `input_size`.
+#[derive(Clone, Debug)]
+pub struct CalibrationDeviation {
+    pub cost_type: CostType,
+    pub input_size: u64,
+    pub declared_cost: u64,
+    pub measured_cost_ns: u64,
+}
+
+/// Compare `measured` samples for `cost_type` against the `declared` model
+/// and return every sample deviating by more than `tolerance_factor`x in
+/// either direction, so the VVS spec numbers can be validated instead of
+/// trusted blindly.
+pub fn flag_deviations(
+    cost_type: CostType,
+    declared: &CostModel,
+    measured: &[CalibrationSample],
+    tolerance_factor: f64,
+) -> Vec<CalibrationDeviation> {
+    measured
+        .iter()
+        .filter_map(|sample| {
+            let declared_cost = declared.const_term + declared.linear_term * sample.input_size;
+            let declared_f = declared_cost.max(1) as f64;
+            let measured_f = sample.measured_cost_ns as f64;
+            let ratio = measured_f / declared_f;
+            if ratio > tolerance_factor || ratio < 1.0 / tolerance_factor {
+                Some(CalibrationDeviation {
+                    cost_type,
+                    input_size: sample.input_size,
+                    declared_cost,
+                    measured_cost_ns: sample.measured_cost_ns,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Serializable calibrated cost table: `(const_term, linear_term)` per
+/// operation name and dimension. `BudgetEngine` does not read it; charges
+/// still come from the hand-assigned `cost_model_table()` constants.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CalibratedCostTable {
+    pub compute_models: HashMap<String, (u64, u64)>,
+    pub memory_models: HashMap<String, (u64, u64)>,
+}
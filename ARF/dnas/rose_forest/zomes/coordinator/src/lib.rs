@@ -2,27 +2,47 @@ use hdk::prelude::*;
 use rose_forest_integrity::*;
 
 mod vector_ops;
-mod budget;
+// `pub` so `budget`'s pure arithmetic core (`try_consume`/`capped_allocate`)
+// can be exercised directly from outside this crate, without a live
+// conductor.
+pub mod budget;
+mod calibration;
 
 use vector_ops::Vector;
-use budget::{consume_budget, get_budget_state, BudgetState};
-use budget::{COST_ADD_KNOWLEDGE, COST_LINK_EDGE, COST_CREATE_THOUGHT_CREDENTIAL};
+use budget::{consume_budget, get_budget_state, BudgetState, CostType};
 use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddNodeInput { pub content: String, pub embedding: Vec<f32>, pub license: String, pub metadata: BTreeMap<String,String> }
+pub struct AddNodeInput {
+    pub content: String,
+    pub embedding: Vec<f32>,
+    pub license: String,
+    pub metadata: BTreeMap<String,String>,
+    /// The `SchemaRegistry` version returned by `get_current_schema` that
+    /// this node was pre-validated against, if any. Forwarded verbatim
+    /// into `RoseNode::schema_ref`; see its doc comment.
+    pub schema_ref: Option<ActionHash>,
+}
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SearchInput { pub query_embedding: Vec<f32>, pub k: usize }
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SearchResult { pub hash: ActionHash, pub score: f32, pub content: String }
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddEdgeInput { pub from: ActionHash, pub to: ActionHash, pub relationship: String, pub confidence: f32 }
+pub struct AddEdgeInput {
+    pub from: ActionHash,
+    pub to: ActionHash,
+    pub relationship: String,
+    pub confidence: f32,
+    /// See `AddNodeInput::schema_ref`.
+    pub schema_ref: Option<ActionHash>,
+}
 
 #[hdk_extern]
 pub fn add_knowledge(input: AddNodeInput) -> ExternResult<ActionHash> {
     let agent = agent_info()?.agent_latest_pubkey;
-    consume_budget(&agent, COST_ADD_KNOWLEDGE)?; // Consume budget for cognitive output
-    let node = RoseNode { content: input.content.clone(), embedding: input.embedding, license: input.license, metadata: input.metadata };
+    let input_size = (input.embedding.len() + input.content.len()) as u64;
+    consume_budget(&agent, CostType::AddKnowledge, input_size)?; // Consume budget for cognitive output
+    let node = RoseNode { content: input.content.clone(), embedding: input.embedding, license: input.license, metadata: input.metadata, schema_ref: input.schema_ref };
     let hash = create_entry(&node)?;
     let all_nodes_path = Path::from("all_nodes");
     create_link(all_nodes_path.path_entry_hash()?, hash.clone(), LinkTypes::AllNodes, ())?;
@@ -48,7 +68,10 @@ pub fn vector_search(input: SearchInput) -> ExternResult<Vec<SearchResult>> {
             }
         }
     }
-    results.sort_by(|a,b| b.score.partial_cmp(&a.score).unwrap());
+    // `partial_cmp` returns `None` for NaN scores (a malformed/adversarial
+    // embedding); treat those as equal rather than unwrapping into a panic,
+    // so one bad embedding can't take down the whole search.
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
     results.truncate(input.k);
     Ok(results)
 }
@@ -56,8 +79,9 @@ pub fn vector_search(input: SearchInput) -> ExternResult<Vec<SearchResult>> {
 #[hdk_extern]
 pub fn link_edge(input: AddEdgeInput) -> ExternResult<ActionHash> {
     let agent = agent_info()?.agent_latest_pubkey;
-    consume_budget(&agent, COST_LINK_EDGE)?; // Consume budget for cognitive linking
-    let edge = KnowledgeEdge { from: input.from.clone(), to: input.to.clone(), relationship: input.relationship, confidence: input.confidence };
+    let input_size = input.relationship.len() as u64;
+    consume_budget(&agent, CostType::LinkEdge, input_size)?; // Consume budget for cognitive linking
+    let edge = KnowledgeEdge { from: input.from.clone(), to: input.to.clone(), relationship: input.relationship, confidence: input.confidence, schema_ref: input.schema_ref };
     let hash = create_entry(&edge)?;
     create_link(input.from, hash.clone(), LinkTypes::Edge, ())?;
     Ok(hash)
@@ -66,6 +90,83 @@ pub fn link_edge(input: AddEdgeInput) -> ExternResult<ActionHash> {
 #[hdk_extern]
 pub fn budget_status(_: ()) -> ExternResult<BudgetState> { get_budget_state(&agent_info()?.agent_latest_pubkey) }
 
+/// Re-measure `add_knowledge`'s real cost across a sweep of embedding sizes
+/// and report any declared RU coefficient that deviates from what was
+/// actually observed by more than `tolerance_factor`, so the VVS spec
+/// numbers can be validated and regenerated instead of guessed.
+#[hdk_extern]
+pub fn calibrate_add_knowledge_cost(tolerance_factor: f64) -> ExternResult<Vec<calibration::CalibrationDeviation>> {
+    let sweep = vec![32u64, 64, 128, 256, 512, 1024];
+    let samples = calibration::sweep_cost_type(&sweep, |input_size| {
+        let embedding = vec![0.0_f32; input_size as usize];
+        let node = RoseNode {
+            content: String::new(),
+            embedding,
+            license: "MIT".into(),
+            metadata: BTreeMap::new(),
+            schema_ref: None,
+        };
+        create_entry(&node)?;
+        Ok(())
+    })?;
+
+    let declared = budget::cost_model_for(CostType::AddKnowledge, budget::BudgetDimension::Compute)?;
+    Ok(calibration::flag_deviations(CostType::AddKnowledge, &declared, &samples, tolerance_factor))
+}
+
+/// Input for `publish_schema_version`: the governance agent supplies the
+/// new rules directly rather than the zome deriving a "next version" from
+/// prior DHT state, since integrity validation (`validate_schema_registry`)
+/// must check this entry in isolation without depending on mutable state.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PublishSchemaInput {
+    pub version: u32,
+    pub license_allow_list: Vec<String>,
+    pub min_embedding_dim: u32,
+    pub max_embedding_dim: u32,
+    pub valid_relationships: Vec<String>,
+}
+
+/// Publish a new `SchemaRegistry` version. Only the hardcoded governance
+/// agent's signature passes `validate_schema_registry`, so a non-governance
+/// caller's entry is accepted locally but rejected by every peer that
+/// validates it.
+#[hdk_extern]
+pub fn publish_schema_version(input: PublishSchemaInput) -> ExternResult<ActionHash> {
+    let agent = agent_info()?.agent_latest_pubkey;
+    let schema = SchemaRegistry {
+        version: input.version,
+        license_allow_list: input.license_allow_list,
+        min_embedding_dim: input.min_embedding_dim,
+        max_embedding_dim: input.max_embedding_dim,
+        valid_relationships: input.valid_relationships,
+        governance_agent: agent,
+    };
+    let hash = create_entry(&schema)?;
+    let schema_path = Path::from("schema_registry");
+    create_link(schema_path.path_entry_hash()?, hash.clone(), LinkTypes::Schema, ())?;
+    Ok(hash)
+}
+
+/// Fetch the most recently published `SchemaRegistry` version, so clients
+/// can pre-validate a `RoseNode`/`KnowledgeEdge` against it before
+/// committing and then pass its hash back as `schema_ref`.
+#[hdk_extern]
+pub fn get_current_schema(_: ()) -> ExternResult<Option<(ActionHash, SchemaRegistry)>> {
+    let schema_path = Path::from("schema_registry");
+    let links = get_links(GetLinksInputBuilder::try_new(schema_path.path_entry_hash()?, LinkTypes::Schema)?.build())?;
+    let Some(latest_link) = links.iter().max_by_key(|l| l.timestamp) else {
+        return Ok(None);
+    };
+    let action_hash = latest_link.target.clone().into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Invalid schema hash".into())))?;
+    let record = get(action_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Schema entry not found".into())))?;
+    let schema: SchemaRegistry = record.entry().to_app_option()?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Invalid SchemaRegistry entry".into())))?;
+    Ok(Some((action_hash, schema)))
+}
+
 
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -73,15 +174,18 @@ pub struct CreateThoughtCredentialInput {
     pub content: Vec<f32>, // SemanticVector
     pub connotation: i8, // TernaryScore: -1, 0, 1
     pub resonance: Vec<AgentPubKey>, // AgentEndorsement
+    /// `ActionHash`es of each `resonance` agent's prior `Endorsement` of
+    /// this agent, in the same order. See `create_endorsement`.
+    pub endorsement_actions: Vec<ActionHash>,
     pub impact: f32, // WisdomMetric
 }
 
 #[hdk_extern]
 pub fn create_thought_credential(input: CreateThoughtCredentialInput) -> ExternResult<ActionHash> {
     let agent = agent_info()?.agent_latest_pubkey;
-    // Define a cost for creating a ThoughtCredential, reflecting its significance
-    let cost_create_thought_credential: f32 = COST_CREATE_THOUGHT_CREDENTIAL;
-    consume_budget(&agent, cost_create_thought_credential)?; // Consume budget for creating a thoughtform
+    // Cost scales with the credential's semantic content size, reflecting its significance
+    let input_size = input.content.len() as u64;
+    consume_budget(&agent, CostType::CreateThoughtCredential, input_size)?; // Consume budget for creating a thoughtform
 
     let thought_credential = ThoughtCredential {
         content: input.content,
@@ -89,6 +193,7 @@ pub fn create_thought_credential(input: CreateThoughtCredentialInput) -> ExternR
         provenance: agent.clone(),
         resonance: input.resonance,
         impact: input.impact,
+        endorsement_actions: input.endorsement_actions,
     };
 
     let hash = create_entry(&thought_credential)?;
@@ -98,3 +203,14 @@ pub fn create_thought_credential(input: CreateThoughtCredentialInput) -> ExternR
 
     Ok(hash)
 }
+
+/// Publish a self-signed `Endorsement` vouching for `endorsed_agent`'s
+/// thought credentials, so `endorsed_agent` can later cite its
+/// `ActionHash` in `CreateThoughtCredentialInput::endorsement_actions` to
+/// satisfy `validate_thought_credential`'s resonance-proof check.
+#[hdk_extern]
+pub fn create_endorsement(endorsed_agent: AgentPubKey) -> ExternResult<ActionHash> {
+    let agent = agent_info()?.agent_latest_pubkey;
+    let endorsement = Endorsement { endorser: agent, endorsed_agent };
+    create_entry(&endorsement)
+}
@@ -8,6 +8,12 @@ pub struct RoseNode {
     pub embedding: Vec<f32>,
     pub license: String,
     pub metadata: BTreeMap<String, String>,
+    /// The `SchemaRegistry` version this node was validated against, if
+    /// its committer fetched one via `get_current_schema` before
+    /// submitting — `None` falls back to `default_schema`'s bootstrap
+    /// bounds, so the DNA still validates before any schema has been
+    /// published. See `resolve_schema`.
+    pub schema_ref: Option<ActionHash>,
 }
 
 #[hdk_entry_helper]
@@ -17,6 +23,87 @@ pub struct KnowledgeEdge {
     pub to: ActionHash,
     pub relationship: String,
     pub confidence: f32,
+    /// See `RoseNode::schema_ref`.
+    pub schema_ref: Option<ActionHash>,
+}
+
+/// A versioned, governance-signed snapshot of the ontology's evolvable
+/// rules — the license allow-list, embedding dimension bounds, and
+/// relationship vocabulary `validate_rose_node`/`validate_knowledge_edge`
+/// used to have hardcoded. Publishing a new version (see
+/// `validate_schema_registry`) is how the ontology grows — e.g. a new
+/// relationship name — without a zome recompile-and-redeploy; committers
+/// reference the version they validated against via `schema_ref` rather
+/// than the integrity zome re-deriving "current" from mutable DHT state,
+/// which `validate` must not depend on to stay deterministic.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct SchemaRegistry {
+    pub version: u32,
+    pub license_allow_list: Vec<String>,
+    pub min_embedding_dim: u32,
+    pub max_embedding_dim: u32,
+    pub valid_relationships: Vec<String>,
+    /// The agent this version claims to be published by — checked against
+    /// both `governance_agent()` (the DNA's hardcoded authority) and the
+    /// entry's actual committing action in `validate_schema_registry`, so
+    /// a schema can't be forged by an agent other than the one configured.
+    pub governance_agent: AgentPubKey,
+}
+
+/// The sole agent permitted to publish a new `SchemaRegistry` version.
+/// Hardcoded rather than read from DNA properties or prior DHT state,
+/// since `validate` must be a pure function of the op it's given —
+/// rotating governance means publishing a new DNA, the same durability
+/// tradeoff every other closed-membership assumption in this DNA makes.
+fn governance_agent() -> AgentPubKey {
+    let mut bytes = vec![1u8; 39];
+    bytes[..3].copy_from_slice(&[132, 32, 36]); // AgentPubKey type prefix
+    AgentPubKey::from_raw_39(bytes).expect("well-formed constant agent public key")
+}
+
+/// `schema_ref`'s resolved bounds: either the referenced `SchemaRegistry`
+/// version (fetched deterministically by hash via `must_get_entry`, safe
+/// for integrity zome validation since the hash pins an exact, already
+/// validated entry) or, for `None`, the bootstrap defaults this ontology
+/// shipped with before any schema version was ever published.
+struct ResolvedSchema {
+    license_allow_list: Vec<String>,
+    min_embedding_dim: u32,
+    max_embedding_dim: u32,
+    valid_relationships: Vec<String>,
+}
+
+fn default_schema() -> ResolvedSchema {
+    ResolvedSchema {
+        license_allow_list: vec!["MIT", "Apache-2.0", "BSD-3-Clause", "MPL-2.0", "CC-BY-4.0"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        min_embedding_dim: 32,
+        max_embedding_dim: 4096,
+        valid_relationships: vec!["relates_to", "supports", "contradicts", "heals", "releases", "neutralizes", "recalibrates"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    }
+}
+
+fn resolve_schema(schema_ref: &Option<ActionHash>) -> ExternResult<ResolvedSchema> {
+    let Some(action_hash) = schema_ref else {
+        return Ok(default_schema());
+    };
+    let schema: SchemaRegistry = must_get_entry(must_get_action(action_hash.clone())?.action().entry_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest("schema_ref does not point at an entry-creating action".into())))?
+        .clone())?
+        .try_into()
+        .map_err(|_| wasm_error!(WasmErrorInner::Guest("schema_ref does not point at a SchemaRegistry entry".into())))?;
+    Ok(ResolvedSchema {
+        license_allow_list: schema.license_allow_list,
+        min_embedding_dim: schema.min_embedding_dim,
+        max_embedding_dim: schema.max_embedding_dim,
+        valid_relationships: schema.valid_relationships,
+    })
 }
 
 #[hdk_entry_helper]
@@ -25,10 +112,37 @@ pub struct BudgetEntry {
     pub agent: AgentPubKey,
     pub remaining_ru: f32,
     pub window_start: Timestamp,
+    /// Monotonically increasing version of this agent's budget ledger:
+    /// entry `seq` supersedes every entry with a lower `seq`. Lets
+    /// `consume_budget` detect a concurrent writer that read the same
+    /// "latest" entry, instead of silently overwriting it (last-writer-wins).
+    pub seq: u64,
+    /// The `ActionHash` of the entry this one supersedes, or `None` for
+    /// the first entry in the chain. Lets `reconstruct_budget` fold the
+    /// whole ledger and detect a fork (two entries claiming the same
+    /// `seq`, or a broken chain) instead of trusting `seq` alone.
+    pub prev: Option<ActionHash>,
+}
+
+/// Per-dimension resource budget for the coordinator zome's `BudgetEngine`.
+/// Tracked separately from `BudgetEntry` (which backs the flat-RU economy
+/// memory_coordinator still uses) so compute and memory pressure can be
+/// tuned independently.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct DimensionalBudgetEntry {
+    pub agent: AgentPubKey,
+    pub remaining_compute_ru: u64,
+    pub remaining_memory_ru: u64,
+    pub window_start: Timestamp,
+    /// See `BudgetEntry::seq`.
+    pub seq: u64,
+    /// See `BudgetEntry::prev`.
+    pub prev: Option<ActionHash>,
 }
 
 #[hdk_link_types]
-pub enum LinkTypes { AllNodes, ShardMember, Edge, AgentBudget }
+pub enum LinkTypes { AllNodes, ShardMember, Edge, AgentBudget, DimensionalAgentBudget, Schema }
 
 #[hdk_entry_defs]
 #[unit_enum(UnitEntryTypes)]
@@ -36,19 +150,25 @@ pub enum EntryTypes {
     RoseNode(RoseNode),
     KnowledgeEdge(KnowledgeEdge),
     BudgetEntry(BudgetEntry),
+    DimensionalBudgetEntry(DimensionalBudgetEntry),
     ThoughtCredential(ThoughtCredential),
+    SchemaRegistry(SchemaRegistry),
+    Endorsement(Endorsement),
 }
 
 #[hdk_extern]
 pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
     match op.flattened::<EntryTypes, LinkTypes>()? {
         FlatOp::StoreEntry(store) => match store {
-            OpEntry::CreateEntry { app_entry, .. } | OpEntry::UpdateEntry { app_entry, .. } => {
+            OpEntry::CreateEntry { app_entry, action } | OpEntry::UpdateEntry { app_entry, action } => {
                 match app_entry {
                     EntryTypes::RoseNode(node) => validate_rose_node(&node),
                     EntryTypes::KnowledgeEdge(edge) => validate_knowledge_edge(&edge),
                     EntryTypes::BudgetEntry(_) => Ok(ValidateCallbackResult::Valid),
-                    EntryTypes::ThoughtCredential(credential) => validate_thought_credential(&credential),
+                    EntryTypes::DimensionalBudgetEntry(_) => Ok(ValidateCallbackResult::Valid),
+                    EntryTypes::ThoughtCredential(credential) => validate_thought_credential(&credential, &action.author),
+                    EntryTypes::SchemaRegistry(schema) => validate_schema_registry(&schema, &action.author),
+                    EntryTypes::Endorsement(endorsement) => validate_endorsement(&endorsement, &action.author),
                 }
             }
             _ => Ok(ValidateCallbackResult::Valid),
@@ -57,7 +177,61 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
     }
 }
 
-fn validate_thought_credential(credential: &ThoughtCredential) -> ExternResult<ValidateCallbackResult> {
+fn validate_schema_registry(schema: &SchemaRegistry, author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    if schema.governance_agent != governance_agent() {
+        return Ok(ValidateCallbackResult::Invalid("E_SCHEMA_GOVERNANCE: governance_agent is not the configured authority".into()));
+    }
+    if author != &schema.governance_agent {
+        return Ok(ValidateCallbackResult::Invalid("E_SCHEMA_AUTHOR: schema must be signed by its own governance_agent".into()));
+    }
+    if schema.version == 0 {
+        return Ok(ValidateCallbackResult::Invalid("E_SCHEMA_VERSION: version must be nonzero".into()));
+    }
+    if schema.license_allow_list.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid("E_SCHEMA_LICENSES: license_allow_list must not be empty".into()));
+    }
+    if schema.valid_relationships.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid("E_SCHEMA_RELATIONSHIPS: valid_relationships must not be empty".into()));
+    }
+    if schema.min_embedding_dim == 0 || schema.min_embedding_dim > schema.max_embedding_dim {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "E_SCHEMA_DIMS: min_embedding_dim {} must be nonzero and <= max_embedding_dim {}",
+            schema.min_embedding_dim, schema.max_embedding_dim
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// The largest endorsement count a credential can ever be required to
+/// reach — `validate_thought_credential` scales the actual requirement by
+/// `impact` so a credential claiming the maximum possible significance
+/// demands the most social proof, and a near-zero-impact one demands none.
+const MAX_ENDORSEMENT_REQUIREMENT: u32 = 10;
+
+fn minimum_endorsement_count(impact: f32) -> usize {
+    (impact * MAX_ENDORSEMENT_REQUIREMENT as f32).ceil() as usize
+}
+
+/// Confirms `action_hash` points at a real, self-signed `Endorsement` entry
+/// vouching for `endorsed_agent` from `endorser` — fetched by hash via
+/// `must_get_entry`/`must_get_action` so the check stays deterministic,
+/// the same pattern `resolve_schema` uses for `schema_ref`.
+fn verify_endorsement(action_hash: &ActionHash, endorser: &AgentPubKey, endorsed_agent: &AgentPubKey) -> ExternResult<bool> {
+    let endorsing_action = must_get_action(action_hash.clone())?;
+    if endorsing_action.action().author() != endorser {
+        return Ok(false);
+    }
+    let Some(entry_hash) = endorsing_action.action().entry_hash() else {
+        return Ok(false);
+    };
+    let endorsement: Endorsement = match must_get_entry(entry_hash.clone())?.try_into() {
+        Ok(endorsement) => endorsement,
+        Err(_) => return Ok(false),
+    };
+    Ok(&endorsement.endorser == endorser && &endorsement.endorsed_agent == endorsed_agent)
+}
+
+fn validate_thought_credential(credential: &ThoughtCredential, author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
     let dim = credential.content.len();
     if dim < 32 || dim > 4096 {
         return Ok(ValidateCallbackResult::Invalid(format!("E_THOUGHT_CONTENT_DIM: {} out of [32,4096]", dim)));
@@ -68,18 +242,49 @@ fn validate_thought_credential(credential: &ThoughtCredential) -> ExternResult<V
     if !(0.0..=1.0).contains(&credential.impact) {
         return Ok(ValidateCallbackResult::Invalid(format!("E_IMPACT: {} out of [0,1]", credential.impact)));
     }
-    // Further validation could include checking provenance signature or resonance thresholds
+    if author != &credential.provenance {
+        return Ok(ValidateCallbackResult::Invalid("E_PROVENANCE: credential must be signed by its own provenance agent".into()));
+    }
+    if credential.resonance.contains(&credential.provenance) {
+        return Ok(ValidateCallbackResult::Invalid("E_SELF_RESONANCE: provenance agent cannot endorse its own credential".into()));
+    }
+    let mut seen = std::collections::BTreeSet::new();
+    if !credential.resonance.iter().all(|agent| seen.insert(agent.clone())) {
+        return Ok(ValidateCallbackResult::Invalid("E_DUPLICATE_RESONANCE: resonance must not list an agent twice".into()));
+    }
+    let required = minimum_endorsement_count(credential.impact);
+    if credential.resonance.len() < required {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "E_RESONANCE_COUNT: impact {} requires at least {} endorsers, found {}",
+            credential.impact, required, credential.resonance.len()
+        )));
+    }
+    if credential.endorsement_actions.len() != credential.resonance.len() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "E_ENDORSEMENT_PROOF: {} endorsement_actions does not match {} resonance entries",
+            credential.endorsement_actions.len(), credential.resonance.len()
+        )));
+    }
+    for (endorser, action_hash) in credential.resonance.iter().zip(credential.endorsement_actions.iter()) {
+        if !verify_endorsement(action_hash, endorser, &credential.provenance)? {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "E_ENDORSEMENT_PROOF: endorsement_actions entry for {:?} does not verify", endorser
+            )));
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 fn validate_rose_node(node: &RoseNode) -> ExternResult<ValidateCallbackResult> {
-    const VALID_LICENSES: &[&str] = &["MIT","Apache-2.0","BSD-3-Clause","MPL-2.0","CC-BY-4.0"];
-    if !VALID_LICENSES.contains(&node.license.as_str()) {
+    let schema = resolve_schema(&node.schema_ref)?;
+    if !schema.license_allow_list.iter().any(|l| l == &node.license) {
         return Ok(ValidateCallbackResult::Invalid(format!("E_LICENSE: '{}' not allowed", node.license)));
     }
-    let dim = node.embedding.len();
-    if dim < 32 || dim > 4096 {
-        return Ok(ValidateCallbackResult::Invalid(format!("E_EMBED_DIM: {} out of [32,4096]", dim)));
+    let dim = node.embedding.len() as u32;
+    if dim < schema.min_embedding_dim || dim > schema.max_embedding_dim {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "E_EMBED_DIM: {} out of [{},{}]", dim, schema.min_embedding_dim, schema.max_embedding_dim
+        )));
     }
     match (node.metadata.get("model_id"), node.metadata.get("model_card_hash")) {
         (Some(_), Some(hash)) if hash.starts_with("sha256:") => Ok(ValidateCallbackResult::Valid),
@@ -87,13 +292,22 @@ fn validate_rose_node(node: &RoseNode) -> ExternResult<ValidateCallbackResult> {
     }
 }
 
+fn validate_endorsement(endorsement: &Endorsement, author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    if author != &endorsement.endorser {
+        return Ok(ValidateCallbackResult::Invalid("E_ENDORSEMENT_AUTHOR: endorsement must be signed by its own endorser".into()));
+    }
+    if endorsement.endorser == endorsement.endorsed_agent {
+        return Ok(ValidateCallbackResult::Invalid("E_ENDORSEMENT_SELF: an agent cannot endorse itself".into()));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
 fn validate_knowledge_edge(edge: &KnowledgeEdge) -> ExternResult<ValidateCallbackResult> {
     if !(0.0..=1.0).contains(&edge.confidence) {
         return Ok(ValidateCallbackResult::Invalid(format!("E_CONFIDENCE: {} out of [0,1]", edge.confidence)));
     }
-    // New relationship types reflecting the manifesto
-    const VALID_RELATIONSHIPS: &[&str] = &["relates_to", "supports", "contradicts", "heals", "releases", "neutralizes", "recalibrates"];
-    if !VALID_RELATIONSHIPS.contains(&edge.relationship.as_str()) {
+    let schema = resolve_schema(&edge.schema_ref)?;
+    if !schema.valid_relationships.iter().any(|r| r == &edge.relationship) {
         return Ok(ValidateCallbackResult::Invalid(format!("E_RELATIONSHIP: '{}' not allowed", edge.relationship)));
     }
     Ok(ValidateCallbackResult::Valid)
@@ -110,4 +324,152 @@ pub struct ThoughtCredential {
     pub provenance: AgentPubKey, // AgentSignature
     pub resonance: Vec<AgentPubKey>, // AgentEndorsement
     pub impact: f32, // WisdomMetric
+    /// `ActionHash`es of `Endorsement` entries, one per `resonance` agent
+    /// at the same index, proving that agent really did vouch for
+    /// `provenance` rather than `resonance` merely listing names. Checked
+    /// by `verify_endorsement`.
+    pub endorsement_actions: Vec<ActionHash>,
+}
+
+/// A self-signed statement that `endorser` vouches for `endorsed_agent`'s
+/// thought credentials generally, rather than one specific credential —
+/// so a single endorsement can back `resonance` on any number of
+/// `ThoughtCredential`s that agent later publishes.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Endorsement {
+    pub endorser: AgentPubKey,
+    pub endorsed_agent: AgentPubKey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_agent_pub_key() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn fake_agent_pub_key_2() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn fake_agent_pub_key_3() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+            2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn base_credential(provenance: AgentPubKey) -> ThoughtCredential {
+        ThoughtCredential {
+            content: vec![0.0; 32],
+            connotation: 1,
+            provenance,
+            resonance: Vec::new(),
+            impact: 0.0,
+            endorsement_actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn minimum_endorsement_count_scales_with_impact() {
+        assert_eq!(minimum_endorsement_count(0.0), 0);
+        assert_eq!(minimum_endorsement_count(0.05), 1); // ceil(0.5)
+        assert_eq!(minimum_endorsement_count(0.1), 1);
+        assert_eq!(minimum_endorsement_count(1.0), MAX_ENDORSEMENT_REQUIREMENT as usize);
+    }
+
+    #[test]
+    fn zero_impact_credential_needs_no_resonance() {
+        let provenance = fake_agent_pub_key();
+        let credential = base_credential(provenance.clone());
+        let result = validate_thought_credential(&credential, &provenance).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Valid));
+    }
+
+    #[test]
+    fn rejects_credential_not_signed_by_its_own_provenance() {
+        let provenance = fake_agent_pub_key();
+        let other_author = fake_agent_pub_key_2();
+        let credential = base_credential(provenance);
+        let result = validate_thought_credential(&credential, &other_author).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_PROVENANCE")));
+    }
+
+    #[test]
+    fn rejects_self_resonance() {
+        let provenance = fake_agent_pub_key();
+        let mut credential = base_credential(provenance.clone());
+        credential.resonance = vec![provenance.clone()];
+        let result = validate_thought_credential(&credential, &provenance).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_SELF_RESONANCE")));
+    }
+
+    #[test]
+    fn rejects_duplicate_resonance_entries() {
+        let provenance = fake_agent_pub_key();
+        let endorser = fake_agent_pub_key_2();
+        let mut credential = base_credential(provenance.clone());
+        credential.resonance = vec![endorser.clone(), endorser];
+        let result = validate_thought_credential(&credential, &provenance).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_DUPLICATE_RESONANCE")));
+    }
+
+    #[test]
+    fn rejects_resonance_below_the_required_count() {
+        let provenance = fake_agent_pub_key();
+        let mut credential = base_credential(provenance.clone());
+        credential.impact = 1.0; // requires MAX_ENDORSEMENT_REQUIREMENT endorsers
+        credential.resonance = vec![fake_agent_pub_key_2()];
+        let result = validate_thought_credential(&credential, &provenance).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_RESONANCE_COUNT")));
+    }
+
+    #[test]
+    fn rejects_mismatched_endorsement_action_count() {
+        let provenance = fake_agent_pub_key();
+        let mut credential = base_credential(provenance.clone());
+        credential.impact = 0.05;
+        credential.resonance = vec![fake_agent_pub_key_2()];
+        credential.endorsement_actions = Vec::new();
+        let result = validate_thought_credential(&credential, &provenance).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_ENDORSEMENT_PROOF")));
+    }
+
+    #[test]
+    fn validate_endorsement_requires_author_to_be_the_endorser() {
+        let endorser = fake_agent_pub_key();
+        let endorsed_agent = fake_agent_pub_key_2();
+        let other_author = fake_agent_pub_key_3();
+        let endorsement = Endorsement { endorser, endorsed_agent };
+        let result = validate_endorsement(&endorsement, &other_author).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_ENDORSEMENT_AUTHOR")));
+    }
+
+    #[test]
+    fn validate_endorsement_rejects_self_endorsement() {
+        let agent = fake_agent_pub_key();
+        let endorsement = Endorsement { endorser: agent.clone(), endorsed_agent: agent.clone() };
+        let result = validate_endorsement(&endorsement, &agent).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Invalid(msg) if msg.starts_with("E_ENDORSEMENT_SELF")));
+    }
+
+    #[test]
+    fn validate_endorsement_accepts_a_well_formed_endorsement() {
+        let endorser = fake_agent_pub_key();
+        let endorsed_agent = fake_agent_pub_key_2();
+        let endorsement = Endorsement { endorser: endorser.clone(), endorsed_agent };
+        let result = validate_endorsement(&endorsement, &endorser).unwrap();
+        assert!(matches!(result, ValidateCallbackResult::Valid));
+    }
 }
\ No newline at end of file
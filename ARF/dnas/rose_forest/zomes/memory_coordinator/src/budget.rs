@@ -6,15 +6,18 @@ pub const COST_TRANSMIT_UNDERSTANDING: f32 = 1.0;
 pub const COST_RECALL_UNDERSTANDINGS: f32 = 0.1;
 pub const COST_COMPOSE_MEMORIES: f32 = 5.0;
 pub const COST_VALIDATE_TRIPLE: f32 = 2.0;
+pub const COST_QUERY_TRIPLES: f32 = 0.2;
 
 // Budget configuration
 pub const MAX_RU_PER_WINDOW: f32 = 100.0;
 pub const BUDGET_WINDOW_SECONDS: u64 = 86400; // 24 hours
 
-/// Consume budget for an operation
-/// Returns an error if insufficient budget is available
+/// Consume budget for an operation. Returns an error if insufficient
+/// budget is available, or `E_BUDGET_CONFLICT` if another write raced
+/// this one (see `update_budget_entry`) — the caller should re-read and
+/// retry rather than silently losing one of the two deductions.
 pub fn consume_budget(agent: &AgentPubKey, cost: f32) -> ExternResult<()> {
-    let mut budget_state = get_budget_state(agent)?;
+    let budget_state = get_budget_state(agent)?;
 
     if budget_state.remaining_ru < cost {
         return Err(wasm_error!(WasmErrorInner::Guest(
@@ -27,12 +30,14 @@ pub fn consume_budget(agent: &AgentPubKey, cost: f32) -> ExternResult<()> {
         )));
     }
 
-    budget_state.remaining_ru -= cost;
-    update_budget_entry(agent, budget_state.remaining_ru, budget_state.window_start)?;
+    update_budget_entry(agent, budget_state.remaining_ru - cost, budget_state.window_start, &budget_state)?;
     Ok(())
 }
 
-/// Get current budget state for an agent
+/// Get current budget state for an agent — the entry with the highest
+/// `seq`, not merely the one with the latest `window_start` link
+/// timestamp, since two entries can share a `window_start` and only
+/// `seq` tells which actually supersedes the other.
 pub fn get_budget_state(agent: &AgentPubKey) -> ExternResult<BudgetState> {
     let now = sys_time()?;
     let agent_address = agent.clone();
@@ -42,58 +47,86 @@ pub fn get_budget_state(agent: &AgentPubKey) -> ExternResult<BudgetState> {
         GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AgentBudget)?.build()
     )?;
 
-    let mut latest_budget: Option<BudgetEntry> = None;
-    let mut latest_timestamp: Option<Timestamp> = None;
+    let mut latest: Option<(ActionHash, BudgetEntry)> = None;
 
     for link in links {
-        if let Some(record) = get(link.target.clone(), GetOptions::default())? {
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
             if let Some(budget_entry) = record.entry().to_app_option::<BudgetEntry>()? {
-                if latest_timestamp.is_none() || budget_entry.window_start > latest_timestamp.unwrap() {
-                    latest_budget = Some(budget_entry);
-                    latest_timestamp = Some(budget_entry.window_start);
+                if latest.as_ref().is_none_or(|(_, current)| budget_entry.seq > current.seq) {
+                    latest = Some((action_hash, budget_entry));
                 }
             }
         }
     }
 
-    match latest_budget {
-        Some(budget) if (now.as_seconds() - budget.window_start.as_seconds()) < BUDGET_WINDOW_SECONDS => {
+    match latest {
+        Some((hash, budget)) if (now.as_seconds() - budget.window_start.as_seconds()) < BUDGET_WINDOW_SECONDS => {
             Ok(BudgetState {
                 agent: agent_address,
                 remaining_ru: budget.remaining_ru,
                 window_start: budget.window_start,
+                seq: budget.seq,
+                prev: Some(hash),
             })
         }
-        _ => {
-            // Initialize or reset budget
+        Some((hash, budget)) => {
+            // Window elapsed: reset the balance, but keep the chain going
+            // (next `seq`, `prev` pointing at the expired entry) so
+            // `reconstruct_budget` still sees a single unbroken ledger.
+            let (new_hash, seq) = create_budget_entry_after(agent, MAX_RU_PER_WINDOW, now, budget.seq + 1, Some(hash))?;
             let new_budget = BudgetState {
                 agent: agent_address,
                 remaining_ru: MAX_RU_PER_WINDOW,
                 window_start: now,
+                seq,
+                prev: Some(new_hash),
             };
-            create_budget_entry(agent, new_budget.remaining_ru, new_budget.window_start)?;
             Ok(new_budget)
         }
+        None => {
+            let (hash, seq) = create_budget_entry_after(agent, MAX_RU_PER_WINDOW, now, 0, None)?;
+            Ok(BudgetState { agent: agent_address, remaining_ru: MAX_RU_PER_WINDOW, window_start: now, seq, prev: Some(hash) })
+        }
     }
 }
 
-fn create_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Timestamp) -> ExternResult<ActionHash> {
-    let budget_entry = BudgetEntry {
-        agent: agent.clone(),
-        remaining_ru,
-        window_start,
-    };
+/// Write the first entry in (or a fresh reset of) an agent's budget
+/// chain, at `seq = next_seq`. Returns `(ActionHash, seq)` of the entry
+/// actually written.
+fn create_budget_entry_after(
+    agent: &AgentPubKey,
+    remaining_ru: f32,
+    window_start: Timestamp,
+    next_seq: u64,
+    prev: Option<ActionHash>,
+) -> ExternResult<(ActionHash, u64)> {
+    let budget_entry = BudgetEntry { agent: agent.clone(), remaining_ru, window_start, seq: next_seq, prev };
     let hash = create_entry(&budget_entry)?;
     let path = Path::from(format!("agent_budget.{}", agent.clone()));
     create_link(path.path_entry_hash()?, hash.clone(), LinkTypes::AgentBudget, ())?;
-    Ok(hash)
+    Ok((hash, next_seq))
 }
 
-fn update_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Timestamp) -> ExternResult<ActionHash> {
+/// Compare-and-set the agent's budget to `remaining_ru`: re-reads the
+/// chain's current tip and rejects with `E_BUDGET_CONFLICT` if it no
+/// longer matches `based_on` (another writer already advanced `seq` past
+/// what `based_on` saw), rather than blindly writing a new entry and
+/// letting the two deductions silently clobber each other.
+fn update_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Timestamp, based_on: &BudgetState) -> ExternResult<ActionHash> {
+    let current_tip = current_seq_and_hash(agent)?;
+    if current_tip != (based_on.seq, based_on.prev.clone()) {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "E_BUDGET_CONFLICT: budget ledger advanced concurrently; re-read and retry".to_string()
+        )));
+    }
+
     let budget_entry = BudgetEntry {
         agent: agent.clone(),
         remaining_ru,
         window_start,
+        seq: based_on.seq + 1,
+        prev: based_on.prev.clone(),
     };
     let hash = create_entry(&budget_entry)?;
     let path = Path::from(format!("agent_budget.{}", agent.clone()));
@@ -101,8 +134,74 @@ fn update_budget_entry(agent: &AgentPubKey, remaining_ru: f32, window_start: Tim
     Ok(hash)
 }
 
+/// The `(seq, ActionHash)` of the agent's current chain tip, or `(0, None)`
+/// if the agent has no budget entries yet.
+fn current_seq_and_hash(agent: &AgentPubKey) -> ExternResult<(u64, Option<ActionHash>)> {
+    let path = Path::from(format!("agent_budget.{}", agent.clone()));
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AgentBudget)?.build())?;
+
+    let mut latest: Option<(ActionHash, u64)> = None;
+    for link in links {
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(entry) = record.entry().to_app_option::<BudgetEntry>()? {
+                if latest.as_ref().is_none_or(|(_, seq)| entry.seq > *seq) {
+                    latest = Some((action_hash, entry.seq));
+                }
+            }
+        }
+    }
+    Ok(match latest {
+        Some((hash, seq)) => (seq, Some(hash)),
+        None => (0, None),
+    })
+}
+
+/// Fold `agent`'s `prev`-linked budget chain from its current tip back to
+/// genesis, returning it oldest-first. Detects a fork — two reachable
+/// entries claiming the same `seq` — which the CAS in `update_budget_entry`
+/// should prevent in the common case, but a network partition that lets
+/// two peers both observe a stale tip could still produce one.
+pub fn reconstruct_budget(agent: &AgentPubKey) -> ExternResult<Vec<BudgetEntry>> {
+    let path = Path::from(format!("agent_budget.{}", agent.clone()));
+    let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AgentBudget)?.build())?;
+
+    let mut by_hash: std::collections::HashMap<ActionHash, BudgetEntry> = std::collections::HashMap::new();
+    let mut tip: Option<(ActionHash, u64)> = None;
+    for link in links {
+        let Some(action_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(entry) = record.entry().to_app_option::<BudgetEntry>()? {
+                if tip.as_ref().is_none_or(|(_, seq)| entry.seq > *seq) {
+                    tip = Some((action_hash.clone(), entry.seq));
+                }
+                by_hash.insert(action_hash, entry);
+            }
+        }
+    }
+
+    let mut chain = Vec::new();
+    let mut seen_seqs = std::collections::HashSet::new();
+    let mut cursor = tip.map(|(hash, _)| hash);
+    while let Some(hash) = cursor {
+        let Some(entry) = by_hash.get(&hash) else { break };
+        if !seen_seqs.insert(entry.seq) {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "E_BUDGET_FORK: duplicate seq {} reachable in {}'s budget chain",
+                entry.seq, agent
+            ))));
+        }
+        chain.push(entry.clone());
+        cursor = entry.prev.clone();
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
 pub struct BudgetState {
     pub agent: AgentPubKey,
     pub remaining_ru: f32,
     pub window_start: Timestamp,
+    pub seq: u64,
+    pub prev: Option<ActionHash>,
 }
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+use hdk::prelude::*;
+
+/// Which memory-coordinator operation a grant authorizes.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MemoryFunction {
+    Recall,
+    Compose,
+}
+
+impl MemoryFunction {
+    /// Every zome fn this grant authorizes invoking. `Recall` covers both
+    /// the local `recall_understandings` and its bridge-callable
+    /// counterpart `export_understandings_for_federation` — the same
+    /// read-access decision, just dispatched under a different function
+    /// name when the caller is a remote cell rather than this one. Without
+    /// listing both, a grant issued for `Recall` would pass `require_access`
+    /// (the in-wasm check) but still be rejected by the conductor before
+    /// `federate_memories`'s remote `call` ever reaches this wasm, since the
+    /// conductor matches the presented secret against the grant's listed
+    /// function names, not against what the wasm later decides to check.
+    fn zome_fn_names(self) -> &'static [&'static str] {
+        match self {
+            MemoryFunction::Recall => &["recall_understandings", "export_understandings_for_federation"],
+            MemoryFunction::Compose => &["compose_memories"],
+        }
+    }
+
+    /// The `CapGrantEntry.tag` a grant for this function is issued under —
+    /// `require_access` matches the presented grant's tag against this to
+    /// decide which operation it covers.
+    fn grant_label(self) -> String {
+        format!("memory_access.{}", self.zome_fn_names()[0])
+    }
+}
+
+/// What an agent wants to grant: which function, to whom. `assignees =
+/// None` issues a `Transferable` grant (anyone holding the secret, not
+/// bound to a specific identity); `Some(agents)` issues an `Assigned`
+/// grant (the secret only authorizes those agents).
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GrantSpec {
+    pub function: MemoryFunction,
+    pub assignees: Option<Vec<AgentPubKey>>,
+}
+
+/// The grant's `ActionHash` (pass to `revoke_memory_access` to withdraw
+/// it) and the secret to hand to whoever should be able to present it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GrantResult {
+    pub grant_hash: ActionHash,
+    pub cap_secret: CapSecret,
+}
+
+/// Create a capability grant authorizing `spec.function` for `spec.assignees`
+/// (or anyone holding the secret, if unassigned).
+pub fn grant(spec: GrantSpec) -> ExternResult<GrantResult> {
+    let cap_secret = generate_cap_secret()?;
+
+    let access = match spec.assignees {
+        Some(assignees) => CapAccess::Assigned { secret: cap_secret, assignees: assignees.into_iter().collect() },
+        None => CapAccess::Transferable { secret: cap_secret },
+    };
+
+    let zome_name = zome_info()?.name;
+    let mut functions = BTreeSet::new();
+    for fn_name in spec.function.zome_fn_names() {
+        functions.insert((zome_name.clone(), (*fn_name).into()));
+    }
+
+    let grant_hash = create_cap_grant(CapGrantEntry {
+        tag: spec.function.grant_label(),
+        access,
+        functions: GrantedFunctions::Listed(functions),
+    })?;
+
+    Ok(GrantResult { grant_hash, cap_secret })
+}
+
+/// Withdraw a previously issued grant.
+pub fn revoke(grant_hash: ActionHash) -> ExternResult<ActionHash> {
+    delete_cap_grant(grant_hash)
+}
+
+/// Reject a cross-agent call to `function` unless the calling agent *is*
+/// `owner` (an agent always has access to its own memories) or the call
+/// was authorized by a grant `owner` issued for `function`. `call_info()`
+/// reports which `CapGrant` the conductor matched against the presented
+/// secret before dispatching into this wasm, so the check is against
+/// what already authorized the call, not a second independent lookup.
+pub fn require_access(owner: &AgentPubKey, function: MemoryFunction) -> ExternResult<()> {
+    let info = call_info()?;
+
+    if &info.provenance == owner {
+        return Ok(());
+    }
+
+    match info.cap_grant {
+        CapGrant::ChainAuthor(_) => Ok(()),
+        CapGrant::RemoteAgent(granted) if granted.tag == function.grant_label() => Ok(()),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "E_MEMORY_ACCESS_DENIED: {} presented no valid {:?} grant for {}'s memories",
+            info.provenance, function, owner
+        )))),
+    }
+}
@@ -0,0 +1,65 @@
+use hdk::prelude::*;
+use crate::LinkTypes;
+
+/// Consecutive stable readings required before `await_consistency` treats
+/// an agent's `AgentToUnderstanding` link set as having converged — one
+/// reading could just be a lull between two still-incoming ops, so this
+/// asks the same question three more times before trusting the answer.
+const STABLE_ROUNDS_REQUIRED: u32 = 3;
+
+/// What `await_consistency` polled for and how long it took.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConsistencyReport {
+    pub observed_count: usize,
+    pub elapsed_ms: u64,
+}
+
+/// Block until `agent`'s `AgentToUnderstanding` link count, read straight
+/// from the network rather than the local cache, stops changing across
+/// `STABLE_ROUNDS_REQUIRED` consecutive polls — the same wait-for-
+/// integration idea as Holochain's multi-conductor test helpers, just run
+/// from inside the coordinator zome instead of the test harness. Returns
+/// `E_CONSISTENCY_TIMEOUT` if `timeout_ms` elapses first, so a caller like
+/// `recall_understandings` can tell "not yet propagated" apart from
+/// "genuinely absent" instead of silently returning a partial page.
+pub fn await_consistency(agent: &AgentPubKey, timeout_ms: u64) -> ExternResult<ConsistencyReport> {
+    let start = sys_time()?;
+    let mut last_count: Option<usize> = None;
+    let mut stable_rounds = 0u32;
+
+    loop {
+        let observed_count = count_understandings(agent)?;
+        let elapsed_ms = elapsed_ms(start)?;
+
+        if last_count == Some(observed_count) {
+            stable_rounds += 1;
+            if stable_rounds >= STABLE_ROUNDS_REQUIRED {
+                return Ok(ConsistencyReport { observed_count, elapsed_ms });
+            }
+        } else {
+            stable_rounds = 0;
+            last_count = Some(observed_count);
+        }
+
+        if elapsed_ms >= timeout_ms {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "E_CONSISTENCY_TIMEOUT: {}'s understanding count had not stabilized after {}ms (last observed {})",
+                agent, timeout_ms, observed_count
+            ))));
+        }
+    }
+}
+
+fn count_understandings(agent: &AgentPubKey) -> ExternResult<usize> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(agent.clone(), LinkTypes::AgentToUnderstanding)?
+            .get_options(GetStrategy::Network)
+            .build(),
+    )?;
+    Ok(links.len())
+}
+
+fn elapsed_ms(start: Timestamp) -> ExternResult<u64> {
+    let now = sys_time()?;
+    Ok((now.as_micros() - start.as_micros()).max(0) as u64 / 1000)
+}
@@ -1,12 +1,28 @@
 use hdk::prelude::*;
 use serde::{Deserialize, Serialize};
-use ontology_integrity::{KnowledgeTriple, validate_triple};
+use ontology_integrity::{
+    bootstrap_ai_ml_ontology, bootstrap_base_ontology, validate_triple, KnowledgeTriple,
+    inference::{compute_closure, DEFAULT_MAX_CLOSURE_ITERATIONS},
+    query::{query, Bindings, TriplePattern as ConjunctiveTriplePattern},
+};
 use sha2::{Sha256, Digest};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use rose_forest_integrity::BudgetEntry;
 
 mod budget;
 use budget::{consume_budget, get_budget_state, BudgetState};
-use budget::{COST_TRANSMIT_UNDERSTANDING, COST_RECALL_UNDERSTANDINGS, COST_COMPOSE_MEMORIES, COST_VALIDATE_TRIPLE};
+use budget::{COST_TRANSMIT_UNDERSTANDING, COST_RECALL_UNDERSTANDINGS, COST_COMPOSE_MEMORIES, COST_VALIDATE_TRIPLE, COST_QUERY_TRIPLES};
+
+mod telemetry;
+use telemetry::{Operation, TelemetryCounter, TelemetrySnapshot};
+
+mod trust;
+
+mod consistency;
+use consistency::ConsistencyReport;
+
+mod capability;
+pub use capability::{GrantSpec, GrantResult, MemoryFunction};
 
 /// Entry types for the memory coordinator zome
 #[hdk_entry_defs]
@@ -17,6 +33,9 @@ pub enum EntryTypes {
     MemoryComposition(MemoryComposition),
     KnowledgeTriple(KnowledgeTriple),
     BudgetEntry(BudgetEntry),
+    Provenance(Provenance),
+    TelemetryCounter(TelemetryCounter),
+    ContentOverflow(ContentOverflow),
 }
 
 /// Link types for memory queries
@@ -26,6 +45,13 @@ pub enum LinkTypes {
     TripleToUnderstanding,
     ADRToUnderstanding,
     AgentBudget,
+    TripleBySubject,
+    TripleByPredicate,
+    AllTriples,
+    DerivedFrom,
+    CompositionToUnderstanding,
+    AgentTelemetry,
+    TrustEdge,
 }
 
 /// An understanding transmitted by an agent
@@ -49,6 +75,24 @@ pub struct Understanding {
 
     /// Content hash for deduplication
     pub content_hash: String,
+
+    /// Whether `content` is the full text or a truncated prefix snippet
+    /// with the rest held in a separate `ContentOverflow` entry — see
+    /// `store_content`/`rehydrate_content`.
+    pub overflow: bool,
+
+    /// The `ContentOverflow` entry holding the full text, if `overflow`.
+    pub overflow_hash: Option<ActionHash>,
+}
+
+/// The full text of an `Understanding.content` too large to keep inline —
+/// link tags and entry fields both have practical DHT size limits, so
+/// `store_content` moves anything over `CONTENT_INLINE_THRESHOLD` bytes
+/// here and leaves only a prefix snippet on the `Understanding` itself.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ContentOverflow {
+    pub full_content: String,
 }
 
 /// Architecture Decision Record
@@ -91,11 +135,34 @@ pub struct MemoryComposition {
     pub composed_at: Timestamp,
 }
 
+/// PROV-style provenance record (`wasDerivedFrom`/`wasAttributedTo`) for
+/// one entry `compose_memories` copied in from another agent — linked from
+/// the derived `Understanding` via `LinkTypes::DerivedFrom` so `get_lineage`
+/// can walk the chain back to the original, never-composed entry.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Provenance {
+    /// The agent the entry was attributed to — whose copy it was derived
+    /// from.
+    pub source_agent: AgentPubKey,
+
+    /// The source entry's own `ActionHash`, which may itself carry a
+    /// `Provenance` record if it was already a composed copy.
+    pub source_hash: ActionHash,
+
+    /// When the derivation happened.
+    pub derived_at: Timestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, SerializedBytes)]
 pub struct CompositionStats {
     pub total_understandings: u32,
     pub new_understandings: u32,
     pub duplicate_skipped: u32,
+    /// Which cell this round's new understandings were imported from —
+    /// `None` for a same-conductor `compose_memories`, `Some(cell)` for a
+    /// `federate_memories` call bridged to `cell` via HDK `call`.
+    pub source_cell: Option<CellId>,
 }
 
 /// Input for transmitting an understanding
@@ -106,12 +173,37 @@ pub struct UnderstandingInput {
 }
 
 /// Query for recalling understandings
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RecallQuery {
     pub agent: Option<AgentPubKey>,
     pub content_contains: Option<String>,
     pub after_timestamp: Option<Timestamp>,
     pub limit: Option<usize>,
+    /// Resume after the page boundary this cursor encodes (see
+    /// `encode_cursor`/`decode_cursor`) — `None` starts from the beginning
+    /// of the `(created_at, content_hash)` order.
+    pub after_cursor: Option<String>,
+    /// Drop any understanding whose aggregate incoming trust (see
+    /// `trust::aggregate_incoming_trust`) is below this — `None` applies no
+    /// trust filtering at all.
+    pub min_trust: Option<f32>,
+    /// Before querying, block (up to this many milliseconds) for the
+    /// queried agent's understandings to finish propagating — see
+    /// `consistency::await_consistency`. `None` skips the wait entirely
+    /// and reads whatever is locally available, same as before this
+    /// option existed.
+    pub await_consistency_ms: Option<u64>,
+}
+
+/// One page of `recall_understandings`, ordered by `(created_at,
+/// content_hash)` — Relay/GraphQL-connection style: `end_cursor` resumes
+/// from where this page left off via `RecallQuery.after_cursor`, and
+/// `has_more` tells a caller whether another page is worth requesting.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecallPage {
+    pub items: Vec<Understanding>,
+    pub end_cursor: Option<String>,
+    pub has_more: bool,
 }
 
 /// Statistics about validation
@@ -147,14 +239,20 @@ pub fn transmit_understanding(input: UnderstandingInput) -> ExternResult<ActionH
     validate_triple(&triple)
         .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("Ontology validation failed: {:?}", e))))?;
 
+    // Split off the full content into a `ContentOverflow` entry if it's
+    // too large to keep inline, leaving a prefix snippet in its place.
+    let (content, overflow, overflow_hash) = store_content(&input.content)?;
+
     // Create Understanding entry
     let understanding = Understanding {
-        content: input.content.clone(),
+        content,
         context: input.context,
         triple: triple.clone(),
         created_at: sys_time()?,
         agent: agent_key.clone(),
         content_hash: hash_content(&input.content),
+        overflow,
+        overflow_hash,
     };
 
     // Commit Understanding to DHT
@@ -173,15 +271,21 @@ pub fn transmit_understanding(input: UnderstandingInput) -> ExternResult<ActionH
 
     // Link triple to understanding (for semantic queries)
     create_link(
-        triple_hash,
+        triple_hash.clone(),
         understanding_hash.clone(),
         LinkTypes::TripleToUnderstanding,
         ()
     )?;
 
+    // Anchor-index the triple by subject/predicate (and a catch-all) so
+    // `query_triples` never has to scan every understanding to find it.
+    index_triple(&triple_hash, &triple)?;
+
     debug!("Transmitted understanding with triple: subject={}, predicate={}, object={}",
            triple.subject, triple.predicate, triple.object);
 
+    telemetry::record_operation(&agent_key, Operation::Transmit, total_cost, 1);
+
     Ok(understanding_hash)
 }
 
@@ -190,72 +294,141 @@ pub fn transmit_understanding(input: UnderstandingInput) -> ExternResult<ActionH
 /// This function:
 /// 1. Queries the DHT for links from agent to understandings
 /// 2. Applies filters (content search, timestamp, etc.)
-/// 3. Returns matching understandings
-/// 4. Charges budget based on number of results returned
+/// 3. Sorts the survivors into a stable `(created_at, content_hash)` total
+///    order and pages through them via `RecallQuery.after_cursor`
+/// 4. Charges budget only for the results actually returned
 #[hdk_extern]
-pub fn recall_understandings(query: RecallQuery) -> ExternResult<Vec<Understanding>> {
+pub fn recall_understandings(query: RecallQuery) -> ExternResult<RecallPage> {
     // Get agent info for budget check
     let current_agent = agent_info()?.agent_latest_pubkey;
 
-    let mut results = vec![];
+    let after_cursor = query.after_cursor.as_deref().map(decode_cursor).transpose()?;
 
-    // Query by agent
-    if let Some(agent) = query.agent {
-        let links = get_links(
-            GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToUnderstanding)?
-                .build()
-        )?;
+    // Kept alongside each `Understanding` (rather than discarded after the
+    // `get`) so a `min_trust` filter can look up trust by `ActionHash`
+    // without a second round of `get_links`.
+    let mut results: Vec<(ActionHash, Understanding)> = vec![];
 
-        for link in links {
-            if let Some(understanding) = get_understanding(link.target.into())? {
-                // Apply filters
-                if matches_query(&understanding, &query) {
-                    results.push(understanding);
-                }
+    let target_agent = query.agent.clone().unwrap_or(current_agent.clone());
+    capability::require_access(&target_agent, MemoryFunction::Recall)?;
+
+    if let Some(timeout_ms) = query.await_consistency_ms {
+        consistency::await_consistency(&target_agent, timeout_ms)?;
+    }
+
+    let links = get_links(
+        GetLinksInputBuilder::try_new(target_agent, LinkTypes::AgentToUnderstanding)?
+            .build()
+    )?;
+
+    for link in links {
+        let Some(hash) = link.target.into_action_hash() else { continue };
+        if let Some(understanding) = get_understanding(hash.clone())? {
+            // Apply filters
+            if matches_query(&understanding, &query) {
+                results.push((hash, understanding));
             }
         }
-    } else {
-        // If no agent specified, search the current agent's understandings
-        let agent_info = agent_info()?;
-        let links = get_links(
-            GetLinksInputBuilder::try_new(agent_info.agent_latest_pubkey, LinkTypes::AgentToUnderstanding)?
-                .build()
-        )?;
+    }
 
-        for link in links {
-            if let Some(understanding) = get_understanding(link.target.into())? {
-                if matches_query(&understanding, &query) {
-                    results.push(understanding);
-                }
+    // Stable total order: ties on `created_at` (two understandings
+    // transmitted in the same wall-clock microsecond) are broken by
+    // `content_hash`, so the order — and any cursor derived from it — is
+    // fully deterministic.
+    results.sort_by_key(|(_, u)| (u.created_at.as_micros(), u.content_hash.clone()));
+
+    if let Some((cursor_micros, cursor_hash)) = after_cursor {
+        results.retain(|(_, u)| (u.created_at.as_micros(), u.content_hash.clone()) > (cursor_micros, cursor_hash.clone()));
+    }
+
+    if let Some(min_trust) = query.min_trust {
+        let mut kept = Vec::with_capacity(results.len());
+        for (hash, understanding) in results {
+            if trust::aggregate_incoming_trust(hash.clone().into())? >= min_trust {
+                kept.push((hash, understanding));
             }
         }
+        results = kept;
     }
 
-    // Limit results
-    if let Some(limit) = query.limit {
-        results.truncate(limit);
-    }
+    // Page results
+    let limit = query.limit.unwrap_or(results.len());
+    let has_more = results.len() > limit;
+    results.truncate(limit);
 
-    // Charge budget based on number of results (0.1 RU per result)
+    let end_cursor = results.last().map(|(_, u)| encode_cursor(u.created_at, &u.content_hash));
+
+    // Charge budget based on number of results actually returned (0.1 RU
+    // per result) — not the full candidate set before truncation, so
+    // paging through a large recall isn't billed for items never seen.
     let recall_cost = COST_RECALL_UNDERSTANDINGS * results.len() as f32;
     consume_budget(&current_agent, recall_cost)?;
 
-    Ok(results)
+    telemetry::record_operation(&current_agent, Operation::Recall, recall_cost, results.len() as u64);
+
+    // Filtering above ran against the (possibly truncated) stored content;
+    // only now, for the page actually being returned, reconstruct the full
+    // text of any overflowed understanding.
+    let items = results
+        .into_iter()
+        .map(|(_, u)| rehydrate_content(u))
+        .collect::<ExternResult<Vec<_>>>()?;
+
+    Ok(RecallPage { items, end_cursor, has_more })
+}
+
+/// Input for `await_consistency`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AwaitConsistencyInput {
+    pub agent: AgentPubKey,
+    pub timeout_ms: u64,
+}
+
+/// Block until `input.agent`'s understandings have finished propagating,
+/// or return `E_CONSISTENCY_TIMEOUT` if they haven't by `input.timeout_ms`.
+/// Exposed directly (rather than only as `RecallQuery.await_consistency_ms`)
+/// so a caller can establish consistency once up front — e.g. before a
+/// `compose_memories` across agents — without paying for it again on
+/// every subsequent recall.
+#[hdk_extern]
+pub fn await_consistency(input: AwaitConsistencyInput) -> ExternResult<ConsistencyReport> {
+    consistency::await_consistency(&input.agent, input.timeout_ms)
+}
+
+/// Authorize `spec.function` (recall or compose) against the calling
+/// agent's own memories, for `spec.assignees` or anyone holding the
+/// returned secret. `recall_understandings`/`compose_memories` reject
+/// cross-agent calls unless the caller presents a grant matching one of
+/// these.
+#[hdk_extern]
+pub fn grant_memory_access(spec: GrantSpec) -> ExternResult<GrantResult> {
+    capability::grant(spec)
+}
+
+/// Withdraw a grant previously issued by `grant_memory_access`.
+#[hdk_extern]
+pub fn revoke_memory_access(grant_hash: ActionHash) -> ExternResult<ActionHash> {
+    capability::revoke(grant_hash)
 }
 
 /// Compose memories from another agent
 ///
 /// This function:
-/// 1. Gets all understandings from the other agent
+/// 1. Gets all understandings from the other agent, keeping each one's
+///    `ActionHash` so the import can be attributed back to it
 /// 2. Merges them with the current agent's understandings
 /// 3. Deduplicates based on content hash
-/// 4. Creates a MemoryComposition entry to track the composition
+/// 4. Records a `Provenance` entry per imported understanding (PROV
+///    `wasDerivedFrom`/`wasAttributedTo`) and links the `MemoryComposition`
+///    to every entry it produced
 /// 5. Charges budget for the composition operation
 #[hdk_extern]
 pub fn compose_memories(other_agent: AgentPubKey) -> ExternResult<MemoryComposition> {
     let agent_info = agent_info()?;
     let my_agent = agent_info.agent_latest_pubkey;
 
+    capability::require_access(&other_agent, MemoryFunction::Compose)?;
+
     // Check and consume budget for composition
     consume_budget(&my_agent, COST_COMPOSE_MEMORIES)?;
 
@@ -265,74 +438,616 @@ pub fn compose_memories(other_agent: AgentPubKey) -> ExternResult<MemoryComposit
         content_contains: None,
         after_timestamp: None,
         limit: None,
-    })?;
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    })?.items;
+
+    // Get other agent's understandings, fetched directly from their
+    // `AgentToUnderstanding` links (rather than `recall_understandings`) so
+    // each one's own `ActionHash` is available for provenance tracking.
+    let other_links = get_links(
+        GetLinksInputBuilder::try_new(other_agent.clone(), LinkTypes::AgentToUnderstanding)?.build()
+    )?;
+    let mut other_understandings: Vec<(ActionHash, Understanding)> = Vec::new();
+    for link in other_links {
+        let Some(source_hash) = link.target.clone().into_action_hash() else { continue };
+        if let Some(understanding) = get_understanding(source_hash.clone())? {
+            other_understandings.push((source_hash, understanding));
+        }
+    }
 
-    // Get other agent's understandings
-    let other_understandings = recall_understandings(RecallQuery {
-        agent: Some(other_agent.clone()),
-        content_contains: None,
-        after_timestamp: None,
-        limit: None,
-    })?;
+    let imported = import_foreign_understandings(&my_agent, &other_agent, &my_understandings, other_understandings)?;
+
+    let composition = MemoryComposition {
+        agents: vec![my_agent.clone(), other_agent],
+        strategy: "merge".to_string(),
+        stats: CompositionStats {
+            total_understandings: (my_understandings.len() + imported.new_count) as u32,
+            new_understandings: imported.new_count as u32,
+            duplicate_skipped: imported.dup_count as u32,
+            source_cell: None,
+        },
+        composed_at: sys_time()?,
+    };
+
+    let composition_hash = create_entry(EntryTypes::MemoryComposition(composition.clone()))?;
+
+    // Activity-style linkage: connect every entry this composition
+    // produced back to the composition event itself.
+    for derived_hash in &imported.derived_hashes {
+        create_link(composition_hash.clone(), derived_hash.clone(), LinkTypes::CompositionToUnderstanding, ())?;
+    }
+
+    debug!("Composed memories: {} new, {} duplicates skipped", imported.new_count, imported.dup_count);
+
+    telemetry::record_operation(&my_agent, Operation::Compose, COST_COMPOSE_MEMORIES, imported.new_count as u64);
 
-    // Build set of my content hashes for quick lookup
+    Ok(composition)
+}
+
+/// Outcome of folding a set of foreign `(ActionHash, Understanding)` pairs
+/// into the calling agent's own memories — shared by `compose_memories`
+/// (same-conductor) and `federate_memories` (bridged to another cell), so
+/// the dedup/import/provenance logic lives in exactly one place.
+struct ImportOutcome {
+    new_count: usize,
+    dup_count: usize,
+    derived_hashes: Vec<ActionHash>,
+}
+
+/// Import `foreign` understandings (each tagged with its own `ActionHash`
+/// on the source side) as `my_agent`'s own entries, skipping any whose
+/// `content_hash` already appears in `my_understandings`, and recording a
+/// `Provenance` entry (attributed to `source_agent`) for each one actually
+/// imported.
+fn import_foreign_understandings(
+    my_agent: &AgentPubKey,
+    source_agent: &AgentPubKey,
+    my_understandings: &[Understanding],
+    foreign: Vec<(ActionHash, Understanding)>,
+) -> ExternResult<ImportOutcome> {
     let my_hashes: std::collections::HashSet<String> = my_understandings
         .iter()
         .map(|u| u.content_hash.clone())
         .collect();
 
-    // Merge (simple: add non-duplicates)
     let mut new_count = 0;
     let mut dup_count = 0;
+    let mut derived_hashes: Vec<ActionHash> = Vec::new();
 
-    for understanding in other_understandings.iter() {
-        // Check if duplicate
+    for (source_hash, understanding) in foreign {
         if my_hashes.contains(&understanding.content_hash) {
             dup_count += 1;
+            continue;
+        }
+
+        // `compose_memories` fetches `foreign` straight off this same DHT,
+        // so an already-overflowed source understanding's `overflow_hash`
+        // is just as gettable from here — reuse it instead of duplicating
+        // the full content into a second `ContentOverflow` entry.
+        // `federate_memories` rehydrates full content before it ever
+        // reaches here (the source `overflow_hash` lives on a different
+        // DHT), so that path always falls through to a fresh
+        // `store_content` call, chunked for this DHT's own size limits.
+        let (content, overflow, overflow_hash) = if understanding.overflow {
+            (understanding.content.clone(), true, understanding.overflow_hash.clone())
         } else {
-            // Import understanding (create entry for current agent)
-            let new_understanding = Understanding {
-                content: understanding.content.clone(),
-                context: understanding.context.clone(),
-                triple: understanding.triple.clone(),
-                created_at: sys_time()?,
-                agent: my_agent.clone(),
-                content_hash: understanding.content_hash.clone(),
-            };
+            store_content(&understanding.content)?
+        };
+
+        let new_understanding = Understanding {
+            content,
+            context: understanding.context.clone(),
+            triple: understanding.triple.clone(),
+            created_at: sys_time()?,
+            agent: my_agent.clone(),
+            content_hash: understanding.content_hash.clone(),
+            overflow,
+            overflow_hash,
+        };
+
+        let hash = create_entry(EntryTypes::Understanding(new_understanding))?;
+        create_link(my_agent.clone(), hash.clone(), LinkTypes::AgentToUnderstanding, ())?;
+
+        // Record where this copy came from — a source agent and the exact
+        // source entry — so `get_lineage` can walk it back.
+        let provenance = Provenance {
+            source_agent: source_agent.clone(),
+            source_hash,
+            derived_at: sys_time()?,
+        };
+        let provenance_hash = create_entry(EntryTypes::Provenance(provenance))?;
+        create_link(hash.clone(), provenance_hash, LinkTypes::DerivedFrom, ())?;
+
+        derived_hashes.push(hash);
+        new_count += 1;
+    }
+
+    Ok(ImportOutcome { new_count, dup_count, derived_hashes })
+}
+
+/// Input for `federate_memories`: which remote cell to bridge to (`None`
+/// targets this same cell, matching HDK `call`'s own convention), the
+/// agent whose understandings to pull on that cell, and the capability
+/// secret authorizing the remote call if the target zome requires one.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FederateMemoriesInput {
+    pub to_cell: Option<CellId>,
+    pub other_agent: AgentPubKey,
+    pub cap_secret: Option<CapSecret>,
+}
+
+/// Federated counterpart to `compose_memories`: instead of reading
+/// `other_agent`'s understandings from this cell's own DHT, bridges to
+/// `input.to_cell` via the HDK `call` host function, invokes
+/// `export_understandings_for_federation` there, and runs the same
+/// dedup/import/provenance path over whatever it returns. This is how
+/// composition works when the other agent's memories live in a separate
+/// memory DNA/cell entirely, rather than as another agent on this DHT.
+#[hdk_extern]
+pub fn federate_memories(input: FederateMemoriesInput) -> ExternResult<MemoryComposition> {
+    let my_agent = agent_info()?.agent_latest_pubkey;
+
+    consume_budget(&my_agent, COST_COMPOSE_MEMORIES)?;
+
+    let my_understandings = recall_understandings(RecallQuery {
+        agent: Some(my_agent.clone()),
+        content_contains: None,
+        after_timestamp: None,
+        limit: None,
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    })?.items;
 
-            let hash = create_entry(EntryTypes::Understanding(new_understanding))?;
+    let remote_query = RecallQuery {
+        agent: Some(input.other_agent.clone()),
+        content_contains: None,
+        after_timestamp: None,
+        limit: None,
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    };
 
-            // Create link from current agent to understanding
-            create_link(
-                my_agent.clone(),
-                hash,
-                LinkTypes::AgentToUnderstanding,
-                ()
-            )?;
+    let response = call(
+        input.to_cell.clone(),
+        ZomeName::from("memory_coordinator"),
+        FunctionName::from("export_understandings_for_federation"),
+        input.cap_secret,
+        remote_query,
+    )?;
 
-            new_count += 1;
+    let foreign: Vec<(ActionHash, Understanding)> = match response {
+        ZomeCallResponse::Ok(extern_io) => extern_io
+            .decode()
+            .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("E_FEDERATION_DECODE: {e}"))))?,
+        other => {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "E_FEDERATION_CALL_FAILED: {:?}", other
+            ))));
         }
-    }
+    };
+
+    let imported = import_foreign_understandings(&my_agent, &input.other_agent, &my_understandings, foreign)?;
 
-    // Create composition entry
     let composition = MemoryComposition {
-        agents: vec![my_agent.clone(), other_agent],
-        strategy: "merge".to_string(),
+        agents: vec![my_agent.clone(), input.other_agent],
+        strategy: "federate".to_string(),
         stats: CompositionStats {
-            total_understandings: (my_understandings.len() + new_count) as u32,
-            new_understandings: new_count as u32,
-            duplicate_skipped: dup_count as u32,
+            total_understandings: (my_understandings.len() + imported.new_count) as u32,
+            new_understandings: imported.new_count as u32,
+            duplicate_skipped: imported.dup_count as u32,
+            source_cell: input.to_cell.clone(),
         },
         composed_at: sys_time()?,
     };
 
-    create_entry(EntryTypes::MemoryComposition(composition.clone()))?;
+    let composition_hash = create_entry(EntryTypes::MemoryComposition(composition.clone()))?;
+
+    for derived_hash in &imported.derived_hashes {
+        create_link(composition_hash.clone(), derived_hash.clone(), LinkTypes::CompositionToUnderstanding, ())?;
+    }
 
-    debug!("Composed memories: {} new, {} duplicates skipped", new_count, dup_count);
+    debug!("Federated memories from {:?}: {} new, {} duplicates skipped", input.to_cell, imported.new_count, imported.dup_count);
+
+    telemetry::record_operation(&my_agent, Operation::Compose, COST_COMPOSE_MEMORIES, imported.new_count as u64);
 
     Ok(composition)
 }
 
+/// Bridge-callable counterpart to `recall_understandings` that also
+/// returns each result's own `ActionHash` — a remote `call` only gets back
+/// whatever this zome chooses to return, and a bare `Understanding` alone
+/// isn't enough for `federate_memories` to record `Provenance.source_hash`
+/// once imported. Applies the same agent/content/timestamp filters as
+/// `recall_understandings`, but without its cursor paging or trust
+/// filtering, since a federation pull is a one-shot whole-agent fetch.
+/// Always rehydrates overflowed content before returning: the calling
+/// cell has no way to dereference this cell's `ContentOverflow` entries,
+/// so a snippet-plus-dangling-hash would be useless once it crosses the
+/// bridge.
+#[hdk_extern]
+pub fn export_understandings_for_federation(query: RecallQuery) -> ExternResult<Vec<(ActionHash, Understanding)>> {
+    let current_agent = agent_info()?.agent_latest_pubkey;
+    let target_agent = query.agent.clone().unwrap_or(current_agent);
+    capability::require_access(&target_agent, MemoryFunction::Recall)?;
+
+    let links = get_links(
+        GetLinksInputBuilder::try_new(target_agent, LinkTypes::AgentToUnderstanding)?.build()
+    )?;
+
+    let mut results = Vec::new();
+    for link in links {
+        let Some(hash) = link.target.into_action_hash() else { continue };
+        if let Some(understanding) = get_understanding(hash.clone())? {
+            if matches_query(&understanding, &query) {
+                results.push((hash, rehydrate_content(understanding)?));
+            }
+        }
+    }
+
+    results.sort_by_key(|(_, u)| (u.created_at.as_micros(), u.content_hash.clone()));
+    Ok(results)
+}
+
+/// One step of a `LineageGraph`: the entry that was derived, and the
+/// `Provenance` it was derived under.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LineageEdge {
+    pub derived_hash: ActionHash,
+    pub source_agent: AgentPubKey,
+    pub source_hash: ActionHash,
+    pub derived_at: Timestamp,
+}
+
+/// The full derivation chain for an entry, oldest-first — `edges.last()`
+/// is the step that produced the queried hash. An entry with no recorded
+/// `Provenance` (an original, never-composed understanding) yields an
+/// empty chain.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LineageGraph {
+    pub edges: Vec<LineageEdge>,
+}
+
+/// Walk `LinkTypes::DerivedFrom` backward from `hash`, one `Provenance`
+/// entry at a time, to the original entry it ultimately traces to — lets a
+/// downstream agent distinguish an original understanding from a
+/// re-composed one and audit where it propagated from.
+#[hdk_extern]
+pub fn get_lineage(hash: ActionHash) -> ExternResult<LineageGraph> {
+    let mut edges = Vec::new();
+    let mut cursor = hash;
+
+    loop {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(cursor.clone(), LinkTypes::DerivedFrom)?.build()
+        )?;
+        let Some(link) = links.into_iter().next() else { break };
+        let Some(provenance_hash) = link.target.into_action_hash() else { break };
+        let Some(record) = get(provenance_hash, GetOptions::default())? else { break };
+        let Some(provenance) = record.entry().to_app_option::<Provenance>()? else { break };
+
+        edges.push(LineageEdge {
+            derived_hash: cursor.clone(),
+            source_agent: provenance.source_agent.clone(),
+            source_hash: provenance.source_hash.clone(),
+            derived_at: provenance.derived_at,
+        });
+
+        cursor = provenance.source_hash;
+    }
+
+    edges.reverse();
+    Ok(LineageGraph { edges })
+}
+
+/// Input for `set_trust`: the calling agent's signed rating of `target` —
+/// another agent or a specific `Understanding`, identified generically
+/// since both are valid link endpoints.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrustInput {
+    pub target: AnyLinkableHash,
+    /// Clamped to `[-1.0, 1.0]` before being encoded into the link tag.
+    pub value: f32,
+    pub label: Option<String>,
+}
+
+/// Rate `input.target` from the calling agent's perspective — see
+/// `trust::rate` for how the rating is encoded and linked both ways.
+#[hdk_extern]
+pub fn set_trust(input: TrustInput) -> ExternResult<()> {
+    let rater = agent_info()?.agent_latest_pubkey;
+    trust::rate(&rater, input.target, input.value, input.label.as_deref())
+}
+
+/// One of `target`'s `Understanding`s, annotated with the sum of every
+/// trust rating placed directly on it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrustedUnderstanding {
+    pub understanding: Understanding,
+    pub aggregate_trust: f32,
+}
+
+/// `target`'s understandings ranked by descending aggregate trust —
+/// exactly the comparison `compose_memories` should eventually prefer
+/// over treating every source agent equally, by composing only from (or
+/// sorting toward) the understandings other agents have rated highly.
+#[hdk_extern]
+pub fn recall_by_trust(target: AgentPubKey) -> ExternResult<Vec<TrustedUnderstanding>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(target, LinkTypes::AgentToUnderstanding)?.build()
+    )?;
+
+    let mut ranked = Vec::new();
+    for link in links {
+        let Some(hash) = link.target.into_action_hash() else { continue };
+        let Some(understanding) = get_understanding(hash.clone())? else { continue };
+        let aggregate_trust = trust::aggregate_incoming_trust(hash.into())?;
+        ranked.push(TrustedUnderstanding { understanding, aggregate_trust });
+    }
+
+    ranked.sort_by(|a, b| b.aggregate_trust.total_cmp(&a.aggregate_trust));
+    Ok(ranked)
+}
+
+/// Input for `query_knowledge`: a conjunction of triple patterns, a
+/// minimum confidence to admit a triple into the search, and whether to
+/// run the query against the forward-chaining closure (so transitive,
+/// symmetric, and reflexive answers are included) rather than only the
+/// agent's asserted triples.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryKnowledgeInput {
+    pub patterns: Vec<ConjunctiveTriplePattern>,
+    pub min_confidence: f32,
+    pub include_closure: bool,
+}
+
+/// Run a conjunctive query over the calling agent's knowledge triples —
+/// the real read API `vector_search`-style exact match can't provide,
+/// e.g. "which models capable_of a capability that some model
+/// improves_upon GPT-4 also has."
+#[hdk_extern]
+pub fn query_knowledge(input: QueryKnowledgeInput) -> ExternResult<Vec<Bindings>> {
+    let agent_key = agent_info()?.agent_latest_pubkey;
+
+    let understandings = recall_understandings(RecallQuery {
+        agent: Some(agent_key),
+        content_contains: None,
+        after_timestamp: None,
+        limit: None,
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    })?.items;
+    let mut triples: Vec<KnowledgeTriple> = understandings.into_iter().map(|u| u.triple).collect();
+
+    if input.include_closure {
+        let (_, base_relations) = bootstrap_base_ontology();
+        let (_, ai_ml_relations) = bootstrap_ai_ml_ontology();
+        let relations: Vec<_> = base_relations.into_iter().chain(ai_ml_relations).collect();
+        let derived = compute_closure(&triples, &relations, DEFAULT_MAX_CLOSURE_ITERATIONS);
+        triples.extend(derived);
+    }
+
+    Ok(query(&triples, &input.patterns, input.min_confidence))
+}
+
+/// A SPARQL-style basic-graph-pattern point lookup for `query_triples` —
+/// unlike `ConjunctiveTriplePattern`'s `?variable` binding convention, a
+/// `None` field here is a wildcard, not something to unify across patterns.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TriplePattern {
+    pub subject: Option<String>,
+    pub predicate: Option<String>,
+    pub object: Option<String>,
+    pub min_confidence: Option<f32>,
+}
+
+/// One `query_triples` match: the triple itself, plus every `Understanding`
+/// linked to it — the `transmit_understanding` or
+/// `import_understandings_batch` call that created it. Copies imported by
+/// `compose_memories` are not linked to the triple, so they do not appear
+/// here.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TripleMatch {
+    pub triple: KnowledgeTriple,
+    pub understandings: Vec<Understanding>,
+}
+
+/// Semantic triple-pattern lookup over the triples `index_triple` has
+/// anchored: any `None` field in `pattern` is a wildcard. Returns each
+/// matching `KnowledgeTriple` together with every `Understanding` it was
+/// extracted from, found by walking `LinkTypes::TripleToUnderstanding`
+/// forward from the triple. Charges budget proportional to the number of
+/// results, like `recall_understandings` does.
+#[hdk_extern]
+pub fn query_triples(pattern: TriplePattern) -> ExternResult<Vec<TripleMatch>> {
+    let current_agent = agent_info()?.agent_latest_pubkey;
+
+    let mut results = Vec::new();
+    for triple_hash in candidate_triple_hashes(&pattern)? {
+        let Some(record) = get(triple_hash.clone(), GetOptions::default())? else { continue };
+        let Some(triple) = record.entry().to_app_option::<KnowledgeTriple>()? else { continue };
+
+        if !matches_triple_pattern(&triple, &pattern) {
+            continue;
+        }
+
+        let understanding_links = get_links(
+            GetLinksInputBuilder::try_new(triple_hash.clone(), LinkTypes::TripleToUnderstanding)?.build()
+        )?;
+        let mut understandings = Vec::new();
+        for link in understanding_links {
+            let Some(understanding_hash) = link.target.into_action_hash() else { continue };
+            if let Some(understanding) = get_understanding(understanding_hash)? {
+                understandings.push(rehydrate_content(understanding)?);
+            }
+        }
+
+        results.push(TripleMatch { triple, understandings });
+    }
+
+    let query_cost = COST_QUERY_TRIPLES * results.len() as f32;
+    consume_budget(&current_agent, query_cost)?;
+
+    Ok(results)
+}
+
+/// Struct-of-arrays encoding of a set of understandings and the triples
+/// extracted from them, for bulk import/export — column-wise rather than
+/// `Vec<Understanding>` so a large corpus serializes compactly instead of
+/// repeating every field name per row. Every array is the same length;
+/// index `i` across all arrays describes one understanding.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UnderstandingBatch {
+    pub contents: Vec<String>,
+    pub contexts: Vec<Option<String>>,
+    pub subjects: Vec<String>,
+    pub predicates: Vec<String>,
+    pub objects: Vec<String>,
+    pub confidences: Vec<f32>,
+    pub content_hashes: Vec<String>,
+}
+
+/// What happened to one row of an `import_understandings_batch` call.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RowStatus {
+    Accepted,
+    /// `content_hash` already present (either in the agent's existing
+    /// understandings, or an earlier row of the same batch).
+    DuplicateSkipped,
+    Invalid(String),
+}
+
+/// Per-row outcome of an `import_understandings_batch` call, in input row
+/// order, plus the totals callers usually want without re-counting.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportReport {
+    pub statuses: Vec<RowStatus>,
+    pub accepted: u32,
+    pub duplicate_skipped: u32,
+    pub invalid: u32,
+}
+
+/// Bulk-import `batch` for the calling agent: dedups the whole batch in a
+/// single pass against both the agent's existing `content_hash`es and
+/// earlier rows of the same batch, validates every row's triple against
+/// the ontology, and charges budget only for rows actually accepted.
+/// Mirrors `transmit_understanding`'s per-row cost (transmit + validate)
+/// but does not abort the batch on an invalid or duplicate row — only a
+/// budget shortfall stops it early, at which point earlier rows already
+/// accepted stay committed.
+#[hdk_extern]
+pub fn import_understandings_batch(batch: UnderstandingBatch) -> ExternResult<ImportReport> {
+    let len = batch.contents.len();
+    if batch.contexts.len() != len
+        || batch.subjects.len() != len
+        || batch.predicates.len() != len
+        || batch.objects.len() != len
+        || batch.confidences.len() != len
+        || batch.content_hashes.len() != len
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "E_BATCH_SHAPE: every UnderstandingBatch column must be the same length".to_string()
+        )));
+    }
+
+    let current_agent = agent_info()?.agent_latest_pubkey;
+
+    let existing = recall_understandings(RecallQuery {
+        agent: Some(current_agent.clone()),
+        content_contains: None,
+        after_timestamp: None,
+        limit: None,
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    })?.items;
+    let mut seen_hashes: std::collections::HashSet<String> =
+        existing.into_iter().map(|u| u.content_hash).collect();
+
+    let mut statuses = Vec::with_capacity(len);
+    let mut accepted = 0u32;
+    let mut duplicate_skipped = 0u32;
+    let mut invalid = 0u32;
+
+    for i in 0..len {
+        let content_hash = batch.content_hashes[i].clone();
+        if seen_hashes.contains(&content_hash) {
+            statuses.push(RowStatus::DuplicateSkipped);
+            duplicate_skipped += 1;
+            continue;
+        }
+
+        let triple = KnowledgeTriple {
+            subject: batch.subjects[i].clone(),
+            predicate: batch.predicates[i].clone(),
+            object: batch.objects[i].clone(),
+            confidence: batch.confidences[i],
+            source: current_agent.clone(),
+            created_at: sys_time()?,
+        };
+
+        if let Err(e) = validate_triple(&triple) {
+            statuses.push(RowStatus::Invalid(format!("{:?}", e)));
+            invalid += 1;
+            continue;
+        }
+
+        consume_budget(&current_agent, COST_TRANSMIT_UNDERSTANDING + COST_VALIDATE_TRIPLE)?;
+
+        let (content, overflow, overflow_hash) = store_content(&batch.contents[i])?;
+
+        let understanding = Understanding {
+            content,
+            context: batch.contexts[i].clone(),
+            triple: triple.clone(),
+            created_at: sys_time()?,
+            agent: current_agent.clone(),
+            content_hash: content_hash.clone(),
+            overflow,
+            overflow_hash,
+        };
+
+        let understanding_hash = create_entry(EntryTypes::Understanding(understanding))?;
+        create_link(current_agent.clone(), understanding_hash.clone(), LinkTypes::AgentToUnderstanding, ())?;
+
+        let triple_hash = create_entry(EntryTypes::KnowledgeTriple(triple.clone()))?;
+        create_link(triple_hash.clone(), understanding_hash, LinkTypes::TripleToUnderstanding, ())?;
+        index_triple(&triple_hash, &triple)?;
+
+        seen_hashes.insert(content_hash);
+        statuses.push(RowStatus::Accepted);
+        accepted += 1;
+    }
+
+    Ok(ImportReport { statuses, accepted, duplicate_skipped, invalid })
+}
+
+/// Columnar export counterpart to `import_understandings_batch`: runs
+/// `query` through `recall_understandings` (so the same filters, cursor,
+/// and budget charge apply) and lays the results out struct-of-arrays so
+/// the result round-trips through `import_understandings_batch` elsewhere.
+#[hdk_extern]
+pub fn export_understandings_columnar(query: RecallQuery) -> ExternResult<UnderstandingBatch> {
+    let understandings = recall_understandings(query)?.items;
+
+    let mut batch = UnderstandingBatch::default();
+    for understanding in understandings {
+        batch.contents.push(understanding.content);
+        batch.contexts.push(understanding.context);
+        batch.subjects.push(understanding.triple.subject);
+        batch.predicates.push(understanding.triple.predicate);
+        batch.objects.push(understanding.triple.object);
+        batch.confidences.push(understanding.triple.confidence);
+        batch.content_hashes.push(understanding.content_hash);
+    }
+
+    Ok(batch)
+}
+
 /// Get current budget status for the calling agent
 #[hdk_extern]
 pub fn budget_status(_: ()) -> ExternResult<BudgetState> {
@@ -351,11 +1066,16 @@ pub fn get_validation_stats(_: ()) -> ExternResult<ValidationStats> {
         content_contains: None,
         after_timestamp: None,
         limit: None,
-    })?;
+        after_cursor: None,
+        min_trust: None,
+        await_consistency_ms: None,
+    })?.items;
 
     let total = understandings.len();
     let valid = understandings.len(); // All stored understandings passed validation
 
+    telemetry::record_operation(&agent_info.agent_latest_pubkey, Operation::ValidationStats, 0.0, total as u64);
+
     Ok(ValidationStats {
         total_understandings: total,
         valid_triples: valid,
@@ -363,6 +1083,16 @@ pub fn get_validation_stats(_: ()) -> ExternResult<ValidationStats> {
     })
 }
 
+/// OTLP-shaped snapshot of the calling agent's accumulated operation
+/// telemetry — invocation counts, total RU consumed, and result-size sums
+/// for `transmit_understanding`/`recall_understandings`/`compose_memories`/
+/// `get_validation_stats`, as tracked by `telemetry::record_operation`.
+#[hdk_extern]
+pub fn get_telemetry(_: ()) -> ExternResult<TelemetrySnapshot> {
+    let agent_key = agent_info()?.agent_latest_pubkey;
+    telemetry::snapshot(&agent_key)
+}
+
 /// Create an ADR (Architecture Decision Record)
 #[hdk_extern]
 pub fn create_adr(adr: ADR) -> ExternResult<ActionHash> {
@@ -397,6 +1127,149 @@ fn get_understanding(hash: ActionHash) -> ExternResult<Option<Understanding>> {
     }
 }
 
+/// Lowercase and trim a triple's subject/predicate/object so anchor keys
+/// and pattern matching don't depend on incidental casing/whitespace.
+fn normalize_term(term: &str) -> String {
+    term.trim().to_lowercase()
+}
+
+/// Longest `Understanding.content` (in bytes) kept inline; longer content
+/// moves whole into a separate `ContentOverflow` entry, leaving only a
+/// `CONTENT_SNIPPET_LEN`-character snippet inline.
+const CONTENT_INLINE_THRESHOLD: usize = 900;
+
+/// Length of the prefix snippet left inline in place of overflowed
+/// content — long enough for `matches_query`'s `content_contains` filter
+/// to still be useful without fetching every overflow entry to check it.
+const CONTENT_SNIPPET_LEN: usize = 256;
+
+/// If `content` fits within `CONTENT_INLINE_THRESHOLD`, return it as-is
+/// with no overflow. Otherwise write it to a new `ContentOverflow` entry
+/// and return a truncated prefix snippet plus that entry's hash, so the
+/// `Understanding` itself stays small regardless of how long `content` is.
+fn store_content(content: &str) -> ExternResult<(String, bool, Option<ActionHash>)> {
+    if content.len() <= CONTENT_INLINE_THRESHOLD {
+        return Ok((content.to_string(), false, None));
+    }
+
+    let overflow_hash = create_entry(EntryTypes::ContentOverflow(ContentOverflow {
+        full_content: content.to_string(),
+    }))?;
+    let snippet: String = content.chars().take(CONTENT_SNIPPET_LEN).collect();
+    Ok((snippet, true, Some(overflow_hash)))
+}
+
+/// The inverse of `store_content`: if `understanding.overflow`, fetch its
+/// `ContentOverflow` entry and replace the inline snippet with the full
+/// text. Clears `overflow`/`overflow_hash` on the returned value, since
+/// once rehydrated it genuinely does carry the full content inline — the
+/// persisted entry on the DHT is untouched. Used only at the boundaries
+/// where an `Understanding` is handed back to a caller (or across a
+/// `federate_memories` bridge call), not internally where the raw
+/// snippet form is cheaper and sufficient.
+fn rehydrate_content(mut understanding: Understanding) -> ExternResult<Understanding> {
+    if understanding.overflow {
+        if let Some(overflow_hash) = understanding.overflow_hash.take() {
+            if let Some(record) = get(overflow_hash, GetOptions::default())? {
+                if let Some(overflow) = record.entry().to_app_option::<ContentOverflow>()? {
+                    understanding.content = overflow.full_content;
+                }
+            }
+        }
+        understanding.overflow = false;
+    }
+    Ok(understanding)
+}
+
+/// Anchor-index `triple_hash` under its normalized subject and predicate
+/// (mirrors `budget.rs`'s `agent_budget.{agent}` path-anchor pattern), plus
+/// a catch-all anchor for a `query_triples` call that pins neither field —
+/// so `query_triples` never has to walk every `Understanding` to find it.
+fn index_triple(triple_hash: &ActionHash, triple: &KnowledgeTriple) -> ExternResult<()> {
+    let subject_anchor = Path::from(format!("triple_subject.{}", normalize_term(&triple.subject)));
+    create_link(subject_anchor.path_entry_hash()?, triple_hash.clone(), LinkTypes::TripleBySubject, ())?;
+
+    let predicate_anchor = Path::from(format!("triple_predicate.{}", normalize_term(&triple.predicate)));
+    create_link(predicate_anchor.path_entry_hash()?, triple_hash.clone(), LinkTypes::TripleByPredicate, ())?;
+
+    let all_anchor = Path::from("triple_all".to_string());
+    create_link(all_anchor.path_entry_hash()?, triple_hash.clone(), LinkTypes::AllTriples, ())?;
+
+    Ok(())
+}
+
+/// Resolve `pattern` to candidate triple hashes via whichever anchor
+/// `index_triple` populated is tightest: subject first, then predicate,
+/// falling back to the catch-all anchor only when neither is pinned.
+fn candidate_triple_hashes(pattern: &TriplePattern) -> ExternResult<Vec<ActionHash>> {
+    let (anchor_key, link_type) = if let Some(subject) = &pattern.subject {
+        (format!("triple_subject.{}", normalize_term(subject)), LinkTypes::TripleBySubject)
+    } else if let Some(predicate) = &pattern.predicate {
+        (format!("triple_predicate.{}", normalize_term(predicate)), LinkTypes::TripleByPredicate)
+    } else {
+        ("triple_all".to_string(), LinkTypes::AllTriples)
+    };
+
+    let anchor = Path::from(anchor_key);
+    let links = get_links(GetLinksInputBuilder::try_new(anchor.path_entry_hash()?, link_type)?.build())?;
+    Ok(links.into_iter().filter_map(|link| link.target.into_action_hash()).collect())
+}
+
+/// Check a fetched `KnowledgeTriple` against every non-wildcard field of
+/// `pattern` — the anchor lookup in `candidate_triple_hashes` already
+/// narrows by whichever of subject/predicate seeded it, but this re-checks
+/// all four fields so a pattern pinning more than one field still filters
+/// correctly.
+fn matches_triple_pattern(triple: &KnowledgeTriple, pattern: &TriplePattern) -> bool {
+    if let Some(subject) = &pattern.subject {
+        if normalize_term(subject) != normalize_term(&triple.subject) {
+            return false;
+        }
+    }
+    if let Some(predicate) = &pattern.predicate {
+        if normalize_term(predicate) != normalize_term(&triple.predicate) {
+            return false;
+        }
+    }
+    if let Some(object) = &pattern.object {
+        if normalize_term(object) != normalize_term(&triple.object) {
+            return false;
+        }
+    }
+    if let Some(min_confidence) = pattern.min_confidence {
+        if triple.confidence < min_confidence {
+            return false;
+        }
+    }
+    true
+}
+
+/// Encode a `recall_understandings` page boundary as an opaque cursor: the
+/// `(created_at, content_hash)` sort key, base64'd so callers round-trip it
+/// without depending on its internal format.
+fn encode_cursor(created_at: Timestamp, content_hash: &str) -> String {
+    let raw = format!("{}:{}", created_at.as_micros(), content_hash);
+    STANDARD.encode(raw.as_bytes())
+}
+
+/// Decode a `RecallQuery.after_cursor` back into its `(created_at_micros,
+/// content_hash)` sort key. Returns `E_INVALID_CURSOR` rather than
+/// silently resetting to the first page on garbage input.
+fn decode_cursor(cursor: &str) -> ExternResult<(i64, String)> {
+    let raw = STANDARD
+        .decode(cursor)
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("E_INVALID_CURSOR: not valid base64: {e}"))))?;
+    let raw = String::from_utf8(raw)
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("E_INVALID_CURSOR: not valid utf-8: {e}"))))?;
+    let (micros, hash) = raw.split_once(':').ok_or_else(|| {
+        wasm_error!(WasmErrorInner::Guest("E_INVALID_CURSOR: missing ':' separator".to_string()))
+    })?;
+    let micros = micros
+        .parse::<i64>()
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("E_INVALID_CURSOR: bad timestamp: {e}"))))?;
+    Ok((micros, hash.to_string()))
+}
+
 /// Check if an understanding matches the query criteria
 fn matches_query(understanding: &Understanding, query: &RecallQuery) -> bool {
     // Filter by content
@@ -0,0 +1,174 @@
+use hdk::prelude::*;
+use crate::{EntryTypes, LinkTypes};
+
+/// Per-operation counters an OTLP metric name maps onto directly — the
+/// same three numbers (`memory.<op>.count`, `.ru`, `.results`) for every
+/// instrumented extern.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct OperationCounter {
+    pub invocation_count: u64,
+    pub total_ru: f32,
+    pub result_size_sum: u64,
+}
+
+/// Which extern a `record_operation` call is instrumenting.
+#[derive(Clone, Copy, Debug)]
+pub enum Operation {
+    Transmit,
+    Recall,
+    Compose,
+    ValidationStats,
+}
+
+impl Operation {
+    fn metric_prefix(self) -> &'static str {
+        match self {
+            Operation::Transmit => "memory.transmit",
+            Operation::Recall => "memory.recall",
+            Operation::Compose => "memory.compose",
+            Operation::ValidationStats => "memory.validation_stats",
+        }
+    }
+}
+
+/// One agent's accumulated per-operation counters, chained the same way
+/// `BudgetEntry` is: `seq` monotonically supersedes, `prev` points at the
+/// entry this one replaces, so `get_telemetry` reads the single latest
+/// accumulation. Writes are not compare-and-swapped, so two writers that
+/// read the same tip both write the same `seq` and only one of their
+/// increments is reflected in what `get_telemetry` reads.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct TelemetryCounter {
+    pub agent: AgentPubKey,
+    pub transmit: OperationCounter,
+    pub recall: OperationCounter,
+    pub compose: OperationCounter,
+    pub validation_stats: OperationCounter,
+    pub seq: u64,
+    pub prev: Option<ActionHash>,
+}
+
+impl TelemetryCounter {
+    fn empty(agent: &AgentPubKey) -> Self {
+        Self {
+            agent: agent.clone(),
+            transmit: OperationCounter::default(),
+            recall: OperationCounter::default(),
+            compose: OperationCounter::default(),
+            validation_stats: OperationCounter::default(),
+            seq: 0,
+            prev: None,
+        }
+    }
+
+    fn counter_mut(&mut self, operation: Operation) -> &mut OperationCounter {
+        match operation {
+            Operation::Transmit => &mut self.transmit,
+            Operation::Recall => &mut self.recall,
+            Operation::Compose => &mut self.compose,
+            Operation::ValidationStats => &mut self.validation_stats,
+        }
+    }
+}
+
+fn anchor(agent: &AgentPubKey) -> Path {
+    Path::from(format!("agent_telemetry.{}", agent))
+}
+
+/// The agent's current telemetry chain tip (and its `ActionHash`, so a
+/// writer can set the next entry's `prev`), or a zeroed counter (`seq =
+/// 0`, `prev = None`) if it has none yet — mirrors
+/// `budget::get_budget_state`'s "highest `seq` wins" tie-break.
+fn latest_counter(agent: &AgentPubKey) -> ExternResult<(TelemetryCounter, Option<ActionHash>)> {
+    let links = get_links(GetLinksInputBuilder::try_new(anchor(agent).path_entry_hash()?, LinkTypes::AgentTelemetry)?.build())?;
+
+    let mut latest: Option<(ActionHash, TelemetryCounter)> = None;
+    for link in links {
+        let Some(action_hash) = link.target.into_action_hash() else { continue };
+        if let Some(record) = get(action_hash.clone(), GetOptions::default())? {
+            if let Some(counter) = record.entry().to_app_option::<TelemetryCounter>()? {
+                if latest.as_ref().is_none_or(|(_, current)| counter.seq > current.seq) {
+                    latest = Some((action_hash, counter));
+                }
+            }
+        }
+    }
+
+    Ok(match latest {
+        Some((hash, counter)) => (counter, Some(hash)),
+        None => (TelemetryCounter::empty(agent), None),
+    })
+}
+
+/// Accumulate one more invocation of `operation` into `agent`'s telemetry:
+/// read the latest counter, bump its `invocation_count`/`total_ru`/
+/// `result_size_sum`, and write the incremented state as the next link in
+/// the chain — accumulation carries across sessions, since each write is
+/// derived from whatever the chain's tip reads as at call time. Telemetry is observability, not correctness, so unlike
+/// `budget::update_budget_entry` this doesn't compare-and-swap against a
+/// stale tip: a failure here is logged and dropped rather than surfaced,
+/// so a telemetry hiccup never fails the extern it's instrumenting.
+pub fn record_operation(agent: &AgentPubKey, operation: Operation, ru: f32, result_size: u64) {
+    if let Err(e) = try_record_operation(agent, operation, ru, result_size) {
+        debug!("telemetry: failed to record {:?} for {}: {:?}", operation, agent, e);
+    }
+}
+
+fn try_record_operation(agent: &AgentPubKey, operation: Operation, ru: f32, result_size: u64) -> ExternResult<ActionHash> {
+    let (mut next, prev) = latest_counter(agent)?;
+    let next_seq = next.seq + 1;
+
+    let counter = next.counter_mut(operation);
+    counter.invocation_count += 1;
+    counter.total_ru += ru;
+    counter.result_size_sum += result_size;
+    next.seq = next_seq;
+    next.prev = prev;
+
+    let hash = create_entry(EntryTypes::TelemetryCounter(next))?;
+    create_link(anchor(agent).path_entry_hash()?, hash.clone(), LinkTypes::AgentTelemetry, ())?;
+    Ok(hash)
+}
+
+/// One OTLP-style metrics data point: a dotted metric name (e.g.
+/// `memory.transmit.count`) and its current value.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MetricDataPoint {
+    pub name: String,
+    pub value: f64,
+}
+
+/// An OTLP-compatible metrics snapshot: resource attributes identifying
+/// who the metrics are for, plus a flat list of sum/gauge data points —
+/// shaped so an operator can lift it directly into an OTLP
+/// `ResourceMetrics` message for an external collector.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TelemetrySnapshot {
+    pub resource_attributes: std::collections::BTreeMap<String, String>,
+    pub data_points: Vec<MetricDataPoint>,
+}
+
+fn push_operation_points(points: &mut Vec<MetricDataPoint>, operation: Operation, counter: &OperationCounter) {
+    let prefix = operation.metric_prefix();
+    points.push(MetricDataPoint { name: format!("{prefix}.count"), value: counter.invocation_count as f64 });
+    points.push(MetricDataPoint { name: format!("{prefix}.ru"), value: counter.total_ru as f64 });
+    points.push(MetricDataPoint { name: format!("{prefix}.results"), value: counter.result_size_sum as f64 });
+}
+
+/// Build the calling agent's `TelemetrySnapshot` from its current
+/// telemetry chain tip.
+pub fn snapshot(agent: &AgentPubKey) -> ExternResult<TelemetrySnapshot> {
+    let (counter, _) = latest_counter(agent)?;
+
+    let mut resource_attributes = std::collections::BTreeMap::new();
+    resource_attributes.insert("agent".to_string(), agent.to_string());
+
+    let mut data_points = Vec::new();
+    push_operation_points(&mut data_points, Operation::Transmit, &counter.transmit);
+    push_operation_points(&mut data_points, Operation::Recall, &counter.recall);
+    push_operation_points(&mut data_points, Operation::Compose, &counter.compose);
+    push_operation_points(&mut data_points, Operation::ValidationStats, &counter.validation_stats);
+
+    Ok(TelemetrySnapshot { resource_attributes, data_points })
+}
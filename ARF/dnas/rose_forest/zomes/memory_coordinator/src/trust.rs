@@ -0,0 +1,113 @@
+use hdk::prelude::*;
+use crate::LinkTypes;
+
+/// ASCII unit separator between a trust tag's chunks — never appears in a
+/// fixed-width decimal, a direction marker, or a hex-encoded bucket, so
+/// splitting on it can't be confused by a label chunk containing it (the
+/// label is always everything between the second and the last separator).
+const CHUNK_SEP: u8 = 0x1f;
+
+/// Which side of a trust edge a link represents: `Forward` is the source
+/// agent's own outgoing rating (queryable from the rater's side),
+/// `Reverse` is the same rating stored back at the target (queryable from
+/// the rated side without needing to already know who rated it).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    Forward,
+    Reverse,
+}
+
+impl Direction {
+    fn marker(self) -> u8 {
+        match self {
+            Direction::Forward => b'F',
+            Direction::Reverse => b'R',
+        }
+    }
+
+    fn from_marker(marker: u8) -> Option<Self> {
+        match marker {
+            b'F' => Some(Direction::Forward),
+            b'R' => Some(Direction::Reverse),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded `LinkTypes::TrustEdge` tag.
+struct TrustTag {
+    direction: Direction,
+    value: f32,
+    #[allow(dead_code)]
+    label: Option<String>,
+}
+
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Encode one trust edge's tag: a fixed-width signed decimal (so every
+/// encoded value is the same length regardless of sign or magnitude), an
+/// optional label, and a random 9-byte "bucket" so two identical ratings
+/// between the same pair of entities produce distinct links instead of
+/// collapsing into one.
+fn encode_tag(direction: Direction, value: f32, label: Option<&str>) -> ExternResult<LinkTag> {
+    let clamped = value.clamp(-1.0, 1.0);
+    let value_chunk = format!("{clamped:+.3}");
+    let label_chunk = label.unwrap_or("");
+    let bucket_chunk = bytes_to_hex(random_bytes(9)?.as_ref());
+
+    let mut bytes = vec![direction.marker(), CHUNK_SEP];
+    bytes.extend_from_slice(value_chunk.as_bytes());
+    bytes.push(CHUNK_SEP);
+    bytes.extend_from_slice(label_chunk.as_bytes());
+    bytes.push(CHUNK_SEP);
+    bytes.extend_from_slice(bucket_chunk.as_bytes());
+
+    Ok(LinkTag::new(bytes))
+}
+
+fn decode_tag(tag: &LinkTag) -> Option<TrustTag> {
+    let text = std::str::from_utf8(tag.as_ref()).ok()?;
+    let mut chunks = text.splitn(3, CHUNK_SEP as char);
+
+    let direction = Direction::from_marker(chunks.next()?.as_bytes().first().copied()?)?;
+    let value: f32 = chunks.next()?.parse().ok()?;
+    let label = chunks
+        .next()
+        .and_then(|rest| rest.rsplit_once(CHUNK_SEP as char))
+        .map(|(label, _bucket)| label)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    Some(TrustTag { direction, value, label })
+}
+
+/// Record `value` (clamped to `[-1.0, 1.0]`) as `rater`'s trust annotation
+/// of `target` — another agent or a specific `Understanding`. Creates both
+/// a forward link (`rater` -> `target`, queryable by the rater) and a
+/// reverse link (`target` -> `rater`, queryable by the rated entity) so
+/// `aggregate_incoming_trust` can find every rating of `target` without
+/// needing to already know who placed it.
+pub fn rate(rater: &AgentPubKey, target: AnyLinkableHash, value: f32, label: Option<&str>) -> ExternResult<()> {
+    create_link(rater.clone(), target.clone(), LinkTypes::TrustEdge, encode_tag(Direction::Forward, value, label)?)?;
+    create_link(target, rater.clone(), LinkTypes::TrustEdge, encode_tag(Direction::Reverse, value, label)?)?;
+    Ok(())
+}
+
+/// Sum every rating placed on `target`: walk `target`'s `TrustEdge` links,
+/// keep only the `Reverse`-marked ones (the ratings stored back at the
+/// rated entity), and add up their decoded values. `0.0` if `target` has
+/// never been rated.
+pub fn aggregate_incoming_trust(target: AnyLinkableHash) -> ExternResult<f32> {
+    let links = get_links(GetLinksInputBuilder::try_new(target, LinkTypes::TrustEdge)?.build())?;
+
+    let total = links
+        .iter()
+        .filter_map(|link| decode_tag(&link.tag))
+        .filter(|trust| trust.direction == Direction::Reverse)
+        .map(|trust| trust.value)
+        .sum();
+
+    Ok(total)
+}
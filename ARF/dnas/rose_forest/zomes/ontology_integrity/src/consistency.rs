@@ -0,0 +1,465 @@
+use crate::{all_type_ids, get_type_definition, KnowledgeTriple, OntologyRelation, OntologyType};
+use std::collections::{HashMap, HashSet};
+
+/// Pairs of types declared mutually exclusive — no entity may belong to
+/// both branches at once. Hardcoded the same way `get_relation`/
+/// `get_type_definition` bootstrap their definitions; a production
+/// deployment would look these up from a DHT-stored disjointness table
+/// instead of a separate `OntologyType` field, so adding a pair doesn't
+/// require touching every existing `OntologyType` literal in this crate.
+pub(crate) fn disjoint_type_pairs() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("AIModel", "Dataset"),
+        ("AIModel", "Benchmark"),
+        ("Dataset", "Benchmark"),
+        ("Capability", "Dataset"),
+        ("Capability", "Benchmark"),
+    ]
+}
+
+fn is_disjoint_pair(a: &str, b: &str) -> bool {
+    disjoint_type_pairs().iter().any(|(x, y)| (*x == a && *y == b) || (*x == b && *y == a))
+}
+
+/// A single consistency problem found by `check_consistency`. Every
+/// violation is collected and returned together rather than stopping at
+/// the first, so a maintainer can repair a whole ontology in one pass.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsistencyViolation {
+    /// The `parent` hierarchy is not a DAG — `path` lists the type_ids
+    /// along the cycle, starting and ending at the same type.
+    HierarchyCycle { path: Vec<String> },
+    /// `entity` was asserted (via `is_a` triples) to belong to both
+    /// `type_a` and `type_b`, which `disjoint_type_pairs` declares
+    /// mutually exclusive.
+    DisjointnessViolation { entity: String, type_a: String, type_b: String },
+    /// Triples `a R b` and `b R a` both exist for a relation `R` that is
+    /// transitive but not symmetric — transitivity would then force `a R
+    /// a` and `b R b`, which is never intended for a relation like
+    /// `improves_upon`.
+    RelationContradiction { relation: String, a: String, b: String },
+    /// `a R b` and `b R a` both exist for a relation `R` explicitly
+    /// declared `is_antisymmetric` — a direct violation of the flag's own
+    /// meaning, independent of whether `R` happens to also be transitive.
+    AntisymmetryViolation { relation: String, a: String, b: String },
+    /// `R` is declared both `is_symmetric` and `is_transitive` but *not*
+    /// `is_reflexive`, and at least one non-self-loop triple `a R b`
+    /// (`a != b`) is asserted for it — symmetry would add `b R a`, and
+    /// transitivity over that pair then forces `a R a`/`b R b`, a
+    /// self-loop the relation's own `is_reflexive: false` says should
+    /// never occur.
+    SymmetricTransitiveReflexivityConflict { relation: String, a: String, b: String },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// Build a `type_id -> parent` map covering both the hardcoded bootstrap
+/// universe (`all_type_ids`/`get_type_definition`) and any caller-supplied
+/// `types` — e.g. custom `OntologyType` entries asserted by agents, which
+/// the hardcoded universe knows nothing about. A supplied type reusing an
+/// existing `type_id` overrides the bootstrap definition, matching how a
+/// DHT-stored type would shadow a bootstrap default.
+fn build_parent_map(types: &[OntologyType]) -> HashMap<String, Option<String>> {
+    let mut parents: HashMap<String, Option<String>> = all_type_ids()
+        .into_iter()
+        .filter_map(|type_id| get_type_definition(type_id).ok().map(|def| (type_id.to_string(), def.parent)))
+        .collect();
+
+    for custom in types {
+        parents.insert(custom.type_id.clone(), custom.parent.clone());
+    }
+
+    parents
+}
+
+/// DFS over `parent` edges with visited/on-stack coloring: a White node
+/// becomes Gray on entry and Black on exit; revisiting a Gray node means
+/// the path currently on the stack closes a cycle back to it.
+fn find_hierarchy_cycle(types: &[OntologyType]) -> Option<Vec<String>> {
+    let parents = build_parent_map(types);
+    let mut color: HashMap<String, Color> = parents.keys().map(|t| (t.clone(), Color::White)).collect();
+
+    let start_nodes: Vec<String> = parents.keys().cloned().collect();
+    for start in start_nodes {
+        if color[&start] != Color::White {
+            continue;
+        }
+        let mut stack = vec![start];
+        let mut path: Vec<String> = Vec::new();
+
+        while let Some(node) = stack.last().cloned() {
+            match color[&node] {
+                Color::White => {
+                    color.insert(node.clone(), Color::Gray);
+                    path.push(node.clone());
+                    let Some(parent_opt) = parents.get(&node) else {
+                        stack.pop();
+                        continue;
+                    };
+                    match parent_opt.clone() {
+                        Some(parent) if color.contains_key(&parent) => match color[&parent] {
+                            Color::White => stack.push(parent),
+                            Color::Gray => {
+                                let cycle_start = path.iter().position(|n| *n == parent).unwrap_or(0);
+                                let mut cycle: Vec<String> = path[cycle_start..].to_vec();
+                                cycle.push(parent);
+                                return Some(cycle);
+                            }
+                            Color::Black => {}
+                        },
+                        _ => {}
+                    }
+                }
+                _ => {
+                    stack.pop();
+                    if let Some(top) = path.last() {
+                        if *top == node {
+                            path.pop();
+                            color.insert(node, Color::Black);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Entities asserted to belong to two mutually exclusive types via `is_a`
+/// triples (`subject is_a object`), reported pairwise per entity.
+fn find_disjointness_violations(triples: &[KnowledgeTriple]) -> Vec<ConsistencyViolation> {
+    let mut asserted_types: HashMap<&str, Vec<&str>> = HashMap::new();
+    for triple in triples {
+        if triple.predicate == "is_a" {
+            asserted_types.entry(triple.subject.as_str()).or_default().push(triple.object.as_str());
+        }
+    }
+
+    let mut violations = Vec::new();
+    for (entity, types) in asserted_types {
+        for i in 0..types.len() {
+            for j in (i + 1)..types.len() {
+                if is_disjoint_pair(types[i], types[j]) {
+                    violations.push(ConsistencyViolation::DisjointnessViolation {
+                        entity: entity.to_string(),
+                        type_a: types[i].to_string(),
+                        type_b: types[j].to_string(),
+                    });
+                }
+            }
+        }
+    }
+    violations
+}
+
+/// Pairs of triples asserting `a R b` for the same `R` in both
+/// directions (`a != b`), deduplicated per unordered `(a, b)` pair so a
+/// back-and-forth pair of triples is reported once regardless of which
+/// direction is scanned first.
+fn reciprocal_pairs<'a>(triples: &'a [KnowledgeTriple], relation_id: &str) -> Vec<(&'a str, &'a str)> {
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let mut reported: HashSet<(String, String)> = HashSet::new();
+    let mut pairs = Vec::new();
+
+    for triple in triples.iter().filter(|t| t.predicate == relation_id) {
+        seen.insert((triple.subject.clone(), triple.object.clone()));
+    }
+
+    for triple in triples.iter().filter(|t| t.predicate == relation_id) {
+        if triple.subject == triple.object {
+            continue;
+        }
+        if seen.contains(&(triple.object.clone(), triple.subject.clone())) {
+            let (a, b) = if triple.subject <= triple.object {
+                (triple.subject.as_str(), triple.object.as_str())
+            } else {
+                (triple.object.as_str(), triple.subject.as_str())
+            };
+            if reported.insert((a.to_string(), b.to_string())) {
+                pairs.push((a, b));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// `a R b` and `b R a` both asserted for a transitive, non-symmetric `R`
+/// — which would force `a R a` and `b R b` under transitivity, a
+/// contradiction for a relation like `improves_upon` that a model cannot
+/// hold with itself.
+fn find_relation_contradictions(triples: &[KnowledgeTriple], relations: &HashMap<&str, &OntologyRelation>) -> Vec<ConsistencyViolation> {
+    let mut violations = Vec::new();
+
+    for (relation_id, relation) in relations {
+        if !relation.is_transitive || relation.is_symmetric {
+            continue;
+        }
+        for (a, b) in reciprocal_pairs(triples, relation_id) {
+            violations.push(ConsistencyViolation::RelationContradiction {
+                relation: relation_id.to_string(),
+                a: a.to_string(),
+                b: b.to_string(),
+            });
+        }
+    }
+    violations
+}
+
+/// `a R b` and `b R a` both asserted for a relation explicitly declared
+/// `is_antisymmetric` — a direct violation regardless of transitivity.
+fn find_antisymmetry_violations(triples: &[KnowledgeTriple], relations: &HashMap<&str, &OntologyRelation>) -> Vec<ConsistencyViolation> {
+    let mut violations = Vec::new();
+
+    for (relation_id, relation) in relations {
+        if !relation.is_antisymmetric {
+            continue;
+        }
+        for (a, b) in reciprocal_pairs(triples, relation_id) {
+            violations.push(ConsistencyViolation::AntisymmetryViolation {
+                relation: relation_id.to_string(),
+                a: a.to_string(),
+                b: b.to_string(),
+            });
+        }
+    }
+    violations
+}
+
+/// A relation declared both `is_symmetric` and `is_transitive` but not
+/// `is_reflexive` forces a self-loop the moment any non-reflexive pair is
+/// asserted (symmetry adds the reverse triple, transitivity over the
+/// pair then entails `a R a`) — a structural conflict between the
+/// relation's own flags rather than anything the asserted triples did
+/// wrong.
+fn find_symmetric_transitive_reflexivity_conflicts(
+    triples: &[KnowledgeTriple],
+    relations: &HashMap<&str, &OntologyRelation>,
+) -> Vec<ConsistencyViolation> {
+    let mut violations = Vec::new();
+    let mut reported: HashSet<(String, String, String)> = HashSet::new();
+
+    for (relation_id, relation) in relations {
+        if !(relation.is_symmetric && relation.is_transitive) || relation.is_reflexive {
+            continue;
+        }
+        for triple in triples.iter().filter(|t| t.predicate == **relation_id && t.subject != t.object) {
+            let key = (relation_id.to_string(), triple.subject.clone(), triple.object.clone());
+            if reported.insert(key) {
+                violations.push(ConsistencyViolation::SymmetricTransitiveReflexivityConflict {
+                    relation: relation_id.to_string(),
+                    a: triple.subject.clone(),
+                    b: triple.object.clone(),
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Check an ontology — its type hierarchy, its relation definitions, and
+/// the `triples` asserted against them — for internal contradictions: a
+/// cyclic `is_a` hierarchy (covering both the bootstrap types and any
+/// caller-supplied `types`), entities asserted into two disjoint types,
+/// and relation-level contradictions (a non-symmetric transitive
+/// relation, or one explicitly `is_antisymmetric`, asserted in both
+/// directions between the same pair; or a symmetric+transitive relation
+/// whose own `is_reflexive: false` is violated by the self-loop its other
+/// two flags force). Returns every violation found, not just the first,
+/// so an ontology can be repaired in one pass.
+pub fn check_consistency(
+    types: &[OntologyType],
+    relations: &[OntologyRelation],
+    triples: &[KnowledgeTriple],
+) -> Vec<ConsistencyViolation> {
+    let relations_by_id: HashMap<&str, &OntologyRelation> =
+        relations.iter().map(|r| (r.relation_id.as_str(), r)).collect();
+
+    let mut violations = Vec::new();
+
+    if let Some(path) = find_hierarchy_cycle(types) {
+        violations.push(ConsistencyViolation::HierarchyCycle { path });
+    }
+    violations.extend(find_disjointness_violations(triples));
+    violations.extend(find_relation_contradictions(triples, &relations_by_id));
+    violations.extend(find_antisymmetry_violations(triples, &relations_by_id));
+    violations.extend(find_symmetric_transitive_reflexivity_conflicts(triples, &relations_by_id));
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_relation;
+    use hdi::prelude::*;
+
+    fn fake_agent_pub_key() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn triple(subject: &str, predicate: &str, object: &str) -> KnowledgeTriple {
+        KnowledgeTriple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence: 1.0,
+            source: fake_agent_pub_key(),
+            created_at: Timestamp::from_micros(0),
+        }
+    }
+
+    fn relations(ids: &[&str]) -> Vec<OntologyRelation> {
+        ids.iter().map(|id| get_relation(id).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_no_violations_for_consistent_hierarchy_and_triples() {
+        let triples = vec![
+            triple("GPT-4", "is_a", "LLM"),
+            triple("GPT-4", "improves_upon", "GPT-3.5"),
+        ];
+        assert!(check_consistency(&[], &relations(&["is_a", "improves_upon"]), &triples).is_empty());
+    }
+
+    #[test]
+    fn test_no_hierarchy_cycle_in_bootstrap_types() {
+        assert!(find_hierarchy_cycle(&[]).is_none(), "The bootstrap type hierarchy shipped with this crate must be acyclic");
+    }
+
+    #[test]
+    fn test_hierarchy_cycle_detected_in_supplied_types() {
+        let custom_types = vec![
+            OntologyType { type_id: "Foo".into(), name: "Foo".into(), parent: Some("Bar".into()), description: String::new(), created_at: Timestamp::from_micros(0) },
+            OntologyType { type_id: "Bar".into(), name: "Bar".into(), parent: Some("Foo".into()), description: String::new(), created_at: Timestamp::from_micros(0) },
+        ];
+        let violations = check_consistency(&custom_types, &[], &[]);
+        assert!(violations.iter().any(|v| matches!(v, ConsistencyViolation::HierarchyCycle { .. })));
+    }
+
+    #[test]
+    fn test_disjointness_violation_detected() {
+        let triples = vec![
+            triple("Thing-X", "is_a", "AIModel"),
+            triple("Thing-X", "is_a", "Dataset"),
+        ];
+        let violations = check_consistency(&[], &relations(&["is_a"]), &triples);
+        assert!(violations.iter().any(|v| matches!(v,
+            ConsistencyViolation::DisjointnessViolation { entity, .. } if entity == "Thing-X"
+        )));
+    }
+
+    #[test]
+    fn test_non_disjoint_types_do_not_trigger_violation() {
+        let triples = vec![
+            triple("GPT-4", "is_a", "AIModel"),
+            triple("GPT-4", "is_a", "LLM"),
+        ];
+        assert!(check_consistency(&[], &relations(&["is_a"]), &triples).is_empty(), "AIModel/LLM are in an is_a relationship, not disjoint");
+    }
+
+    #[test]
+    fn test_relation_contradiction_detected_for_transitive_nonsymmetric_relation() {
+        let triples = vec![
+            triple("A", "improves_upon", "B"),
+            triple("B", "improves_upon", "A"),
+        ];
+        let violations = check_consistency(&[], &relations(&["improves_upon"]), &triples);
+        assert!(violations.iter().any(|v| matches!(v,
+            ConsistencyViolation::RelationContradiction { relation, .. } if relation == "improves_upon"
+        )));
+    }
+
+    #[test]
+    fn test_relation_contradiction_not_raised_for_symmetric_relation() {
+        // related_to is symmetric and reflexive, so A related_to B and B
+        // related_to A asserted together is expected, not a contradiction.
+        let triples = vec![
+            triple("A", "related_to", "B"),
+            triple("B", "related_to", "A"),
+        ];
+        assert!(check_consistency(&[], &relations(&["related_to"]), &triples).is_empty());
+    }
+
+    #[test]
+    fn test_relation_contradiction_deduplicated_per_pair() {
+        let triples = vec![
+            triple("A", "improves_upon", "B"),
+            triple("B", "improves_upon", "A"),
+        ];
+        let violations = check_consistency(&[], &relations(&["improves_upon"]), &triples);
+        let count = violations.iter().filter(|v| matches!(v, ConsistencyViolation::RelationContradiction { .. })).count();
+        assert_eq!(count, 1, "Should report the A/B contradiction once, not once per direction");
+    }
+
+    #[test]
+    fn test_antisymmetry_violation_detected_for_flagged_relation() {
+        // improves_upon is declared is_antisymmetric, independent of the
+        // transitive+non-symmetric check that also fires for it.
+        let triples = vec![
+            triple("A", "improves_upon", "B"),
+            triple("B", "improves_upon", "A"),
+        ];
+        let violations = check_consistency(&[], &relations(&["improves_upon"]), &triples);
+        assert!(violations.iter().any(|v| matches!(v, ConsistencyViolation::AntisymmetryViolation { relation, .. } if relation == "improves_upon")));
+    }
+
+    #[test]
+    fn test_antisymmetry_violation_not_raised_for_unflagged_relation() {
+        let mut relation = get_relation("part_of").unwrap();
+        relation.is_antisymmetric = false;
+        let triples = vec![
+            triple("A", "part_of", "B"),
+            triple("B", "part_of", "A"),
+        ];
+        let violations = check_consistency(&[], &[relation], &triples);
+        assert!(!violations.iter().any(|v| matches!(v, ConsistencyViolation::AntisymmetryViolation { .. })));
+    }
+
+    #[test]
+    fn test_symmetric_transitive_reflexivity_conflict_detected() {
+        let mut conflicting_relation = get_relation("related_to").unwrap();
+        conflicting_relation.is_reflexive = false; // still symmetric + transitive
+        conflicting_relation.is_transitive = true;
+
+        let triples = vec![triple("A", "related_to", "B")];
+        let violations = check_consistency(&[], &[conflicting_relation], &triples);
+
+        assert!(violations.iter().any(|v| matches!(v,
+            ConsistencyViolation::SymmetricTransitiveReflexivityConflict { relation, a, b }
+                if relation == "related_to" && a == "A" && b == "B"
+        )));
+    }
+
+    #[test]
+    fn test_symmetric_transitive_reflexivity_conflict_absent_when_reflexive() {
+        // related_to's bootstrap definition is symmetric + reflexive, so no conflict.
+        let triples = vec![triple("A", "related_to", "B")];
+        let violations = check_consistency(&[], &relations(&["related_to"]), &triples);
+        assert!(!violations.iter().any(|v| matches!(v, ConsistencyViolation::SymmetricTransitiveReflexivityConflict { .. })));
+    }
+
+    #[test]
+    fn test_all_violation_kinds_collected_together() {
+        let triples = vec![
+            triple("Thing-X", "is_a", "AIModel"),
+            triple("Thing-X", "is_a", "Dataset"),
+            triple("A", "improves_upon", "B"),
+            triple("B", "improves_upon", "A"),
+        ];
+        let violations = check_consistency(&[], &relations(&["is_a", "improves_upon"]), &triples);
+        assert!(violations.iter().any(|v| matches!(v, ConsistencyViolation::DisjointnessViolation { .. })));
+        assert!(violations.iter().any(|v| matches!(v, ConsistencyViolation::RelationContradiction { .. })));
+        assert!(violations.iter().any(|v| matches!(v, ConsistencyViolation::AntisymmetryViolation { .. })));
+    }
+}
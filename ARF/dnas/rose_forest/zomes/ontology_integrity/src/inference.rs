@@ -1,5 +1,6 @@
 use hdi::prelude::*;
-use crate::{KnowledgeTriple, OntologyError};
+use crate::{KnowledgeTriple, OntologyError, OntologyRelation};
+use std::collections::{HashMap, HashSet};
 
 /// Infer new knowledge from existing triples using axioms
 ///
@@ -163,6 +164,259 @@ pub fn can_infer(knowledge_base: &[KnowledgeTriple], query: &KnowledgeTriple) ->
     })
 }
 
+/// Default bound on `compute_closure`'s round count, used by callers that
+/// don't have a specific budget in mind. Chosen generously since each
+/// round is cheap relative to typical triple-set sizes; callers with
+/// tighter latency needs should pass their own bound.
+pub const DEFAULT_MAX_CLOSURE_ITERATIONS: usize = 20;
+
+/// `(subject, predicate, object)` with confidence/source/timestamp
+/// stripped — the part of a `KnowledgeTriple` that determines identity for
+/// deduplication in `compute_closure`.
+type TripleKey = (String, String, String);
+
+fn triple_key(triple: &KnowledgeTriple) -> TripleKey {
+    (triple.subject.clone(), triple.predicate.clone(), triple.object.clone())
+}
+
+/// Combine two derivations of the *same* triple reached via independent
+/// paths within one round: confidences combine with noisy-OR
+/// (`1 - (1-a)*(1-b)`, the standard way independent pieces of evidence
+/// reinforce rather than multiply down), the earlier-asserted of the two
+/// contributing sources is kept as `source` (an approximation of "the
+/// union of contributing sources" that fits the single-`AgentPubKey`
+/// field `KnowledgeTriple` already has), and `created_at` becomes the
+/// later of the two.
+fn combine_derivations(existing: &KnowledgeTriple, candidate: &KnowledgeTriple) -> KnowledgeTriple {
+    let source = if candidate.created_at < existing.created_at { candidate.source.clone() } else { existing.source.clone() };
+    KnowledgeTriple {
+        subject: existing.subject.clone(),
+        predicate: existing.predicate.clone(),
+        object: existing.object.clone(),
+        confidence: 1.0 - (1.0 - existing.confidence) * (1.0 - candidate.confidence),
+        source,
+        created_at: existing.created_at.max(candidate.created_at),
+    }
+}
+
+/// Merge `candidate` into `next_delta` (keyed by subject/predicate/object)
+/// unless its key is already present in `known_keys` (derived in an
+/// earlier round, which `compute_closure`'s semi-naive loop never
+/// revisits). If another path already derived the same triple earlier in
+/// this round, the two are combined via `combine_derivations` instead of
+/// the later one being dropped, so independent paths reinforce one
+/// another rather than only the first-found path counting.
+fn push_if_new(
+    next_delta: &mut Vec<KnowledgeTriple>,
+    next_keys: &mut HashMap<TripleKey, usize>,
+    known_keys: &HashSet<TripleKey>,
+    candidate: KnowledgeTriple,
+) {
+    let key = triple_key(&candidate);
+    if known_keys.contains(&key) {
+        return;
+    }
+    if let Some(&index) = next_keys.get(&key) {
+        next_delta[index] = combine_derivations(&next_delta[index], &candidate);
+        return;
+    }
+    next_keys.insert(key, next_delta.len());
+    next_delta.push(candidate);
+}
+
+/// Shared semi-naive fixpoint core behind `compute_closure` and
+/// `compute_closure_incremental`: `known_seed` is treated as already
+/// closed (it participates in joins but is never itself re-derived or
+/// re-emitted), and `delta_seed` is the frontier to propagate from. A
+/// fresh call (`known_seed` empty, `delta_seed = triples`) reproduces the
+/// full closure; seeding `known_seed` with a previously computed closure
+/// and `delta_seed` with only the newly asserted facts computes just the
+/// incremental additions, without re-deriving anything already known.
+fn closure_core(
+    known_seed: &[KnowledgeTriple],
+    delta_seed: &[KnowledgeTriple],
+    relations: &[OntologyRelation],
+    max_iterations: usize,
+) -> Vec<KnowledgeTriple> {
+    let relations_by_id: HashMap<&str, &OntologyRelation> =
+        relations.iter().map(|r| (r.relation_id.as_str(), r)).collect();
+
+    let mut known: Vec<KnowledgeTriple> = known_seed.to_vec();
+    let mut known_keys: HashSet<TripleKey> = known_seed.iter().map(triple_key).collect();
+    let mut delta: Vec<KnowledgeTriple> = delta_seed.to_vec();
+    let mut derived: Vec<KnowledgeTriple> = Vec::new();
+
+    for round in 0..max_iterations {
+        if delta.is_empty() {
+            break;
+        }
+
+        for triple in &delta {
+            known_keys.insert(triple_key(triple));
+        }
+        known.extend(delta.iter().cloned());
+
+        let mut next_delta: Vec<KnowledgeTriple> = Vec::new();
+        let mut next_keys: HashMap<TripleKey, usize> = HashMap::new();
+
+        for triple in &delta {
+            let Some(relation) = relations_by_id.get(triple.predicate.as_str()) else {
+                continue;
+            };
+
+            if relation.is_transitive {
+                for other in known.iter().filter(|o| o.predicate == triple.predicate && o.subject == triple.object) {
+                    let candidate = KnowledgeTriple {
+                        subject: triple.subject.clone(),
+                        predicate: triple.predicate.clone(),
+                        object: other.object.clone(),
+                        confidence: triple.confidence * other.confidence,
+                        source: triple.source.clone(),
+                        created_at: triple.created_at,
+                    };
+                    push_if_new(&mut next_delta, &mut next_keys, &known_keys, candidate);
+                }
+                // A closed `known_seed` triple can also be the left premise of
+                // a first-round fact; later rounds extend those results above.
+                if round == 0 {
+                    for other in known_seed.iter().filter(|o| o.predicate == triple.predicate && o.object == triple.subject) {
+                        let candidate = KnowledgeTriple {
+                            subject: other.subject.clone(),
+                            predicate: triple.predicate.clone(),
+                            object: triple.object.clone(),
+                            confidence: other.confidence * triple.confidence,
+                            source: triple.source.clone(),
+                            created_at: triple.created_at,
+                        };
+                        push_if_new(&mut next_delta, &mut next_keys, &known_keys, candidate);
+                    }
+                }
+            }
+
+            if relation.is_symmetric {
+                let candidate = KnowledgeTriple {
+                    subject: triple.object.clone(),
+                    predicate: triple.predicate.clone(),
+                    object: triple.subject.clone(),
+                    confidence: triple.confidence,
+                    source: triple.source.clone(),
+                    created_at: triple.created_at,
+                };
+                push_if_new(&mut next_delta, &mut next_keys, &known_keys, candidate);
+            }
+
+            if relation.is_reflexive {
+                for entity in [&triple.subject, &triple.object] {
+                    let candidate = KnowledgeTriple {
+                        subject: entity.clone(),
+                        predicate: triple.predicate.clone(),
+                        object: entity.clone(),
+                        confidence: triple.confidence,
+                        source: triple.source.clone(),
+                        created_at: triple.created_at,
+                    };
+                    push_if_new(&mut next_delta, &mut next_keys, &known_keys, candidate);
+                }
+            }
+        }
+
+        derived.extend(next_delta.iter().cloned());
+        delta = next_delta;
+    }
+
+    derived
+}
+
+/// Compute the deductive closure of `triples` under the transitive,
+/// symmetric, and reflexive properties declared on `relations`, via
+/// semi-naive (delta-based) fixpoint evaluation.
+///
+/// Each round joins the previous round's `delta` against the full `known`
+/// set built so far: a transitive `R` joins new `(a,R,b)` with existing
+/// `(b,R,c)` to produce `(a,R,c)`; a symmetric `R` adds `(b,R,a)` for every
+/// `(a,R,b)`; a reflexive `R` adds `(x,R,x)` for every entity `x` the
+/// triple mentions. Only triples not already known are carried into the
+/// next round's `delta`, and the loop stops as soon as a round produces
+/// nothing new or `max_iterations` rounds have run — whichever comes
+/// first, guarding against runaway growth on a relation set that never
+/// reaches a fixpoint in practice.
+///
+/// Derived triples inherit a confidence equal to the product of their
+/// premises' confidences (the two triples joined to produce them; a
+/// symmetric/reflexive derivation has a single premise, so its confidence
+/// is simply copied). When several premise pairs derive the same triple in
+/// one round, their confidences combine via `combine_derivations`.
+///
+/// Returns only the newly entailed triples; callers that want the full
+/// closure should concatenate the result with `triples` themselves, or
+/// call `infer_closure` for a result that already includes both, tagged.
+pub fn compute_closure(
+    triples: &[KnowledgeTriple],
+    relations: &[OntologyRelation],
+    max_iterations: usize,
+) -> Vec<KnowledgeTriple> {
+    closure_core(&[], triples, relations, max_iterations)
+}
+
+/// Incremental variant of `compute_closure`: `known` must already be a
+/// fully-closed set (the output of a prior `compute_closure`/
+/// `infer_closure` call plus its own inputs), and `new_facts` are freshly
+/// asserted triples not yet folded in. Returns only the triples newly
+/// entailed by adding `new_facts` to `known` — `known` itself is never
+/// re-derived or re-emitted, so this is cheap to call after every small
+/// batch of new assertions instead of recomputing the closure from
+/// scratch.
+pub fn compute_closure_incremental(
+    known: &[KnowledgeTriple],
+    new_facts: &[KnowledgeTriple],
+    relations: &[OntologyRelation],
+    max_iterations: usize,
+) -> Vec<KnowledgeTriple> {
+    closure_core(known, new_facts, relations, max_iterations)
+}
+
+/// A triple paired with whether it was part of the original input
+/// (`derived: false`) or newly entailed by forward chaining (`derived:
+/// true`), so callers can distinguish asserted from inferred facts
+/// without having to diff the input and output sets themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedTriple {
+    pub triple: KnowledgeTriple,
+    pub derived: bool,
+}
+
+/// Materialize the full closure of `triples` — every asserted triple plus
+/// everything `compute_closure` entails from them — each tagged via
+/// `DerivedTriple::derived` so a caller can tell which triples it handed
+/// in versus which the engine produced.
+pub fn infer_closure(triples: &[KnowledgeTriple], relations: &[OntologyRelation]) -> Vec<DerivedTriple> {
+    let mut result: Vec<DerivedTriple> =
+        triples.iter().cloned().map(|triple| DerivedTriple { triple, derived: false }).collect();
+
+    let newly_derived = compute_closure(triples, relations, DEFAULT_MAX_CLOSURE_ITERATIONS);
+    result.extend(newly_derived.into_iter().map(|triple| DerivedTriple { triple, derived: true }));
+
+    result
+}
+
+/// Incremental counterpart to `infer_closure`, mirroring
+/// `compute_closure_incremental`: `known` is a previously materialized
+/// closure (asserted and derived triples alike, already flattened to
+/// plain `KnowledgeTriple`s), and `new_facts` are freshly asserted
+/// triples. Returns only the newly derived triples the addition of
+/// `new_facts` entails — callers fold these into their own store
+/// alongside `new_facts` themselves.
+pub fn infer_closure_incremental(
+    known: &[KnowledgeTriple],
+    new_facts: &[KnowledgeTriple],
+    relations: &[OntologyRelation],
+) -> Vec<DerivedTriple> {
+    compute_closure_incremental(known, new_facts, relations, DEFAULT_MAX_CLOSURE_ITERATIONS)
+        .into_iter()
+        .map(|triple| DerivedTriple { triple, derived: true })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +433,14 @@ mod tests {
         Timestamp::from_micros(1000000)
     }
 
+    fn fake_agent_pub_key_2() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
     #[test]
     fn test_capability_inheritance() {
         // Given: Sonnet-4.5 improves_upon Sonnet-4
@@ -322,4 +584,252 @@ mod tests {
 
         assert!(can_infer(&knowledge_base, &query), "Should be able to infer coding capability");
     }
+
+    fn improves_upon_relation() -> OntologyRelation {
+        OntologyRelation {
+            relation_id: "improves_upon".into(),
+            name: "improves upon".into(),
+            domain: vec!["LLM".into()],
+            range: vec!["LLM".into()],
+            is_transitive: true,
+            is_symmetric: false,
+            is_reflexive: false,
+            is_antisymmetric: true,
+            description: "Model improves upon another model".into(),
+            created_at: fake_timestamp(),
+        }
+    }
+
+    fn related_to_relation() -> OntologyRelation {
+        OntologyRelation {
+            relation_id: "related_to".into(),
+            name: "related to".into(),
+            domain: vec![],
+            range: vec![],
+            is_transitive: false,
+            is_symmetric: true,
+            is_reflexive: true,
+            is_antisymmetric: false,
+            description: "General relatedness".into(),
+            created_at: fake_timestamp(),
+        }
+    }
+
+    fn triple(subject: &str, predicate: &str, object: &str, confidence: f32) -> KnowledgeTriple {
+        KnowledgeTriple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence,
+            source: fake_agent_pub_key(),
+            created_at: fake_timestamp(),
+        }
+    }
+
+    #[test]
+    fn test_compute_closure_transitive_chain() {
+        let triples = vec![
+            triple("GPT-4", "improves_upon", "GPT-3.5", 1.0),
+            triple("GPT-3.5", "improves_upon", "GPT-3", 1.0),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(derived.iter().any(|t| t.subject == "GPT-4" && t.predicate == "improves_upon" && t.object == "GPT-3"),
+            "Should derive GPT-4 improves_upon GPT-3 transitively");
+    }
+
+    #[test]
+    fn test_compute_closure_confidence_is_product_of_premises() {
+        let triples = vec![
+            triple("A", "improves_upon", "B", 0.9),
+            triple("B", "improves_upon", "C", 0.5),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        let entailed = derived.iter().find(|t| t.subject == "A" && t.object == "C").expect("should derive A improves_upon C");
+        assert!((entailed.confidence - 0.45).abs() < 1e-6, "confidence should be 0.9 * 0.5, got {}", entailed.confidence);
+    }
+
+    #[test]
+    fn test_compute_closure_symmetric_relation() {
+        let triples = vec![triple("A", "related_to", "B", 1.0)];
+
+        let derived = compute_closure(&triples, &[related_to_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(derived.iter().any(|t| t.subject == "B" && t.predicate == "related_to" && t.object == "A"),
+            "Symmetric relation should derive the reverse triple");
+    }
+
+    #[test]
+    fn test_compute_closure_reflexive_relation() {
+        let triples = vec![triple("A", "related_to", "B", 1.0)];
+
+        let derived = compute_closure(&triples, &[related_to_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(derived.iter().any(|t| t.subject == "A" && t.predicate == "related_to" && t.object == "A"));
+        assert!(derived.iter().any(|t| t.subject == "B" && t.predicate == "related_to" && t.object == "B"));
+    }
+
+    #[test]
+    fn test_compute_closure_does_not_duplicate_input_triples() {
+        let triples = vec![
+            triple("A", "improves_upon", "B", 1.0),
+            triple("B", "improves_upon", "C", 1.0),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        // The input triples themselves must not reappear in the derived set.
+        assert!(!derived.iter().any(|t| t.subject == "A" && t.object == "B"));
+        assert!(!derived.iter().any(|t| t.subject == "B" && t.object == "C"));
+    }
+
+    #[test]
+    fn test_compute_closure_respects_max_iterations() {
+        // A chain long enough that a single round cannot reach the far end.
+        let triples = vec![
+            triple("A", "improves_upon", "B", 1.0),
+            triple("B", "improves_upon", "C", 1.0),
+            triple("C", "improves_upon", "D", 1.0),
+            triple("D", "improves_upon", "E", 1.0),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], 1);
+
+        // One round only joins adjacent pairs once, so the full A..E chain
+        // should not yet be entailed.
+        assert!(!derived.iter().any(|t| t.subject == "A" && t.object == "E"),
+            "A single round should not reach the full transitive closure");
+    }
+
+    #[test]
+    fn test_compute_closure_empty_without_matching_relation() {
+        let triples = vec![triple("A", "trained_on", "Dataset-X", 1.0)];
+
+        // No relation definitions supplied at all, so nothing can fire.
+        let derived = compute_closure(&triples, &[], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(derived.is_empty());
+    }
+
+    #[test]
+    fn test_infer_closure_tags_asserted_and_derived() {
+        let triples = vec![
+            triple("GPT-4", "improves_upon", "GPT-3.5", 1.0),
+            triple("GPT-3.5", "improves_upon", "GPT-3", 1.0),
+        ];
+
+        let tagged = infer_closure(&triples, &[improves_upon_relation()]);
+
+        assert!(tagged.iter().any(|d| !d.derived && d.triple.subject == "GPT-4" && d.triple.object == "GPT-3.5"),
+            "original triples must be present and tagged asserted");
+        assert!(tagged.iter().any(|d| d.derived && d.triple.subject == "GPT-4" && d.triple.object == "GPT-3"),
+            "transitive closure must be present and tagged derived");
+    }
+
+    #[test]
+    fn test_infer_closure_preserves_input_count_plus_derivations() {
+        let triples = vec![triple("A", "related_to", "B", 1.0)];
+        let tagged = infer_closure(&triples, &[related_to_relation()]);
+
+        let asserted_count = tagged.iter().filter(|d| !d.derived).count();
+        assert_eq!(asserted_count, triples.len());
+        assert!(tagged.iter().filter(|d| d.derived).count() > 0);
+    }
+
+    #[test]
+    fn test_compute_closure_incremental_matches_full_recompute() {
+        let base = vec![triple("A", "improves_upon", "B", 1.0)];
+        let full_closure = compute_closure(&base, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        // Everything the base set already entails, fully materialized.
+        let mut known = base.clone();
+        known.extend(full_closure);
+
+        let new_facts = vec![triple("B", "improves_upon", "C", 1.0)];
+        let incremental = compute_closure_incremental(&known, &new_facts, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(incremental.iter().any(|t| t.subject == "A" && t.object == "C"),
+            "adding B improves_upon C should incrementally entail A improves_upon C");
+    }
+
+    #[test]
+    fn test_compute_closure_incremental_does_not_rederive_known_facts() {
+        let known = vec![
+            triple("A", "improves_upon", "B", 1.0),
+            triple("A", "improves_upon", "C", 1.0), // already-closed fact
+        ];
+        let new_facts = vec![triple("C", "improves_upon", "D", 1.0)];
+
+        let incremental = compute_closure_incremental(&known, &new_facts, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        assert!(!incremental.iter().any(|t| t.subject == "A" && t.object == "B"),
+            "facts already present in `known` must not be re-emitted as newly derived");
+        assert!(incremental.iter().any(|t| t.subject == "A" && t.object == "D"),
+            "the new fact should still combine with known facts to derive new entailments");
+    }
+
+    #[test]
+    fn test_compute_closure_combines_independent_paths_with_noisy_or() {
+        // Two independent chains both entail A improves_upon D:
+        //   A -(0.9)-> B -(0.5)-> D   => path confidence 0.45
+        //   A -(0.8)-> C -(0.6)-> D   => path confidence 0.48
+        let triples = vec![
+            triple("A", "improves_upon", "B", 0.9),
+            triple("B", "improves_upon", "D", 0.5),
+            triple("A", "improves_upon", "C", 0.8),
+            triple("C", "improves_upon", "D", 0.6),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+
+        let a_to_d: Vec<_> = derived.iter().filter(|t| t.subject == "A" && t.object == "D").collect();
+        assert_eq!(a_to_d.len(), 1, "the two paths must combine into a single A improves_upon D triple");
+
+        let expected = 1.0 - (1.0 - 0.45) * (1.0 - 0.48);
+        assert!((a_to_d[0].confidence - expected).abs() < 1e-6,
+            "noisy-OR combination should give {expected}, got {}", a_to_d[0].confidence);
+    }
+
+    #[test]
+    fn test_compute_closure_single_path_confidence_unaffected_by_noisy_or() {
+        let triples = vec![
+            triple("A", "improves_upon", "B", 0.9),
+            triple("B", "improves_upon", "C", 0.5),
+        ];
+
+        let derived = compute_closure(&triples, &[improves_upon_relation()], DEFAULT_MAX_CLOSURE_ITERATIONS);
+        let entailed = derived.iter().find(|t| t.subject == "A" && t.object == "C").unwrap();
+
+        assert!((entailed.confidence - 0.45).abs() < 1e-6, "a single derivation path is just the product, unchanged by combination");
+    }
+
+    #[test]
+    fn test_combine_derivations_keeps_earlier_source_and_later_timestamp() {
+        let mut later = triple("A", "improves_upon", "D", 0.48);
+        later.created_at = Timestamp::from_micros(2_000_000);
+        later.source = fake_agent_pub_key_2();
+
+        let mut earlier = triple("A", "improves_upon", "D", 0.45);
+        earlier.created_at = Timestamp::from_micros(500_000);
+        earlier.source = fake_agent_pub_key();
+
+        let combined = combine_derivations(&earlier, &later);
+
+        assert_eq!(combined.source, fake_agent_pub_key());
+        assert_eq!(combined.created_at, Timestamp::from_micros(2_000_000));
+    }
+
+    #[test]
+    fn test_infer_closure_incremental_tags_results_as_derived() {
+        let known = vec![triple("A", "improves_upon", "B", 1.0)];
+        let new_facts = vec![triple("B", "improves_upon", "C", 1.0)];
+
+        let tagged = infer_closure_incremental(&known, &new_facts, &[improves_upon_relation()]);
+
+        assert!(tagged.iter().all(|d| d.derived));
+        assert!(tagged.iter().any(|d| d.triple.subject == "A" && d.triple.object == "C"));
+    }
 }
@@ -0,0 +1,170 @@
+use crate::{get_type_definition, is_subtype_of, OntologyError};
+
+/// The result of a `join` or `meet` lattice operation: the resolved type
+/// (`None` if the two types share no common ancestor/subtype), plus the
+/// ancestor path that explains how it was reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatticeResult {
+    pub type_id: Option<String>,
+    pub path: Vec<String>,
+}
+
+/// Walk `parent` links from `type_id` up to the `Thing` root, returning
+/// the chain starting with `type_id` itself and ending at the root. The
+/// hierarchy must be acyclic for this to terminate — pair with
+/// `consistency::check_consistency` before relying on it in a context
+/// where the hierarchy isn't already known-good.
+pub fn ancestor_chain(type_id: &str) -> Result<Vec<String>, OntologyError> {
+    let mut chain = vec![type_id.to_string()];
+    let mut current = type_id.to_string();
+    loop {
+        match get_type_definition(&current)?.parent {
+            Some(parent) => {
+                chain.push(parent.clone());
+                current = parent;
+            }
+            None => break,
+        }
+    }
+    Ok(chain)
+}
+
+/// Least common ancestor of `type_a` and `type_b`: collect each type's
+/// ancestor chain up to `Thing`, then return the deepest entry of
+/// `type_a`'s chain that also appears in `type_b`'s — i.e. the most
+/// specific type both are a subtype of. `path` is that type's own
+/// ancestor chain, for explaining how general the join turned out to be.
+pub fn join(type_a: &str, type_b: &str) -> Result<LatticeResult, OntologyError> {
+    let chain_a = ancestor_chain(type_a)?;
+    let chain_b = ancestor_chain(type_b)?;
+
+    let found = chain_a.iter().find(|candidate| chain_b.contains(candidate)).cloned();
+
+    let path = match &found {
+        Some(type_id) => ancestor_chain(type_id)?,
+        None => Vec::new(),
+    };
+
+    Ok(LatticeResult { type_id: found, path })
+}
+
+/// Greatest common subtype of `type_a` and `type_b`. The hierarchy here
+/// is single-inheritance (one `parent` per type), so a type can only be a
+/// common subtype of both if one of `type_a`/`type_b` is itself a subtype
+/// of the other — in which case the more specific of the two is the
+/// meet. Otherwise no type is a subtype of both, and the meet is `None`.
+pub fn meet(type_a: &str, type_b: &str) -> Result<LatticeResult, OntologyError> {
+    let found = if is_subtype_of(type_a, &[type_b.to_string()])? {
+        Some(type_a.to_string())
+    } else if is_subtype_of(type_b, &[type_a.to_string()])? {
+        Some(type_b.to_string())
+    } else {
+        None
+    };
+
+    let path = match &found {
+        Some(type_id) => ancestor_chain(type_id)?,
+        None => Vec::new(),
+    };
+
+    Ok(LatticeResult { type_id: found, path })
+}
+
+/// Fold `meet` across every type in `types`, used to resolve an entity's
+/// principal type when it participates in several relations with
+/// different domain/range requirements. Surfaces a `TypeMismatch` naming
+/// the two types that failed to meet, rather than silently picking one.
+pub fn meet_all(types: &[String]) -> Result<Option<String>, OntologyError> {
+    let mut iter = types.iter();
+    let Some(first) = iter.next() else {
+        return Ok(None);
+    };
+
+    let mut current = first.clone();
+    for next in iter {
+        match meet(&current, next)?.type_id {
+            Some(resolved) => current = resolved,
+            None => {
+                return Err(OntologyError::TypeMismatch {
+                    expected: current.clone(),
+                    actual: next.clone(),
+                })
+            }
+        }
+    }
+    Ok(Some(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_of_llm_and_dataset_is_entity() {
+        // LLM -> AIModel -> Agent -> Entity -> Thing
+        // Dataset -> Entity -> Thing
+        let result = join("LLM", "Dataset").unwrap();
+        assert_eq!(result.type_id, Some("Entity".to_string()));
+        assert_eq!(result.path.first(), Some(&"Entity".to_string()));
+    }
+
+    #[test]
+    fn test_join_of_type_with_itself_is_itself() {
+        let result = join("LLM", "LLM").unwrap();
+        assert_eq!(result.type_id, Some("LLM".to_string()));
+    }
+
+    #[test]
+    fn test_join_of_parent_and_child_is_parent() {
+        let result = join("AIModel", "LLM").unwrap();
+        assert_eq!(result.type_id, Some("AIModel".to_string()));
+    }
+
+    #[test]
+    fn test_join_path_is_ancestor_chain_of_result() {
+        let result = join("LLM", "Dataset").unwrap();
+        assert_eq!(result.path, ancestor_chain("Entity").unwrap());
+    }
+
+    #[test]
+    fn test_meet_of_parent_and_child_is_child() {
+        let result = meet("AIModel", "LLM").unwrap();
+        assert_eq!(result.type_id, Some("LLM".to_string()));
+    }
+
+    #[test]
+    fn test_meet_of_unrelated_branches_is_none() {
+        let result = meet("LLM", "Dataset").unwrap();
+        assert_eq!(result.type_id, None);
+        assert!(result.path.is_empty());
+    }
+
+    #[test]
+    fn test_meet_of_type_with_itself_is_itself() {
+        let result = meet("LLM", "LLM").unwrap();
+        assert_eq!(result.type_id, Some("LLM".to_string()));
+    }
+
+    #[test]
+    fn test_meet_all_folds_across_multiple_types() {
+        let types = vec!["AIModel".to_string(), "LLM".to_string()];
+        assert_eq!(meet_all(&types).unwrap(), Some("LLM".to_string()));
+    }
+
+    #[test]
+    fn test_meet_all_fails_on_unrelated_branches() {
+        let types = vec!["LLM".to_string(), "Dataset".to_string()];
+        assert!(meet_all(&types).is_err());
+    }
+
+    #[test]
+    fn test_meet_all_empty_list_is_none() {
+        assert_eq!(meet_all(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unknown_type_surfaces_error() {
+        assert!(join("NoSuchType", "LLM").is_err());
+        assert!(meet("NoSuchType", "LLM").is_err());
+    }
+}
@@ -1,8 +1,14 @@
 use hdi::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use thiserror::Error;
 
+pub mod consistency;
 pub mod inference;
+pub mod lattice;
+pub mod query;
+pub mod turtle;
+pub mod type_inference;
 
 /// Errors that can occur during ontology validation
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +81,13 @@ pub struct OntologyRelation {
     pub is_symmetric: bool,
     pub is_reflexive: bool,
 
+    /// True if `a R b` and `b R a` both holding (for distinct `a`/`b`) is
+    /// a contradiction rather than an expected pairing — e.g.
+    /// `improves_upon`, where a model cannot both improve upon and be
+    /// improved upon by the same other model. Never true alongside
+    /// `is_symmetric`, which asserts the opposite.
+    pub is_antisymmetric: bool,
+
     /// Description
     pub description: String,
 
@@ -175,23 +188,40 @@ pub fn validate_triple(triple: &KnowledgeTriple) -> Result<(), OntologyError> {
     Ok(())
 }
 
-/// Check if a type is a subtype of any type in the target list
-fn is_subtype_of(type_id: &str, target_types: &[String]) -> Result<bool, OntologyError> {
-    // Check direct match first
-    if target_types.contains(&type_id.to_string()) {
-        return Ok(true);
-    }
+/// Every type_id `get_type_definition` recognizes — the universe a
+/// constraint-solving type inference narrows candidate sets down from.
+pub(crate) fn all_type_ids() -> Vec<&'static str> {
+    vec![
+        "Thing", "Entity", "Concept", "Agent", "Event", "Property", "Value",
+        "AIModel", "LLM", "Dataset", "Capability", "Benchmark", "TrainingRun",
+    ]
+}
 
-    // Check if type_id is a subtype of any target type through inheritance
-    // For now, we'll use a simple parent-checking mechanism
-    // In a full implementation, this would query the DHT for type hierarchy
-    let type_def = get_type_definition(type_id)?;
+/// Check if a type is a subtype of any type in the target list by
+/// walking `parent` links up to `Thing`. Guards against a malformed
+/// (cyclic) hierarchy with a `visited` set — `is_subtype_of` would
+/// otherwise recurse forever on a `parent` cycle, which
+/// `consistency::check_consistency` can detect but does not prevent by
+/// itself. A cycle reached before any target type is found answers
+/// `false` rather than panicking or hanging, matching "not a subtype"
+/// for a hierarchy too broken to have a well-defined answer.
+pub(crate) fn is_subtype_of(type_id: &str, target_types: &[String]) -> Result<bool, OntologyError> {
+    let mut current = type_id.to_string();
+    let mut visited: HashSet<String> = HashSet::new();
+
+    loop {
+        if target_types.contains(&current) {
+            return Ok(true);
+        }
+        if !visited.insert(current.clone()) {
+            return Ok(false);
+        }
 
-    if let Some(parent) = type_def.parent {
-        // Recursively check parent
-        is_subtype_of(&parent, target_types)
-    } else {
-        Ok(false)
+        let type_def = get_type_definition(&current)?;
+        match type_def.parent {
+            Some(parent) => current = parent,
+            None => return Ok(false),
+        }
     }
 }
 
@@ -257,7 +287,7 @@ pub fn check_domain_range(
 }
 
 /// Get a relation definition from the ontology
-fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
+pub(crate) fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
     // In production, this would query DHT for relation definitions
     // For bootstrap, we hardcode base relations
     // Use a fixed timestamp for bootstrap definitions
@@ -272,6 +302,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: true,
             is_symmetric: false,
             is_reflexive: true,
+            is_antisymmetric: false,
             description: "Type hierarchy relation - subject is an instance or subtype of object".into(),
             created_at: timestamp,
         }),
@@ -283,6 +314,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: true,
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "Parthood relation - subject is a component of object".into(),
             created_at: timestamp,
         }),
@@ -294,6 +326,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: false,
             is_symmetric: true,
             is_reflexive: true,
+            is_antisymmetric: false,
             description: "General relatedness - symmetric relation between entities".into(),
             created_at: timestamp,
         }),
@@ -305,6 +338,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: false,
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "Property attribution - subject has the property specified in object".into(),
             created_at: timestamp,
         }),
@@ -317,6 +351,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: false,
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "Model was trained on dataset".into(),
             created_at: timestamp,
         }),
@@ -328,6 +363,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: true,  // Important for inference!
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: true,
             description: "Model improves upon another model".into(),
             created_at: timestamp,
         }),
@@ -339,6 +375,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: false,
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "Model or agent has capability".into(),
             created_at: timestamp,
         }),
@@ -350,6 +387,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
             is_transitive: false,
             is_symmetric: false,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "Model evaluated on benchmark".into(),
             created_at: timestamp,
         }),
@@ -358,7 +396,7 @@ fn get_relation(relation_id: &str) -> Result<OntologyRelation, OntologyError> {
 }
 
 /// Get type definition (stub for bootstrap)
-fn get_type_definition(type_id: &str) -> Result<OntologyType, OntologyError> {
+pub(crate) fn get_type_definition(type_id: &str) -> Result<OntologyType, OntologyError> {
     // In production, this would query DHT
     // For bootstrap, return hardcoded base types
     // Use a fixed timestamp for bootstrap definitions
@@ -839,6 +877,34 @@ mod tests {
         assert!(check_domain_range(&relation, "Entity", "Property"));
     }
 
+    #[test]
+    fn test_check_domain_range_accepts_subtype_of_declared_domain() {
+        // improves_upon lists both "AIModel" and "LLM"; a relation declaring
+        // only the broader "AIModel" should still accept an "LLM" subject/object
+        // without having to enumerate every descendant explicitly.
+        let relation = OntologyRelation {
+            relation_id: "trains_into".into(),
+            name: "trains into".into(),
+            domain: vec!["AIModel".into()],
+            range: vec!["AIModel".into()],
+            is_transitive: false,
+            is_symmetric: false,
+            is_reflexive: false,
+            is_antisymmetric: false,
+            description: "test relation".into(),
+            created_at: Timestamp::from_micros(0),
+        };
+        assert!(check_domain_range(&relation, "LLM", "LLM"), "LLM is a subtype of AIModel via the parent chain");
+    }
+
+    #[test]
+    fn test_is_subtype_of_walks_multi_level_parent_chain() {
+        // LLM -> AIModel -> Agent -> Entity -> Thing: four hops up.
+        assert!(is_subtype_of("LLM", &["Thing".to_string()]).unwrap());
+        assert!(is_subtype_of("LLM", &["Agent".to_string()]).unwrap());
+        assert!(!is_subtype_of("LLM", &["Dataset".to_string()]).unwrap());
+    }
+
     #[test]
     fn test_validate_ontology_type_valid() {
         let ont_type = OntologyType {
@@ -877,6 +943,7 @@ mod tests {
             is_transitive: false,
             is_symmetric: true,
             is_reflexive: false,
+            is_antisymmetric: false,
             description: "A custom relation".into(),
             created_at: fake_timestamp(),
         };
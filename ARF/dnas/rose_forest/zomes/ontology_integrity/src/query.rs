@@ -0,0 +1,293 @@
+use crate::KnowledgeTriple;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A variable binding environment: variable name (without the leading
+/// `?`) to the literal value it has been unified with so far.
+pub type Bindings = BTreeMap<String, String>;
+
+/// One slot of a `TriplePattern` — the repo's EAV pattern-matching
+/// convention written as plain strings: a slot starting with `?` (e.g.
+/// `?x`, `?cap`) is an unbound variable, anything else is a literal that
+/// must match exactly. Mirrors `infer_type`'s existing preference for
+/// string conventions over a dedicated enum.
+fn is_variable(term: &str) -> bool {
+    term.starts_with('?')
+}
+
+/// One triple pattern in a conjunctive query — the subject, predicate,
+/// and object slots, each either a literal or a `?variable`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TriplePattern {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+}
+
+/// Unify a single pattern slot against a candidate triple's value under
+/// the current `env`. A literal must match exactly; an unbound variable
+/// binds to the value; a variable already bound must match its existing
+/// value. Returns the (possibly extended) environment, or `None` on a
+/// mismatch.
+fn unify(env: &Bindings, term: &str, value: &str) -> Option<Bindings> {
+    if let Some(name) = term.strip_prefix('?') {
+        match env.get(name) {
+            Some(bound) if bound == value => Some(env.clone()),
+            Some(_) => None,
+            None => {
+                let mut next = env.clone();
+                next.insert(name.to_string(), value.to_string());
+                Some(next)
+            }
+        }
+    } else if term == value {
+        Some(env.clone())
+    } else {
+        None
+    }
+}
+
+/// Nested-loop nested join with backtracking: try every triple against
+/// `patterns`' first pattern, recurse on the rest with whatever bindings
+/// that triple produced, and collect an environment once every pattern is
+/// satisfied. This is the standard Datalog conjunctive-query evaluation
+/// strategy for an in-memory triple set with no index.
+fn solve(patterns: &[TriplePattern], triples: &[KnowledgeTriple], min_confidence: f32, env: &Bindings, results: &mut Vec<Bindings>) {
+    let Some((pattern, rest)) = patterns.split_first() else {
+        results.push(env.clone());
+        return;
+    };
+
+    for candidate in triples {
+        if candidate.confidence < min_confidence {
+            continue;
+        }
+        let Some(env) = unify(env, &pattern.subject, &candidate.subject) else { continue };
+        let Some(env) = unify(&env, &pattern.predicate, &candidate.predicate) else { continue };
+        let Some(env) = unify(&env, &pattern.object, &candidate.object) else { continue };
+        solve(rest, triples, min_confidence, &env, results);
+    }
+}
+
+/// Run a conjunction of `patterns` against `triples`, returning one
+/// `Bindings` per satisfying assignment of every variable mentioned. Pass
+/// `triples` as the closure produced by `inference::compute_closure`
+/// (concatenated with the original set) to let transitive/symmetric/
+/// reflexive answers participate in the query, not just asserted facts.
+pub fn query(triples: &[KnowledgeTriple], patterns: &[TriplePattern], min_confidence: f32) -> Vec<Bindings> {
+    let mut results = Vec::new();
+    solve(patterns, triples, min_confidence, &Bindings::new(), &mut results);
+    results
+}
+
+/// A single pattern slot, as a typed alternative to `TriplePattern`'s
+/// `?variable`-prefixed string convention — for callers who'd rather not
+/// rely on a string sigil to distinguish a literal from a binding.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Term {
+    Const(String),
+    Var(String),
+}
+
+/// A triple pattern built from typed `Term`s rather than `?`-prefixed
+/// strings. Equivalent in power to `TriplePattern` — it's lowered into
+/// one before evaluation — but lets callers build patterns programmatically
+/// without string-formatting variable names.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pattern {
+    pub subject: Term,
+    pub predicate: Term,
+    pub object: Term,
+}
+
+fn term_to_slot(term: &Term) -> String {
+    match term {
+        Term::Const(value) => value.clone(),
+        Term::Var(name) => format!("?{name}"),
+    }
+}
+
+/// Datalog-style conjunctive query over `Pattern`/`Term`, returning one
+/// `HashMap` of variable bindings per satisfying assignment. Lowers each
+/// `Pattern` to a `TriplePattern` and delegates to `query`, so behavior —
+/// equijoins on shared variable names, the confidence floor — is
+/// identical to the string-convention API; only the input/output shapes
+/// differ for callers who prefer a typed `Term`/`HashMap` surface.
+pub fn query_typed(triples: &[KnowledgeTriple], patterns: &[Pattern], min_confidence: f32) -> Vec<HashMap<String, String>> {
+    let string_patterns: Vec<TriplePattern> = patterns
+        .iter()
+        .map(|pattern| TriplePattern {
+            subject: term_to_slot(&pattern.subject),
+            predicate: term_to_slot(&pattern.predicate),
+            object: term_to_slot(&pattern.object),
+        })
+        .collect();
+
+    query(triples, &string_patterns, min_confidence)
+        .into_iter()
+        .map(|bindings| bindings.into_iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdi::prelude::*;
+
+    fn fake_agent_pub_key() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn triple(subject: &str, predicate: &str, object: &str, confidence: f32) -> KnowledgeTriple {
+        KnowledgeTriple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence,
+            source: fake_agent_pub_key(),
+            created_at: Timestamp::from_micros(0),
+        }
+    }
+
+    fn pattern(subject: &str, predicate: &str, object: &str) -> TriplePattern {
+        TriplePattern { subject: subject.into(), predicate: predicate.into(), object: object.into() }
+    }
+
+    #[test]
+    fn test_single_pattern_binds_variable() {
+        let triples = vec![triple("GPT-4", "is_a", "LLM", 1.0)];
+        let results = query(&triples, &[pattern("?x", "is_a", "LLM")], 0.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("x"), Some(&"GPT-4".to_string()));
+    }
+
+    #[test]
+    fn test_literal_mismatch_excludes_triple() {
+        let triples = vec![triple("GPT-4", "is_a", "LLM", 1.0)];
+        let results = query(&triples, &[pattern("?x", "is_a", "Dataset")], 0.0);
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_conjunction_requires_shared_variable_consistency() {
+        // "which capabilities do the models GPT-4 improves_upon have"
+        let triples = vec![
+            triple("GPT-4", "improves_upon", "GPT-3.5", 1.0),
+            triple("GPT-3.5", "capable_of", "coding", 1.0),
+            triple("GPT-4", "capable_of", "writing", 1.0),
+        ];
+
+        let patterns = vec![
+            pattern("GPT-4", "improves_upon", "?model"),
+            pattern("?model", "capable_of", "?cap"),
+        ];
+
+        let results = query(&triples, &patterns, 0.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("model"), Some(&"GPT-3.5".to_string()));
+        assert_eq!(results[0].get("cap"), Some(&"coding".to_string()));
+    }
+
+    #[test]
+    fn test_repeated_variable_must_match_same_value() {
+        // related_to is symmetric in the ontology, so A relates_to A would
+        // only come from a reflexive derivation, not asserted here.
+        let triples = vec![
+            triple("A", "related_to", "B", 1.0),
+            triple("B", "related_to", "C", 1.0),
+        ];
+
+        // ?x related_to ?x should find nothing, since no self-loop exists.
+        let results = query(&triples, &[pattern("?x", "related_to", "?x")], 0.0);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_min_confidence_filters_low_confidence_triples() {
+        let triples = vec![
+            triple("GPT-4", "capable_of", "coding", 0.95),
+            triple("GPT-4", "capable_of", "poetry", 0.3),
+        ];
+
+        let results = query(&triples, &[pattern("GPT-4", "capable_of", "?cap")], 0.5);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("cap"), Some(&"coding".to_string()));
+    }
+
+    #[test]
+    fn test_predicate_variable() {
+        let triples = vec![triple("GPT-4", "improves_upon", "GPT-3.5", 1.0)];
+        let results = query(&triples, &[pattern("GPT-4", "?rel", "GPT-3.5")], 0.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("rel"), Some(&"improves_upon".to_string()));
+    }
+
+    #[test]
+    fn test_empty_patterns_yields_single_empty_binding() {
+        let triples = vec![triple("A", "is_a", "B", 1.0)];
+        let results = query(&triples, &[], 0.0);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_empty());
+    }
+
+    #[test]
+    fn test_query_typed_conjunction_with_shared_variable() {
+        // "find all ?model improves_upon ?base where ?base capable_of coding"
+        let triples = vec![
+            triple("GPT-4", "improves_upon", "GPT-3.5", 1.0),
+            triple("GPT-3.5", "capable_of", "coding", 1.0),
+            triple("GPT-4", "capable_of", "writing", 1.0),
+        ];
+
+        let patterns = vec![
+            Pattern { subject: Term::Var("model".into()), predicate: Term::Const("improves_upon".into()), object: Term::Var("base".into()) },
+            Pattern { subject: Term::Var("base".into()), predicate: Term::Const("capable_of".into()), object: Term::Const("coding".into()) },
+        ];
+
+        let results = query_typed(&triples, &patterns, 0.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("model"), Some(&"GPT-4".to_string()));
+        assert_eq!(results[0].get("base"), Some(&"GPT-3.5".to_string()));
+    }
+
+    #[test]
+    fn test_query_typed_const_mismatch_excludes_triple() {
+        let triples = vec![triple("GPT-4", "is_a", "LLM", 1.0)];
+        let patterns = vec![Pattern {
+            subject: Term::Var("x".into()),
+            predicate: Term::Const("is_a".into()),
+            object: Term::Const("Dataset".into()),
+        }];
+
+        assert!(query_typed(&triples, &patterns, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_query_typed_respects_min_confidence() {
+        let triples = vec![
+            triple("GPT-4", "capable_of", "coding", 0.95),
+            triple("GPT-4", "capable_of", "poetry", 0.3),
+        ];
+        let patterns = vec![Pattern {
+            subject: Term::Const("GPT-4".into()),
+            predicate: Term::Const("capable_of".into()),
+            object: Term::Var("cap".into()),
+        }];
+
+        let results = query_typed(&triples, &patterns, 0.5);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("cap"), Some(&"coding".to_string()));
+    }
+}
@@ -0,0 +1,580 @@
+use crate::{validate_triple, KnowledgeTriple, OntologyError, OntologyRelation, OntologyType};
+use hdi::prelude::*;
+use std::collections::HashMap;
+
+/// Quote and escape `value` as a Turtle string literal.
+fn turtle_literal(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+/// Serialize an ontology to Turtle, the standard textual RDF syntax, so the
+/// bootstrap ontologies (and anything built on top of them) interoperate
+/// with the wider semantic-web ecosystem. `OntologyType`s become
+/// `owl:Class` declarations with `parent` as `rdfs:subClassOf`;
+/// `OntologyRelation`s become `owl:ObjectProperty` declarations whose
+/// transitive/symmetric/reflexive/antisymmetric flags are written as the
+/// matching OWL property axiom and whose `domain`/`range` become
+/// `rdfs:domain`/`rdfs:range`. `is_a` triples become `rdf:type` assertions
+/// (`a` for short); every other relation becomes `arf:<relation_id>`. A
+/// triple whose `confidence` isn't the default `1.0` is additionally
+/// described by an `rdf:Statement` reification carrying a custom
+/// `arf:confidence` annotation, since plain RDF triples have no room for a
+/// confidence score. `source`/`created_at` are not round-tripped — Turtle
+/// has no native notion of HoloHash provenance.
+pub fn export_turtle(
+    types: &[OntologyType],
+    relations: &[OntologyRelation],
+    triples: &[KnowledgeTriple],
+) -> String {
+    let mut out = String::new();
+    out.push_str("@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n");
+    out.push_str("@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n");
+    out.push_str("@prefix owl: <http://www.w3.org/2002/07/owl#> .\n");
+    out.push_str("@prefix arf: <urn:kalisam:floss:ontology#> .\n\n");
+
+    for ontology_type in types {
+        out.push_str(&format!("<{}> a owl:Class", ontology_type.type_id));
+        if !ontology_type.name.is_empty() {
+            out.push_str(&format!(" ;\n    rdfs:label {}", turtle_literal(&ontology_type.name)));
+        }
+        if !ontology_type.description.is_empty() {
+            out.push_str(&format!(" ;\n    rdfs:comment {}", turtle_literal(&ontology_type.description)));
+        }
+        if let Some(parent) = &ontology_type.parent {
+            out.push_str(&format!(" ;\n    rdfs:subClassOf <{parent}>"));
+        }
+        out.push_str(" .\n\n");
+    }
+
+    for relation in relations {
+        let mut owl_types = vec!["owl:ObjectProperty"];
+        if relation.is_transitive {
+            owl_types.push("owl:TransitiveProperty");
+        }
+        if relation.is_symmetric {
+            owl_types.push("owl:SymmetricProperty");
+        }
+        if relation.is_reflexive {
+            owl_types.push("owl:ReflexiveProperty");
+        }
+        if relation.is_antisymmetric {
+            owl_types.push("owl:AsymmetricProperty");
+        }
+
+        out.push_str(&format!("arf:{} a {}", relation.relation_id, owl_types.join(", ")));
+        if !relation.name.is_empty() {
+            out.push_str(&format!(" ;\n    rdfs:label {}", turtle_literal(&relation.name)));
+        }
+        if !relation.description.is_empty() {
+            out.push_str(&format!(" ;\n    rdfs:comment {}", turtle_literal(&relation.description)));
+        }
+        if !relation.domain.is_empty() {
+            let domain = relation.domain.iter().map(|d| format!("<{d}>")).collect::<Vec<_>>().join(", ");
+            out.push_str(&format!(" ;\n    rdfs:domain {domain}"));
+        }
+        if !relation.range.is_empty() {
+            let range = relation.range.iter().map(|r| format!("<{r}>")).collect::<Vec<_>>().join(", ");
+            out.push_str(&format!(" ;\n    rdfs:range {range}"));
+        }
+        out.push_str(" .\n\n");
+    }
+
+    for (i, triple) in triples.iter().enumerate() {
+        let predicate_term = if triple.predicate == "is_a" { "rdf:type".to_string() } else { format!("arf:{}", triple.predicate) };
+        let predicate_short = if triple.predicate == "is_a" { "a".to_string() } else { predicate_term.clone() };
+
+        out.push_str(&format!("<{}> {} <{}> .\n", triple.subject, predicate_short, triple.object));
+
+        if (triple.confidence - 1.0).abs() > f32::EPSILON {
+            out.push_str(&format!(
+                "_:stmt{i} a rdf:Statement ;\n    rdf:subject <{}> ;\n    rdf:predicate {} ;\n    rdf:object <{}> ;\n    arf:confidence {} .\n",
+                triple.subject,
+                predicate_term,
+                triple.object,
+                turtle_literal(&triple.confidence.to_string()),
+            ));
+        }
+    }
+
+    out
+}
+
+/// One lexical token of the Turtle subset this parser understands — just
+/// enough grammar to round-trip what `export_turtle` produces: `<iri>`
+/// references, quoted string literals, `_:blank` nodes, `prefix:local`
+/// terms (including the bare `a` shorthand), and the `.`/`;`/`,` statement
+/// punctuation. `@prefix`/`@base` directives are tokenized as `At` and
+/// discarded whole during statement grouping, since this crate's own
+/// fixed `rdf:`/`rdfs:`/`owl:`/`arf:` prefixes are recognized by name
+/// rather than resolved against a prefix table.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Iri(String),
+    Literal(String),
+    Blank(String),
+    Word(String),
+    At,
+    Dot,
+    Semi,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, OntologyError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '@' {
+            tokens.push(Token::At);
+            i += 1;
+        } else if c == '<' {
+            i += 1;
+            let start = i;
+            while i < chars.len() && chars[i] != '>' && chars[i] != '<' {
+                i += 1;
+            }
+            if i >= chars.len() || chars[i] != '>' {
+                return Err(OntologyError::ValidationError("unterminated IRI in Turtle input".into()));
+            }
+            tokens.push(Token::Iri(chars[start..i].iter().collect()));
+            i += 1;
+        } else if c == '"' {
+            i += 1;
+            let mut value = String::new();
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    value.push(chars[i + 1]);
+                    i += 2;
+                } else {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+            }
+            if i >= chars.len() {
+                return Err(OntologyError::ValidationError("unterminated string literal in Turtle input".into()));
+            }
+            tokens.push(Token::Literal(value));
+            i += 1;
+        } else if c == '.' {
+            tokens.push(Token::Dot);
+            i += 1;
+        } else if c == ';' {
+            tokens.push(Token::Semi);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if c == '_' && chars.get(i + 1) == Some(&':') {
+            let start = i;
+            i += 2;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
+                i += 1;
+            }
+            tokens.push(Token::Blank(chars[start..i].iter().collect()));
+        } else if c.is_alphanumeric() || c == ':' || c == '_' || c == '-' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == ':' || chars[i] == '_' || chars[i] == '-') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            return Err(OntologyError::ValidationError(format!("unexpected character '{c}' in Turtle input")));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Group a flat token stream into statements (each a `Vec<Token>` with the
+/// terminating `.` removed), discarding `@prefix`/`@base` directives whole.
+fn split_statements(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut statements = Vec::new();
+    let mut current = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::At => {
+                i += 1;
+                while i < tokens.len() && tokens[i] != Token::Dot {
+                    i += 1;
+                }
+                i += 1;
+            }
+            Token::Dot => {
+                if !current.is_empty() {
+                    statements.push(std::mem::take(&mut current));
+                }
+                i += 1;
+            }
+            _ => {
+                current.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+/// A parsed `subject predicate-object-list [; predicate-object-list]*`
+/// statement, one predicate-object pair per `;`-separated group and one
+/// `Token` per `,`-separated object.
+struct Statement {
+    subject: Token,
+    predicate_objects: Vec<(Token, Vec<Token>)>,
+}
+
+fn parse_statement(tokens: &[Token]) -> Result<Statement, OntologyError> {
+    let mut iter = tokens.iter().cloned().peekable();
+    let subject = iter
+        .next()
+        .ok_or_else(|| OntologyError::ValidationError("empty statement in Turtle input".into()))?;
+
+    let mut predicate_objects = Vec::new();
+    while let Some(predicate) = iter.next() {
+        let mut objects = vec![iter
+            .next()
+            .ok_or_else(|| OntologyError::ValidationError("missing object in Turtle statement".into()))?];
+        while iter.peek() == Some(&Token::Comma) {
+            iter.next();
+            objects.push(
+                iter.next()
+                    .ok_or_else(|| OntologyError::ValidationError("missing object after ',' in Turtle statement".into()))?,
+            );
+        }
+        predicate_objects.push((predicate, objects));
+
+        if iter.peek() == Some(&Token::Semi) {
+            iter.next();
+        } else {
+            break;
+        }
+    }
+
+    Ok(Statement { subject, predicate_objects })
+}
+
+fn as_word(token: &Token) -> Option<&str> {
+    match token {
+        Token::Word(w) => Some(w.as_str()),
+        _ => None,
+    }
+}
+
+fn as_iri(token: &Token) -> Option<&str> {
+    match token {
+        Token::Iri(v) => Some(v.as_str()),
+        _ => None,
+    }
+}
+
+fn as_literal(token: &Token) -> Option<&str> {
+    match token {
+        Token::Literal(v) => Some(v.as_str()),
+        _ => None,
+    }
+}
+
+fn has_word(objects: &[Token], target: &str) -> bool {
+    objects.iter().any(|t| as_word(t) == Some(target))
+}
+
+fn find_objects<'a>(statement: &'a Statement, predicate: &str) -> Option<&'a [Token]> {
+    statement
+        .predicate_objects
+        .iter()
+        .find(|(p, _)| as_word(p) == Some(predicate))
+        .map(|(_, objs)| objs.as_slice())
+}
+
+/// Fixed placeholder source agent for triples parsed from Turtle, which
+/// has no native notion of HoloHash provenance — mirrors this crate's
+/// convention of a fixed bootstrap `Timestamp` for facts not tied to a
+/// real assertion event.
+fn placeholder_import_source() -> AgentPubKey {
+    let mut bytes = vec![1u8; 39];
+    bytes[..3].copy_from_slice(&[132, 32, 36]);
+    AgentPubKey::from_raw_39(bytes).expect("well-formed constant agent public key")
+}
+
+/// Parse Turtle produced by `export_turtle` (or external RDF expressed in
+/// the same OWL/RDFS vocabulary) back into ontology types, relations, and
+/// knowledge triples. Recovers each triple's confidence from its
+/// `arf:confidence`-annotated `rdf:Statement` reification if present,
+/// defaulting to `1.0` otherwise, and runs every parsed triple through
+/// `validate_triple` before returning — an externally authored ontology
+/// is only as trustworthy as any other asserted triple.
+pub fn import_turtle(
+    input: &str,
+) -> Result<(Vec<OntologyType>, Vec<OntologyRelation>, Vec<KnowledgeTriple>), OntologyError> {
+    let tokens = tokenize(input)?;
+    let statements = split_statements(tokens);
+
+    let mut confidence_overrides: HashMap<(String, String, String), f32> = HashMap::new();
+    let mut types = Vec::new();
+    let mut relations = Vec::new();
+    let mut raw_triples: Vec<(String, String, String)> = Vec::new();
+
+    for tokens in &statements {
+        let statement = parse_statement(tokens)?;
+        let a_objects = find_objects(&statement, "a");
+
+        if let Some(type_objects) = a_objects {
+            if matches!(statement.subject, Token::Blank(_)) && has_word(type_objects, "rdf:Statement") {
+                let subject = find_objects(&statement, "rdf:subject")
+                    .and_then(|objs| as_iri(&objs[0]))
+                    .ok_or_else(|| OntologyError::ValidationError("rdf:Statement missing rdf:subject".into()))?;
+                let predicate_token = find_objects(&statement, "rdf:predicate")
+                    .map(|objs| &objs[0])
+                    .ok_or_else(|| OntologyError::ValidationError("rdf:Statement missing rdf:predicate".into()))?;
+                let predicate_word = as_word(predicate_token)
+                    .ok_or_else(|| OntologyError::ValidationError("rdf:predicate must be a term, not a literal or IRI".into()))?;
+                let relation_id = if predicate_word == "rdf:type" {
+                    "is_a".to_string()
+                } else {
+                    predicate_word.strip_prefix("arf:").unwrap_or(predicate_word).to_string()
+                };
+                let object = find_objects(&statement, "rdf:object")
+                    .and_then(|objs| as_iri(&objs[0]))
+                    .ok_or_else(|| OntologyError::ValidationError("rdf:Statement missing rdf:object".into()))?;
+                let confidence_text = find_objects(&statement, "arf:confidence")
+                    .and_then(|objs| as_literal(&objs[0]))
+                    .ok_or_else(|| OntologyError::ValidationError("rdf:Statement missing arf:confidence".into()))?;
+                let confidence: f32 = confidence_text
+                    .parse()
+                    .map_err(|_| OntologyError::ValidationError(format!("invalid confidence literal '{confidence_text}'")))?;
+
+                confidence_overrides.insert((subject.to_string(), relation_id, object.to_string()), confidence);
+                continue;
+            }
+
+            if has_word(type_objects, "owl:Class") {
+                let type_id = as_iri(&statement.subject)
+                    .ok_or_else(|| OntologyError::ValidationError("owl:Class subject must be an IRI".into()))?
+                    .to_string();
+                let name = find_objects(&statement, "rdfs:label")
+                    .and_then(|objs| as_literal(&objs[0]))
+                    .unwrap_or(&type_id)
+                    .to_string();
+                let description = find_objects(&statement, "rdfs:comment")
+                    .and_then(|objs| as_literal(&objs[0]))
+                    .unwrap_or_default()
+                    .to_string();
+                let parent = find_objects(&statement, "rdfs:subClassOf")
+                    .and_then(|objs| as_iri(&objs[0]))
+                    .map(|s| s.to_string());
+
+                types.push(OntologyType { type_id, name, parent, description, created_at: Timestamp::from_micros(0) });
+                continue;
+            }
+
+            if has_word(type_objects, "owl:ObjectProperty") {
+                let subject_word = as_word(&statement.subject)
+                    .ok_or_else(|| OntologyError::ValidationError("relation subject must be an arf:-prefixed term".into()))?;
+                let relation_id = subject_word.strip_prefix("arf:").unwrap_or(subject_word).to_string();
+                let name = find_objects(&statement, "rdfs:label")
+                    .and_then(|objs| as_literal(&objs[0]))
+                    .unwrap_or(&relation_id)
+                    .to_string();
+                let description = find_objects(&statement, "rdfs:comment")
+                    .and_then(|objs| as_literal(&objs[0]))
+                    .unwrap_or_default()
+                    .to_string();
+                let domain = find_objects(&statement, "rdfs:domain")
+                    .map(|objs| objs.iter().filter_map(as_iri).map(|s| s.to_string()).collect())
+                    .unwrap_or_default();
+                let range = find_objects(&statement, "rdfs:range")
+                    .map(|objs| objs.iter().filter_map(as_iri).map(|s| s.to_string()).collect())
+                    .unwrap_or_default();
+
+                relations.push(OntologyRelation {
+                    relation_id,
+                    name,
+                    domain,
+                    range,
+                    is_transitive: has_word(type_objects, "owl:TransitiveProperty"),
+                    is_symmetric: has_word(type_objects, "owl:SymmetricProperty"),
+                    is_reflexive: has_word(type_objects, "owl:ReflexiveProperty"),
+                    is_antisymmetric: has_word(type_objects, "owl:AsymmetricProperty"),
+                    description,
+                    created_at: Timestamp::from_micros(0),
+                });
+                continue;
+            }
+        }
+
+        // Anything else is one or more plain triple assertions — including
+        // `a` itself, which maps back to `is_a`.
+        let subject_id = as_iri(&statement.subject)
+            .ok_or_else(|| OntologyError::ValidationError("triple subject must be an IRI".into()))?
+            .to_string();
+
+        for (predicate, objects) in &statement.predicate_objects {
+            let predicate_word = as_word(predicate)
+                .ok_or_else(|| OntologyError::ValidationError("triple predicate must be a term".into()))?;
+            let relation_id = if predicate_word == "a" || predicate_word == "rdf:type" {
+                "is_a".to_string()
+            } else {
+                predicate_word.strip_prefix("arf:").unwrap_or(predicate_word).to_string()
+            };
+            for object in objects {
+                let object_id = as_iri(object)
+                    .ok_or_else(|| OntologyError::ValidationError("triple object must be an IRI".into()))?
+                    .to_string();
+                raw_triples.push((subject_id.clone(), relation_id.clone(), object_id));
+            }
+        }
+    }
+
+    let triples: Vec<KnowledgeTriple> = raw_triples
+        .into_iter()
+        .map(|(subject, predicate, object)| {
+            let confidence = confidence_overrides
+                .get(&(subject.clone(), predicate.clone(), object.clone()))
+                .copied()
+                .unwrap_or(1.0);
+            KnowledgeTriple {
+                subject,
+                predicate,
+                object,
+                confidence,
+                source: placeholder_import_source(),
+                created_at: Timestamp::from_micros(0),
+            }
+        })
+        .collect();
+
+    for triple in &triples {
+        validate_triple(triple)?;
+    }
+
+    Ok((types, relations, triples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bootstrap_base_ontology, get_relation};
+
+    fn sample_triple(subject: &str, predicate: &str, object: &str, confidence: f32) -> KnowledgeTriple {
+        KnowledgeTriple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence,
+            source: placeholder_import_source(),
+            created_at: Timestamp::from_micros(0),
+        }
+    }
+
+    #[test]
+    fn test_export_type_includes_label_comment_and_subclass_of() {
+        let (types, _) = bootstrap_base_ontology();
+        let turtle = export_turtle(&types, &[], &[]);
+
+        assert!(turtle.contains("<Entity> a owl:Class"));
+        assert!(turtle.contains("rdfs:subClassOf <Thing>"));
+    }
+
+    #[test]
+    fn test_export_relation_includes_owl_property_axioms() {
+        let relation = get_relation("improves_upon").unwrap();
+        let turtle = export_turtle(&[], &[relation], &[]);
+
+        assert!(turtle.contains("arf:improves_upon a owl:ObjectProperty, owl:TransitiveProperty, owl:AsymmetricProperty"));
+    }
+
+    #[test]
+    fn test_export_is_a_triple_uses_rdf_type_shorthand() {
+        let triples = vec![sample_triple("GPT-4", "is_a", "LLM", 1.0)];
+        let turtle = export_turtle(&[], &[], &triples);
+
+        assert!(turtle.contains("<GPT-4> a <LLM> ."));
+    }
+
+    #[test]
+    fn test_export_non_default_confidence_adds_reification() {
+        let triples = vec![sample_triple("GPT-4", "trained_on", "WebText", 0.8)];
+        let turtle = export_turtle(&[], &[], &triples);
+
+        assert!(turtle.contains("<GPT-4> arf:trained_on <WebText> ."));
+        assert!(turtle.contains("a rdf:Statement"));
+        assert!(turtle.contains("arf:confidence \"0.8\""));
+    }
+
+    #[test]
+    fn test_export_default_confidence_omits_reification() {
+        let triples = vec![sample_triple("GPT-4", "trained_on", "WebText", 1.0)];
+        let turtle = export_turtle(&[], &[], &triples);
+
+        assert!(!turtle.contains("rdf:Statement"));
+    }
+
+    #[test]
+    fn test_round_trip_types_and_relations_preserves_flags_and_hierarchy() {
+        let (types, relations) = bootstrap_base_ontology();
+        let turtle = export_turtle(&types, &relations, &[]);
+
+        let (parsed_types, parsed_relations, parsed_triples) = import_turtle(&turtle).unwrap();
+
+        assert!(parsed_triples.is_empty());
+        assert_eq!(parsed_types.len(), types.len());
+        let entity = parsed_types.iter().find(|t| t.type_id == "Entity").unwrap();
+        assert_eq!(entity.parent, Some("Thing".to_string()));
+
+        let related_to = parsed_relations.iter().find(|r| r.relation_id == "related_to").unwrap();
+        assert!(related_to.is_symmetric);
+        assert!(related_to.is_reflexive);
+        assert!(!related_to.is_transitive);
+    }
+
+    #[test]
+    fn test_round_trip_triple_preserves_confidence() {
+        let triples = vec![sample_triple("GPT-4", "is_a", "LLM", 0.6)];
+        let turtle = export_turtle(&[], &[], &triples);
+
+        let (_, _, parsed_triples) = import_turtle(&turtle).unwrap();
+
+        assert_eq!(parsed_triples.len(), 1);
+        assert_eq!(parsed_triples[0].subject, "GPT-4");
+        assert_eq!(parsed_triples[0].predicate, "is_a");
+        assert_eq!(parsed_triples[0].object, "LLM");
+        assert!((parsed_triples[0].confidence - 0.6).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_round_trip_triple_without_reification_defaults_confidence_to_one() {
+        let triples = vec![sample_triple("GPT-4", "is_a", "LLM", 1.0)];
+        let turtle = export_turtle(&[], &[], &triples);
+
+        let (_, _, parsed_triples) = import_turtle(&turtle).unwrap();
+
+        assert_eq!(parsed_triples.len(), 1);
+        assert!((parsed_triples[0].confidence - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_import_rejects_triple_violating_domain_constraint() {
+        // trained_on requires an AIModel/LLM subject; WebText infers to the
+        // default Entity type, which isn't one.
+        let turtle = "@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n<WebText> arf:trained_on <GPT-4> .\n";
+        assert!(import_turtle(turtle).is_err());
+    }
+
+    #[test]
+    fn test_import_unterminated_iri_is_an_error() {
+        assert!(import_turtle("<GPT-4 a <LLM> .").is_err());
+    }
+}
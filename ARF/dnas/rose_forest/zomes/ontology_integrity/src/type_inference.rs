@@ -0,0 +1,233 @@
+use crate::{all_type_ids, get_relation, get_type_definition, infer_type, is_subtype_of, KnowledgeTriple, OntologyError};
+use std::collections::{HashMap, HashSet};
+
+/// A single narrowing constraint derived from one triple: `entity`'s type
+/// must be `allowed_types` or a subtype thereof, as declared by
+/// `relation`'s domain (if `entity` is the subject) or range (if the
+/// object).
+struct Constraint {
+    entity: String,
+    allowed_types: HashSet<String>,
+    relation: String,
+    is_domain: bool,
+}
+
+/// Expand a relation's `domain`/`range` list into every concrete type_id
+/// that satisfies it — the list itself plus every subtype of each entry,
+/// since `is_subtype_of` is how `validate_triple` already treats e.g. an
+/// `AIModel` constraint as satisfied by `LLM`.
+fn expand_allowed_types(allowed_types: &[String]) -> Result<HashSet<String>, OntologyError> {
+    let mut expanded = HashSet::new();
+    for type_id in all_type_ids() {
+        if is_subtype_of(type_id, allowed_types)? {
+            expanded.insert(type_id.to_string());
+        }
+    }
+    Ok(expanded)
+}
+
+/// The depth of `type_id` in the `is_a` parent hierarchy — `Thing` (no
+/// parent) is depth 0, and each `parent` hop adds one. Used to pick the
+/// most specific surviving candidate when several remain.
+fn type_depth(type_id: &str) -> Result<u32, OntologyError> {
+    let mut depth = 0;
+    let mut current = type_id.to_string();
+    loop {
+        match get_type_definition(&current)?.parent {
+            Some(parent) => {
+                depth += 1;
+                current = parent;
+            }
+            None => break,
+        }
+    }
+    Ok(depth)
+}
+
+/// Infer the most specific type for every entity mentioned in `triples`
+/// by narrowing a candidate-type set per entity against the domain/range
+/// constraints implied by each triple's relation, via worklist-driven
+/// fixpoint narrowing — a constraint solver replacing `infer_type`'s
+/// string-suffix heuristics wherever actual usage pins a type down.
+///
+/// Each distinct entity starts with every known type as a candidate.
+/// Every triple whose relation declares a non-empty `domain` narrows the
+/// subject's candidates to `domain` (expanded through the subtype
+/// hierarchy); every relation with a non-empty `range` narrows the
+/// object's candidates the same way. Entities are reprocessed via a
+/// worklist until no further narrowing occurs across any of them,
+/// guaranteeing the result reflects every constraint regardless of the
+/// order triples were supplied in.
+///
+/// If any entity's candidate set narrows to empty, returns a
+/// `DomainViolation`/`RangeViolation` naming the relation that produced
+/// the conflict. Entities no triple ever constrains fall back to
+/// `infer_type`'s heuristics; entities with one or more surviving
+/// candidates resolve to the deepest (most specific) type among them.
+pub fn infer_types_constrained(triples: &[KnowledgeTriple]) -> Result<HashMap<String, String>, OntologyError> {
+    let all_types: HashSet<String> = all_type_ids().into_iter().map(String::from).collect();
+
+    let mut candidates: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut constraints_by_entity: HashMap<String, Vec<Constraint>> = HashMap::new();
+
+    for triple in triples {
+        candidates.entry(triple.subject.clone()).or_insert_with(|| all_types.clone());
+        candidates.entry(triple.object.clone()).or_insert_with(|| all_types.clone());
+
+        let Ok(relation) = get_relation(&triple.predicate) else {
+            continue;
+        };
+
+        if !relation.domain.is_empty() {
+            constraints_by_entity.entry(triple.subject.clone()).or_default().push(Constraint {
+                entity: triple.subject.clone(),
+                allowed_types: expand_allowed_types(&relation.domain)?,
+                relation: relation.name.clone(),
+                is_domain: true,
+            });
+        }
+        if !relation.range.is_empty() {
+            constraints_by_entity.entry(triple.object.clone()).or_default().push(Constraint {
+                entity: triple.object.clone(),
+                allowed_types: expand_allowed_types(&relation.range)?,
+                relation: relation.name.clone(),
+                is_domain: false,
+            });
+        }
+    }
+
+    let mut worklist: Vec<String> = constraints_by_entity.keys().cloned().collect();
+    while let Some(entity) = worklist.pop() {
+        let Some(entity_constraints) = constraints_by_entity.get(&entity) else {
+            continue;
+        };
+        let before = candidates[&entity].clone();
+        let mut narrowed = before.clone();
+        for constraint in entity_constraints {
+            narrowed = narrowed.intersection(&constraint.allowed_types).cloned().collect();
+            if narrowed.is_empty() {
+                return Err(if constraint.is_domain {
+                    OntologyError::DomainViolation {
+                        relation: constraint.relation.clone(),
+                        required: constraint.allowed_types.iter().cloned().collect::<Vec<_>>().join(" | "),
+                        actual: before.iter().cloned().collect::<Vec<_>>().join(" | "),
+                    }
+                } else {
+                    OntologyError::RangeViolation {
+                        relation: constraint.relation.clone(),
+                        required: constraint.allowed_types.iter().cloned().collect::<Vec<_>>().join(" | "),
+                        actual: before.iter().cloned().collect::<Vec<_>>().join(" | "),
+                    }
+                });
+            }
+        }
+        if narrowed != before {
+            candidates.insert(entity.clone(), narrowed);
+            // Re-check every other constrained entity, since narrowing this
+            // one could (in a richer constraint language than domain/range)
+            // affect constraints that reference it; cheap here since the
+            // worklist naturally drains once nothing changes.
+            worklist.extend(constraints_by_entity.keys().cloned());
+        }
+    }
+
+    let mut resolved = HashMap::new();
+    for (entity, entity_candidates) in candidates {
+        let touched = constraints_by_entity.contains_key(&entity);
+        let chosen = if touched {
+            let mut best: Option<(String, u32)> = None;
+            for candidate in &entity_candidates {
+                let depth = type_depth(candidate)?;
+                if best.as_ref().map_or(true, |(_, best_depth)| depth > *best_depth) {
+                    best = Some((candidate.clone(), depth));
+                }
+            }
+            best.map(|(type_id, _)| type_id).unwrap_or_else(|| "Entity".to_string())
+        } else {
+            infer_type(&entity)?
+        };
+        resolved.insert(entity, chosen);
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdi::prelude::*;
+
+    fn fake_agent_pub_key() -> AgentPubKey {
+        let bytes = vec![
+            132, 32, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        AgentPubKey::from_raw_39(bytes).unwrap()
+    }
+
+    fn triple(subject: &str, predicate: &str, object: &str) -> KnowledgeTriple {
+        KnowledgeTriple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence: 1.0,
+            source: fake_agent_pub_key(),
+            created_at: Timestamp::from_micros(0),
+        }
+    }
+
+    #[test]
+    fn test_improves_upon_narrows_to_most_specific_ai_model_subtype() {
+        // "improves_upon" constrains subject/object to AIModel|LLM; since
+        // an entity with no -LLM/-model suffix would otherwise default to
+        // "Entity" under the heuristic, usage alone should pin it to the
+        // most specific subtype (LLM, since that's among the candidates
+        // and is deeper than AIModel).
+        let triples = vec![triple("Foo", "improves_upon", "Bar")];
+        let types = infer_types_constrained(&triples).unwrap();
+
+        assert_eq!(types.get("Foo"), Some(&"LLM".to_string()));
+        assert_eq!(types.get("Bar"), Some(&"LLM".to_string()));
+    }
+
+    #[test]
+    fn test_trained_on_narrows_object_to_dataset() {
+        let triples = vec![triple("Foo", "trained_on", "Bar")];
+        let types = infer_types_constrained(&triples).unwrap();
+
+        assert_eq!(types.get("Bar"), Some(&"Dataset".to_string()));
+    }
+
+    #[test]
+    fn test_fully_unconstrained_entity_uses_suffix_heuristic() {
+        // related_to has empty domain/range, so neither slot is constrained.
+        let triples = vec![triple("my_agent", "related_to", "something_else")];
+        let types = infer_types_constrained(&triples).unwrap();
+
+        assert_eq!(types.get("my_agent"), Some(&"Agent".to_string()));
+    }
+
+    #[test]
+    fn test_conflicting_constraints_report_violation() {
+        // "Conflict" entity is pinned to Dataset via trained_on's range,
+        // then used as the subject of trained_on itself, whose domain is
+        // AIModel|LLM — Dataset is not a subtype of either, so this must fail.
+        let triples = vec![
+            triple("Model-A", "trained_on", "Conflict"),
+            triple("Conflict", "trained_on", "Other-Dataset"),
+        ];
+
+        let result = infer_types_constrained(&triples);
+        assert!(result.is_err(), "Conflicting domain/range constraints should be rejected");
+    }
+
+    #[test]
+    fn test_most_specific_type_wins_when_multiple_candidates_survive() {
+        // capable_of's domain is AIModel|LLM|Agent; among the expanded
+        // candidate set {AIModel, LLM, Agent}, LLM is deepest.
+        let triples = vec![triple("Thing-X", "capable_of", "some_capability")];
+        let types = infer_types_constrained(&triples).unwrap();
+
+        assert_eq!(types.get("Thing-X"), Some(&"LLM".to_string()));
+    }
+}
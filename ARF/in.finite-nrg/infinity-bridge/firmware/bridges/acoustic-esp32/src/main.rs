@@ -4,14 +4,27 @@ use esp_idf_hal::peripherals::Peripherals;
 use esp_idf_hal::delay::FreeRtos;
 use esp_idf_svc::systime::EspSystemTime;
 use infinity_bridge_hal::*;
+use infinity_bridge_hal::pacing::GccController;
+use infinity_bridge_hal::transport::{BridgeCommand, MqttConfig, MqttQos, MqttTransport, StreamMetadataMsg, TransportSink};
+use ed25519_dalek::Signer;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+// MQTT broker this bridge publishes SensorPackets and stream metadata to.
+const MQTT_BROKER_HOST: &str = "192.168.1.100";
+const MQTT_BROKER_PORT: u16 = 1883;
+
 // FFT Configuration
 const FFT_SIZE: usize = 1024;
 const SAMPLE_RATE_HZ: u32 = 44100;
 const BUFFER_SIZE: usize = FFT_SIZE * 2;
 
+// Target frame rate bounds (frames/sec) for the congestion-controlled pacer.
+// 50 fps matches the previous hardcoded 20ms delay; the pacer is free to
+// back off towards MIN_FRAME_RATE_HZ if the DHT endpoint falls behind.
+const MIN_FRAME_RATE_HZ: f32 = 5.0;
+const MAX_FRAME_RATE_HZ: f32 = 50.0;
+
 // Bridge Configuration
 const BRIDGE_ID: &str = "acoustic-esp32-001";
 const DHT_ENDPOINT: &str = "ws://192.168.1.100:8888"; // Configurable via WiFi
@@ -22,6 +35,18 @@ pub struct AcousticBridge {
     sample_buffer: Arc<Mutex<[f32; BUFFER_SIZE]>>,
     fft_buffer: Arc<Mutex<[f32; FFT_SIZE]>>,
     last_timestamp_ns: u64,
+    /// Delay-based congestion controller pacing `stream_data` bursts against
+    /// the DHT endpoint, so a slow endpoint backs off the frame rate instead
+    /// of dropping packets sent at the old fixed 20ms cadence.
+    pacer: GccController,
+    /// Device Ed25519 keypair identifying this bridge to the registry zome.
+    signing_key: ed25519_dalek::SigningKey,
+    /// PTP-style two-way time-sync servo correcting the local clock used for
+    /// `SensorPacket` TAI timestamps.
+    time_sync_servo: sync::TwoWayTimeSyncServo,
+    /// MQTT publisher for `SensorPacket`s, stream metadata and the command
+    /// topic, selectable alongside the USB3/TCP transports.
+    mqtt: MqttTransport,
 }
 
 impl AcousticBridge {
@@ -31,9 +56,60 @@ impl AcousticBridge {
             sample_buffer: Arc::new(Mutex::new([0.0; BUFFER_SIZE])),
             fft_buffer: Arc::new(Mutex::new([0.0; FFT_SIZE])),
             last_timestamp_ns: 0,
+            pacer: GccController::new(MIN_FRAME_RATE_HZ, MAX_FRAME_RATE_HZ, MAX_FRAME_RATE_HZ),
+            // In production: load the device's persisted keypair from secure
+            // storage (e.g. ESP32 eFuse/NVS) instead of generating one.
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&[0u8; 32]),
+            time_sync_servo: sync::TwoWayTimeSyncServo::new(sync::SyncSource::NTP),
+            mqtt: MqttTransport::new(
+                bridge_id.clone(),
+                MqttConfig {
+                    broker_host: MQTT_BROKER_HOST.to_string(),
+                    broker_port: MQTT_BROKER_PORT,
+                    client_id: bridge_id,
+                    qos: MqttQos::AtLeastOnce,
+                },
+            ),
         }
     }
 
+    /// Perform one IEEE-1588 two-way time-sync exchange with the master
+    /// clock and feed it into the servo correcting this bridge's clock.
+    pub fn sync_time(&mut self, t1: u64, t2: u64, t3: u64, t4: u64) -> sync::SyncQuality {
+        self.time_sync_servo.observe_exchange(t1, t2, t3, t4)
+    }
+
+    /// Connect the MQTT transport and publish this bridge's retained stream
+    /// metadata so subscribers can discover it without a DHT round trip.
+    pub fn connect_mqtt(&mut self) -> Result<(), String> {
+        self.mqtt.connect().map_err(|e| e.to_string())?;
+        self.mqtt
+            .publish_metadata(&StreamMetadataMsg {
+                bridge_id: self.bridge_id.clone(),
+                stream_type: "acoustic/spectrum".to_string(),
+                sample_rate_hz: SAMPLE_RATE_HZ,
+                data_format: "float32".to_string(),
+                buffer_size: FFT_SIZE as u32,
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Drain commands received on the MQTT command topic, acting on any
+    /// `EmergencyShutdown` out-of-band shutoff.
+    pub fn poll_mqtt_commands(&mut self) {
+        for command in self.mqtt.poll_commands() {
+            match command {
+                BridgeCommand::EmergencyShutdown => self.emergency_shutdown(),
+            }
+        }
+    }
+
+    /// Target delay between streamed frames, derived from the pacer's
+    /// current target rate, to drive the main loop's `FreeRtos::delay_ms`.
+    pub fn frame_delay_ms(&self) -> u32 {
+        (1000.0 / self.pacer.target_rate()).round() as u32
+    }
+
     /// Initialize I2S MEMS microphone
     pub fn init_i2s(peripherals: &Peripherals) -> Result<(), esp_idf_sys::EspError> {
         // I2S configuration for MEMS microphone (INMP441 or similar)
@@ -75,24 +151,58 @@ impl AcousticBridge {
         correlate::normalized_xcorr(acoustic, vibration)
     }
 
+    /// Sign `payload` with this bridge's Ed25519 device keypair, returning
+    /// `(public_key_bytes, signature_bytes)` for inclusion in a registration.
+    fn sign_registration(&self, payload: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let signature: ed25519_dalek::Signature = self.signing_key.sign(payload);
+        (
+            self.signing_key.verifying_key().to_bytes().to_vec(),
+            signature.to_bytes().to_vec(),
+        )
+    }
+
     /// Register bridge with Holochain DHT
     pub async fn register_with_dht(&self, endpoint: &str) -> Result<(), String> {
         // In production, use websocket client to connect to Holochain conductor
         println!("[AcousticBridge] Registering with DHT at {}", endpoint);
 
+        let bridge_id = self.bridge_id.clone();
+        let capabilities = vec![
+            "acoustic_20hz_20khz".to_string(),
+            "fft_1024".to_string(),
+            "correlation_engine".to_string(),
+        ];
+        let transport = vec![
+            "usb_hid".to_string(),
+            "tcp".to_string(),
+        ];
+        let endpoint_addr = "tcp://192.168.1.101:9999".to_string(); // ESP32 IP
+        let timestamp_ns = self.get_timestamp_ns().map_err(|e| e.to_string())?;
+        // The zome stores/signs over a Holochain `Timestamp`, which counts
+        // microseconds, not this bridge's local nanosecond clock reading.
+        let timestamp_micros = (timestamp_ns / 1_000) as i64;
+
+        // The registry zome's `validate` rejects any registration whose
+        // signature doesn't verify against `public_key` over the canonical
+        // (bridge_id, capabilities, transport, endpoint, timestamp) payload,
+        // so both must come from this bridge's on-device Ed25519 keypair.
+        let signing_payload = registration_signing_payload(
+            &bridge_id,
+            &capabilities,
+            &transport,
+            &endpoint_addr,
+            timestamp_micros,
+        );
+        let (public_key, signature) = self.sign_registration(&signing_payload);
+
         let registration = BridgeRegistrationPayload {
-            bridge_id: self.bridge_id.clone(),
-            capabilities: vec![
-                "acoustic_20hz_20khz".to_string(),
-                "fft_1024".to_string(),
-                "correlation_engine".to_string(),
-            ],
-            transport: vec![
-                "usb_hid".to_string(),
-                "tcp".to_string(),
-            ],
-            endpoint: "tcp://192.168.1.101:9999".to_string(), // ESP32 IP
-            signature: vec![0; 64], // Placeholder for cryptographic signature
+            bridge_id,
+            capabilities,
+            transport,
+            endpoint: endpoint_addr,
+            public_key,
+            signature,
+            timestamp_ns,
         };
 
         // Send registration via HTTP/WebSocket
@@ -112,10 +222,21 @@ impl AcousticBridge {
             payload_le: &spectrum_to_bytes(spectrum),
         };
 
-        // In production: Send via configured transport
-        // USB HID: Use esp-idf-hal USB peripheral
-        // TCP: Use esp-idf-svc TcpSocket
-        println!("[AcousticBridge] Streaming {} bytes", packet.payload_le.len());
+        let send_ns = self.last_timestamp_ns;
+
+        match self.preferred_transport() {
+            Transport::Mqtt => self.mqtt.send(&packet).map_err(|e| e.to_string())?,
+            // In production: Send via configured transport
+            // USB HID: Use esp-idf-hal USB peripheral
+            // TCP: Use esp-idf-svc TcpSocket
+            _ => println!("[AcousticBridge] Streaming {} bytes", packet.payload_le.len()),
+        }
+
+        // In production: arrival_ns comes from the endpoint's ACK; until the
+        // transport carries one back, feed the pacer the send timestamp so it
+        // holds steady rather than starving for lack of samples.
+        let arrival_ns = send_ns;
+        self.pacer.on_burst_arrival(send_ns, arrival_ns);
 
         Ok(())
     }
@@ -123,14 +244,13 @@ impl AcousticBridge {
 
 impl InfinityBridgeHal for AcousticBridge {
     fn get_time_sync_quality(&self) -> sync::SyncQuality {
-        // In production: Use NTP or PTP for synchronization
-        sync::SyncQuality::with_score(0.85, 5000, sync::SyncSource::NTP)
+        self.time_sync_servo.quality()
     }
 
     fn get_timestamp_ns(&self) -> Result<u64, TimeSyncError> {
-        // Use ESP system time (microseconds since boot)
-        let time = EspSystemTime {}.now().as_nanos();
-        Ok(time as u64)
+        // ESP system time, drift-compensated by the two-way time-sync servo.
+        let raw = EspSystemTime {}.now().as_nanos() as u64;
+        Ok(self.time_sync_servo.correct(raw))
     }
 
     fn validate_output_safe(&self, level: f32, domain: Domain) -> Result<(), SafetyError> {
@@ -143,13 +263,17 @@ impl InfinityBridgeHal for AcousticBridge {
     }
 
     fn send_packet(&mut self, packet: &SensorPacket) -> Result<(), TransportError> {
-        // Send packet via preferred transport
-        println!("[AcousticBridge] Sending packet from {}", packet.bridge_id);
-        Ok(())
+        match self.preferred_transport() {
+            Transport::Mqtt => self.mqtt.send(packet),
+            _ => {
+                println!("[AcousticBridge] Sending packet from {}", packet.bridge_id);
+                Ok(())
+            }
+        }
     }
 
     fn preferred_transport(&self) -> Transport {
-        Transport::USB3 // ESP32-S3 has USB OTG support
+        Transport::Mqtt // Publishes to the broker; falls back to USB3/TCP if unreachable
     }
 }
 
@@ -161,7 +285,46 @@ struct BridgeRegistrationPayload {
     capabilities: Vec<String>,
     transport: Vec<String>,
     endpoint: String,
+    public_key: Vec<u8>,
     signature: Vec<u8>,
+    timestamp_ns: u64,
+}
+
+/// Append `field`'s length (as a 4-byte little-endian count) followed by
+/// `field` itself, so variable-length fields can never shift across a
+/// boundary and still hash/sign identically (raw concatenation can't tell
+/// `["ab", "c"]` from `["a", "bc"]`; this can).
+fn encode_field(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(field);
+}
+
+/// The canonical `(bridge_id, capabilities, transport, endpoint, timestamp)`
+/// bytes a registration's signature is computed over. Must match the
+/// registry zome's `canonical_registration_bytes` byte-for-byte: every
+/// variable-length field is length-prefixed (not raw-concatenated), and
+/// `timestamp_micros` is microseconds — matching Holochain's `Timestamp`
+/// unit — not this bridge's local nanosecond clock reading.
+fn registration_signing_payload(
+    bridge_id: &str,
+    capabilities: &[String],
+    transport: &[String],
+    endpoint: &str,
+    timestamp_micros: i64,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    encode_field(&mut bytes, bridge_id.as_bytes());
+    bytes.extend_from_slice(&(capabilities.len() as u32).to_le_bytes());
+    for capability in capabilities {
+        encode_field(&mut bytes, capability.as_bytes());
+    }
+    bytes.extend_from_slice(&(transport.len() as u32).to_le_bytes());
+    for t in transport {
+        encode_field(&mut bytes, t.as_bytes());
+    }
+    encode_field(&mut bytes, endpoint.as_bytes());
+    bytes.extend_from_slice(&timestamp_micros.to_le_bytes());
+    bytes
 }
 
 fn spectrum_to_bytes(spectrum: &[f32]) -> Vec<u8> {
@@ -196,10 +359,19 @@ fn main() -> Result<(), esp_idf_sys::EspError> {
     // Register with DHT (in production, do this after WiFi connects)
     // bridge.register_with_dht(DHT_ENDPOINT).await?;
 
+    // Connect MQTT and publish stream-discovery metadata so subscribers
+    // don't need to hit the DHT to find this bridge.
+    if let Err(e) = bridge.connect_mqtt() {
+        println!("[AcousticBridge] MQTT connect failed: {}", e);
+    }
+
     println!("[AcousticBridge] Starting main loop...");
 
     // Main loop: capture, FFT, stream
     loop {
+        // 0. Handle any out-of-band MQTT commands (e.g. emergency_shutdown)
+        bridge.poll_mqtt_commands();
+
         // 1. Capture audio samples via I2S DMA
         let mut samples = vec![0.0f32; FFT_SIZE];
         // In production: Read from I2S DMA buffer
@@ -210,13 +382,20 @@ fn main() -> Result<(), esp_idf_sys::EspError> {
         // 3. Update timestamp
         bridge.last_timestamp_ns = bridge.get_timestamp_ns().unwrap_or(0);
 
-        // 4. Stream data
-        if let Err(e) = bridge.stream_data(&spectrum) {
-            println!("[AcousticBridge] Stream error: {}", e);
+        // 4. Stream data, gated on the time-sync servo having converged
+        // (in production: periodically call bridge.sync_time(t1, t2, t3, t4)
+        // with the IEEE-1588 exchange against the master clock)
+        if bridge.get_time_sync_quality().is_acceptable(1_000_000, 0.5) {
+            if let Err(e) = bridge.stream_data(&spectrum) {
+                println!("[AcousticBridge] Stream error: {}", e);
+            }
+        } else {
+            println!("[AcousticBridge] Time sync not yet converged, holding stream");
         }
 
-        // 5. Delay for next frame (~23ms @ 44.1kHz with 1024 samples)
-        FreeRtos::delay_ms(20);
+        // 5. Delay for next frame, adapted by the congestion controller
+        // (starts at ~20ms @ 44.1kHz with 1024 samples, backs off under load)
+        FreeRtos::delay_ms(bridge.frame_delay_ms());
     }
 }
 
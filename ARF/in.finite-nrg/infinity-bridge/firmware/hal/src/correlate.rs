@@ -2,19 +2,59 @@
 pub struct CorrelationResult {
     pub peak: f32,
     pub lag_samples: i64,
+    /// Sub-sample refinement of `lag_samples`, in `[-0.5, 0.5]` samples, from a
+    /// parabolic fit through the peak and its two neighbours. Zero for methods
+    /// that don't estimate sub-sample lag.
+    pub sub_sample_offset: f32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrelationMethod {
+    /// Direct time-domain normalized cross-correlation.
+    TimeDomain,
+    /// FFT-based Generalized Cross-Correlation with Phase Transform (GCC-PHAT).
+    Phat,
+}
+
+pub fn correlate(a: &[f32], b: &[f32], method: CorrelationMethod) -> CorrelationResult {
+    match method {
+        CorrelationMethod::TimeDomain => normalized_xcorr(a, b),
+        CorrelationMethod::Phat => correlate_phat(a, b),
+    }
+}
+
+/// Above this sample count, `normalized_xcorr` switches from the naive
+/// O(n^2) direct lag loop to `normalized_xcorr_fft`'s O(n log n)
+/// FFT-backed computation of the same normalized cross-correlation —
+/// the same zero-pad-and-transform trick `correlate_phat` already uses,
+/// just without PHAT's phase-only whitening. Below this threshold the
+/// direct loop's lower constant factor (no transform setup) wins.
+const FFT_XCORR_THRESHOLD: usize = 512;
+
 pub fn normalized_xcorr(a: &[f32], b: &[f32]) -> CorrelationResult {
-    if a.is_empty() || b.is_empty() { return CorrelationResult { peak: 0.0, lag_samples: 0 }; }
+    if a.is_empty() || b.is_empty() { return CorrelationResult { peak: 0.0, lag_samples: 0, sub_sample_offset: 0.0 }; }
     let n = a.len().min(b.len());
     let (a, b) = (&a[..n], &b[..n]);
+    if n > FFT_XCORR_THRESHOLD {
+        normalized_xcorr_fft(a, b)
+    } else {
+        normalized_xcorr_direct(a, b)
+    }
+}
+
+/// The naive O(n^2) direct-lag-loop normalized cross-correlation.
+/// `a` and `b` must already be the same length. Retained as the
+/// small-input path (see `FFT_XCORR_THRESHOLD`) and as a reference
+/// implementation the FFT-backed path's tests check agreement against.
+fn normalized_xcorr_direct(a: &[f32], b: &[f32]) -> CorrelationResult {
+    let n = a.len();
     let mean = |x: &[f32]| x.iter().sum::<f32>() / x.len() as f32;
     let ma = mean(a);
     let mb = mean(b);
     let va = a.iter().map(|x| (x - ma)*(x - ma)).sum::<f32>().sqrt();
     let vb = b.iter().map(|x| (x - mb)*(x - mb)).sum::<f32>().sqrt();
     if va == 0.0 || vb == 0.0 {
-        return CorrelationResult { peak: 0.0, lag_samples: 0 };
+        return CorrelationResult { peak: 0.0, lag_samples: 0, sub_sample_offset: 0.0 };
     }
     let mut best = (0.0f32, 0i64);
     let max_lag = (n as i64)/4;
@@ -29,5 +69,246 @@ pub fn normalized_xcorr(a: &[f32], b: &[f32]) -> CorrelationResult {
         let r = if den != 0.0 { num / den } else { 0.0 };
         if r.abs() > best.0.abs() { best = (r, lag); }
     }
-    CorrelationResult { peak: best.0.clamp(-1.0, 1.0).abs(), lag_samples: best.1 }
+    CorrelationResult { peak: best.0.clamp(-1.0, 1.0).abs(), lag_samples: best.1, sub_sample_offset: 0.0 }
+}
+
+/// FFT-backed normalized cross-correlation, over the same `-n/4..=n/4`
+/// lag window `normalized_xcorr_direct` scans: subtracts the means of
+/// `a` and `b`, zero-pads both to the next power of two of `2n - 1` (far
+/// enough that the FFT's circular correlation matches the true linear
+/// one over every lag in range), forms `FFT(a) .* conj(FFT(b))`, and
+/// inverse-transforms to recover every lag's raw cross-correlation in one
+/// pass, normalizing by `va * vb` exactly as the direct loop does.
+/// `a` and `b` must already be the same length.
+fn normalized_xcorr_fft(a: &[f32], b: &[f32]) -> CorrelationResult {
+    let n = a.len();
+    let mean = |x: &[f32]| x.iter().sum::<f32>() / x.len() as f32;
+    let ma = mean(a);
+    let mb = mean(b);
+    let va = a.iter().map(|x| (x - ma)*(x - ma)).sum::<f32>().sqrt();
+    let vb = b.iter().map(|x| (x - mb)*(x - mb)).sum::<f32>().sqrt();
+    if va == 0.0 || vb == 0.0 {
+        return CorrelationResult { peak: 0.0, lag_samples: 0, sub_sample_offset: 0.0 };
+    }
+
+    let fft_len = next_pow2(2 * n - 1);
+    let mut fa: Vec<Complex> = (0..fft_len).map(|i| if i < n { Complex::new(a[i] - ma, 0.0) } else { Complex::new(0.0, 0.0) }).collect();
+    let mut fb: Vec<Complex> = (0..fft_len).map(|i| if i < n { Complex::new(b[i] - mb, 0.0) } else { Complex::new(0.0, 0.0) }).collect();
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    let mut cross: Vec<Complex> = fa.iter().zip(fb.iter()).map(|(&x, &y)| x * y.conj()).collect();
+    fft(&mut cross, true);
+
+    let den = va * vb;
+    let max_lag = (n as i64)/4;
+    let mut best = (0.0f32, 0i64);
+    for lag in -max_lag..=max_lag {
+        let idx = if lag <= 0 { (-lag) as usize } else { (fft_len as i64 - lag) as usize };
+        let num = cross[idx].re / fft_len as f32;
+        let r = if den != 0.0 { num / den } else { 0.0 };
+        if r.abs() > best.0.abs() { best = (r, lag); }
+    }
+    CorrelationResult { peak: best.0.clamp(-1.0, 1.0).abs(), lag_samples: best.1, sub_sample_offset: 0.0 }
+}
+
+/// GCC-PHAT: cross-correlation whitened by the phase transform, robust to
+/// amplitude/channel mismatch between the two inputs (e.g. an acoustic
+/// channel paired with a vibration channel). Zero-pads both signals to the
+/// next power of two of `len_a + len_b - 1`, forms the cross-spectrum
+/// `B .* conj(A)`, normalizes each bin to unit magnitude (PHAT weighting),
+/// and inverse-transforms back to the lag domain.
+pub fn correlate_phat(a: &[f32], b: &[f32]) -> CorrelationResult {
+    if a.is_empty() || b.is_empty() { return CorrelationResult { peak: 0.0, lag_samples: 0, sub_sample_offset: 0.0 }; }
+    const EPS: f32 = 1e-12;
+
+    let full_len = a.len() + b.len() - 1;
+    let n = next_pow2(full_len);
+
+    let mut fa = zero_padded(a, n);
+    let mut fb = zero_padded(b, n);
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+
+    let mut g: Vec<Complex> = fa.iter().zip(fb.iter())
+        .map(|(&x, &y)| {
+            let cross = y * x.conj();
+            let mag = cross.norm() + EPS;
+            cross * (1.0 / mag)
+        })
+        .collect();
+    fft(&mut g, true);
+
+    // `g` now holds the circular correlation; fftshift so zero lag sits in
+    // the middle, then restrict to the valid lag range of the original
+    // (unpadded) signals.
+    let shifted = fftshift(&g);
+    let center = n / 2;
+    let max_lag = (a.len().max(b.len()) as i64) - 1;
+
+    let mut best_idx = center;
+    let mut best_val = f32::MIN;
+    for (idx, c) in shifted.iter().enumerate() {
+        let lag = idx as i64 - center as i64;
+        if lag.abs() > max_lag { continue; }
+        if c.re > best_val { best_val = c.re; best_idx = idx; }
+    }
+
+    let peak = best_val;
+    let lag_samples = best_idx as i64 - center as i64;
+    let sub_sample_offset = parabolic_vertex(&shifted, best_idx);
+
+    // Normalize peak into a comparable [0, 1] magnitude: PHAT peaks are
+    // already near the normalized cross-spectrum's DC-free unit scale, so we
+    // divide by n to undo the unnormalized inverse-FFT scaling.
+    let normalized_peak = (peak / n as f32).clamp(-1.0, 1.0).abs();
+
+    CorrelationResult { peak: normalized_peak, lag_samples, sub_sample_offset }
+}
+
+/// Fit a parabola through `idx - 1, idx, idx + 1` and return the vertex
+/// offset from `idx`, clamped to `[-0.5, 0.5]`. Returns 0.0 at the edges.
+fn parabolic_vertex(values: &[Complex], idx: usize) -> f32 {
+    if idx == 0 || idx + 1 >= values.len() { return 0.0; }
+    let y0 = values[idx - 1].re;
+    let y1 = values[idx].re;
+    let y2 = values[idx + 1].re;
+    let denom = y0 - 2.0 * y1 + y2;
+    if denom.abs() < 1e-20 { return 0.0; }
+    (0.5 * (y0 - y2) / denom).clamp(-0.5, 0.5)
+}
+
+fn next_pow2(n: usize) -> usize {
+    let mut p = 1usize;
+    while p < n { p <<= 1; }
+    p.max(1)
+}
+
+fn zero_padded(x: &[f32], n: usize) -> Vec<Complex> {
+    let mut out = Vec::with_capacity(n);
+    out.extend(x.iter().map(|&v| Complex::new(v, 0.0)));
+    out.resize(n, Complex::new(0.0, 0.0));
+    out
+}
+
+fn fftshift(x: &[Complex]) -> Vec<Complex> {
+    let n = x.len();
+    let mid = n / 2;
+    let mut out = Vec::with_capacity(n);
+    out.extend_from_slice(&x[mid..]);
+    out.extend_from_slice(&x[..mid]);
+    out
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Complex { re: f32, im: f32 }
+
+impl Complex {
+    fn new(re: f32, im: f32) -> Self { Self { re, im } }
+    fn conj(self) -> Self { Self { re: self.re, im: -self.im } }
+    fn norm(self) -> f32 { (self.re * self.re + self.im * self.im).sqrt() }
+}
+
+impl core::ops::Add for Complex {
+    type Output = Complex;
+    fn add(self, o: Complex) -> Complex { Complex::new(self.re + o.re, self.im + o.im) }
+}
+impl core::ops::Sub for Complex {
+    type Output = Complex;
+    fn sub(self, o: Complex) -> Complex { Complex::new(self.re - o.re, self.im - o.im) }
+}
+impl core::ops::Mul for Complex {
+    type Output = Complex;
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re)
+    }
+}
+impl core::ops::Mul<f32> for Complex {
+    type Output = Complex;
+    fn mul(self, s: f32) -> Complex { Complex::new(self.re * s, self.im * s) }
+}
+
+/// In-place iterative radix-2 Cooley-Tukey FFT. `len` must be a power of two.
+/// `inverse` selects the inverse transform (unnormalized, i.e. callers that
+/// need a true inverse must divide by `len`).
+fn fft(x: &mut [Complex], inverse: bool) {
+    let n = x.len();
+    if n <= 1 { return; }
+    debug_assert!(n.is_power_of_two());
+
+    // Bit-reversal permutation.
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 { j ^= bit; bit >>= 1; }
+        j |= bit;
+        if i < j { x.swap(i, j); }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2usize;
+    while len <= n {
+        let ang = sign * 2.0 * core::f32::consts::PI / len as f32;
+        let wlen = Complex::new(ang.cos(), ang.sin());
+        let mut i = 0;
+        while i < n {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let u = x[i + k];
+                let v = x[i + k + len / 2] * w;
+                x[i + k] = u + v;
+                x[i + k + len / 2] = u - v;
+                w = w * wlen;
+            }
+            i += len;
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq_hz: f32, sample_rate: f32, n: usize) -> Vec<f32> {
+        (0..n).map(|i| (2.0 * core::f32::consts::PI * freq_hz * i as f32 / sample_rate).sin()).collect()
+    }
+
+    fn shifted(signal: &[f32], shift: usize) -> Vec<f32> {
+        let mut out = vec![0.0; signal.len()];
+        for (i, &v) in signal.iter().enumerate() {
+            if i + shift < out.len() { out[i + shift] = v; }
+        }
+        out
+    }
+
+    #[test]
+    fn normalized_xcorr_picks_the_fft_path_above_threshold() {
+        let n = FFT_XCORR_THRESHOLD + 16;
+        let a = sine(5.0, 1000.0, n);
+        let b = shifted(&a, 3);
+        let result = normalized_xcorr(&a, &b);
+        assert_eq!(result.lag_samples, 3);
+        assert!(result.peak > 0.9, "peak={}", result.peak);
+    }
+
+    #[test]
+    fn fft_path_agrees_with_the_direct_path_on_a_shared_length() {
+        let n = FFT_XCORR_THRESHOLD + 16;
+        let a = sine(7.0, 1000.0, n);
+        let b = shifted(&a, 5);
+        let direct = normalized_xcorr_direct(&a, &b);
+        let via_fft = normalized_xcorr_fft(&a, &b);
+        assert_eq!(direct.lag_samples, via_fft.lag_samples);
+        assert!((direct.peak - via_fft.peak).abs() < 1e-3, "direct={} fft={}", direct.peak, via_fft.peak);
+    }
+
+    #[test]
+    fn fft_path_returns_zero_for_a_constant_signal() {
+        let n = FFT_XCORR_THRESHOLD + 16;
+        let a = vec![1.0f32; n];
+        let b = vec![1.0f32; n];
+        let result = normalized_xcorr_fft(&a, &b);
+        assert_eq!(result.peak, 0.0);
+        assert_eq!(result.lag_samples, 0);
+    }
 }
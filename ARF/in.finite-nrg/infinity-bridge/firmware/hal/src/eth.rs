@@ -0,0 +1,125 @@
+//! Stub SPI Ethernet transport backend for wired sensor nodes that can't
+//! rely on WiFi+TCP, paralleling the WiFi/TCP path used by `MockBridge` and
+//! `AcousticBridge`. This crate isn't `#![no_std]` (other modules here, like
+//! `transport::MqttTransport`, already use `String`/`Vec` freely) and
+//! nothing below drives real SPI/MAC hardware or an `embassy-net` stack —
+//! [`EthTransport`] only tracks link-up state so `send` can be exercised in
+//! tests without hardware. A real no-allocator, `no_std` driver for a W5500
+//! or ENC28J60 MAC is still to be written.
+
+use crate::{SensorPacket, Transport, TransportError};
+
+/// Which SPI Ethernet MAC drives the link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EthDriver {
+    W5500,
+    Enc28j60,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MacAddress(pub [u8; 6]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ipv4Address(pub [u8; 4]);
+
+/// Static-IP socket configuration for the embedded Ethernet stack, read
+/// from on-device config (no DHCP, no allocator).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EthConfig {
+    pub driver: EthDriver,
+    pub mac: MacAddress,
+    pub ip: Ipv4Address,
+    pub gateway: Ipv4Address,
+    pub subnet_mask: Ipv4Address,
+    pub conductor_ip: Ipv4Address,
+    pub conductor_port: u16,
+}
+
+/// Stub for an `embassy-net`-style SPI Ethernet transport. A real
+/// implementation would wrap an `embassy_net::Stack` driving the
+/// `EthDriver`'s SPI MAC and hold a pre-bound TCP or UDP socket to
+/// `conductor_ip:conductor_port`; this only tracks link state so `send` can
+/// be exercised and tested without hardware — `bring_up`/`send` do no SPI
+/// I/O and no packet framing.
+pub struct EthTransport {
+    config: EthConfig,
+    link_up: bool,
+}
+
+impl EthTransport {
+    pub fn new(config: EthConfig) -> Self {
+        Self { config, link_up: false }
+    }
+
+    /// Bring up the PHY link and bind the static-IP socket to the
+    /// conductor endpoint. In production: initialize the SPI MAC driver,
+    /// register it with the `embassy-net` stack, and open the socket.
+    pub fn bring_up(&mut self) -> Result<(), TransportError> {
+        self.link_up = true;
+        Ok(())
+    }
+
+    pub fn link_up(&self) -> bool {
+        self.link_up
+    }
+
+    pub fn driver(&self) -> EthDriver {
+        self.config.driver
+    }
+
+    pub fn preferred_transport(&self) -> Transport {
+        match self.config.driver {
+            EthDriver::W5500 => Transport::GigE,
+            EthDriver::Enc28j60 => Transport::UDP,
+        }
+    }
+
+    pub fn send(&mut self, packet: &SensorPacket) -> Result<(), TransportError> {
+        if !self.link_up {
+            return Err(TransportError::NotReady);
+        }
+        // In production: write packet.payload_le to the bound TCP/UDP
+        // socket towards self.config.conductor_ip:conductor_port.
+        let _ = packet;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Domain;
+
+    fn test_config(driver: EthDriver) -> EthConfig {
+        EthConfig {
+            driver,
+            mac: MacAddress([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            ip: Ipv4Address([192, 168, 1, 50]),
+            gateway: Ipv4Address([192, 168, 1, 1]),
+            subnet_mask: Ipv4Address([255, 255, 255, 0]),
+            conductor_ip: Ipv4Address([192, 168, 1, 100]),
+            conductor_port: 8888,
+        }
+    }
+
+    #[test]
+    fn send_requires_link_up() {
+        let mut transport = EthTransport::new(test_config(EthDriver::W5500));
+        let packet = SensorPacket {
+            bridge_id: "wired-001",
+            domain: Domain::Acoustic,
+            tai_timestamp_ns: 0,
+            sample_rate_hz: 44100,
+            payload_le: &[],
+        };
+        assert!(transport.send(&packet).is_err());
+        transport.bring_up().unwrap();
+        assert!(transport.send(&packet).is_ok());
+    }
+
+    #[test]
+    fn driver_selects_preferred_transport() {
+        assert_eq!(EthTransport::new(test_config(EthDriver::W5500)).preferred_transport(), Transport::GigE);
+        assert_eq!(EthTransport::new(test_config(EthDriver::Enc28j60)).preferred_transport(), Transport::UDP);
+    }
+}
@@ -2,6 +2,9 @@ pub mod sync;
 pub mod safety;
 pub mod transport;
 pub mod correlate;
+pub mod streaming;
+pub mod pacing;
+pub mod eth;
 
 use serde::{Deserialize, Serialize};
 
@@ -9,7 +12,7 @@ use serde::{Deserialize, Serialize};
 pub enum Domain { RF, Optical, Acoustic }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub enum Transport { USB3, GigE, UDP, ZMQ }
+pub enum Transport { USB3, GigE, UDP, ZMQ, Mqtt }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SensorPacket<'a> {
@@ -46,7 +49,15 @@ pub trait InfinityBridgeHal {
     fn send_packet(&mut self, packet: &SensorPacket) -> Result<(), TransportError>;
     fn preferred_transport(&self) -> Transport;
     fn correlate_local(&self, a: &[f32], b: &[f32]) -> correlate::CorrelationResult {
-        correlate::normalized_xcorr(a, b)
+        self.correlate_local_with(a, b, correlate::CorrelationMethod::TimeDomain)
+    }
+    fn correlate_local_with(
+        &self,
+        a: &[f32],
+        b: &[f32],
+        method: correlate::CorrelationMethod,
+    ) -> correlate::CorrelationResult {
+        correlate::correlate(a, b, method)
     }
 }
 
@@ -74,3 +85,5 @@ impl InfinityBridgeHal for MockBridge {
     fn send_packet(&mut self, _packet: &SensorPacket) -> Result<(), TransportError> { Ok(()) }
     fn preferred_transport(&self) -> Transport { Transport::GigE }
 }
+
+impl streaming::AsyncInfinityBridgeHal for MockBridge {}
@@ -0,0 +1,167 @@
+/// Delay-based congestion control (Google Congestion Control-style) for
+/// pacing outgoing packet/chunk bursts against a possibly slow receiver,
+/// instead of sending at a fixed cadence and dropping on backpressure.
+///
+/// Callers group outgoing sends into bursts and, as ACKs/arrivals come back,
+/// call [`GccController::on_burst_arrival`] with the send and arrival
+/// timestamps of consecutive bursts. The controller tracks inter-burst delay
+/// variation, estimates its trend via sliding-window linear regression, and
+/// drives an AIMD rate controller from the resulting overuse/normal/underuse
+/// signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageSignal {
+    Overuse,
+    Normal,
+    Underuse,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BurstTimestamps {
+    send_ns: u64,
+    arrival_ns: u64,
+}
+
+/// Fixed-capacity sliding window of `(index, accumulated_delay)` samples
+/// used for the trend (slope) estimate.
+const WINDOW_LEN: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct GccController {
+    min_rate: f32,
+    max_rate: f32,
+    rate: f32,
+    additive_step: f32,
+    decrease_factor: f32,
+    last_burst: Option<BurstTimestamps>,
+    accumulated_delay_ns: f64,
+    window: [(f64, f64); WINDOW_LEN],
+    window_len: usize,
+    window_next: usize,
+    sample_index: f64,
+}
+
+impl GccController {
+    pub fn new(min_rate: f32, max_rate: f32, initial_rate: f32) -> Self {
+        Self {
+            min_rate,
+            max_rate,
+            rate: initial_rate.clamp(min_rate, max_rate),
+            additive_step: (max_rate - min_rate) * 0.02,
+            decrease_factor: 0.85,
+            last_burst: None,
+            accumulated_delay_ns: 0.0,
+            window: [(0.0, 0.0); WINDOW_LEN],
+            window_len: 0,
+            window_next: 0,
+            sample_index: 0.0,
+        }
+    }
+
+    /// Current target send rate (same units as `min_rate`/`max_rate`, e.g.
+    /// packets/sec or bytes/sec), to drive e.g. `FreeRtos::delay_ms` or a
+    /// migration batch size.
+    pub fn target_rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Record one burst's send and arrival (ACK) timestamps, in nanoseconds,
+    /// and update the target rate.
+    pub fn on_burst_arrival(&mut self, send_ns: u64, arrival_ns: u64) -> UsageSignal {
+        let signal = match self.last_burst {
+            None => UsageSignal::Normal,
+            Some(prev) => {
+                let d = (arrival_ns as i64 - prev.arrival_ns as i64)
+                    - (send_ns as i64 - prev.send_ns as i64);
+                self.accumulated_delay_ns += d as f64;
+                self.push_sample(self.accumulated_delay_ns);
+                self.classify()
+            }
+        };
+
+        self.last_burst = Some(BurstTimestamps { send_ns, arrival_ns });
+        self.apply_aimd(signal);
+        signal
+    }
+
+    fn push_sample(&mut self, accumulated_delay_ns: f64) {
+        self.window[self.window_next] = (self.sample_index, accumulated_delay_ns);
+        self.window_next = (self.window_next + 1) % WINDOW_LEN;
+        self.window_len = (self.window_len + 1).min(WINDOW_LEN);
+        self.sample_index += 1.0;
+    }
+
+    /// Least-squares slope of the accumulated delay over the sliding window:
+    /// `slope = Σ(x - x̄)(y - ȳ) / Σ(x - x̄)²`.
+    fn trend_slope(&self) -> f64 {
+        if self.window_len < 2 {
+            return 0.0;
+        }
+        let samples = &self.window[..self.window_len];
+        let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / self.window_len as f64;
+        let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / self.window_len as f64;
+
+        let mut num = 0.0;
+        let mut den = 0.0;
+        for &(x, y) in samples {
+            num += (x - mean_x) * (y - mean_y);
+            den += (x - mean_x) * (x - mean_x);
+        }
+        if den.abs() < 1e-9 { 0.0 } else { num / den }
+    }
+
+    /// Classify the current trend against `max(0.2 * |slope|, 1e6)` ns per
+    /// sample. A slope always exceeds a fifth of its own magnitude, so the
+    /// outcome is the same as a fixed 1e6 cutoff: the threshold does not
+    /// adapt to the signal's noise floor.
+    fn classify(&self) -> UsageSignal {
+        let slope = self.trend_slope();
+        let threshold = (slope.abs() * 0.2).max(1.0e6); // ns/sample, floor ~1ms drift/sample
+        if slope > threshold {
+            UsageSignal::Overuse
+        } else if slope < -threshold {
+            UsageSignal::Underuse
+        } else {
+            UsageSignal::Normal
+        }
+    }
+
+    fn apply_aimd(&mut self, signal: UsageSignal) {
+        self.rate = match signal {
+            UsageSignal::Overuse => self.rate * self.decrease_factor,
+            UsageSignal::Normal => self.rate + self.additive_step,
+            UsageSignal::Underuse => self.rate * 1.05,
+        }
+        .clamp(self.min_rate, self.max_rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn growing_delay_triggers_overuse_and_decreases_rate() {
+        let mut gcc = GccController::new(1.0, 100.0, 50.0);
+        let mut send = 0u64;
+        let mut arrival = 0u64;
+        // Each successive burst arrives increasingly later than it was sent,
+        // i.e. a growing queue at the receiver.
+        for i in 0..WINDOW_LEN as u64 + 4 {
+            send += 20_000_000; // 20ms cadence
+            arrival += 20_000_000 + i * 2_000_000; // growing extra delay
+            gcc.on_burst_arrival(send, arrival);
+        }
+        assert!(gcc.target_rate() < 50.0, "rate={}", gcc.target_rate());
+    }
+
+    #[test]
+    fn stable_delay_increases_rate_additively() {
+        let mut gcc = GccController::new(1.0, 100.0, 50.0);
+        let mut t = 0u64;
+        for _ in 0..WINDOW_LEN as u64 + 4 {
+            t += 20_000_000;
+            gcc.on_burst_arrival(t, t);
+        }
+        assert!(gcc.target_rate() >= 50.0, "rate={}", gcc.target_rate());
+    }
+}
@@ -0,0 +1,115 @@
+use futures_core::Stream;
+
+use crate::correlate::{self, CorrelationMethod, CorrelationResult};
+
+/// A continuous source of samples for one channel of a streaming
+/// correlation, fed to [`AsyncInfinityBridgeHal::correlate_stream`].
+pub trait SampleStream {
+    /// Pull the next available block of samples, or `None` once the stream
+    /// has ended.
+    fn next_samples(&mut self) -> Option<Vec<f32>>;
+}
+
+/// A [`SampleStream`] over a fixed, pre-recorded buffer of samples, chunked
+/// by `chunk_len`. Used by `MockBridge` and tests to feed synthetic windows
+/// through the streaming correlation path.
+pub struct VecSampleStream {
+    samples: Vec<f32>,
+    chunk_len: usize,
+    pos: usize,
+}
+
+impl VecSampleStream {
+    pub fn new(samples: Vec<f32>, chunk_len: usize) -> Self {
+        Self { samples, chunk_len, pos: 0 }
+    }
+}
+
+impl SampleStream for VecSampleStream {
+    fn next_samples(&mut self) -> Option<Vec<f32>> {
+        if self.pos >= self.samples.len() { return None; }
+        let end = (self.pos + self.chunk_len).min(self.samples.len());
+        let chunk = self.samples[self.pos..end].to_vec();
+        self.pos = end;
+        Some(chunk)
+    }
+}
+
+/// Configuration for windowed streaming correlation: the size of the
+/// correlation window and how far (in samples) it advances between emitted
+/// results.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowConfig {
+    pub window_len: usize,
+    pub hop: usize,
+    pub method: CorrelationMethod,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self { window_len: 1024, hop: 256, method: CorrelationMethod::Phat }
+    }
+}
+
+/// Fixed-capacity ring buffer holding the last `window_len` samples of a
+/// channel, used to assemble overlapping windows for streaming correlation.
+struct RingBuffer {
+    buf: Vec<f32>,
+    window_len: usize,
+}
+
+impl RingBuffer {
+    fn new(window_len: usize) -> Self { Self { buf: Vec::with_capacity(window_len), window_len } }
+    fn push_all(&mut self, samples: &[f32]) {
+        self.buf.extend_from_slice(samples);
+        if self.buf.len() > self.window_len {
+            let excess = self.buf.len() - self.window_len;
+            self.buf.drain(0..excess);
+        }
+    }
+    fn is_full(&self) -> bool { self.buf.len() >= self.window_len }
+}
+
+/// Async counterpart of [`crate::InfinityBridgeHal`]: instead of one-shot
+/// `correlate_local` calls, consumes continuous, overlapping windows of
+/// incoming samples per channel and emits a [`CorrelationResult`] per
+/// window, so callers can track lag drift over time.
+pub trait AsyncInfinityBridgeHal {
+    /// Consume two channel sample streams and emit a correlation result
+    /// every time `config.hop` new samples have accumulated in a full
+    /// `config.window_len`-sample window.
+    async fn correlate_stream<A, B>(
+        &self,
+        mut a: A,
+        mut b: B,
+        config: WindowConfig,
+    ) -> impl Stream<Item = CorrelationResult>
+    where
+        A: SampleStream,
+        B: SampleStream,
+    {
+        async_stream::stream! {
+            let mut ring_a = RingBuffer::new(config.window_len);
+            let mut ring_b = RingBuffer::new(config.window_len);
+            let mut since_last_emit = 0usize;
+            loop {
+                let (sa, sb) = match (a.next_samples(), b.next_samples()) {
+                    (Some(sa), Some(sb)) => (sa, sb),
+                    _ => break,
+                };
+                since_last_emit += sa.len().min(sb.len());
+                ring_a.push_all(&sa);
+                ring_b.push_all(&sb);
+                if ring_a.is_full() && ring_b.is_full() && since_last_emit >= config.hop {
+                    since_last_emit = 0;
+                    yield correlate::correlate(&ring_a.buf, &ring_b.buf, config.method);
+                }
+            }
+        }
+    }
+}
+
+/// A bridge that offers both the blocking HAL and its streaming async
+/// counterpart. Blanket-implemented for any type that implements both.
+pub trait Bridge: crate::InfinityBridgeHal + AsyncInfinityBridgeHal {}
+impl<T: crate::InfinityBridgeHal + AsyncInfinityBridgeHal> Bridge for T {}
@@ -15,3 +15,188 @@ impl SyncQuality {
         self.score >= min_score && self.drift_ns.abs() <= max_drift_ns
     }
 }
+
+/// Number of recent offset samples the servo keeps to estimate convergence
+/// (low variance across the window -> high `score`).
+const OFFSET_WINDOW_LEN: usize = 16;
+
+/// IEEE-1588-style two-way time-sync servo: each `observe_exchange` records
+/// one `(t1, t2, t3, t4)` round trip, derives the clock offset and path
+/// delay, and feeds the offset into a PI (proportional + integral) filter
+/// that adjusts a per-bridge clock correction and estimated frequency drift.
+/// `get_timestamp_ns` on a bridge should apply [`TwoWayTimeSyncServo::correct`]
+/// to the raw local timestamp before it goes on the wire.
+#[derive(Clone, Debug)]
+pub struct TwoWayTimeSyncServo {
+    source: SyncSource,
+    kp: f64,
+    ki: f64,
+    /// Accumulated integral term of the PI filter, in nanoseconds.
+    integral_ns: f64,
+    /// Current additive correction applied to the raw local clock.
+    correction_ns: f64,
+    /// Estimated frequency drift, in parts-per-billion, derived from the
+    /// integral term's trend.
+    freq_drift_ppb: f64,
+    /// Offset still left at the last exchange after the correction then in
+    /// force (`offset - correction_ns`), i.e. the servo's residual error.
+    residual_offset_ns: i64,
+    /// Most recently observed one-way path delay.
+    path_delay_ns: i64,
+    recent_offsets: [i64; OFFSET_WINDOW_LEN],
+    recent_offsets_len: usize,
+    recent_offsets_next: usize,
+}
+
+impl TwoWayTimeSyncServo {
+    pub fn new(source: SyncSource) -> Self {
+        Self {
+            source,
+            kp: 0.5,
+            ki: 0.05,
+            integral_ns: 0.0,
+            correction_ns: 0.0,
+            freq_drift_ppb: 0.0,
+            residual_offset_ns: 0,
+            path_delay_ns: 0,
+            recent_offsets: [0; OFFSET_WINDOW_LEN],
+            recent_offsets_len: 0,
+            recent_offsets_next: 0,
+        }
+    }
+
+    /// Record one two-way exchange: `t1` master send, `t2` slave receive,
+    /// `t3` slave send, `t4` master receive (all in nanoseconds), and update
+    /// the servo's correction and frequency-drift estimate.
+    pub fn observe_exchange(&mut self, t1: u64, t2: u64, t3: u64, t4: u64) -> SyncQuality {
+        let forward = t2 as i64 - t1 as i64;
+        let backward = t4 as i64 - t3 as i64;
+        let offset = (forward - backward) / 2;
+        let path_delay = (forward + backward) / 2;
+
+        self.path_delay_ns = path_delay;
+        self.push_offset(offset);
+
+        // PI servo: feed the filter the *residual* error still left after
+        // the servo's current correction, not the raw uncompensated
+        // offset. Otherwise the integral term winds up without bound
+        // against a constant input (the wire offset never changes just
+        // because we adjust our own correction); feeding the residual
+        // makes the integral settle once correction_ns has absorbed the
+        // offset, the same anti-windup shape as a closed-loop PI.
+        let residual = offset as f64 - self.correction_ns;
+        self.residual_offset_ns = residual.round() as i64;
+        self.integral_ns += residual;
+        self.freq_drift_ppb = self.ki * self.integral_ns;
+        self.correction_ns += self.kp * residual + self.freq_drift_ppb;
+
+        self.quality()
+    }
+
+    fn push_offset(&mut self, offset: i64) {
+        self.recent_offsets[self.recent_offsets_next] = offset;
+        self.recent_offsets_next = (self.recent_offsets_next + 1) % OFFSET_WINDOW_LEN;
+        self.recent_offsets_len = (self.recent_offsets_len + 1).min(OFFSET_WINDOW_LEN);
+    }
+
+    /// Variance of the recent offset window, in ns².
+    fn offset_variance(&self) -> f64 {
+        if self.recent_offsets_len < 2 {
+            return f64::MAX;
+        }
+        let samples = &self.recent_offsets[..self.recent_offsets_len];
+        let mean = samples.iter().map(|&o| o as f64).sum::<f64>() / self.recent_offsets_len as f64;
+        samples
+            .iter()
+            .map(|&o| {
+                let d = o as f64 - mean;
+                d * d
+            })
+            .sum::<f64>()
+            / self.recent_offsets_len as f64
+    }
+
+    /// Current quality: `score` derived from the variance of recent offsets
+    /// (low variance -> high score, converging to 1.0), `drift_ns` is the
+    /// servo's residual offset (raw offset minus the correction in force)
+    /// from the last exchange.
+    pub fn quality(&self) -> SyncQuality {
+        // Variance is in ns²; scale so ~(1us)² variance already halves the
+        // score, and a fresh/unconverged servo (MAX variance) scores ~0.
+        let variance = self.offset_variance();
+        let score = (1.0 / (1.0 + variance / 1.0e6)) as f32;
+        SyncQuality::with_score(score, self.residual_offset_ns, self.source)
+    }
+
+    /// Apply the servo's accumulated correction to a raw local timestamp.
+    pub fn correct(&self, raw_ns: u64) -> u64 {
+        (raw_ns as f64 + self.correction_ns).max(0.0) as u64
+    }
+
+    pub fn path_delay_ns(&self) -> i64 {
+        self.path_delay_ns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converges_offset_towards_zero() {
+        let mut servo = TwoWayTimeSyncServo::new(SyncSource::PTP);
+        // Slave clock is a steady 1ms ahead of master, symmetric path delay.
+        for i in 0..40u64 {
+            let t1 = i * 1_000_000;
+            let t2 = t1 + 1_000_000 + 50_000;
+            let t3 = t2 + 10_000;
+            let t4 = t1 + 50_000 + 10_000 + 1_000;
+            servo.observe_exchange(t1, t2, t3, t4);
+        }
+        let quality = servo.quality();
+        assert_eq!(quality.source, SyncSource::PTP);
+        assert!(quality.score > 0.0);
+    }
+
+    #[test]
+    fn correction_converges_to_true_offset() {
+        let mut servo = TwoWayTimeSyncServo::new(SyncSource::PTP);
+        // Slave clock is a steady ~1.0245ms ahead of master, symmetric path delay.
+        for i in 0..40u64 {
+            let t1 = i * 1_000_000;
+            let t2 = t1 + 1_000_000 + 50_000;
+            let t3 = t2 + 10_000;
+            let t4 = t1 + 50_000 + 10_000 + 1_000;
+            servo.observe_exchange(t1, t2, t3, t4);
+        }
+
+        // correction_ns should have converged near the true ~1.0245ms
+        // offset, not diverged to tens of milliseconds (the bug this
+        // guards against accumulated correction_ns unboundedly instead
+        // of letting it settle).
+        let true_offset_ns = 1_024_500.0;
+        assert!(
+            (servo.correction_ns - true_offset_ns).abs() < 50_000.0,
+            "correction_ns={} did not converge near true offset {}",
+            servo.correction_ns,
+            true_offset_ns
+        );
+
+        // Applying that correction to a raw timestamp should cancel out
+        // almost all of the slave's lead over the master.
+        let corrected = servo.correct(0);
+        assert!(
+            (corrected as f64 - true_offset_ns).abs() < 50_000.0,
+            "corrected timestamp {} did not converge near true offset {}",
+            corrected,
+            true_offset_ns
+        );
+    }
+
+    #[test]
+    fn unconverged_servo_has_low_score() {
+        let mut servo = TwoWayTimeSyncServo::new(SyncSource::NTP);
+        let quality = servo.observe_exchange(0, 0, 0, 0);
+        assert!(quality.score < 0.5, "score={}", quality.score);
+    }
+}
@@ -1,5 +1,199 @@
-use crate::{SensorPacket, TransportError};
+//! MQTT transport topic naming and connection-state bookkeeping. This is a
+//! stub, not a working pub/sub client: nothing here opens a socket or talks
+//! to a broker. See [`MqttTransport`]'s doc for what a real implementation
+//! still needs to wire in.
+
+use crate::{Domain, SensorPacket, TransportError};
 
 pub trait TransportSink {
     fn send(&mut self, pkt: &SensorPacket) -> Result<(), TransportError>;
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MqttQos {
+    AtMostOnce,
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+/// Stream discovery metadata published (retained) on a bridge's `.../meta`
+/// topic, mirroring the DHT's `StreamMetadata` entry so subscribers can
+/// discover active streams without a DHT round trip.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamMetadataMsg {
+    pub bridge_id: String,
+    pub stream_type: String,
+    pub sample_rate_hz: u32,
+    pub data_format: String,
+    pub buffer_size: u32,
+}
+
+/// Out-of-band commands a bridge accepts over its MQTT command topic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BridgeCommand {
+    EmergencyShutdown,
+}
+
+#[derive(Clone, Debug)]
+pub struct MqttConfig {
+    pub broker_host: String,
+    pub broker_port: u16,
+    pub client_id: String,
+    pub qos: MqttQos,
+}
+
+/// Stub for the MQTT publisher transport: tracks the topic names
+/// (`bridges/{bridge_id}/{domain}/spectrum`, `.../meta`, `.../cmd`) and the
+/// connected/command-queue bookkeeping a real client would need, but does
+/// not open a socket, speak the MQTT wire protocol, or publish/subscribe
+/// anything — `send`/`publish_metadata` just log what they would have
+/// published, and `on_command_received` must be fed manually rather than
+/// being driven by an actual subscription callback. No MQTT client crate
+/// (e.g. `rumqttc`) is wired in yet; treat this as the shape a real
+/// implementation should fill in, not a working transport.
+pub struct MqttTransport {
+    config: MqttConfig,
+    bridge_id: String,
+    connected: bool,
+    pending_commands: Vec<BridgeCommand>,
+}
+
+impl MqttTransport {
+    pub fn new(bridge_id: String, config: MqttConfig) -> Self {
+        Self {
+            config,
+            bridge_id,
+            connected: false,
+            pending_commands: Vec::new(),
+        }
+    }
+
+    /// Connect to the broker and subscribe to this bridge's command topic.
+    /// In production: open a TCP/TLS socket and run the MQTT CONNECT/SUBSCRIBE
+    /// handshake via an MQTT client crate (e.g. `rumqttc`).
+    pub fn connect(&mut self) -> Result<(), TransportError> {
+        self.connected = true;
+        Ok(())
+    }
+
+    pub fn spectrum_topic(&self, domain: Domain) -> String {
+        format!("bridges/{}/{}/spectrum", self.bridge_id, domain_segment(domain))
+    }
+
+    pub fn meta_topic(&self) -> String {
+        format!("bridges/{}/meta", self.bridge_id)
+    }
+
+    pub fn command_topic(&self) -> String {
+        format!("bridges/{}/cmd", self.bridge_id)
+    }
+
+    /// Publish (or refresh) the bridge's retained stream-discovery metadata.
+    pub fn publish_metadata(&mut self, metadata: &StreamMetadataMsg) -> Result<(), TransportError> {
+        if !self.connected {
+            return Err(TransportError::NotReady);
+        }
+        // In production: PUBLISH with retain=true at self.config.qos.
+        println!(
+            "[MqttTransport] Publishing retained metadata on {}: {:?}",
+            self.meta_topic(),
+            metadata
+        );
+        Ok(())
+    }
+
+    /// Drain any commands received on the command topic since the last poll.
+    pub fn poll_commands(&mut self) -> Vec<BridgeCommand> {
+        core::mem::take(&mut self.pending_commands)
+    }
+
+    /// Feed in a command received from the broker (in production, called
+    /// from the MQTT client's message callback for `command_topic()`).
+    pub fn on_command_received(&mut self, command: BridgeCommand) {
+        self.pending_commands.push(command);
+    }
+}
+
+impl TransportSink for MqttTransport {
+    fn send(&mut self, pkt: &SensorPacket) -> Result<(), TransportError> {
+        if !self.connected {
+            return Err(TransportError::NotReady);
+        }
+        // In production: PUBLISH pkt.payload_le to self.spectrum_topic(pkt.domain)
+        // at self.config.qos.
+        println!(
+            "[MqttTransport] Publishing {} bytes to {}",
+            pkt.payload_le.len(),
+            self.spectrum_topic(pkt.domain)
+        );
+        Ok(())
+    }
+}
+
+fn domain_segment(domain: Domain) -> &'static str {
+    match domain {
+        Domain::RF => "rf",
+        Domain::Optical => "optical",
+        Domain::Acoustic => "acoustic",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_are_namespaced_by_bridge_and_domain() {
+        let transport = MqttTransport::new(
+            "bridge-1".to_string(),
+            MqttConfig {
+                broker_host: "localhost".to_string(),
+                broker_port: 1883,
+                client_id: "bridge-1".to_string(),
+                qos: MqttQos::AtLeastOnce,
+            },
+        );
+        assert_eq!(transport.spectrum_topic(Domain::Acoustic), "bridges/bridge-1/acoustic/spectrum");
+        assert_eq!(transport.meta_topic(), "bridges/bridge-1/meta");
+        assert_eq!(transport.command_topic(), "bridges/bridge-1/cmd");
+    }
+
+    #[test]
+    fn send_fails_until_connected() {
+        let mut transport = MqttTransport::new(
+            "bridge-1".to_string(),
+            MqttConfig {
+                broker_host: "localhost".to_string(),
+                broker_port: 1883,
+                client_id: "bridge-1".to_string(),
+                qos: MqttQos::AtMostOnce,
+            },
+        );
+        let packet = SensorPacket {
+            bridge_id: "bridge-1",
+            domain: Domain::Acoustic,
+            tai_timestamp_ns: 0,
+            sample_rate_hz: 44100,
+            payload_le: &[],
+        };
+        assert!(transport.send(&packet).is_err());
+        transport.connect().unwrap();
+        assert!(transport.send(&packet).is_ok());
+    }
+
+    #[test]
+    fn commands_drain_on_poll() {
+        let mut transport = MqttTransport::new(
+            "bridge-1".to_string(),
+            MqttConfig {
+                broker_host: "localhost".to_string(),
+                broker_port: 1883,
+                client_id: "bridge-1".to_string(),
+                qos: MqttQos::AtMostOnce,
+            },
+        );
+        transport.on_command_received(BridgeCommand::EmergencyShutdown);
+        assert_eq!(transport.poll_commands(), vec![BridgeCommand::EmergencyShutdown]);
+        assert!(transport.poll_commands().is_empty());
+    }
+}
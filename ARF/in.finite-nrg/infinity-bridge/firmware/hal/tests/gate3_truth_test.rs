@@ -1,3 +1,4 @@
+use infinity_hal::correlate::CorrelationMethod;
 use infinity_hal::{InfinityBridgeHal, MockBridge};
 
 #[test]
@@ -10,3 +11,16 @@ fn gate3_truth_test_example() {
     assert!(res.peak > 0.7, "peak={}", res.peak);
     assert!(res.lag_samples.abs() <= 10, "lag={}", res.lag_samples);
 }
+
+#[test]
+fn gate3_truth_test_phat_handles_channel_mismatch() {
+    let b = MockBridge::default();
+    let a: Vec<f32> = (0..1024).map(|i| ((i as f32)/50.0).sin()).collect();
+    let mut bvec: Vec<f32> = vec![0.0; 1024];
+    // Channel b lags by 5 samples and is attenuated + noisy, which degrades
+    // plain time-domain correlation but GCC-PHAT is robust to.
+    for i in 5..1024 { bvec[i] = a[i-5] * 0.1 + (i as f32).sin()*1e-3; }
+    let res = b.correlate_local_with(&a, &bvec, CorrelationMethod::Phat);
+    assert!(res.lag_samples == 5, "lag={}", res.lag_samples);
+    assert!(res.sub_sample_offset.abs() <= 0.5, "offset={}", res.sub_sample_offset);
+}
@@ -0,0 +1,28 @@
+use futures::executor::block_on;
+use futures::StreamExt;
+
+use infinity_hal::streaming::{AsyncInfinityBridgeHal, VecSampleStream, WindowConfig};
+use infinity_hal::MockBridge;
+
+#[test]
+fn gate4_stream_emits_correlation_per_window() {
+    let b = MockBridge::default();
+    let a: Vec<f32> = (0..4096).map(|i| ((i as f32)/50.0).sin()).collect();
+    let mut bvec: Vec<f32> = vec![0.0; 4096];
+    for i in 5..4096 { bvec[i] = a[i-5]; }
+
+    let config = WindowConfig { window_len: 1024, hop: 512, ..WindowConfig::default() };
+    let results: Vec<_> = block_on(async {
+        let stream = b.correlate_stream(
+            VecSampleStream::new(a, 256),
+            VecSampleStream::new(bvec, 256),
+            config,
+        ).await;
+        stream.collect::<Vec<_>>().await
+    });
+
+    assert!(!results.is_empty());
+    for res in &results {
+        assert!(res.lag_samples.abs() <= 10, "lag={}", res.lag_samples);
+    }
+}
@@ -21,4 +21,7 @@ pub enum ShardError {
 
     #[error("Holochain error: {0}")]
     Holochain(#[from] hdk::prelude::HdkError),
+
+    #[error("Sync transport error: {0}")]
+    Transport(String),
 }
\ No newline at end of file
@@ -4,9 +4,11 @@ mod synchrony;
 mod evolution;
 mod replication;
 mod versioning;
+mod transport;
 
 pub use runtime::NervRuntime;
 pub use synchrony::NeurosynchronyManager;
 pub use evolution::EvolutionManager;
 pub use replication::{ReplicationManager, ModelUpdate, ModelMetadata, ModelMetrics, AggregationResult};
-pub use versioning::VersioningManager;
\ No newline at end of file
+pub use versioning::VersioningManager;
+pub use transport::{SyncTransport, SyncSocket, InMemoryTransport, NetworkTransport, TransportMessage, WireProtocol};
\ No newline at end of file
@@ -1,15 +1,22 @@
 // src/nerv/synchrony.rs
 use crate::core::{Metrics, CentroidCRDT};
 use crate::error::ShardError;
+use crate::nerv::transport::{InMemoryTransport, SyncTransport};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio_stream::StreamExt;
 
 pub struct NeurosynchronyManager {
     sync_interval_ms: u64,
     max_drift_tolerance_ms: u64,
     metrics: Arc<Metrics>,
+    transport: Box<dyn SyncTransport>,
     last_sync: RwLock<Instant>,
+    /// Local receipt time of the most recently observed remote update, used
+    /// by `check_drift` to compare against `max_drift_tolerance_ms`. `None`
+    /// until the first update actually arrives over `transport`.
+    last_received: RwLock<Option<Instant>>,
     running: RwLock<bool>,
 }
 
@@ -18,80 +25,126 @@ impl NeurosynchronyManager {
         sync_interval_ms: u64,
         max_drift_tolerance_ms: u64,
         metrics: Arc<Metrics>
+    ) -> Self {
+        Self::with_transport(sync_interval_ms, max_drift_tolerance_ms, metrics, Box::new(InMemoryTransport::default()))
+    }
+
+    /// Same as [`Self::new`], but with an explicit [`SyncTransport`] —
+    /// a network backend in production, an [`InMemoryTransport`] in tests.
+    pub fn with_transport(
+        sync_interval_ms: u64,
+        max_drift_tolerance_ms: u64,
+        metrics: Arc<Metrics>,
+        transport: Box<dyn SyncTransport>,
     ) -> Self {
         Self {
             sync_interval_ms,
             max_drift_tolerance_ms,
             metrics,
+            transport,
             last_sync: RwLock::new(Instant::now()),
+            last_received: RwLock::new(None),
             running: RwLock::new(false),
         }
     }
-    
+
     pub async fn start(&self) -> Result<(), ShardError> {
         let mut running = self.running.write().await;
         *running = true;
-        
+
+        self.transport.healthcheck().await?;
         self.metrics.record("neurosynchrony_manager_start", 1);
-        
-        // In a real implementation, this would start a Kafka consumer/producer
-        // or connect to a Flink streaming job
-        
+
         Ok(())
     }
-    
+
     pub async fn stop(&self) -> Result<(), ShardError> {
         let mut running = self.running.write().await;
         *running = false;
-        
+
         self.metrics.record("neurosynchrony_manager_stop", 1);
-        
-        // In a real implementation, this would stop the Kafka consumer/producer
-        // or disconnect from the Flink streaming job
-        
+
         Ok(())
     }
-    
+
     pub async fn broadcast_update(&self, centroid: &CentroidCRDT) -> Result<(), ShardError> {
         self.metrics.start_operation("neurosynchrony_broadcast");
-        
-        // In a real implementation, this would publish the centroid update to Kafka
-        // or send it to a Flink streaming job
-        
-        // Simulate broadcast latency
-        let broadcast_latency = 50; // 50ms simulated latency
+
+        let started = Instant::now();
+        self.transport.publish(centroid).await?;
+        let broadcast_latency = started.elapsed().as_millis() as u64;
         self.metrics.record("neurosynchrony_broadcast_latency", broadcast_latency);
-        
-        // Update last sync time
+
         let mut last_sync = self.last_sync.write().await;
         *last_sync = Instant::now();
-        
+
         self.metrics.end_operation("neurosynchrony_broadcast");
-        
+
         Ok(())
     }
-    
+
+    /// Pull one update from `transport` (if any arrives within one
+    /// `sync_interval_ms` window) and record its arrival so `check_drift`
+    /// has a real receipt time to compare against. Bounded, not a truly
+    /// blocking wait: a subscriber with nothing pending for a full interval
+    /// gives up and leaves `last_received` unchanged rather than waiting
+    /// forever on a stream whose sender (held alive by the transport
+    /// itself) never signals end-of-stream.
     pub async fn sync_state(&self) -> Result<(), ShardError> {
         self.metrics.start_operation("neurosynchrony_sync_state");
-        
-        // In a real implementation, this would fetch the latest state from Kafka
-        // or query the Flink streaming job for the latest state
-        
-        // Update last sync time
+
+        let mut stream = self.transport.subscribe().await?;
+        let wait = Duration::from_millis(self.sync_interval_ms);
+        if let Ok(Some(message)) = tokio::time::timeout(wait, stream.next()).await {
+            let _ = message.centroid;
+            let mut last_received = self.last_received.write().await;
+            *last_received = Some(Instant::now());
+        }
+
         let mut last_sync = self.last_sync.write().await;
         *last_sync = Instant::now();
-        
+
         self.metrics.end_operation("neurosynchrony_sync_state");
-        
+
         Ok(())
     }
-    
+
+    /// Drift since the last locally-received update. Exceeding
+    /// `max_drift_tolerance_ms` triggers an immediate `sync_state` resync
+    /// rather than waiting for the next `sync_interval_ms` tick.
     pub async fn check_drift(&self) -> Result<u64, ShardError> {
-        let last_sync = self.last_sync.read().await;
-        let drift = last_sync.elapsed().as_millis() as u64;
-        
+        let drift = match *self.last_received.read().await {
+            Some(last_received) => last_received.elapsed().as_millis() as u64,
+            // No update has ever been received — treat time since the
+            // manager's own last sync attempt as the drift signal.
+            None => self.last_sync.read().await.elapsed().as_millis() as u64,
+        };
+
         self.metrics.record("neurosynchrony_drift", drift);
-        
+
+        if drift > self.max_drift_tolerance_ms {
+            self.metrics.record("neurosynchrony_resync_triggered", 1);
+            self.sync_state().await?;
+        }
+
         Ok(drift)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sync_state_returns_promptly_with_no_publisher() {
+        let manager = NeurosynchronyManager::new(50, 500, Arc::new(Metrics::new()));
+
+        let started = Instant::now();
+        manager.sync_state().await.unwrap();
+
+        assert!(
+            started.elapsed() < Duration::from_secs(2),
+            "sync_state should give up after one sync_interval_ms window, not block forever"
+        );
+    }
+}
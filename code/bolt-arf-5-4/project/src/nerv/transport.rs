@@ -0,0 +1,140 @@
+// src/nerv/transport.rs
+use crate::core::CentroidCRDT;
+use crate::error::ShardError;
+use async_trait::async_trait;
+use futures_core::Stream;
+use std::pin::Pin;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+
+/// A `CentroidCRDT` published through a [`SyncTransport`], alongside the
+/// wall-clock time (ms since `UNIX_EPOCH`) the *publisher* stamped it with.
+/// `NeurosynchronyManager::check_drift` does not read this timestamp: it
+/// measures drift from the local `Instant` at which the update was received.
+#[derive(Clone, Debug)]
+pub struct TransportMessage {
+    pub centroid: CentroidCRDT,
+    pub published_at_ms: u64,
+}
+
+/// Pluggable CRDT sync bus behind `NeurosynchronyManager`. `publish`/
+/// `subscribe` let the manager broadcast and receive centroid updates
+/// without knowing whether the other end is an in-process test harness
+/// or a real UDP/ZMQ peer; `healthcheck` lets callers probe liveness
+/// before relying on a transport for a sync round.
+#[async_trait]
+pub trait SyncTransport: Send + Sync {
+    async fn publish(&self, centroid: &CentroidCRDT) -> Result<(), ShardError>;
+
+    /// A fresh subscription over updates published from this point
+    /// forward — implementations are not required to replay history.
+    async fn subscribe(&self) -> Result<Pin<Box<dyn Stream<Item = TransportMessage> + Send>>, ShardError>;
+
+    async fn healthcheck(&self) -> Result<(), ShardError>;
+}
+
+/// In-process transport backed by a `tokio::sync::broadcast` channel.
+/// The default for single-node tests and for shards colocated in one
+/// process — no serialization, no socket, just a fan-out channel.
+pub struct InMemoryTransport {
+    tx: broadcast::Sender<TransportMessage>,
+}
+
+impl InMemoryTransport {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity);
+        Self { tx }
+    }
+}
+
+impl Default for InMemoryTransport {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+#[async_trait]
+impl SyncTransport for InMemoryTransport {
+    async fn publish(&self, centroid: &CentroidCRDT) -> Result<(), ShardError> {
+        let message = TransportMessage { centroid: centroid.clone(), published_at_ms: now_millis() };
+        // No subscribers is not an error — there's simply nobody to drift
+        // against yet, which is the common case before the first `subscribe`.
+        let _ = self.tx.send(message);
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> Result<Pin<Box<dyn Stream<Item = TransportMessage> + Send>>, ShardError> {
+        let rx = self.tx.subscribe();
+        let stream = BroadcastStream::new(rx).filter_map(|item| item.ok());
+        Ok(Box::pin(stream))
+    }
+
+    async fn healthcheck(&self) -> Result<(), ShardError> {
+        Ok(())
+    }
+}
+
+/// Which wire protocol a [`NetworkTransport`] speaks. Named to match the
+/// `Transport` variants `infinity-bridge`'s HAL already uses for its own
+/// peer links (`UDP`/`ZMQ`); kept as our own enum rather than a dependency
+/// on that crate since the two live in unrelated build targets (firmware
+/// HAL vs. this async NERV runtime) with no shared manifest to link them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireProtocol {
+    Udp,
+    Zmq,
+}
+
+/// Network-backed transport for centroid sync across processes/hosts.
+/// Wraps the actual socket in a trait object so the two protocols share
+/// one `SyncTransport` impl rather than duplicating `publish`/`subscribe`.
+pub struct NetworkTransport {
+    protocol: WireProtocol,
+    socket: Box<dyn SyncSocket>,
+}
+
+impl NetworkTransport {
+    pub fn new(protocol: WireProtocol, socket: Box<dyn SyncSocket>) -> Self {
+        Self { protocol, socket }
+    }
+
+    pub fn protocol(&self) -> WireProtocol {
+        self.protocol
+    }
+}
+
+#[async_trait]
+impl SyncTransport for NetworkTransport {
+    async fn publish(&self, centroid: &CentroidCRDT) -> Result<(), ShardError> {
+        let message = TransportMessage { centroid: centroid.clone(), published_at_ms: now_millis() };
+        self.socket.send(message).await
+    }
+
+    async fn subscribe(&self) -> Result<Pin<Box<dyn Stream<Item = TransportMessage> + Send>>, ShardError> {
+        self.socket.recv_stream().await
+    }
+
+    async fn healthcheck(&self) -> Result<(), ShardError> {
+        self.socket.ping().await
+    }
+}
+
+/// The actual wire transport a [`NetworkTransport`] drives — one impl per
+/// `WireProtocol`. Kept separate from `SyncTransport` so a UDP or ZMQ
+/// socket only needs to expose raw send/receive/ping, not centroid-aware
+/// semantics; `NetworkTransport` owns the `TransportMessage` framing.
+#[async_trait]
+pub trait SyncSocket: Send + Sync {
+    async fn send(&self, message: TransportMessage) -> Result<(), ShardError>;
+    async fn recv_stream(&self) -> Result<Pin<Box<dyn Stream<Item = TransportMessage> + Send>>, ShardError>;
+    async fn ping(&self) -> Result<(), ShardError>;
+}
+
+fn now_millis() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,200 @@
+//! Byzantine-robust aggregation strategies for `aggregate_model_updates`.
+//!
+//! The plain uniform mean in `lib.rs::average_weights_and_bias` lets a
+//! single malicious `ModelUpdate` arbitrarily shift the global model and
+//! ignores how much data each client actually trained on. The strategies
+//! here give callers a choice of a sample-weighted mean or one of two
+//! outlier-resistant combiners, mirroring the richer `AggregationStrategy`
+//! already used by `nerv::replication::ReplicationManager`.
+
+use crate::ModelUpdate;
+use serde::{Deserialize, Serialize};
+
+/// How `aggregate_model_updates` should combine a round's updates.
+/// `Mean` (the historical behavior) stays the default so existing callers
+/// that don't pass a strategy see no change.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AggregationStrategy {
+    Mean,
+    /// Scale each update's weights/bias by `samples_count / total_samples`
+    /// before summing, so a client that trained on more data counts for
+    /// more than one that trained on a handful of samples.
+    WeightedFedAvg,
+    /// Per-coordinate: sort the submitted values, drop the lowest and
+    /// highest `beta` fraction, and average what's left.
+    TrimmedMean { beta: f32 },
+    /// Score every update by the sum of squared L2 distances to its
+    /// `n - f - 2` closest other updates (`f` = assumed Byzantine count),
+    /// then average the `m` lowest-scoring updates.
+    MultiKrum { f: usize, m: usize },
+}
+
+impl Default for AggregationStrategy {
+    fn default() -> Self {
+        AggregationStrategy::Mean
+    }
+}
+
+fn is_finite_update(update: &ModelUpdate) -> bool {
+    update.bias.is_finite() && update.weights.iter().all(|w| w.is_finite())
+}
+
+/// Drop any update carrying a NaN/Inf weight or bias rather than letting it
+/// poison every coordinate it touches — "rejecting the offending update",
+/// per the chunk12-2 request, not failing the whole round over one bad entry.
+fn reject_non_finite(updates: &[ModelUpdate]) -> Vec<&ModelUpdate> {
+    updates.iter().filter(|u| is_finite_update(u)).collect()
+}
+
+fn squared_l2_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Flatten `weights` followed by `bias` into one vector per update, the
+/// common shape the strategies below score and combine.
+fn updates_to_vectors(updates: &[&ModelUpdate]) -> Vec<Vec<f32>> {
+    updates
+        .iter()
+        .map(|u| {
+            let mut v = u.weights.clone();
+            v.push(u.bias);
+            v
+        })
+        .collect()
+}
+
+fn split_combined(combined: &[f32]) -> (Vec<f32>, f32) {
+    let width = combined.len() - 1;
+    (combined[..width].to_vec(), combined[width])
+}
+
+/// `samples_count / total_samples`-weighted FedAvg over the surviving
+/// (finite-valued) updates.
+fn weighted_fedavg(updates: &[&ModelUpdate]) -> Result<(Vec<f32>, f32), String> {
+    let width = updates[0].weights.len();
+    let total_samples: u64 = updates.iter().map(|u| u.metadata.metrics.samples_count as u64).sum();
+    if total_samples == 0 {
+        return Err("WeightedFedAvg requires at least one update with samples_count > 0".to_string());
+    }
+
+    let mut weights = vec![0.0f32; width];
+    let mut bias = 0.0f32;
+    for update in updates {
+        let share = update.metadata.metrics.samples_count as f32 / total_samples as f32;
+        for (w, value) in weights.iter_mut().zip(update.weights.iter()) {
+            *w += value * share;
+        }
+        bias += update.bias * share;
+    }
+    Ok((weights, bias))
+}
+
+/// Coordinate-wise trimmed mean: for each index, sort the values across
+/// every update, drop the lowest/highest `beta` fraction, and average the
+/// rest.
+fn coordinate_trimmed_mean(vectors: &[Vec<f32>], beta: f32) -> Vec<f32> {
+    let n = vectors.len();
+    let width = vectors[0].len();
+    // Clamp so `trim` never consumes the whole column, matching
+    // `nerv::replication::coordinate_trimmed_mean`'s guard.
+    let trim = ((n as f32 * beta.clamp(0.0, 0.5)).floor() as usize).min(n.saturating_sub(1) / 2);
+
+    (0..width)
+        .map(|coord| {
+            let mut column: Vec<f32> = vectors.iter().map(|v| v[coord]).collect();
+            column.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let kept = &column[trim..n - trim];
+            kept.iter().sum::<f32>() / kept.len() as f32
+        })
+        .collect()
+}
+
+/// Multi-Krum selection: score each update by the sum of squared distances
+/// to its `n - f - 2` nearest neighbors, then keep the `m` lowest-scoring
+/// indices.
+fn multi_krum_select(vectors: &[Vec<f32>], f: usize, m: usize) -> Vec<usize> {
+    let n = vectors.len();
+    let neighbors = n.saturating_sub(f + 2).max(1);
+
+    let mut scores: Vec<(usize, f32)> = (0..n)
+        .map(|i| {
+            let mut distances: Vec<f32> = (0..n).filter(|&j| j != i).map(|j| squared_l2_distance(&vectors[i], &vectors[j])).collect();
+            distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let score: f32 = distances.iter().take(neighbors).sum();
+            (i, score)
+        })
+        .collect();
+    scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    scores.into_iter().take(m.min(n)).map(|(i, _)| i).collect()
+}
+
+/// Combine `updates` under `strategy`, returning the aggregated
+/// `(weights, bias)` plus the indices (into `updates`) that actually fed
+/// the result — every survivor for `Mean`/`WeightedFedAvg`/`TrimmedMean`,
+/// or just the `m` Multi-Krum winners.
+pub fn aggregate(updates: &[ModelUpdate], strategy: AggregationStrategy) -> Result<(Vec<f32>, f32, Vec<usize>), String> {
+    if updates.is_empty() {
+        return Err("No updates to aggregate".to_string());
+    }
+    let width = updates[0].weights.len();
+    if updates.iter().any(|u| u.weights.len() != width) {
+        return Err("Mismatched weights length across updates".to_string());
+    }
+
+    let finite = reject_non_finite(updates);
+    if finite.is_empty() {
+        return Err("Every update had a non-finite weight or bias".to_string());
+    }
+
+    match strategy {
+        AggregationStrategy::Mean => {
+            let n = finite.len() as f32;
+            let mut weights = vec![0.0f32; width];
+            let mut bias = 0.0f32;
+            for update in &finite {
+                for (w, value) in weights.iter_mut().zip(update.weights.iter()) {
+                    *w += value;
+                }
+                bias += update.bias;
+            }
+            for w in weights.iter_mut() {
+                *w /= n;
+            }
+            bias /= n;
+            let indices = updates.iter().enumerate().filter(|(_, u)| is_finite_update(u)).map(|(i, _)| i).collect();
+            Ok((weights, bias, indices))
+        }
+        AggregationStrategy::WeightedFedAvg => {
+            let (weights, bias) = weighted_fedavg(&finite)?;
+            let indices = updates.iter().enumerate().filter(|(_, u)| is_finite_update(u)).map(|(i, _)| i).collect();
+            Ok((weights, bias, indices))
+        }
+        AggregationStrategy::TrimmedMean { beta } => {
+            let vectors = updates_to_vectors(&finite);
+            let combined = coordinate_trimmed_mean(&vectors, beta);
+            let (weights, bias) = split_combined(&combined);
+            let indices = updates.iter().enumerate().filter(|(_, u)| is_finite_update(u)).map(|(i, _)| i).collect();
+            Ok((weights, bias, indices))
+        }
+        AggregationStrategy::MultiKrum { f, m } => {
+            let n = finite.len();
+            if n <= 2 * f + 2 {
+                return Err(format!("Multi-Krum requires n > 2f+2 surviving updates (n={n}, f={f})"));
+            }
+            let vectors = updates_to_vectors(&finite);
+            let selected = multi_krum_select(&vectors, f, m);
+            let count = selected.len() as f32;
+            let mut combined = vec![0.0f32; width + 1];
+            for &idx in &selected {
+                for (c, v) in combined.iter_mut().zip(vectors[idx].iter()) {
+                    *c += v / count;
+                }
+            }
+            let (weights, bias) = split_combined(&combined);
+            // Map back from indices into `finite` to indices into `updates`.
+            let finite_original_indices: Vec<usize> = updates.iter().enumerate().filter(|(_, u)| is_finite_update(u)).map(|(i, _)| i).collect();
+            let indices = selected.into_iter().map(|i| finite_original_indices[i]).collect();
+            Ok((weights, bias, indices))
+        }
+    }
+}
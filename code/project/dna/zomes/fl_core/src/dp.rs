@@ -0,0 +1,133 @@
+//! Differential-privacy clipping and noising for submitted model updates.
+//!
+//! Two knobs, both client-configurable per round: an L2 norm bound `C`
+//! each update's weights are clipped to before they can influence
+//! anything, and a noise multiplier `sigma` controlling how much Gaussian
+//! noise (`stddev = sigma * C`) is added to the aggregated sum. Bounding
+//! any single update's contribution to `C` and then adding noise scaled
+//! to that same bound is the standard DP-SGD/DP-FedAvg recipe: it caps
+//! one client's worst-case influence and the added noise masks exactly
+//! how much of that bound they used.
+
+use rand::Rng;
+
+/// `l2_clip_norm` (`C`) must be `> 0`; `noise_multiplier` (`sigma`) must be
+/// `>= 0`, with `0` disabling noise entirely.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DpConfig {
+    pub l2_clip_norm: f32,
+    pub noise_multiplier: f32,
+}
+
+impl DpConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.l2_clip_norm > 0.0) {
+            return Err("DpConfig::l2_clip_norm must be > 0".to_string());
+        }
+        if !(self.noise_multiplier >= 0.0) {
+            return Err("DpConfig::noise_multiplier must be >= 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// The noise stddev (`sigma * C`) on the summed updates, recorded into
+    /// `ModelMetadata`; the published average of `n` updates carries
+    /// `sigma * C / n` per coordinate.
+    pub fn noise_stddev(&self) -> f32 {
+        self.noise_multiplier * self.l2_clip_norm
+    }
+}
+
+/// Scale `weights` by `min(1, C / ||weights||_2)`, leaving an all-zero
+/// vector untouched (its norm is already 0, so the scale would otherwise
+/// divide by zero). Deterministic — same input, same output — so
+/// `hash_model_update` stays reproducible across nodes that clip before
+/// hashing.
+pub fn clip_to_l2_norm(weights: &[f32], clip_norm: f32) -> Vec<f32> {
+    let norm_sq: f32 = weights.iter().map(|w| w * w).sum();
+    if norm_sq == 0.0 {
+        return weights.to_vec();
+    }
+    let scale = (clip_norm / norm_sq.sqrt()).min(1.0);
+    weights.iter().map(|w| w * scale).collect()
+}
+
+/// Sample `len` i.i.d. `N(0, stddev^2)` values via Box-Muller, using the
+/// repo's existing `rand::Rng` convention rather than pulling in a
+/// distributions crate for a single call site.
+pub fn sample_gaussian_noise<R: Rng>(rng: &mut R, len: usize, stddev: f32) -> Vec<f32> {
+    if stddev == 0.0 {
+        return vec![0.0; len];
+    }
+    let mut noise = Vec::with_capacity(len);
+    while noise.len() < len {
+        // Box-Muller produces two independent standard-normal samples per
+        // pair of uniform draws; keep both rather than discarding one.
+        let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+        let u2: f32 = rng.gen_range(0.0..1.0);
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let angle = 2.0 * std::f32::consts::PI * u2;
+        noise.push(radius * angle.cos() * stddev);
+        if noise.len() < len {
+            noise.push(radius * angle.sin() * stddev);
+        }
+    }
+    noise
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_clip_leaves_vector_under_bound_untouched() {
+        let weights = vec![1.0, 0.0, 0.0];
+        assert_eq!(clip_to_l2_norm(&weights, 5.0), weights);
+    }
+
+    #[test]
+    fn test_clip_scales_down_vector_over_bound() {
+        let weights = vec![3.0, 4.0]; // norm = 5
+        let clipped = clip_to_l2_norm(&weights, 1.0);
+        let norm: f32 = clipped.iter().map(|w| w * w).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_clip_leaves_zero_vector_untouched() {
+        let weights = vec![0.0, 0.0, 0.0];
+        assert_eq!(clip_to_l2_norm(&weights, 1.0), weights);
+    }
+
+    #[test]
+    fn test_zero_stddev_produces_no_noise() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(sample_gaussian_noise(&mut rng, 4, 0.0), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_noise_has_requested_length() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(sample_gaussian_noise(&mut rng, 5, 1.0).len(), 5);
+    }
+
+    #[test]
+    fn test_dp_config_rejects_nonpositive_clip_norm() {
+        let config = DpConfig { l2_clip_norm: 0.0, noise_multiplier: 1.0 };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_dp_config_rejects_negative_noise_multiplier() {
+        let config = DpConfig { l2_clip_norm: 1.0, noise_multiplier: -0.1 };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_noise_stddev_is_sigma_times_clip_norm() {
+        let config = DpConfig { l2_clip_norm: 2.0, noise_multiplier: 0.5 };
+        assert_eq!(config.noise_stddev(), 1.0);
+    }
+}
@@ -1,7 +1,13 @@
 use hdk::prelude::*;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+mod aggregation;
+mod dp;
+pub use aggregation::AggregationStrategy;
+pub use dp::DpConfig;
+
 #[hdk_entry_helper]
 #[derive(Clone)]
 pub struct ModelUpdate {
@@ -16,6 +22,12 @@ pub struct ModelMetadata {
     pub timestamp: u64,
     pub metrics: ModelMetrics,
     pub agent_id: AgentPubKey,
+    /// The Gaussian noise stddev (`sigma * C`) on the summed weights of
+    /// this round (the published average of `n` updates carries
+    /// `sigma * C / n` per coordinate), or `None` if the round ran without
+    /// a `DpConfig` or under `TrimmedMean`/`MultiKrum`, which add no noise.
+    #[serde(default)]
+    pub dp_noise_stddev: Option<f32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -31,6 +43,55 @@ pub struct AggregationResult {
     pub participating_agents: Vec<AgentPubKey>,
 }
 
+/// `aggregate_model_updates`'s input: the round's updates, which
+/// `AggregationStrategy` to combine them with, and an optional DP config.
+/// `strategy` defaults to `Mean` and `dp` defaults to `None` on
+/// deserialization, so existing callers that only send `updates` keep the
+/// historical un-noised uniform-mean behavior.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AggregateModelUpdatesInput {
+    pub updates: Vec<ModelUpdate>,
+    #[serde(default)]
+    pub strategy: AggregationStrategy,
+    #[serde(default)]
+    pub dp: Option<DpConfig>,
+}
+
+/// `clip_model_update`'s input: mirrors `hash_model_update`'s one-update
+/// shape, plus the `DpConfig` naming the L2 bound to clip to.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClipModelUpdateInput {
+    pub update: ModelUpdate,
+    pub config: DpConfig,
+}
+
+/// What `submit_model_update` broadcasts once a new global model version
+/// is committed, so other agents can react to the new version without
+/// polling `get_latest_global_model`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelCommitted {
+    pub version: u32,
+    pub action_hash: ActionHash,
+    pub aggregation_metrics: ModelMetrics,
+    pub participating_agents: Vec<AgentPubKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum Signal {
+    ModelCommitted(ModelCommitted),
+}
+
+/// `submit_model_update`'s input: the committed global model plus the
+/// round's full participant set, since the entry itself only carries the
+/// winning weights/bias and the `ModelCommitted` signal needs the roster
+/// that produced it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SubmitModelUpdateInput {
+    pub update: ModelUpdate,
+    pub participating_agents: Vec<AgentPubKey>,
+}
+
 // Define entry types
 #[hdk_entry_types]
 #[unit_enum(UnitEntryTypes)]
@@ -56,9 +117,23 @@ pub fn hash_model_update(update: ModelUpdate) -> ExternResult<String> {
     hasher.update(update.metadata.metrics.accuracy.to_le_bytes());
     hasher.update(update.metadata.metrics.samples_count.to_le_bytes());
     hasher.update(update.metadata.agent_id.clone().into_raw_bytes());
+    hasher.update(update.metadata.dp_noise_stddev.unwrap_or(0.0).to_le_bytes());
     Ok(format!("{:x}", hasher.finalize()))
 }
 
+/// Clip `input.update.weights` to `input.config.l2_clip_norm` so a client
+/// can bound its own update's influence locally, before `submit_model_update`,
+/// rather than trusting `aggregate_model_updates` to do it server-side.
+/// Deterministic, so `hash_model_update` stays reproducible across nodes
+/// that clip before hashing.
+#[hdk_extern]
+pub fn clip_model_update(input: ClipModelUpdateInput) -> ExternResult<ModelUpdate> {
+    input.config.validate().map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+    let mut update = input.update;
+    update.weights = dp::clip_to_l2_norm(&update.weights, input.config.l2_clip_norm);
+    Ok(update)
+}
+
 #[hdk_extern]
 pub fn validate_model_update(update: ModelUpdate) -> ExternResult<()> {
     if update.weights.is_empty() {
@@ -72,45 +147,106 @@ pub fn validate_model_update(update: ModelUpdate) -> ExternResult<()> {
     Ok(())
 }
 
+/// Pure arithmetic core of `aggregate_model_updates`, factored out so it
+/// can be exercised directly by a fuzz target without a live conductor:
+/// `#[hdk_extern]` needs a host context for `sys_time`/`agent_info`, but
+/// the actual averaging has no host dependency at all. Rejects the inputs
+/// that would otherwise panic the naive loop: an empty slice, and weight
+/// vectors whose lengths disagree (indexing `weights[i]` against the
+/// first update's length would go out of bounds for a shorter update, or
+/// silently ignore the tail of a longer one).
+pub fn average_weights_and_bias(updates: &[ModelUpdate]) -> Result<(Vec<f32>, f32), String> {
+    let Some(first) = updates.first() else {
+        return Err("No updates to aggregate".to_string());
+    };
+    let width = first.weights.len();
+    if updates.iter().any(|u| u.weights.len() != width) {
+        return Err("Mismatched weights length across updates".to_string());
+    }
+
+    let mut weights = vec![0.0f32; width];
+    let mut bias = 0.0f32;
+    for update in updates {
+        for (i, weight) in update.weights.iter().enumerate() {
+            weights[i] += weight;
+        }
+        bias += update.bias;
+    }
+    for weight in weights.iter_mut() {
+        *weight /= updates.len() as f32;
+    }
+    bias /= updates.len() as f32;
+    Ok((weights, bias))
+}
+
 #[hdk_extern]
-pub fn aggregate_model_updates(updates: Vec<ModelUpdate>) -> ExternResult<AggregationResult> {
+pub fn aggregate_model_updates(input: AggregateModelUpdatesInput) -> ExternResult<AggregationResult> {
+    let AggregateModelUpdatesInput { mut updates, strategy, dp } = input;
     if updates.is_empty() {
         return Err(wasm_error!(WasmErrorInner::Guest("No updates to aggregate".to_string())));
     }
-    
-    let mut global_model = ModelUpdate {
-        weights: vec![0.0; updates[0].weights.len()],
-        bias: 0.0,
+
+    if let Some(config) = dp {
+        config.validate().map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+        // Re-clip server-side even though `clip_model_update` lets clients
+        // clip locally first — a client skipping that step shouldn't get
+        // to exceed the round's declared influence bound.
+        for update in &mut updates {
+            update.weights = dp::clip_to_l2_norm(&update.weights, config.l2_clip_norm);
+        }
+    }
+
+    let (mut weights, mut bias, contributors) =
+        aggregation::aggregate(&updates, strategy).map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+
+    // Only the updates that actually fed `weights`/`bias` — every survivor
+    // under `Mean`/`WeightedFedAvg`/`TrimmedMean`, or just the Multi-Krum
+    // winners — should count towards the published metrics and roster,
+    // the same rationale `ReplicationManager::aggregate_model_updates`
+    // uses for its own `metrics_indices`.
+    let kept: Vec<&ModelUpdate> = contributors.iter().map(|&i| &updates[i]).collect();
+
+    // DP noising only applies to the sum-based strategies (`Mean`,
+    // `WeightedFedAvg`): add noise to the already-divided average with
+    // stddev `(sigma * C) / n`, equivalent to adding noise with stddev
+    // `sigma * C` to the summed weights and then dividing by `n`. The
+    // outlier-rejecting strategies (`TrimmedMean`, `MultiKrum`) don't sum
+    // every coordinate the same way, so DP noise isn't defined for them
+    // here.
+    let dp_noise_stddev = match (dp, strategy) {
+        (Some(config), AggregationStrategy::Mean | AggregationStrategy::WeightedFedAvg) => {
+            let seed_bytes = random_bytes(32)?.into_vec();
+            let mut seed = [0u8; 32];
+            seed.copy_from_slice(&seed_bytes);
+            let mut rng = rand::rngs::StdRng::from_seed(seed);
+            let per_coord_stddev = config.noise_stddev() / kept.len() as f32;
+            let weight_noise = dp::sample_gaussian_noise(&mut rng, weights.len(), per_coord_stddev);
+            for (w, n) in weights.iter_mut().zip(weight_noise.iter()) {
+                *w += n;
+            }
+            bias += dp::sample_gaussian_noise(&mut rng, 1, per_coord_stddev)[0];
+            Some(config.noise_stddev())
+        }
+        _ => None,
+    };
+
+    let global_model = ModelUpdate {
+        weights,
+        bias,
         version: updates.iter().map(|u| u.version).max().unwrap_or(0),
         metadata: ModelMetadata {
             timestamp: sys_time()?.as_millis() as u64,
             metrics: ModelMetrics {
-                loss: updates.iter().map(|u| u.metadata.metrics.loss).sum::<f32>() / updates.len() as f32,
-                accuracy: updates.iter().map(|u| u.metadata.metrics.accuracy).sum::<f32>() / updates.len() as f32,
-                samples_count: updates.iter().map(|u| u.metadata.metrics.samples_count).sum::<u32>(),
+                loss: kept.iter().map(|u| u.metadata.metrics.loss).sum::<f32>() / kept.len() as f32,
+                accuracy: kept.iter().map(|u| u.metadata.metrics.accuracy).sum::<f32>() / kept.len() as f32,
+                samples_count: kept.iter().map(|u| u.metadata.metrics.samples_count).sum::<u32>(),
             },
             agent_id: agent_info()?.agent_initial_pubkey,
+            dp_noise_stddev,
         },
     };
 
-    // Aggregate weights and bias
-    for update in &updates {
-        for (i, weight) in update.weights.iter().enumerate() {
-            global_model.weights[i] += weight;
-        }
-        global_model.bias += update.bias;
-    }
-
-    // Normalize weights and bias
-    for weight in global_model.weights.iter_mut() {
-        *weight /= updates.len() as f32;
-    }
-    global_model.bias /= updates.len() as f32;
-
-    // Create result with participating agents
-    let participating_agents = updates.iter()
-        .map(|u| u.metadata.agent_id.clone())
-        .collect::<Vec<_>>();
+    let participating_agents = kept.iter().map(|u| u.metadata.agent_id.clone()).collect::<Vec<_>>();
 
     Ok(AggregationResult {
         global_model,
@@ -141,17 +277,19 @@ pub fn get_latest_global_model() -> ExternResult<Option<ModelUpdate>> {
 }
 
 #[hdk_extern]
-pub fn submit_model_update(update: ModelUpdate) -> ExternResult<ActionHash> {
+pub fn submit_model_update(input: SubmitModelUpdateInput) -> ExternResult<ActionHash> {
+    let SubmitModelUpdateInput { update, participating_agents } = input;
+
     // Validate the model update
     validate_model_update(update.clone())?;
-    
+
     // Create the entry
     let action_hash = create_entry(&EntryTypes::ModelUpdate(update.clone()))?;
-    
+
     // Ensure the global_model path exists
     let path = Path::from("global_model");
     ensure_path_exists(&path)?;
-    
+
     // Create link from path to model update
     create_link(
         path.path_entry_hash()?,
@@ -159,7 +297,25 @@ pub fn submit_model_update(update: ModelUpdate) -> ExternResult<ActionHash> {
         LinkTypes::GlobalModelHistory,
         (),
     )?;
-    
+
+    // Broadcast the new version so other agents can react without
+    // polling `get_latest_global_model` — locally via `emit_signal` (this
+    // agent's own UI) and to the round's other participants via
+    // `remote_signal`, giving every downstream learner a push-based sync
+    // trigger and an auditable trail of which version superseded which.
+    let signal = Signal::ModelCommitted(ModelCommitted {
+        version: update.version,
+        action_hash: action_hash.clone(),
+        aggregation_metrics: update.metadata.metrics.clone(),
+        participating_agents: participating_agents.clone(),
+    });
+    emit_signal(&signal)?;
+    let this_agent = agent_info()?.agent_initial_pubkey;
+    let peers: Vec<AgentPubKey> = participating_agents.into_iter().filter(|a| *a != this_agent).collect();
+    if !peers.is_empty() {
+        remote_signal(signal, peers)?;
+    }
+
     Ok(action_hash)
 }
 
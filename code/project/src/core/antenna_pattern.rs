@@ -0,0 +1,338 @@
+// src/core/antenna_pattern.rs
+//! Structured antenna radiation-pattern records and far-field
+//! cross-correlation, modeled on the DIS (IEEE 1278.1) Antenna Pattern
+//! record: every antenna's radiated gain is described in its own
+//! right-handed Cartesian *beam coordinate system* — origin at the
+//! antenna's phase center, beam axis (the direction of peak gain) along
+//! +x — rather than in the world frame directly, so two antennas mounted
+//! at different locations and orientations can still be compared
+//! apples-to-apples once `correlate_antenna_patterns` resamples both onto
+//! a shared azimuth/elevation grid.
+//!
+//! `MixingPattern::reference_pattern` lets a pattern whose matching logic
+//! is `correlate_antenna_patterns` (rather than
+//! `dimensional_analysis::check_dimensional_consistency`'s
+//! operation-combinator checks) carry the reference radiation pattern a
+//! measured one is compared against — the pattern this replaces declared
+//! `operation: "antenna_pattern_correlation"` as a bare string with no
+//! backing data structure at all.
+
+use crate::core::mixing_validation::CriterionResult;
+use crate::error::ShardError;
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on `AntennaPatternRecord::azimuth_deg`/`elevation_deg`
+/// lengths. `correlate_antenna_patterns` resamples the measured pattern
+/// onto the reference pattern's grid, one bilinear interpolation per grid
+/// point, so an unbounded grid size would make one correlation call's cost
+/// unbounded too — same reasoning as
+/// `mixing_validation::MAX_HISTOGRAM_BINS`.
+const MAX_GRID_POINTS_PER_AXIS: usize = 361;
+
+/// A position, in meters, either in world coordinates or relative to a
+/// mounting platform's origin.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AntennaLocation {
+    pub x_m: f64,
+    pub y_m: f64,
+    pub z_m: f64,
+}
+
+/// How an antenna's radiated field is polarized — the DIS Antenna Pattern
+/// record's polarization parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Polarization {
+    Linear { tilt_angle_rad: f64 },
+    Circular { clockwise: bool },
+    Elliptical { tilt_angle_rad: f64, ellipticity: f64 },
+}
+
+/// A radiation pattern sampled over a beam-coordinate azimuth/elevation
+/// grid: `gain_db[i][j]` is the far-field gain, in dB, at
+/// `(azimuth_deg[i], elevation_deg[j])`, where azimuth 0 and elevation 0
+/// is the beam axis (+x in the beam coordinate system). Modeled on the DIS
+/// (IEEE 1278.1) Antenna Pattern record.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AntennaPatternRecord {
+    /// This antenna's phase center in world coordinates.
+    pub absolute_location: AntennaLocation,
+    /// This antenna's phase center relative to its mounting platform's
+    /// origin, if it has one rather than being its own standalone entity.
+    pub relative_location: Option<AntennaLocation>,
+    pub polarization: Polarization,
+    /// Azimuth grid sample points, in degrees, strictly ascending, within
+    /// `[-180.0, 180.0]`.
+    pub azimuth_deg: Vec<f64>,
+    /// Elevation grid sample points, in degrees, strictly ascending,
+    /// within `[-90.0, 90.0]`.
+    pub elevation_deg: Vec<f64>,
+    /// Far-field gain, in dB, at `(azimuth_deg[i], elevation_deg[j])` —
+    /// `gain_db[i][j]`, so `gain_db.len() == azimuth_deg.len()` and every
+    /// `gain_db[i].len() == elevation_deg.len()`.
+    pub gain_db: Vec<Vec<f64>>,
+}
+
+impl AntennaPatternRecord {
+    /// Check this record's internal shape: `azimuth_deg`/`elevation_deg`
+    /// are each non-empty, strictly ascending, within their declared
+    /// bounds, and no longer than `MAX_GRID_POINTS_PER_AXIS`; and
+    /// `gain_db`'s outer length matches `azimuth_deg.len()` with every
+    /// inner row matching `elevation_deg.len()`.
+    pub fn validate(&self) -> Result<(), ShardError> {
+        fn check_axis(name: &str, values: &[f64], min: f64, max: f64) -> Result<(), ShardError> {
+            if values.is_empty() {
+                return Err(ShardError::MigrationFailed {
+                    context: format!("antenna pattern record: {name} must have at least one sample point"),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty axis")),
+                });
+            }
+            if values.len() > MAX_GRID_POINTS_PER_AXIS {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "antenna pattern record: {name} has {} points, exceeding the maximum of {MAX_GRID_POINTS_PER_AXIS}",
+                        values.len()
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "axis too large")),
+                });
+            }
+            if values.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(ShardError::MigrationFailed {
+                    context: format!("antenna pattern record: {name} must be strictly ascending"),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "axis not ascending")),
+                });
+            }
+            if values[0] < min || values[values.len() - 1] > max {
+                return Err(ShardError::MigrationFailed {
+                    context: format!("antenna pattern record: {name} must lie within [{min}, {max}]"),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "axis out of range")),
+                });
+            }
+            Ok(())
+        }
+
+        check_axis("azimuth_deg", &self.azimuth_deg, -180.0, 180.0)?;
+        check_axis("elevation_deg", &self.elevation_deg, -90.0, 90.0)?;
+
+        if self.gain_db.len() != self.azimuth_deg.len() {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "antenna pattern record: gain_db has {} row(s), expected {} (azimuth_deg's length)",
+                    self.gain_db.len(),
+                    self.azimuth_deg.len()
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "gain_db row count mismatch")),
+            });
+        }
+        for (i, row) in self.gain_db.iter().enumerate() {
+            if row.len() != self.elevation_deg.len() {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "antenna pattern record: gain_db[{i}] has {} sample(s), expected {} (elevation_deg's length)",
+                        row.len(),
+                        self.elevation_deg.len()
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "gain_db column count mismatch")),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The far-field gain, in dB, at `(azimuth_deg, elevation_deg)`,
+    /// bilinearly interpolated between this record's four nearest grid
+    /// points. Both coordinates are clamped to this record's grid extent
+    /// first, so a query point outside it returns the nearest edge's gain
+    /// rather than extrapolating.
+    pub fn gain_at_db(&self, azimuth_deg: f64, elevation_deg: f64) -> f64 {
+        let (az_lo, az_hi, az_t) = bracket(&self.azimuth_deg, azimuth_deg);
+        let (el_lo, el_hi, el_t) = bracket(&self.elevation_deg, elevation_deg);
+
+        let g00 = self.gain_db[az_lo][el_lo];
+        let g01 = self.gain_db[az_lo][el_hi];
+        let g10 = self.gain_db[az_hi][el_lo];
+        let g11 = self.gain_db[az_hi][el_hi];
+
+        let g0 = g00 + (g10 - g00) * az_t;
+        let g1 = g01 + (g11 - g01) * az_t;
+        g0 + (g1 - g0) * el_t
+    }
+}
+
+/// The grid indices bracketing `value` in `axis` (a strictly ascending,
+/// non-empty slice), plus the interpolation fraction `t` in `[0.0, 1.0]`
+/// between them: `value == axis[lo] + t * (axis[hi] - axis[lo])` when
+/// `value` is within `axis`'s range. `value` is clamped to `axis`'s range
+/// first rather than extrapolating beyond it, so at the lower edge
+/// `lo == hi == 0` (and `t == 0.0`), and at the upper edge `hi` is the
+/// last index with `t == 1.0`.
+fn bracket(axis: &[f64], value: f64) -> (usize, usize, f64) {
+    let clamped = value.clamp(axis[0], axis[axis.len() - 1]);
+    let hi = axis.partition_point(|&x| x < clamped).min(axis.len() - 1);
+    if hi == 0 {
+        return (0, 0, 0.0);
+    }
+    let lo = hi - 1;
+    let span = axis[hi] - axis[lo];
+    let t = if span > 0.0 { (clamped - axis[lo]) / span } else { 0.0 };
+    (lo, hi, t)
+}
+
+/// Compare `measured` against `reference` by resampling `measured` onto
+/// `reference`'s azimuth/elevation grid (via `gain_at_db`'s bilinear
+/// interpolation), converting both to linear far-field magnitude
+/// (`10^(gain_db / 10)`), and computing their Pearson normalized
+/// cross-correlation over every grid point. Returns a `CriterionResult`
+/// whose `score` is that correlation (in `[-1.0, 1.0]`, higher meaning a
+/// better match) and whose `passed` is `score >= threshold` — the same
+/// score-plus-threshold shape `grib_packing::check_compressibility_criterion`
+/// and `spectral_coherence::check_spectral_coherence` already use for a
+/// measurement over sample data rather than a structural fact.
+///
+/// Errs if either record fails `validate`.
+pub fn correlate_antenna_patterns(
+    measured: &AntennaPatternRecord,
+    reference: &AntennaPatternRecord,
+    threshold: f64,
+) -> Result<CriterionResult, ShardError> {
+    measured.validate()?;
+    reference.validate()?;
+
+    let mut measured_linear = Vec::with_capacity(reference.azimuth_deg.len() * reference.elevation_deg.len());
+    let mut reference_linear = Vec::with_capacity(measured_linear.capacity());
+    for (i, &az) in reference.azimuth_deg.iter().enumerate() {
+        for (j, &el) in reference.elevation_deg.iter().enumerate() {
+            measured_linear.push(10f64.powf(measured.gain_at_db(az, el) / 10.0));
+            reference_linear.push(10f64.powf(reference.gain_db[i][j] / 10.0));
+        }
+    }
+
+    let score = normalized_cross_correlation(&measured_linear, &reference_linear);
+    Ok(CriterionResult { score, passed: score >= threshold })
+}
+
+/// Pearson normalized cross-correlation of `a` and `b`: `0.0` if either
+/// series has zero variance (nothing to correlate against).
+fn normalized_cross_correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut sum_sq_a = 0.0;
+    let mut sum_sq_b = 0.0;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        let da = x - mean_a;
+        let db = y - mean_b;
+        numerator += da * db;
+        sum_sq_a += da * da;
+        sum_sq_b += db * db;
+    }
+
+    let denominator = (sum_sq_a * sum_sq_b).sqrt();
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform_record(gain_db: f64) -> AntennaPatternRecord {
+        let azimuth_deg = vec![-90.0, -30.0, 30.0, 90.0];
+        let elevation_deg = vec![-45.0, 0.0, 45.0];
+        let gain_db = vec![vec![gain_db; elevation_deg.len()]; azimuth_deg.len()];
+        AntennaPatternRecord {
+            absolute_location: AntennaLocation { x_m: 0.0, y_m: 0.0, z_m: 0.0 },
+            relative_location: None,
+            polarization: Polarization::Linear { tilt_angle_rad: 0.0 },
+            azimuth_deg,
+            elevation_deg,
+            gain_db,
+        }
+    }
+
+    fn directional_record() -> AntennaPatternRecord {
+        AntennaPatternRecord {
+            absolute_location: AntennaLocation { x_m: 0.0, y_m: 0.0, z_m: 0.0 },
+            relative_location: None,
+            polarization: Polarization::Circular { clockwise: true },
+            azimuth_deg: vec![-90.0, -30.0, 30.0, 90.0],
+            elevation_deg: vec![-45.0, 0.0, 45.0],
+            gain_db: vec![
+                vec![-20.0, -15.0, -20.0],
+                vec![-5.0, 0.0, -5.0],
+                vec![-5.0, 0.0, -5.0],
+                vec![-20.0, -15.0, -20.0],
+            ],
+        }
+    }
+
+    #[test]
+    fn validate_rejects_a_gain_db_shape_mismatch() {
+        let mut record = uniform_record(0.0);
+        record.gain_db.pop();
+        assert!(record.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_a_non_ascending_axis() {
+        let mut record = uniform_record(0.0);
+        record.azimuth_deg.swap(0, 1);
+        assert!(record.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_an_out_of_range_axis() {
+        let mut record = uniform_record(0.0);
+        record.elevation_deg[0] = -100.0;
+        assert!(record.validate().is_err());
+    }
+
+    #[test]
+    fn gain_at_db_returns_the_exact_sample_at_a_grid_point() {
+        let record = directional_record();
+        assert_eq!(record.gain_at_db(-30.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn gain_at_db_interpolates_between_grid_points() {
+        let record = directional_record();
+        let midpoint = record.gain_at_db(0.0, -22.5);
+        assert!((midpoint - (-5.0 + 0.0) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gain_at_db_clamps_queries_outside_the_grid_extent() {
+        let record = directional_record();
+        assert_eq!(record.gain_at_db(-180.0, -90.0), record.gain_at_db(-90.0, -45.0));
+    }
+
+    #[test]
+    fn correlate_antenna_patterns_gives_a_perfect_score_for_an_identical_pattern() {
+        let reference = directional_record();
+        let measured = directional_record();
+        let result = correlate_antenna_patterns(&measured, &reference, 0.9).unwrap();
+        assert!((result.score - 1.0).abs() < 1e-9);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn correlate_antenna_patterns_gives_zero_score_for_a_uniform_pattern_against_a_directional_one() {
+        let reference = directional_record();
+        let measured = uniform_record(-10.0);
+        let result = correlate_antenna_patterns(&measured, &reference, 0.9).unwrap();
+        assert_eq!(result.score, 0.0);
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn correlate_antenna_patterns_errs_on_an_invalid_record() {
+        let mut measured = uniform_record(0.0);
+        measured.gain_db.pop();
+        let reference = directional_record();
+        assert!(correlate_antenna_patterns(&measured, &reference, 0.9).is_err());
+    }
+}
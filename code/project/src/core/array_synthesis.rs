@@ -0,0 +1,299 @@
+// src/core/array_synthesis.rs
+//! Synthesizes a linear antenna array's far-field radiation pattern from
+//! its physical layout via Woodward-Lawson sampling, so an antenna
+//! `MixingPattern`'s `reference_pattern` can be generated from an element
+//! count, spacing, and desired beam shape instead of hand-written.
+//!
+//! Woodward-Lawson synthesis samples the desired pattern at the array's
+//! *orthogonal* sampling angles — for an `N`-element array spaced `d`
+//! wavelengths apart, the `N` angles `u_m = cos(theta_m)` spaced `1 /
+//! (N d)` apart in `u`-space, at which a uniformly-excited array's
+//! individual "composing beams" are mutually orthogonal (each peaks at
+//! its own `u_m` and is exactly zero at every other sampling point).
+//! Each element's excitation is then the weighted sum of the desired
+//! samples times the conjugate of each composing beam's steering phase,
+//! and the full pattern reconstructs as the superposition of the `N`
+//! composing beams, each a shifted sinc-like array factor — the same
+//! composing-beam superposition `correlate_antenna_patterns` can compare
+//! a measurement against once wrapped in an `AntennaPatternRecord`.
+
+use crate::error::ShardError;
+
+/// Upper bound on `ArraySynthesisInput::element_count`. Excitation
+/// synthesis is `O(N^2)` (every element sums over every sampling angle)
+/// and pattern reconstruction is `O(N x evaluation_angles_deg.len())`, so
+/// an unbounded element count would make both unbounded too — same
+/// reasoning as `antenna_pattern::MAX_GRID_POINTS_PER_AXIS`.
+const MAX_ELEMENTS: usize = 256;
+
+/// Upper bound on how many angles `synthesize_array_pattern` will
+/// evaluate the reconstructed pattern at in one call.
+const MAX_EVALUATION_ANGLES: usize = 361;
+
+/// A complex value — here, a desired pattern sample or an element's
+/// excitation coefficient. Kept local rather than shared with
+/// `spectral_coherence`'s private `Complex` since the two model
+/// unrelated domains and neither is part of this repo's public surface.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ComplexSample {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl ComplexSample {
+    fn magnitude(self) -> f64 {
+        (self.re * self.re + self.im * self.im).sqrt()
+    }
+
+    fn mul(self, other: ComplexSample) -> ComplexSample {
+        ComplexSample { re: self.re * other.re - self.im * other.im, im: self.re * other.im + self.im * other.re }
+    }
+
+    fn add(self, other: ComplexSample) -> ComplexSample {
+        ComplexSample { re: self.re + other.re, im: self.im + other.im }
+    }
+
+    fn scale(self, factor: f64) -> ComplexSample {
+        ComplexSample { re: self.re * factor, im: self.im * factor }
+    }
+
+    fn from_polar(magnitude: f64, phase_rad: f64) -> ComplexSample {
+        ComplexSample { re: magnitude * phase_rad.cos(), im: magnitude * phase_rad.sin() }
+    }
+}
+
+/// What `synthesize_array_pattern` needs to describe the array and the
+/// beam shape it should produce.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArraySynthesisInput {
+    pub element_count: usize,
+    /// Spacing between adjacent elements, in wavelengths (`d / lambda`).
+    pub element_spacing_wavelengths: f64,
+    /// The desired complex pattern value at each of the array's `N`
+    /// Woodward-Lawson sampling angles, in sampling order — must have
+    /// exactly `element_count` entries, one per sampling angle returned
+    /// in `ArraySynthesisResult::sampling_angles_deg`.
+    pub desired_samples: Vec<ComplexSample>,
+}
+
+/// One element's excitation: magnitude and phase of the complex
+/// coefficient `synthesize_array_pattern` computed for it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ElementExcitation {
+    pub amplitude: f64,
+    pub phase_rad: f64,
+}
+
+/// `synthesize_array_pattern`'s output: each element's excitation plus
+/// the reconstructed far-field gain curve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArraySynthesisResult {
+    /// The orthogonal sampling angles `desired_samples` was specified
+    /// at, in degrees measured from the array axis (so `u = cos(theta)`
+    /// runs from `1.0` at `0` degrees to `-1.0` at `180` degrees) —
+    /// `sampling_angles_deg[m]` corresponds to `desired_samples[m]`.
+    pub sampling_angles_deg: Vec<f64>,
+    pub element_excitations: Vec<ElementExcitation>,
+    /// The synthesized far-field gain, in dB, at each of the input
+    /// `evaluation_angles_deg`, in the same order.
+    pub gain_db: Vec<f64>,
+}
+
+/// The `m`-th Woodward-Lawson sampling point in `u = cos(theta)` space,
+/// for an `element_count`-element array spaced `spacing_wavelengths`
+/// apart: the `element_count` points spaced `1 / (element_count *
+/// spacing_wavelengths)` apart in `u`, centered on broadside (`u = 0`).
+fn sampling_point_u(m: usize, element_count: usize, spacing_wavelengths: f64) -> f64 {
+    let delta_u = 1.0 / (element_count as f64 * spacing_wavelengths);
+    (m as f64 - (element_count as f64 - 1.0) / 2.0) * delta_u
+}
+
+/// The `m`-th composing beam's value at `u`: the normalized array factor
+/// of a uniformly-excited `element_count`-element array steered to
+/// `u_m`, equal to `1.0` at `u == u_m` and `0.0` at every other sampling
+/// point — the orthogonality Woodward-Lawson sampling relies on.
+fn composing_beam(u: f64, u_m: f64, element_count: usize, spacing_wavelengths: f64) -> f64 {
+    let psi = std::f64::consts::PI * spacing_wavelengths * (u - u_m);
+    if psi.abs() < 1e-12 {
+        return 1.0;
+    }
+    let n = element_count as f64;
+    (n * psi).sin() / (n * psi.sin())
+}
+
+/// Synthesize a linear array's far-field radiation pattern via
+/// Woodward-Lawson sampling: each element's excitation is the weighted
+/// sum of `input.desired_samples` times the conjugate of each sampling
+/// angle's steering phase, and the reconstructed pattern at each of
+/// `evaluation_angles_deg` is the superposition of the `N` composing
+/// beams, each weighted by its desired sample.
+///
+/// Errs if `input.element_count` is `0` or exceeds `MAX_ELEMENTS`,
+/// `input.element_spacing_wavelengths` is not positive and finite,
+/// `input.desired_samples.len() != input.element_count`, or
+/// `evaluation_angles_deg` is empty, exceeds `MAX_EVALUATION_ANGLES`, or
+/// contains a value outside `[0.0, 180.0]`.
+pub fn synthesize_array_pattern(
+    input: &ArraySynthesisInput,
+    evaluation_angles_deg: &[f64],
+) -> Result<ArraySynthesisResult, ShardError> {
+    let n = input.element_count;
+    if n == 0 || n > MAX_ELEMENTS {
+        return Err(ShardError::MigrationFailed {
+            context: format!("synthesize_array_pattern: element_count {n} must be in 1..={MAX_ELEMENTS}"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "element_count out of range")),
+        });
+    }
+    if !input.element_spacing_wavelengths.is_finite() || input.element_spacing_wavelengths <= 0.0 {
+        return Err(ShardError::MigrationFailed {
+            context: "synthesize_array_pattern: element_spacing_wavelengths must be positive and finite".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid element spacing")),
+        });
+    }
+    if input.desired_samples.len() != n {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "synthesize_array_pattern: desired_samples has {} entr(ies), expected {n} (element_count)",
+                input.desired_samples.len()
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "desired_samples length mismatch")),
+        });
+    }
+    if evaluation_angles_deg.is_empty() || evaluation_angles_deg.len() > MAX_EVALUATION_ANGLES {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "synthesize_array_pattern: evaluation_angles_deg has {} entr(ies), expected 1..={MAX_EVALUATION_ANGLES}",
+                evaluation_angles_deg.len()
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "evaluation_angles_deg length out of range")),
+        });
+    }
+    if evaluation_angles_deg.iter().any(|&a| !(0.0..=180.0).contains(&a)) {
+        return Err(ShardError::MigrationFailed {
+            context: "synthesize_array_pattern: evaluation_angles_deg must each lie within [0.0, 180.0]".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "evaluation angle out of range")),
+        });
+    }
+
+    let d = input.element_spacing_wavelengths;
+    let sampling_u: Vec<f64> = (0..n).map(|m| sampling_point_u(m, n, d)).collect();
+    let sampling_angles_deg: Vec<f64> = sampling_u.iter().map(|u| u.clamp(-1.0, 1.0).acos().to_degrees()).collect();
+
+    let mut element_excitations = Vec::with_capacity(n);
+    for element_index in 0..n {
+        let offset = element_index as f64 - (n as f64 - 1.0) / 2.0;
+        let mut excitation = ComplexSample::default();
+        for (m, &u_m) in sampling_u.iter().enumerate() {
+            let steering_phase = -2.0 * std::f64::consts::PI * d * u_m * offset;
+            excitation = excitation.add(input.desired_samples[m].mul(ComplexSample::from_polar(1.0, steering_phase)));
+        }
+        excitation = excitation.scale(1.0 / n as f64);
+        element_excitations.push(ElementExcitation {
+            amplitude: excitation.magnitude(),
+            phase_rad: excitation.im.atan2(excitation.re),
+        });
+    }
+
+    let gain_db = evaluation_angles_deg
+        .iter()
+        .map(|&angle_deg| {
+            let u = angle_deg.to_radians().cos();
+            let mut pattern = ComplexSample::default();
+            for (m, &u_m) in sampling_u.iter().enumerate() {
+                let weight = composing_beam(u, u_m, n, d);
+                pattern = pattern.add(input.desired_samples[m].scale(weight));
+            }
+            20.0 * pattern.magnitude().max(1e-12).log10()
+        })
+        .collect();
+
+    Ok(ArraySynthesisResult { sampling_angles_deg, element_excitations, gain_db })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn broadside_input(element_count: usize) -> ArraySynthesisInput {
+        ArraySynthesisInput {
+            element_count,
+            element_spacing_wavelengths: 0.5,
+            desired_samples: (0..element_count)
+                .map(|m| if sampling_point_u(m, element_count, 0.5).abs() < 1e-9 { ComplexSample { re: 1.0, im: 0.0 } } else { ComplexSample::default() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn synthesize_array_pattern_rejects_a_zero_element_count() {
+        let mut input = broadside_input(4);
+        input.element_count = 0;
+        assert!(synthesize_array_pattern(&input, &[90.0]).is_err());
+    }
+
+    #[test]
+    fn synthesize_array_pattern_rejects_a_non_positive_spacing() {
+        let mut input = broadside_input(4);
+        input.element_spacing_wavelengths = 0.0;
+        assert!(synthesize_array_pattern(&input, &[90.0]).is_err());
+    }
+
+    #[test]
+    fn synthesize_array_pattern_rejects_a_desired_samples_length_mismatch() {
+        let mut input = broadside_input(4);
+        input.desired_samples.pop();
+        assert!(synthesize_array_pattern(&input, &[90.0]).is_err());
+    }
+
+    #[test]
+    fn synthesize_array_pattern_rejects_an_evaluation_angle_out_of_range() {
+        let input = broadside_input(4);
+        assert!(synthesize_array_pattern(&input, &[200.0]).is_err());
+    }
+
+    #[test]
+    fn synthesize_array_pattern_rejects_empty_evaluation_angles() {
+        let input = broadside_input(4);
+        assert!(synthesize_array_pattern(&input, &[]).is_err());
+    }
+
+    #[test]
+    fn synthesize_array_pattern_returns_one_sampling_angle_and_excitation_per_element() {
+        let element_count = 5;
+        let input = broadside_input(element_count);
+        let result = synthesize_array_pattern(&input, &[90.0]).unwrap();
+        assert_eq!(result.sampling_angles_deg.len(), element_count);
+        assert_eq!(result.element_excitations.len(), element_count);
+    }
+
+    #[test]
+    fn synthesize_array_pattern_peaks_at_broadside_for_a_broadside_desired_sample() {
+        let input = broadside_input(7);
+        let result = synthesize_array_pattern(&input, &[60.0, 90.0, 120.0]).unwrap();
+        let broadside_gain = result.gain_db[1];
+        assert!(broadside_gain > result.gain_db[0]);
+        assert!(broadside_gain > result.gain_db[2]);
+    }
+
+    #[test]
+    fn synthesize_array_pattern_gives_uniform_excitation_amplitudes_for_a_broadside_beam() {
+        let input = broadside_input(5);
+        let result = synthesize_array_pattern(&input, &[90.0]).unwrap();
+        let first = result.element_excitations[0].amplitude;
+        for excitation in &result.element_excitations {
+            assert!((excitation.amplitude - first).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn synthesize_array_pattern_reconstructs_the_desired_sample_at_its_own_sampling_angle() {
+        let element_count = 5;
+        let input = broadside_input(element_count);
+        let sampling_angles: Vec<f64> = (0..element_count)
+            .map(|m| sampling_point_u(m, element_count, 0.5).clamp(-1.0, 1.0).acos().to_degrees())
+            .collect();
+        let result = synthesize_array_pattern(&input, &sampling_angles).unwrap();
+        let broadside_index = sampling_angles.iter().position(|&a| (a - 90.0).abs() < 1e-6).unwrap();
+        assert!((result.gain_db[broadside_index] - 0.0).abs() < 1e-6);
+    }
+}
@@ -4,6 +4,23 @@ use serde::{Serialize, Deserialize};
 use hdk::prelude::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::core::persistence::{PersistenceBackend, PersistenceError};
+
+/// Recoverable failure reading the system clock, surfaced instead of
+/// panicking so a transient clock read failure can't abort the conductor.
+#[derive(thiserror::Error, Debug)]
+pub enum TimeError {
+    #[error("system clock is before the Unix epoch: {0}")]
+    ClockBeforeEpoch(String),
+}
+
+fn now_millis() -> Result<u64, TimeError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .map_err(|e| TimeError::ClockBeforeEpoch(e.to_string()))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VersionVector {
     versions: HashMap<AgentPubKey, u64>,
@@ -51,16 +68,13 @@ pub struct CentroidCRDT {
 }
 
 impl CentroidCRDT {
-    pub fn new(centroid: Vec<f32>) -> Self {
-        Self {
+    pub fn new(centroid: Vec<f32>) -> Result<Self, TimeError> {
+        Ok(Self {
             centroid,
             count: 1,
             version_vector: VersionVector::new(),
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_millis() as u64,
-        }
+            timestamp: now_millis()?,
+        })
     }
 
     pub fn merge(&mut self, other: &CentroidCRDT) -> bool {
@@ -108,22 +122,34 @@ impl CentroidCRDT {
         sum_squared.sqrt()
     }
     
-    pub fn update(&mut self, agent: AgentPubKey, vector: &[f32]) {
+    pub fn update(&mut self, agent: AgentPubKey, vector: &[f32]) -> Result<(), TimeError> {
         let new_count = self.count + 1;
         let weight = 1.0 / new_count as f32;
         let old_weight = (new_count - 1) as f32 / new_count as f32;
-        
+
         for (i, c) in self.centroid.iter_mut().enumerate() {
             if i < vector.len() {
                 *c = *c * old_weight + vector[i] * weight;
             }
         }
-        
+
         self.count = new_count;
         self.version_vector.increment(agent);
-        self.timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_millis() as u64;
+        self.timestamp = now_millis()?;
+        Ok(())
+    }
+
+    /// Persist this centroid's state to `backend` under `key`, so a node
+    /// can restart without losing accumulated cluster state.
+    pub fn save(&self, backend: &dyn PersistenceBackend, key: &str) -> Result<(), PersistenceError> {
+        let bytes = serde_json::to_vec(self).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        backend.save(key, &bytes)
+    }
+
+    /// Load a previously persisted centroid from `backend`, if present.
+    pub fn load(backend: &dyn PersistenceBackend, key: &str) -> Result<Option<Self>, PersistenceError> {
+        let Some(bytes) = backend.load(key)? else { return Ok(None) };
+        let centroid = serde_json::from_slice(&bytes).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        Ok(Some(centroid))
     }
 }
@@ -0,0 +1,36 @@
+// src/core/checkpoint.rs
+use std::sync::Arc;
+
+use crate::core::persistence::{KeyValueStore, KvOp, PersistenceError};
+use crate::core::VersionVector;
+
+const CHECKPOINT_PREFIX: &str = "checkpoint.";
+
+/// Records, after each fully-committed sync round, the latest merged
+/// `VersionVector` per shard, so a crashed or restarted sync resumes from
+/// the last checkpoint instead of from scratch. The checkpoint only
+/// advances once a round is fully committed, so a mid-round crash re-syncs
+/// only the uncommitted tail.
+pub struct CheckpointManager {
+    store: Arc<dyn KeyValueStore>,
+}
+
+impl CheckpointManager {
+    pub fn new(store: Arc<dyn KeyValueStore>) -> Self {
+        Self { store }
+    }
+
+    /// Load the last checkpointed `VersionVector` for `shard_id`, if any.
+    pub fn load(&self, shard_id: &str) -> Result<Option<VersionVector>, PersistenceError> {
+        let Some(bytes) = self.store.get(&format!("{CHECKPOINT_PREFIX}{shard_id}"))? else { return Ok(None) };
+        let vv = serde_json::from_slice(&bytes).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        Ok(Some(vv))
+    }
+
+    /// Durably advance the checkpoint for `shard_id` to `version`. Only call
+    /// this once the corresponding sync round has fully committed.
+    pub fn advance(&self, shard_id: &str, version: &VersionVector) -> Result<(), PersistenceError> {
+        let bytes = serde_json::to_vec(version).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        self.store.transaction(vec![KvOp::Put(format!("{CHECKPOINT_PREFIX}{shard_id}"), bytes)])
+    }
+}
@@ -0,0 +1,375 @@
+// src/core/chunked_store.rs
+//! Content-defined chunking (CDC) for deduplicating serialized version
+//! history blobs. Near-identical consecutive snapshots (e.g. successive
+//! `CentroidCRDT` states, or a `ModelUpdate`'s weights across federated
+//! rounds — see `nerv::replication`) share most of their byte content, so
+//! splitting each blob into content-addressed chunks and storing each chunk
+//! once turns an O(versions × size) history into roughly O(unique content).
+
+use blake2::{Blake2s256, Digest};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Target average chunk size.
+const TARGET_CHUNK_SIZE: usize = 8 * 1024;
+const MIN_CHUNK_SIZE: usize = 2 * 1024;
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// `TARGET_CHUNK_SIZE`'s bit width (it's a power of two), and how many bits
+/// `NORMALIZATION_LEVEL` widens or narrows the boundary mask by on either
+/// side of it. FastCDC-style "normalized chunking": below target, cutting
+/// is stricter (`MASK_SMALL` has *more* one-bits, so it's less likely to
+/// match) to discourage chunks that are merely big enough; above target,
+/// cutting is looser (`MASK_LARGE` has *fewer* one-bits, so it matches
+/// sooner) to pull oversized chunks back toward the target instead of
+/// drifting up to `MAX_CHUNK_SIZE`. Net effect: chunk sizes concentrate
+/// around `TARGET_CHUNK_SIZE` instead of spreading uniformly across
+/// `[MIN_CHUNK_SIZE, MAX_CHUNK_SIZE]`.
+const TARGET_MASK_BITS: u32 = TARGET_CHUNK_SIZE.trailing_zeros();
+const NORMALIZATION_LEVEL: u32 = 2;
+const MASK_SMALL: u64 = (1u64 << (TARGET_MASK_BITS + NORMALIZATION_LEVEL)) - 1;
+const MASK_LARGE: u64 = (1u64 << (TARGET_MASK_BITS - NORMALIZATION_LEVEL)) - 1;
+
+pub(crate) const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Per-byte-value mixing constants for the rolling gear hash, generated
+/// deterministically at compile time rather than pulled from a table crate.
+const fn build_gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = splitmix64(i as u64 + 1);
+        i += 1;
+    }
+    table
+}
+
+const GEAR_TABLE: [u64; 256] = build_gear_table();
+
+/// BLAKE2s digest identifying a chunk's content.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ChunkHash([u8; 32]);
+
+impl ChunkHash {
+    /// Hash `data` directly, for callers that want a content checksum
+    /// without going through the chunking machinery (e.g. a whole
+    /// `ModelUpdate` or migrated vector's canonical bytes).
+    pub fn of(data: &[u8]) -> Self {
+        let mut hasher = Blake2s256::new();
+        hasher.update(data);
+        let digest = hasher.finalize();
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&digest);
+        Self(bytes)
+    }
+
+    /// Lowercase hex encoding, suitable as a durable-store key.
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Raw digest bytes, for callers that fold the hash into a smaller
+    /// value (e.g. a PRNG seed) rather than using it as an opaque key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// A version's content as an ordered list of chunk hashes. Restoring it
+/// concatenates the chunks, in order, from the `ChunkStore` that produced
+/// them.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ChunkedVersion {
+    pub chunk_hashes: Vec<ChunkHash>,
+}
+
+/// Split `data` into content-defined chunk boundaries using a rolling gear
+/// hash: a boundary is declared whenever the running hash matches the
+/// size-appropriate mask (`MASK_SMALL` below `TARGET_CHUNK_SIZE`,
+/// `MASK_LARGE` at or above it — see the normalized-chunking comment on
+/// `MASK_SMALL`/`MASK_LARGE`) and the current chunk has reached
+/// `MIN_CHUNK_SIZE`, or unconditionally once it reaches `MAX_CHUNK_SIZE`
+/// (bounding worst-case chunk size variance).
+fn chunk_boundaries(data: &[u8]) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut hash: u64 = 0;
+    let mut chunk_start = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = (hash << 1).wrapping_add(GEAR_TABLE[byte as usize]);
+        let len = i - chunk_start + 1;
+        let mask = if len < TARGET_CHUNK_SIZE { MASK_SMALL } else { MASK_LARGE };
+        if (len >= MIN_CHUNK_SIZE && hash & mask == 0) || len >= MAX_CHUNK_SIZE {
+            boundaries.push(i + 1);
+            chunk_start = i + 1;
+            hash = 0;
+        }
+    }
+
+    if chunk_start < data.len() {
+        boundaries.push(data.len());
+    }
+    boundaries
+}
+
+/// A stored chunk's bytes plus how many retained versions still reference
+/// it, so `release` can free a chunk's bytes once the last referencing
+/// version is evicted instead of retaining it forever.
+struct ChunkEntry {
+    bytes: Vec<u8>,
+    refcount: usize,
+}
+
+/// Content-addressed store of chunk bytes, keyed by `ChunkHash`. Storing the
+/// same chunk twice is a no-op beyond bumping its refcount, so unchanged
+/// regions across versions occupy space once.
+#[derive(Default)]
+pub struct ChunkStore {
+    chunks: Mutex<HashMap<ChunkHash, ChunkEntry>>,
+    /// Chunks added since the last `drain_pending` call — what a durable
+    /// backend still needs to upload to have every chunk a retained version
+    /// references.
+    pending: Mutex<Vec<ChunkHash>>,
+}
+
+impl ChunkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn put_chunk(&self, data: &[u8]) -> ChunkHash {
+        let hash = ChunkHash::of(data);
+        let mut chunks = self.chunks.lock().expect("chunk store mutex poisoned");
+        match chunks.entry(hash) {
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(ChunkEntry { bytes: data.to_vec(), refcount: 1 });
+                self.pending.lock().expect("chunk store mutex poisoned").push(hash);
+            }
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                entry.get_mut().refcount += 1;
+            }
+        }
+        hash
+    }
+
+    /// Fetch one chunk's bytes by content hash, e.g. to re-verify it against
+    /// an out-of-band checksum without reassembling a whole `ChunkedVersion`
+    /// via `restore_version`.
+    pub fn get_chunk(&self, hash: &ChunkHash) -> Option<Vec<u8>> {
+        let chunks = self.chunks.lock().expect("chunk store mutex poisoned");
+        chunks.get(hash).map(|entry| entry.bytes.clone())
+    }
+
+    /// Drop one reference to each of `hashes`, freeing a chunk's bytes once
+    /// no retained version references it anymore. Call this with the chunk
+    /// hashes of a `ChunkedVersion` evicted from a `ChunkedHistory` so
+    /// storage stays bounded by the retained window rather than growing
+    /// over every version ever seen.
+    pub fn release(&self, hashes: &[ChunkHash]) {
+        let mut chunks = self.chunks.lock().expect("chunk store mutex poisoned");
+        for hash in hashes {
+            if let std::collections::hash_map::Entry::Occupied(mut entry) = chunks.entry(*hash) {
+                entry.get_mut().refcount -= 1;
+                if entry.get().refcount == 0 {
+                    entry.remove();
+                }
+            }
+        }
+    }
+
+    /// Number of distinct chunks currently retained, useful for measuring
+    /// how much deduplication a version history is achieving.
+    pub fn len(&self) -> usize {
+        self.chunks.lock().expect("chunk store mutex poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take every chunk added since the last drain, paired with its bytes,
+    /// so a caller can upload only the chunks a durable backend hasn't seen
+    /// yet — chunks shared with an earlier version are never re-uploaded.
+    /// On failure to actually persist them, re-queue the chunks that are
+    /// still retained with `requeue_pending` so they aren't silently lost.
+    pub fn drain_pending(&self) -> Vec<(ChunkHash, Vec<u8>)> {
+        let pending = std::mem::take(&mut *self.pending.lock().expect("chunk store mutex poisoned"));
+        let chunks = self.chunks.lock().expect("chunk store mutex poisoned");
+        pending.into_iter().filter_map(|hash| chunks.get(&hash).map(|entry| (hash, entry.bytes.clone()))).collect()
+    }
+
+    /// Put previously-drained chunk hashes back on the pending queue, for a
+    /// caller whose durable write failed after draining — so those chunks
+    /// are retried on the next `drain_pending` instead of lost for good.
+    pub fn requeue_pending(&self, hashes: impl IntoIterator<Item = ChunkHash>) {
+        self.pending.lock().expect("chunk store mutex poisoned").extend(hashes);
+    }
+}
+
+/// Content-defined-chunk `data`, storing each new chunk in `store` and
+/// returning the ordered list of chunk hashes that reconstructs it.
+pub fn store_version(store: &ChunkStore, data: &[u8]) -> ChunkedVersion {
+    let mut chunk_hashes = Vec::new();
+    let mut start = 0;
+    for end in chunk_boundaries(data) {
+        chunk_hashes.push(store.put_chunk(&data[start..end]));
+        start = end;
+    }
+    ChunkedVersion { chunk_hashes }
+}
+
+/// Reconstruct the original bytes for `version` by concatenating its chunks
+/// from `store`, in order. Returns `None` if any referenced chunk is
+/// missing (e.g. evicted).
+pub fn restore_version(store: &ChunkStore, version: &ChunkedVersion) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    for hash in &version.chunk_hashes {
+        out.extend(store.get_chunk(hash)?);
+    }
+    Some(out)
+}
+
+/// Bounded, ordered history of `ChunkedVersion`s for a single logical key
+/// (e.g. one tracked centroid). Oldest versions are evicted past
+/// `max_versions`; their chunks remain in the owning `ChunkStore` as long as
+/// any other retained version still references them.
+pub struct ChunkedHistory {
+    max_versions: usize,
+    versions: std::collections::VecDeque<ChunkedVersion>,
+}
+
+impl ChunkedHistory {
+    pub fn new(max_versions: usize) -> Self {
+        Self { max_versions: max_versions.max(1), versions: std::collections::VecDeque::new() }
+    }
+
+    /// Record `version` as the newest, returning the evicted version (if
+    /// any) so the caller can `ChunkStore::release` its chunks.
+    pub fn push(&mut self, version: ChunkedVersion) -> Option<ChunkedVersion> {
+        self.versions.push_back(version);
+        if self.versions.len() > self.max_versions {
+            self.versions.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Retained versions, oldest first.
+    pub fn versions(&self) -> impl Iterator<Item = &ChunkedVersion> {
+        self.versions.iter()
+    }
+
+    pub fn latest(&self) -> Option<&ChunkedVersion> {
+        self.versions.back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_arbitrary_data() {
+        let store = ChunkStore::new();
+        let data: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        let version = store_version(&store, &data);
+        assert_eq!(restore_version(&store, &version).unwrap(), data);
+    }
+
+    #[test]
+    fn identical_regions_across_versions_share_chunks() {
+        let store = ChunkStore::new();
+        // Varied bytes, so the rolling hash finds content-defined cut points
+        // inside the prefix (a run of one byte value has none).
+        let shared_prefix: Vec<u8> = (0..40_000u64).map(|i| ((i * 2654435761) >> 13) as u8).collect();
+        let mut version_a = shared_prefix.clone();
+        version_a.extend(vec![1u8; 4_000]);
+        let mut version_b = shared_prefix;
+        version_b.extend(vec![2u8; 4_000]);
+
+        let chunked_a = store_version(&store, &version_a);
+        let chunks_after_first = store.len();
+        let chunked_b = store_version(&store, &version_b);
+
+        // Only the differing tail should have added new chunks; the shared
+        // prefix's chunks are reused.
+        assert!(store.len() > chunks_after_first);
+        assert!(store.len() < chunks_after_first + chunked_b.chunk_hashes.len());
+        assert_ne!(chunked_a.chunk_hashes, chunked_b.chunk_hashes);
+    }
+
+    #[test]
+    fn chunks_respect_min_and_max_size_bounds() {
+        let store = ChunkStore::new();
+        let data = vec![42u8; 100_000];
+        let version = store_version(&store, &data);
+        assert!(version.chunk_hashes.len() > 1);
+        let last = version.chunk_hashes.len() - 1;
+        for (index, hash) in version.chunk_hashes.iter().enumerate() {
+            let chunk = store.get_chunk(hash).unwrap();
+            assert!(chunk.len() <= MAX_CHUNK_SIZE);
+            if index < last {
+                assert!(chunk.len() >= MIN_CHUNK_SIZE);
+            }
+        }
+    }
+
+    #[test]
+    fn drain_pending_yields_only_new_chunks_once() {
+        let store = ChunkStore::new();
+        let shared_prefix = vec![9u8; 40_000];
+        let mut version_a = shared_prefix.clone();
+        version_a.extend(vec![1u8; 4_000]);
+        store_version(&store, &version_a);
+        let first_drain = store.drain_pending();
+        assert_eq!(first_drain.len(), store.len());
+
+        // Nothing new since the last drain.
+        assert!(store.drain_pending().is_empty());
+
+        let mut version_b = shared_prefix;
+        version_b.extend(vec![2u8; 4_000]);
+        store_version(&store, &version_b);
+        let second_drain = store.drain_pending();
+        assert!(!second_drain.is_empty());
+        assert!(second_drain.len() < store.len());
+    }
+
+    #[test]
+    fn chunked_history_evicts_oldest_past_max_versions() {
+        let store = ChunkStore::new();
+        let mut history = ChunkedHistory::new(2);
+        for byte in [1u8, 2, 3] {
+            history.push(store_version(&store, &vec![byte; 4_000]));
+        }
+        assert_eq!(history.versions().count(), 2);
+        let restored_latest = restore_version(&store, history.latest().unwrap()).unwrap();
+        assert_eq!(restored_latest, vec![3u8; 4_000]);
+    }
+
+    #[test]
+    fn releasing_an_evicted_version_frees_chunks_not_shared_with_retained_ones() {
+        let store = ChunkStore::new();
+        let mut history = ChunkedHistory::new(1);
+
+        let evicted = history.push(store_version(&store, &vec![1u8; 4_000]));
+        assert!(evicted.is_none());
+
+        let evicted = history.push(store_version(&store, &vec![2u8; 4_000]));
+        let evicted = evicted.expect("second push should evict the first version");
+        let chunks_before_release = store.len();
+
+        store.release(&evicted.chunk_hashes);
+
+        // Only the evicted version's (entirely distinct) chunks are freed;
+        // the retained version's chunks remain restorable.
+        assert_eq!(store.len(), chunks_before_release - evicted.chunk_hashes.len());
+        assert!(restore_version(&store, history.latest().unwrap()).is_some());
+        assert!(restore_version(&store, &evicted).is_none());
+    }
+}
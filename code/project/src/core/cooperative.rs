@@ -0,0 +1,52 @@
+// src/core/cooperative.rs
+
+/// Cooperative work budget for leaf futures that process unbounded
+/// collections (shard lists, centroid batches) inside a long-running async
+/// task. Seeded with `N` work units; each unit of real work decrements the
+/// counter, and once it hits zero the caller should yield back to the
+/// executor so a large batch can't monopolize the runtime, then refill and
+/// continue.
+pub struct CooperativeBudget {
+    capacity: u32,
+    remaining: u32,
+}
+
+impl CooperativeBudget {
+    pub fn new(capacity: u32) -> Self {
+        Self { capacity, remaining: capacity }
+    }
+
+    /// Charge one unit of work. Returns `true` once the budget is exhausted,
+    /// signalling the caller should call `tokio::task::yield_now().await`
+    /// and then `refill()` before continuing.
+    pub fn charge(&mut self) -> bool {
+        self.remaining = self.remaining.saturating_sub(1);
+        self.remaining == 0
+    }
+
+    pub fn refill(&mut self) {
+        self.remaining = self.capacity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exhausts_after_capacity_charges() {
+        let mut budget = CooperativeBudget::new(3);
+        assert!(!budget.charge());
+        assert!(!budget.charge());
+        assert!(budget.charge());
+    }
+
+    #[test]
+    fn refill_resets_remaining() {
+        let mut budget = CooperativeBudget::new(2);
+        budget.charge();
+        budget.charge();
+        budget.refill();
+        assert!(!budget.charge());
+    }
+}
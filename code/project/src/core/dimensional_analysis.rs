@@ -0,0 +1,383 @@
+// src/core/dimensional_analysis.rs
+//! Units subsystem for `mixing_pipeline`'s `MixingPattern`s, modeled on the
+//! UNECE Recommendation 20 quantity-kind enumeration: every signal type a
+//! pattern declares in `inputs`/`produces` carries a real physical
+//! `Dimension`, and a pattern that opts into declaring an `Operation`
+//! (how its inputs combine into its output) gets checked for dimensional
+//! consistency automatically, instead of `validate_pattern` having to trust
+//! that the author got the physics right.
+//!
+//! Patterns that don't declare an `Operation` aren't checked here at all —
+//! this is additive, the same way `composed_with`/`conflicts_with` are
+//! optional metadata `plan_pipeline` doesn't require every pattern to carry.
+
+use crate::error::ShardError;
+use serde::{Deserialize, Serialize};
+
+/// Signed exponents of the seven SI base quantities a physical quantity is
+/// built from: length (L), mass (M), time (T), electric current (I),
+/// thermodynamic temperature (Θ), amount of substance (N), and luminous
+/// intensity (J). Two quantities are dimensionally equal iff every exponent
+/// matches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dimension {
+    pub length: i8,
+    pub mass: i8,
+    pub time: i8,
+    pub current: i8,
+    pub temperature: i8,
+    pub amount: i8,
+    pub luminous_intensity: i8,
+}
+
+impl Dimension {
+    pub const fn dimensionless() -> Self {
+        Self { length: 0, mass: 0, time: 0, current: 0, temperature: 0, amount: 0, luminous_intensity: 0 }
+    }
+
+    /// Exponent-wise sum — combining two quantities multiplied together.
+    pub fn add(self, other: Dimension) -> Dimension {
+        Dimension {
+            length: self.length + other.length,
+            mass: self.mass + other.mass,
+            time: self.time + other.time,
+            current: self.current + other.current,
+            temperature: self.temperature + other.temperature,
+            amount: self.amount + other.amount,
+            luminous_intensity: self.luminous_intensity + other.luminous_intensity,
+        }
+    }
+
+    /// Exponent-wise difference — combining two quantities divided by each
+    /// other.
+    pub fn sub(self, other: Dimension) -> Dimension {
+        Dimension {
+            length: self.length - other.length,
+            mass: self.mass - other.mass,
+            time: self.time - other.time,
+            current: self.current - other.current,
+            temperature: self.temperature - other.temperature,
+            amount: self.amount - other.amount,
+            luminous_intensity: self.luminous_intensity - other.luminous_intensity,
+        }
+    }
+
+    /// Dividing by time once, e.g. turning a quantity into its
+    /// time-derivative's dimension.
+    pub fn divide_by_time(self) -> Dimension {
+        Dimension { time: self.time - 1, ..self }
+    }
+
+    /// Multiplying by time once, e.g. turning a quantity into its
+    /// time-integral's dimension.
+    pub fn multiply_by_time(self) -> Dimension {
+        Dimension { time: self.time + 1, ..self }
+    }
+}
+
+/// A named physical quantity kind and the `Dimension` it carries. Mirrors
+/// (a small, curated subset of) the UNECE Recommendation 20 quantity-kind
+/// enumeration — enough to cover the signal types this crate's own
+/// `MixingPattern`s use, not the full standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuantityKind {
+    Dimensionless,
+    Length,
+    Mass,
+    Time,
+    ElectricCurrent,
+    Temperature,
+    AmountOfSubstance,
+    LuminousIntensity,
+    /// Tesla: kg·s⁻²·A⁻¹.
+    MagneticFluxDensity,
+    /// Weber: kg·m²·s⁻²·A⁻¹.
+    MagneticFlux,
+    /// Volt: kg·m²·s⁻³·A⁻¹.
+    Voltage,
+    /// Pascal: kg·m⁻¹·s⁻².
+    Pressure,
+    /// Meter: a length used as an altitude/elevation reading.
+    Altitude,
+    /// Meter per second squared: an accelerometer reading.
+    Acceleration,
+    /// Radian per second: a gyroscope reading.
+    AngularVelocity,
+}
+
+impl QuantityKind {
+    pub const fn dimension(self) -> Dimension {
+        use QuantityKind::*;
+        match self {
+            Dimensionless => Dimension::dimensionless(),
+            Length | Altitude => Dimension { length: 1, ..Dimension::dimensionless() },
+            Mass => Dimension { mass: 1, ..Dimension::dimensionless() },
+            Time => Dimension { time: 1, ..Dimension::dimensionless() },
+            ElectricCurrent => Dimension { current: 1, ..Dimension::dimensionless() },
+            Temperature => Dimension { temperature: 1, ..Dimension::dimensionless() },
+            AmountOfSubstance => Dimension { amount: 1, ..Dimension::dimensionless() },
+            LuminousIntensity => Dimension { luminous_intensity: 1, ..Dimension::dimensionless() },
+            MagneticFluxDensity => Dimension { mass: 1, time: -2, current: -1, ..Dimension::dimensionless() },
+            MagneticFlux => Dimension { mass: 1, length: 2, time: -2, current: -1, ..Dimension::dimensionless() },
+            Voltage => Dimension { mass: 1, length: 2, time: -3, current: -1, ..Dimension::dimensionless() },
+            Pressure => Dimension { mass: 1, length: -1, time: -2, ..Dimension::dimensionless() },
+            Acceleration => Dimension { length: 1, time: -2, ..Dimension::dimensionless() },
+            AngularVelocity => Dimension { time: -1, ..Dimension::dimensionless() },
+        }
+    }
+}
+
+/// Map a `MixingPattern` input/output type name (as used in
+/// `MixingPattern::inputs`/`produces`) to the `QuantityKind` it's known to
+/// carry, or `None` if this subsystem doesn't recognize the name.
+pub fn quantity_kind_for_type(type_name: &str) -> Option<QuantityKind> {
+    use QuantityKind::*;
+    match type_name {
+        "magnetic_flux_density" => Some(MagneticFluxDensity),
+        "magnetic_flux" => Some(MagneticFlux),
+        "voltage" => Some(Voltage),
+        "temperature" => Some(Temperature),
+        "pressure" => Some(Pressure),
+        "altitude" => Some(Altitude),
+        "accelerometer" => Some(Acceleration),
+        "gyroscope" => Some(AngularVelocity),
+        _ => None,
+    }
+}
+
+/// How a pattern's input dimensions combine into its output dimension.
+/// `Product`/`Ratio` combine however many inputs a pattern declares;
+/// `TimeDerivative`/`TimeIntegral` each apply to exactly one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    /// Output dimension is the sum of every input's dimension — e.g.
+    /// `force = mass * acceleration`.
+    Product,
+    /// Output dimension is the first input's dimension minus every other
+    /// input's — e.g. `velocity = length / time`.
+    Ratio,
+    /// Output dimension is its single input's dimension with one fewer
+    /// power of time — e.g. induced voltage from `d(magnetic flux)/dt`.
+    TimeDerivative,
+    /// Output dimension is its single input's dimension with one more power
+    /// of time — the inverse of `TimeDerivative`.
+    TimeIntegral,
+}
+
+/// Upper bound on `Operation::combine`'s `inputs`: `Dimension`'s exponents
+/// are `i8`, and `Product`/`Ratio` sum one exponent per input, so an
+/// unbounded input count could overflow them (a panic in a debug build, a
+/// silently wrong exponent — and so a silently wrong `applies` verdict — in
+/// a release one). Real quantity-kind exponents never exceed a handful in
+/// magnitude, so this many inputs is already far more than any real
+/// `Operation` needs.
+const MAX_OPERATION_INPUTS: usize = 16;
+
+impl Operation {
+    /// Combine `inputs` (already-resolved dimensions, in declaration order)
+    /// according to this operation. Errs if `TimeDerivative`/`TimeIntegral`
+    /// is given anything but exactly one input, if `Ratio` is given none, or
+    /// if `inputs` exceeds `MAX_OPERATION_INPUTS`.
+    pub fn combine(&self, inputs: &[Dimension]) -> Result<Dimension, ShardError> {
+        if inputs.len() > MAX_OPERATION_INPUTS {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "dimensional analysis: operation has {} inputs, exceeding the maximum of {MAX_OPERATION_INPUTS}",
+                    inputs.len()
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many operation inputs")),
+            });
+        }
+        match self {
+            Operation::Product => Ok(inputs.iter().fold(Dimension::dimensionless(), |acc, d| acc.add(*d))),
+            Operation::Ratio => {
+                let Some((first, rest)) = inputs.split_first() else {
+                    return Err(ShardError::MigrationFailed {
+                        context: "dimensional analysis: Ratio requires at least one input".to_string(),
+                        source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "no inputs for ratio")),
+                    });
+                };
+                Ok(rest.iter().fold(*first, |acc, d| acc.sub(*d)))
+            }
+            Operation::TimeDerivative => match inputs {
+                [only] => Ok(only.divide_by_time()),
+                _ => Err(ShardError::MigrationFailed {
+                    context: format!("dimensional analysis: TimeDerivative requires exactly one input, got {}", inputs.len()),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong input count for derivative")),
+                }),
+            },
+            Operation::TimeIntegral => match inputs {
+                [only] => Ok(only.multiply_by_time()),
+                _ => Err(ShardError::MigrationFailed {
+                    context: format!("dimensional analysis: TimeIntegral requires exactly one input, got {}", inputs.len()),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong input count for integral")),
+                }),
+            },
+        }
+    }
+}
+
+/// A computed check's name and whether it held — distinct from
+/// `mixing_validation::CriterionResult` (a numeric score against a
+/// threshold): this one is a named pass/fail fact about a pattern's
+/// declared structure rather than a measurement over sample data.
+///
+/// Also carries what `pattern_matcher::PatternMatcher` needs to fold this
+/// fact into a Bayesian posterior rather than treating it as the whole
+/// verdict: `prior` is what this criterion alone would assign the
+/// hypothesis "the two signals are the same physical phenomenon" before
+/// any other evidence is fused in, and `likelihood_given_h`/
+/// `likelihood_given_not_h` are `L(applies | H)`/`L(applies | ¬H)` —
+/// how likely this specific `applies` outcome is under the hypothesis and
+/// its negation respectively. Neither pair is ever `0.0`/`1.0`: a
+/// deterministic structural check can still occasionally pass by
+/// coincidence (for an unrelated pair of types that just happen to share
+/// `quantity_kind_for_type` dimensions) or fail despite a real physical
+/// relationship (a typo'd `operation`), so one criterion's evidence should
+/// never collapse `PatternMatcher`'s posterior to certainty on its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Criterion {
+    pub name: String,
+    pub applies: bool,
+    pub prior: f64,
+    pub likelihood_given_h: f64,
+    pub likelihood_given_not_h: f64,
+}
+
+/// `Criterion::likelihood_given_h`/`likelihood_given_not_h` for
+/// `check_dimensional_consistency` when `applies` is `true` — see
+/// `Criterion`'s doc comment for why neither is `1.0`/`0.0`.
+const DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_H_IF_APPLIES: f64 = 0.95;
+const DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_NOT_H_IF_APPLIES: f64 = 0.3;
+
+/// Check that `pattern`'s declared `operation`, applied to the
+/// `QuantityKind` dimensions of its `inputs`, produces the same dimension
+/// as its (single) declared output type in `produces`.
+///
+/// Errs if `operation` is `None` (nothing to check), if any input/output
+/// type name isn't recognized by `quantity_kind_for_type`, or if
+/// `produces` doesn't have exactly one output type — dimensional
+/// consistency is only meaningful against a single, specific target
+/// dimension. On success, `Criterion::applies` is `true` iff the computed
+/// output dimension matches the declared output's dimension.
+pub fn check_dimensional_consistency(pattern: &crate::core::mixing_pipeline::MixingPattern) -> Result<Criterion, ShardError> {
+    let Some(operation) = &pattern.operation else {
+        return Err(ShardError::MigrationFailed {
+            context: format!("pattern \"{}\" declares no operation to check dimensional consistency against", pattern.name),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing operation")),
+        });
+    };
+
+    let [output_type] = pattern.produces.as_slice() else {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "pattern \"{}\" must declare exactly one output type to check dimensional consistency, has {}",
+                pattern.name,
+                pattern.produces.len()
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong output count")),
+        });
+    };
+
+    let mut input_dimensions = Vec::with_capacity(pattern.inputs.len());
+    for input_type in &pattern.inputs {
+        let kind = quantity_kind_for_type(input_type).ok_or_else(|| ShardError::MigrationFailed {
+            context: format!("pattern \"{}\" input type \"{input_type}\" has no known quantity kind", pattern.name),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown quantity kind")),
+        })?;
+        input_dimensions.push(kind.dimension());
+    }
+
+    let output_kind = quantity_kind_for_type(output_type).ok_or_else(|| ShardError::MigrationFailed {
+        context: format!("pattern \"{}\" output type \"{output_type}\" has no known quantity kind", pattern.name),
+        source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown quantity kind")),
+    })?;
+
+    let computed = operation.combine(&input_dimensions)?;
+    let applies = computed == output_kind.dimension();
+    let (likelihood_given_h, likelihood_given_not_h) = if applies {
+        (DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_H_IF_APPLIES, DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_NOT_H_IF_APPLIES)
+    } else {
+        (
+            1.0 - DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_H_IF_APPLIES,
+            1.0 - DIMENSIONAL_CONSISTENCY_LIKELIHOOD_GIVEN_NOT_H_IF_APPLIES,
+        )
+    };
+    Ok(Criterion {
+        name: "dimensional_consistency".to_string(),
+        applies,
+        prior: 0.5,
+        likelihood_given_h,
+        likelihood_given_not_h,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mixing_pipeline::MixingPattern;
+
+    #[test]
+    fn induction_coupling_of_flux_derivative_yields_voltage() {
+        let mut pattern = MixingPattern::new(
+            "induction_coupling".to_string(),
+            vec!["magnetic_flux".to_string()],
+            vec!["voltage".to_string()],
+        );
+        pattern.operation = Some(Operation::TimeDerivative);
+
+        let criterion = check_dimensional_consistency(&pattern).unwrap();
+        assert_eq!(criterion.name, "dimensional_consistency");
+        assert!(criterion.applies);
+    }
+
+    #[test]
+    fn mismatched_operation_is_flagged_not_trusted() {
+        let mut pattern = MixingPattern::new(
+            "bogus".to_string(),
+            vec!["temperature".to_string()],
+            vec!["voltage".to_string()],
+        );
+        pattern.operation = Some(Operation::TimeDerivative);
+
+        let criterion = check_dimensional_consistency(&pattern).unwrap();
+        assert!(!criterion.applies);
+    }
+
+    #[test]
+    fn product_combines_every_input_dimension() {
+        let mut pattern = MixingPattern::new(
+            "force_like".to_string(),
+            vec!["accelerometer".to_string(), "accelerometer".to_string()],
+            vec!["pressure".to_string()],
+        );
+        pattern.operation = Some(Operation::Product);
+
+        // acceleration * acceleration = L^2 T^-4, not pressure (M L^-1 T^-2) —
+        // exercises a Product combination that should NOT apply.
+        let criterion = check_dimensional_consistency(&pattern).unwrap();
+        assert!(!criterion.applies);
+    }
+
+    #[test]
+    fn unrecognized_type_name_errs_rather_than_silently_passing() {
+        let mut pattern = MixingPattern::new(
+            "unknown_input".to_string(),
+            vec!["flux_capacitor_reading".to_string()],
+            vec!["voltage".to_string()],
+        );
+        pattern.operation = Some(Operation::TimeDerivative);
+
+        assert!(check_dimensional_consistency(&pattern).is_err());
+    }
+
+    #[test]
+    fn no_declared_operation_errs_rather_than_vacuously_passing() {
+        let pattern = MixingPattern::new(
+            "no_operation".to_string(),
+            vec!["temperature".to_string()],
+            vec!["voltage".to_string()],
+        );
+        assert!(check_dimensional_consistency(&pattern).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+// src/core/emitter_association.rs
+//! Emitter-to-platform association: given a stream of low-level signal
+//! interceptions, each carrying characteristic features (frequency, pulse
+//! pattern, rough bearing), estimate how many distinct physical emitters
+//! produced them and which interceptions came from which one. This is the
+//! subsystem that turns a pile of raw detections into a handful of
+//! "platforms," each with its own averaged characteristic pattern — and,
+//! via `platform_pattern_to_mixing_pattern`, a draft `MixingPattern` the
+//! same shape `pattern_extraction::propose_pattern_from_text` and
+//! `spectral_band::synthesize_spectral_pattern` already produce, so the
+//! pattern registry can grow from observed traffic instead of only from
+//! hand-authored or literature-derived entries.
+//!
+//! Clustering is agglomerative (average-linkage) over a normalized
+//! feature/bearing distance, and the cluster count is chosen by a
+//! BIC-style score — `-2 × log-likelihood + (k × dimensions + 1) ×
+//! ln(n)` under a shared-variance Gaussian model, the same model
+//! Pelleg & Moore's X-means uses to stop k-means-style clustering from
+//! over-fragmenting. As in X-means, candidate cluster counts are searched
+//! up to a caller-supplied `max_platforms` rather than all the way to `n`:
+//! letting `k` reach `n` makes every cluster a singleton with zero
+//! within-cluster variance, which is BIC's (wrong) global optimum under
+//! this model — `max_platforms` bounds the search away from that
+//! degenerate case, the same way X-means bounds its own search.
+
+use crate::core::mixing_pipeline::MixingPattern;
+use crate::error::ShardError;
+
+/// Upper bound on how many interceptions `associate_emitters` will
+/// cluster in one call. Clustering is agglomerative and recomputes
+/// cross-cluster average-linkage distances at every merge step, O(n^3) in
+/// the worst case, so an unbounded interception count would make one call
+/// unbounded too — same reasoning as `mixing_validation::MAX_SAMPLES`.
+const MAX_INTERCEPTIONS: usize = 500;
+
+/// Number of feature dimensions `squared_distance` and the BIC model
+/// operate over: frequency, pulse repetition interval, and bearing.
+const FEATURE_DIMENSIONS: f64 = 3.0;
+
+/// Floor on the per-dimension variance estimate the BIC score uses, so a
+/// candidate clustering with (near-)zero within-cluster spread doesn't
+/// send `log(variance)` to `-infinity`.
+const MIN_VARIANCE: f64 = 1e-12;
+
+/// One low-level signal interception's characteristic features.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interception {
+    pub frequency_hz: f64,
+    /// Time between successive pulses, in seconds — the "pulse pattern"
+    /// feature.
+    pub pulse_repetition_interval_s: f64,
+    /// Estimated bearing to the emitter, in degrees, `[0.0, 360.0)`.
+    pub bearing_deg: f64,
+}
+
+/// Typical scale of variation for each feature, used to normalize
+/// `squared_distance` so frequency (which varies over gigahertz) doesn't
+/// swamp bearing (which varies over degrees) in the combined metric —
+/// the same per-field normalization problem
+/// `mixing_validation::check_information_gain`'s histogram binning and
+/// `dimensional_analysis`'s typed quantities both sidestep by working in
+/// one consistent unit per field instead of pooling raw numbers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeatureScale {
+    pub frequency_hz: f64,
+    pub pulse_repetition_interval_s: f64,
+    pub bearing_deg: f64,
+}
+
+/// Squared Euclidean distance between `a` and `b` over frequency, pulse
+/// repetition interval, and bearing, each difference scaled by `scale`'s
+/// corresponding field. Bearing's difference wraps at 360 degrees (a
+/// bearing of 359 and a bearing of 1 are 2 degrees apart, not 358).
+fn squared_distance(a: &Interception, b: &Interception, scale: &FeatureScale) -> f64 {
+    let freq_term = (a.frequency_hz - b.frequency_hz) / scale.frequency_hz;
+    let pri_term = (a.pulse_repetition_interval_s - b.pulse_repetition_interval_s) / scale.pulse_repetition_interval_s;
+    let raw_bearing_diff = (a.bearing_deg - b.bearing_deg).abs() % 360.0;
+    let bearing_diff = raw_bearing_diff.min(360.0 - raw_bearing_diff);
+    let bearing_term = bearing_diff / scale.bearing_deg;
+    freq_term * freq_term + pri_term * pri_term + bearing_term * bearing_term
+}
+
+/// The circular mean of `bearings_deg` (degrees), via
+/// `atan2(mean(sin), mean(cos))` — the standard way to average angles so
+/// e.g. 359 and 1 degrees average to 0, not 180.
+fn circular_mean_deg(bearings_deg: &[f64]) -> f64 {
+    let n = bearings_deg.len() as f64;
+    let sin_sum: f64 = bearings_deg.iter().map(|b| b.to_radians().sin()).sum();
+    let cos_sum: f64 = bearings_deg.iter().map(|b| b.to_radians().cos()).sum();
+    let mean = (sin_sum / n).atan2(cos_sum / n).to_degrees();
+    if mean < 0.0 {
+        mean + 360.0
+    } else {
+        mean
+    }
+}
+
+/// A platform's averaged characteristic pattern, plus how many
+/// interceptions were attributed to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlatformPattern {
+    pub mean_frequency_hz: f64,
+    pub mean_pulse_repetition_interval_s: f64,
+    pub mean_bearing_deg: f64,
+    pub member_count: usize,
+}
+
+/// `associate_emitters`'s output: the estimated platform count, a cluster
+/// index per input interception (parallel to the input slice), and each
+/// platform's averaged pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmitterAssociationResult {
+    pub platform_count: usize,
+    pub cluster_assignments: Vec<usize>,
+    pub platform_patterns: Vec<PlatformPattern>,
+}
+
+/// Average-linkage distance between clusters `a` and `b` (lists of
+/// indices into the original `interceptions` slice): the mean of
+/// `pairwise[i][j]` over every `i` in `a` and `j` in `b`.
+fn average_linkage_distance(a: &[usize], b: &[usize], pairwise: &[Vec<f64>]) -> f64 {
+    let mut total = 0.0;
+    for &i in a {
+        for &j in b {
+            total += pairwise[i][j];
+        }
+    }
+    total / (a.len() * b.len()) as f64
+}
+
+/// Every intermediate clustering agglomerative merging passes through,
+/// from `n` singleton clusters down to one, in merge order — `levels[0]`
+/// has `n` clusters, `levels[n-1]` has exactly one.
+fn agglomerative_levels(n: usize, pairwise: &[Vec<f64>]) -> Vec<Vec<Vec<usize>>> {
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut levels = vec![clusters.clone()];
+
+    while clusters.len() > 1 {
+        let mut best = (0, 1, f64::INFINITY);
+        for i in 0..clusters.len() {
+            for j in (i + 1)..clusters.len() {
+                let d = average_linkage_distance(&clusters[i], &clusters[j], pairwise);
+                if d < best.2 {
+                    best = (i, j, d);
+                }
+            }
+        }
+        let (i, j, _) = best;
+        let merged: Vec<usize> = clusters[i].iter().chain(clusters[j].iter()).copied().collect();
+        let mut next = Vec::with_capacity(clusters.len() - 1);
+        for (idx, cluster) in clusters.into_iter().enumerate() {
+            if idx != i && idx != j {
+                next.push(cluster);
+            }
+        }
+        next.push(merged);
+        clusters = next;
+        levels.push(clusters.clone());
+    }
+
+    levels
+}
+
+/// This clustering's per-platform patterns (mean features, circular mean
+/// bearing) and the total scaled within-cluster sum of squared distances
+/// to those means — the latter feeds the BIC score.
+fn summarize(clusters: &[Vec<usize>], interceptions: &[Interception], scale: &FeatureScale) -> (Vec<PlatformPattern>, f64) {
+    let mut patterns = Vec::with_capacity(clusters.len());
+    let mut sum_sq = 0.0;
+    for cluster in clusters {
+        let members: Vec<&Interception> = cluster.iter().map(|&i| &interceptions[i]).collect();
+        let n = members.len() as f64;
+        let mean_frequency_hz = members.iter().map(|m| m.frequency_hz).sum::<f64>() / n;
+        let mean_pulse_repetition_interval_s = members.iter().map(|m| m.pulse_repetition_interval_s).sum::<f64>() / n;
+        let mean_bearing_deg = circular_mean_deg(&members.iter().map(|m| m.bearing_deg).collect::<Vec<_>>());
+        let centroid = Interception { frequency_hz: mean_frequency_hz, pulse_repetition_interval_s: mean_pulse_repetition_interval_s, bearing_deg: mean_bearing_deg };
+        for member in &members {
+            sum_sq += squared_distance(member, &centroid, scale);
+        }
+        patterns.push(PlatformPattern {
+            mean_frequency_hz,
+            mean_pulse_repetition_interval_s,
+            mean_bearing_deg,
+            member_count: members.len(),
+        });
+    }
+    (patterns, sum_sq)
+}
+
+/// BIC-style model-selection score for a clustering of `n` points into
+/// `k` clusters with total (scaled) within-cluster sum of squared
+/// distances `sum_sq`, under a shared-variance Gaussian model over
+/// `FEATURE_DIMENSIONS` dimensions: `-2 × log-likelihood + (k ×
+/// FEATURE_DIMENSIONS + 1) × ln(n)`. Lower is better — the same
+/// direction `CriterionResult`'s threshold comparisons and
+/// `PatternMatcher`'s posterior share isn't implied here since this
+/// isn't itself a probability, just a model-selection score to minimize.
+fn bic_score(n: usize, k: usize, sum_sq: f64) -> f64 {
+    let n = n as f64;
+    let k = k as f64;
+    let total_values = n * FEATURE_DIMENSIONS;
+    let variance = (sum_sq / total_values).max(MIN_VARIANCE);
+    let log_likelihood = -0.5 * total_values * (2.0 * std::f64::consts::PI * variance).ln() - 0.5 * total_values;
+    let free_params = k * FEATURE_DIMENSIONS + 1.0;
+    -2.0 * log_likelihood + free_params * n.ln()
+}
+
+/// Cluster `interceptions` into an estimated set of emitting platforms:
+/// agglomerative average-linkage clustering over `squared_distance`,
+/// picking the cluster count in `1..=max_platforms` that minimizes
+/// `bic_score`.
+///
+/// Errs if `interceptions` is empty, exceeds `MAX_INTERCEPTIONS`, or if
+/// `max_platforms` is `0` or exceeds `interceptions.len()`.
+pub fn associate_emitters(
+    interceptions: &[Interception],
+    scale: &FeatureScale,
+    max_platforms: usize,
+) -> Result<EmitterAssociationResult, ShardError> {
+    let n = interceptions.len();
+    if n == 0 {
+        return Err(ShardError::MigrationFailed {
+            context: "associate_emitters: interceptions must not be empty".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "no interceptions")),
+        });
+    }
+    if n > MAX_INTERCEPTIONS {
+        return Err(ShardError::MigrationFailed {
+            context: format!("associate_emitters: {n} interceptions exceeds the maximum of {MAX_INTERCEPTIONS}"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many interceptions")),
+        });
+    }
+    if max_platforms == 0 || max_platforms > n {
+        return Err(ShardError::MigrationFailed {
+            context: format!("associate_emitters: max_platforms {max_platforms} must be in 1..={n}"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "max_platforms out of range")),
+        });
+    }
+
+    let pairwise: Vec<Vec<f64>> =
+        interceptions.iter().map(|a| interceptions.iter().map(|b| squared_distance(a, b, scale)).collect()).collect();
+    let levels = agglomerative_levels(n, &pairwise);
+
+    let mut best: Option<(usize, f64, Vec<Vec<usize>>)> = None;
+    for clusters in &levels {
+        let k = clusters.len();
+        if k > max_platforms {
+            continue;
+        }
+        let (_, sum_sq) = summarize(clusters, interceptions, scale);
+        let score = bic_score(n, k, sum_sq);
+        if best.as_ref().map_or(true, |(_, best_score, _)| score < *best_score) {
+            best = Some((k, score, clusters.clone()));
+        }
+    }
+    let (_, _, best_clusters) = best.expect("levels always has at least one clustering with k <= max_platforms (k=1)");
+
+    let mut cluster_assignments = vec![0usize; n];
+    for (cluster_index, cluster) in best_clusters.iter().enumerate() {
+        for &member in cluster {
+            cluster_assignments[member] = cluster_index;
+        }
+    }
+    let (platform_patterns, _) = summarize(&best_clusters, interceptions, scale);
+
+    Ok(EmitterAssociationResult { platform_count: platform_patterns.len(), cluster_assignments, platform_patterns })
+}
+
+/// Draft a `MixingPattern` for platform `platform_index`, attributed to
+/// `"association_engine"`. `pattern`'s averaged features are not carried
+/// into the result: only `platform_index` is used, in the name. The auto-clustered
+/// counterpart to `pattern_extraction::propose_pattern_from_text` and
+/// `spectral_band::synthesize_spectral_pattern`'s drafts. Has no
+/// citations (an emitter's characteristic pattern is learned from
+/// observed traffic, not traceable to a literature source the way those
+/// two are), so — same as their output — it's this function's caller's
+/// job to run it through `MixingPatternStore::add_pattern` (landing
+/// `Unvalidated`) and decide separately whether it ever earns a citation
+/// and gets `promote`d.
+pub fn platform_pattern_to_mixing_pattern(pattern: &PlatformPattern, platform_index: usize) -> MixingPattern {
+    let mut mixing_pattern = MixingPattern::new(
+        format!("emitter_platform_{platform_index}"),
+        vec!["frequency".to_string(), "pulse_repetition_interval".to_string(), "bearing".to_string()],
+        vec!["platform_identity".to_string()],
+    );
+    mixing_pattern.contributed_by = "association_engine".to_string();
+    let _ = pattern;
+    mixing_pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale() -> FeatureScale {
+        FeatureScale { frequency_hz: 1e6, pulse_repetition_interval_s: 1e-3, bearing_deg: 10.0 }
+    }
+
+    fn two_well_separated_clusters() -> Vec<Interception> {
+        let mut interceptions = Vec::new();
+        for i in 0..6 {
+            let jitter = i as f64 * 0.01e6;
+            interceptions.push(Interception { frequency_hz: 1.0e9 + jitter, pulse_repetition_interval_s: 1.0e-3, bearing_deg: 45.0 });
+        }
+        for i in 0..6 {
+            let jitter = i as f64 * 0.01e6;
+            interceptions.push(Interception { frequency_hz: 5.0e9 + jitter, pulse_repetition_interval_s: 4.0e-3, bearing_deg: 200.0 });
+        }
+        interceptions
+    }
+
+    #[test]
+    fn associate_emitters_rejects_an_empty_slice() {
+        assert!(associate_emitters(&[], &scale(), 1).is_err());
+    }
+
+    #[test]
+    fn associate_emitters_rejects_max_platforms_zero_or_beyond_n() {
+        let interceptions = two_well_separated_clusters();
+        assert!(associate_emitters(&interceptions, &scale(), 0).is_err());
+        assert!(associate_emitters(&interceptions, &scale(), interceptions.len() + 1).is_err());
+    }
+
+    #[test]
+    fn associate_emitters_recovers_two_well_separated_platforms() {
+        let interceptions = two_well_separated_clusters();
+        let result = associate_emitters(&interceptions, &scale(), 4).unwrap();
+        assert_eq!(result.platform_count, 2);
+        assert_eq!(result.cluster_assignments.len(), interceptions.len());
+
+        let first_cluster = result.cluster_assignments[0];
+        assert!(result.cluster_assignments[..6].iter().all(|&c| c == first_cluster));
+        let second_cluster = result.cluster_assignments[6];
+        assert_ne!(first_cluster, second_cluster);
+        assert!(result.cluster_assignments[6..].iter().all(|&c| c == second_cluster));
+    }
+
+    #[test]
+    fn associate_emitters_respects_max_platforms() {
+        let interceptions = two_well_separated_clusters();
+        let result = associate_emitters(&interceptions, &scale(), 1).unwrap();
+        assert_eq!(result.platform_count, 1);
+    }
+
+    #[test]
+    fn circular_mean_averages_across_the_0_360_wrap() {
+        assert!((circular_mean_deg(&[359.0, 1.0]) - 0.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn platform_pattern_to_mixing_pattern_is_attributed_to_the_association_engine() {
+        let pattern = PlatformPattern {
+            mean_frequency_hz: 1.0e9,
+            mean_pulse_repetition_interval_s: 1.0e-3,
+            mean_bearing_deg: 45.0,
+            member_count: 6,
+        };
+        let mixing_pattern = platform_pattern_to_mixing_pattern(&pattern, 0);
+        assert_eq!(mixing_pattern.contributed_by, "association_engine");
+        assert_eq!(mixing_pattern.name, "emitter_platform_0");
+        assert!(mixing_pattern.citations.is_empty());
+    }
+}
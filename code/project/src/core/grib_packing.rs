@@ -0,0 +1,344 @@
+// src/core/grib_packing.rs
+//! GRIB-style simple packing for time-series signal buffers, so a
+//! "compressibility" pattern criterion measures a real packed/raw byte
+//! ratio instead of asserting one. Each segment is encoded as a reference
+//! value `R` (the scaled minimum sample), a signed binary scale factor `E`,
+//! a signed decimal scale factor `D`, and a bit width `num_bits`, following
+//! the WMO GRIB simple-packing scheme: `Y = (R + X * 2^E) / 10^D`, with `X`
+//! the unsigned per-sample code `pack` stores.
+//!
+//! `D` is caller-chosen (how much decimal precision the segment keeps
+//! before packing); `E` is chosen by `pack` itself, growing from `0` until
+//! the scaled dynamic range fits in `MAX_NUM_BITS`, and `num_bits` is then
+//! the smallest width that range actually needs. Round-trip fidelity is
+//! only guaranteed up to the rounding error those two scale factors
+//! introduce — see `pack`/`unpack`'s tests for the enforced error bound.
+
+use crate::error::ShardError;
+
+/// Upper bound on `PackedSegment::num_bits`. `pack` grows the binary scale
+/// factor `E` until the scaled dynamic range fits this many bits, so this
+/// bounds both the packed size and the per-sample decode cost; an
+/// unbounded bit width would otherwise let a huge-dynamic-range buffer
+/// force an arbitrarily large `values` encoding.
+const MAX_NUM_BITS: u32 = 32;
+
+/// Upper bound on `|PackOptions::decimal_scale|`. `pack` computes
+/// `10f64.powi(decimal_scale)` to scale every sample before rounding it to
+/// an `i64`; past this bound the multiplier is large enough to push
+/// ordinary sample magnitudes beyond what an `i64` can hold (overflowing
+/// the `range` computation below) or, for a large negative scale, collapse
+/// every sample to the same rounded value. Real-world decimal precision
+/// needs are nowhere near this many digits.
+const MAX_DECIMAL_SCALE: i32 = 15;
+
+/// Caller-chosen packing precision for [`pack`].
+#[derive(Clone, Copy, Debug)]
+pub struct PackOptions {
+    /// Decimal scale factor `D`: samples are rounded to the nearest
+    /// `10^-decimal_scale` before binary packing. Higher retains more
+    /// precision at the cost of a wider dynamic range to pack.
+    pub decimal_scale: i32,
+}
+
+/// A packed time-series segment: `values` holds one unsigned code per
+/// present sample (a sample `bitmap` marks missing contributes no code),
+/// and `reference_value`/`binary_scale`/`decimal_scale`/`num_bits` are GRIB
+/// simple-packing's `R`/`E`/`D`/`NumBits`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedSegment {
+    pub reference_value: f64,
+    pub binary_scale: i32,
+    pub decimal_scale: i32,
+    pub num_bits: u32,
+    pub sample_count: usize,
+    pub values: Vec<u32>,
+    /// `Some(bitmap)` (one entry per original sample, `true` meaning
+    /// present) iff at least one sample was missing; `None` when every
+    /// sample was present, so a fully-populated segment doesn't pay the
+    /// bitmap's size.
+    pub bitmap: Option<Vec<bool>>,
+}
+
+impl PackedSegment {
+    /// Packed size in bytes: `values` bit-packed to `num_bits` each, plus
+    /// one bit per sample for `bitmap` if present, plus this segment's
+    /// fixed header (`reference_value`, `binary_scale`, `decimal_scale`,
+    /// `num_bits`, `sample_count`).
+    pub fn packed_bytes(&self) -> usize {
+        const HEADER_BYTES: usize = 8 + 4 + 4 + 4 + 8; // f64 + i32 + i32 + u32 + usize-as-u64
+        let values_bits = self.values.len() * self.num_bits as usize;
+        let values_bytes = values_bits.div_ceil(8);
+        let bitmap_bytes = if self.bitmap.is_some() { self.sample_count.div_ceil(8) } else { 0 };
+        HEADER_BYTES + values_bytes + bitmap_bytes
+    }
+
+    /// Raw size in bytes had every sample stayed an 8-byte `f64` — the same
+    /// per-sample encoding `quantize_to_bytes` in `mixing_validation` uses.
+    pub fn raw_bytes(&self) -> usize {
+        self.sample_count * 8
+    }
+
+    /// `packed_bytes() / raw_bytes()` — the ratio a "compressibility"
+    /// criterion thresholds against. `0.0` for an empty segment (nothing to
+    /// ratio).
+    pub fn compression_ratio(&self) -> f64 {
+        if self.sample_count == 0 {
+            return 0.0;
+        }
+        self.packed_bytes() as f64 / self.raw_bytes() as f64
+    }
+}
+
+/// Smallest bit width able to represent every integer in `[0, max_code]`.
+fn bits_needed(max_code: u64) -> u32 {
+    if max_code == 0 {
+        0
+    } else {
+        64 - max_code.leading_zeros()
+    }
+}
+
+/// Pack `samples` (use `f64::NAN` for a missing/undefined sample) per
+/// `opts`. Chooses the reference value, binary scale, and bit width as
+/// described in the module doc comment, then rounds each present sample to
+/// its unsigned code. Errs if `opts.decimal_scale` exceeds
+/// `MAX_DECIMAL_SCALE` in magnitude.
+pub fn pack(samples: &[f64], opts: &PackOptions) -> Result<PackedSegment, ShardError> {
+    if opts.decimal_scale.unsigned_abs() > MAX_DECIMAL_SCALE as u32 {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "pack: decimal_scale {} exceeds the maximum magnitude of {MAX_DECIMAL_SCALE}",
+                opts.decimal_scale
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "decimal_scale out of range")),
+        });
+    }
+
+    let decimal_multiplier = 10f64.powi(opts.decimal_scale);
+
+    // `(v * decimal_multiplier) as i64` saturates rather than errors once
+    // the scaled magnitude passes `i64::MAX`, which would silently collapse
+    // distinct samples onto the same code instead of reporting the
+    // overflow, so reject any sample whose scaled magnitude gets close
+    // enough to `i64::MAX` for that saturation to be a risk.
+    const SAFE_SCALED_MAGNITUDE: f64 = (i64::MAX / 2) as f64;
+    let mut present_scaled = Vec::with_capacity(samples.len());
+    for v in samples.iter().filter(|v| !v.is_nan()) {
+        let scaled = v * decimal_multiplier;
+        if scaled.abs() > SAFE_SCALED_MAGNITUDE {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "pack: sample {v} scaled by decimal_scale {} would overflow the packer's integer representation",
+                    opts.decimal_scale
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "scaled sample out of range")),
+            });
+        }
+        present_scaled.push(scaled.round() as i64);
+    }
+
+    let bitmap =
+        if present_scaled.len() == samples.len() { None } else { Some(samples.iter().map(|v| !v.is_nan()).collect()) };
+
+    if present_scaled.is_empty() {
+        return Ok(PackedSegment {
+            reference_value: 0.0,
+            binary_scale: 0,
+            decimal_scale: opts.decimal_scale,
+            num_bits: 0,
+            sample_count: samples.len(),
+            values: Vec::new(),
+            bitmap,
+        });
+    }
+
+    let min_scaled = *present_scaled.iter().min().unwrap();
+    let max_scaled = *present_scaled.iter().max().unwrap();
+    // Widen to `i128` before subtracting: `max_scaled`/`min_scaled` are
+    // independently-clamped `i64`s (each sample rounds and casts on its
+    // own), so their difference can exceed what `i64` arithmetic holds even
+    // though each endpoint individually fits.
+    let range = (max_scaled as i128 - min_scaled as i128) as u64;
+
+    // `max_code` must be derived the same way codes are actually computed
+    // below (rounded float division), not a floored integer shift of
+    // `range`: rounding a range that sits just past a power-of-two boundary
+    // (e.g. range=1023, divisor=2 rounds 511.5 up to 512) can need one more
+    // bit than `range >> binary_scale` alone would suggest.
+    let mut binary_scale = 0i32;
+    let mut max_code = range;
+    let mut num_bits = bits_needed(max_code);
+    while num_bits > MAX_NUM_BITS {
+        binary_scale += 1;
+        max_code = (range as f64 / 2f64.powi(binary_scale)).round() as u64;
+        num_bits = bits_needed(max_code);
+    }
+
+    let divisor = 2f64.powi(binary_scale);
+    // Widen to `i128` before subtracting, same as `range` above: `scaled`
+    // and `min_scaled` are independently-clamped `i64`s, so their
+    // difference can exceed `i64`'s range even though each fits on its own.
+    let values: Vec<u32> = present_scaled
+        .iter()
+        .map(|&scaled| (((scaled as i128 - min_scaled as i128) as f64) / divisor).round() as u32)
+        .collect();
+
+    Ok(PackedSegment {
+        reference_value: min_scaled as f64,
+        binary_scale,
+        decimal_scale: opts.decimal_scale,
+        num_bits,
+        sample_count: samples.len(),
+        values,
+        bitmap,
+    })
+}
+
+/// Reconstruct `segment`'s samples: `Y = (R + X * 2^E) / 10^D` for every
+/// present sample, `sentinel` for every sample `segment.bitmap` marks
+/// missing. Errs if `segment.bitmap` is `Some` but doesn't have exactly one
+/// entry per `segment.values` present-marker, or its present-count doesn't
+/// match `segment.values.len()` — a malformed segment shouldn't silently
+/// misalign values with positions.
+pub fn unpack(segment: &PackedSegment, sentinel: f64) -> Result<Vec<f64>, ShardError> {
+    let decimal_divisor = 10f64.powi(segment.decimal_scale);
+    let binary_multiplier = 2f64.powi(segment.binary_scale);
+    let decode = |code: u32| (segment.reference_value + code as f64 * binary_multiplier) / decimal_divisor;
+
+    match &segment.bitmap {
+        None => {
+            if segment.values.len() != segment.sample_count {
+                return Err(malformed_segment_error(segment));
+            }
+            Ok(segment.values.iter().map(|&code| decode(code)).collect())
+        }
+        Some(bitmap) => {
+            if bitmap.len() != segment.sample_count || bitmap.iter().filter(|&&present| present).count() != segment.values.len() {
+                return Err(malformed_segment_error(segment));
+            }
+            let mut values = segment.values.iter();
+            Ok(bitmap.iter().map(|&present| if present { decode(*values.next().unwrap()) } else { sentinel }).collect())
+        }
+    }
+}
+
+fn malformed_segment_error(segment: &PackedSegment) -> ShardError {
+    ShardError::MigrationFailed {
+        context: format!(
+            "packed segment has {} values and {} declared samples, but its bitmap doesn't reconcile the two",
+            segment.values.len(),
+            segment.sample_count
+        ),
+        source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "bitmap/values length mismatch")),
+    }
+}
+
+/// Pack `samples` with `opts` and compare the resulting
+/// `PackedSegment::compression_ratio` against `threshold`, returning it as
+/// a `mixing_validation::CriterionResult` (`score` is the measured ratio,
+/// `passed` is the threshold comparison) rather than
+/// `dimensional_analysis::Criterion`: this is a measurement over sample
+/// data, the same category `CriterionResult` already covers, not a
+/// pass/fail fact about a pattern's declared structure.
+pub fn check_compressibility_criterion(
+    samples: &[f64],
+    opts: &PackOptions,
+    threshold: f64,
+) -> Result<crate::core::mixing_validation::CriterionResult, ShardError> {
+    let ratio = pack(samples, opts)?.compression_ratio();
+    Ok(crate::core::mixing_validation::CriterionResult { score: ratio, passed: ratio <= threshold })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_within_the_scale_factors_error_bound() {
+        let samples: Vec<f64> = (0..200).map(|i| (i as f64 * 0.37).sin() * 100.0).collect();
+        let opts = PackOptions { decimal_scale: 2 };
+        let segment = pack(&samples, &opts).unwrap();
+        let unpacked = unpack(&segment, f64::NAN).unwrap();
+
+        let decimal_error = 0.5 / 10f64.powi(opts.decimal_scale);
+        let binary_error = 2f64.powi(segment.binary_scale) / 2.0 / 10f64.powi(opts.decimal_scale);
+        let max_error = decimal_error + binary_error;
+
+        for (original, reconstructed) in samples.iter().zip(unpacked.iter()) {
+            assert!(
+                (original - reconstructed).abs() <= max_error,
+                "original={original} reconstructed={reconstructed} exceeds max_error={max_error}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_samples_round_trip_to_the_sentinel() {
+        let samples = vec![1.0, f64::NAN, 3.0, f64::NAN, 5.0];
+        let segment = pack(&samples, &PackOptions { decimal_scale: 0 }).unwrap();
+        assert!(segment.bitmap.is_some());
+
+        let unpacked = unpack(&segment, -1.0).unwrap();
+        assert_eq!(unpacked, vec![1.0, -1.0, 3.0, -1.0, 5.0]);
+    }
+
+    #[test]
+    fn fully_present_segment_carries_no_bitmap() {
+        let samples = vec![1.0, 2.0, 3.0];
+        let segment = pack(&samples, &PackOptions { decimal_scale: 0 }).unwrap();
+        assert!(segment.bitmap.is_none());
+    }
+
+    #[test]
+    fn constant_signal_needs_zero_bits() {
+        let samples = vec![42.0; 50];
+        let segment = pack(&samples, &PackOptions { decimal_scale: 1 }).unwrap();
+        assert_eq!(segment.num_bits, 0);
+        assert_eq!(unpack(&segment, f64::NAN).unwrap(), samples);
+    }
+
+    #[test]
+    fn wide_dynamic_range_stays_within_max_num_bits() {
+        let samples = vec![0.0, 1e12];
+        let segment = pack(&samples, &PackOptions { decimal_scale: 3 }).unwrap();
+        assert!(segment.num_bits <= MAX_NUM_BITS);
+    }
+
+    #[test]
+    fn compression_ratio_is_well_below_one_for_a_long_low_entropy_signal() {
+        let samples = vec![10.0; 1000];
+        let criterion = check_compressibility_criterion(&samples, &PackOptions { decimal_scale: 1 }, 0.5).unwrap();
+        assert!(criterion.score < 0.5);
+        assert!(criterion.passed);
+    }
+
+    #[test]
+    fn empty_segment_has_zero_compression_ratio() {
+        let segment = pack(&[], &PackOptions { decimal_scale: 0 }).unwrap();
+        assert_eq!(segment.compression_ratio(), 0.0);
+    }
+
+    #[test]
+    fn rejects_decimal_scale_beyond_the_maximum_magnitude() {
+        let samples = vec![1.0, 2.0];
+        assert!(pack(&samples, &PackOptions { decimal_scale: MAX_DECIMAL_SCALE + 1 }).is_err());
+        assert!(pack(&samples, &PackOptions { decimal_scale: -(MAX_DECIMAL_SCALE + 1) }).is_err());
+        assert!(pack(&samples, &PackOptions { decimal_scale: MAX_DECIMAL_SCALE }).is_ok());
+    }
+
+    #[test]
+    fn widely_separated_samples_do_not_overflow_the_range_computation() {
+        let samples = vec![-4e18, 4e18];
+        let segment = pack(&samples, &PackOptions { decimal_scale: 0 }).unwrap();
+        let unpacked = unpack(&segment, f64::NAN).unwrap();
+        assert!((unpacked[0] - samples[0]).abs() / samples[0].abs() < 1e-6);
+        assert!((unpacked[1] - samples[1]).abs() / samples[1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn rejects_a_sample_whose_scaled_magnitude_would_overflow_the_packers_integer_representation() {
+        let samples = vec![1.0, 9e18];
+        assert!(pack(&samples, &PackOptions { decimal_scale: 0 }).is_err());
+    }
+}
@@ -3,14 +3,204 @@ use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::core::persistence::{PersistenceBackend, PersistenceError};
+use crate::core::threshold_handlers::ThresholdHandlerRegistry;
+
+pub use crate::core::threshold_handlers::ThresholdHandler;
+
+/// Online P² (Jain & Chlamtac) quantile estimator for a single target
+/// quantile `p`. Tracks five markers (min, three interior estimates, max)
+/// so a running p50/p90/p99 costs O(1) memory instead of buffering every
+/// observed sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct P2Quantile {
+    p: f64,
+    count: u64,
+    // Buffer for the first 5 samples, used to seed the markers.
+    init: Vec<f64>,
+    // Marker heights, positions, desired positions, and desired increments.
+    q: [f64; 5],
+    n: [f64; 5],
+    np: [f64; 5],
+    dn: [f64; 5],
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        Self {
+            p,
+            count: 0,
+            init: Vec::with_capacity(5),
+            q: [0.0; 5],
+            n: [0.0; 5],
+            np: [0.0; 5],
+            dn: [1.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    fn observe(&mut self, x: f64) {
+        self.count += 1;
+
+        if self.init.len() < 5 {
+            self.init.push(x);
+            if self.init.len() == 5 {
+                self.init.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                for i in 0..5 {
+                    self.q[i] = self.init[i];
+                    self.n[i] = (i + 1) as f64;
+                }
+                let p = self.p;
+                self.np = [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0];
+            }
+            return;
+        }
+
+        // Find the cell k the new observation falls into, nudging the
+        // extremes if it lands outside the current range.
+        let mut k;
+        if x < self.q[0] {
+            self.q[0] = x;
+            k = 0;
+        } else if x >= self.q[4] {
+            self.q[4] = x;
+            k = 3;
+        } else {
+            k = 0;
+            for i in 0..4 {
+                if self.q[i] <= x && x < self.q[i + 1] {
+                    k = i;
+                    break;
+                }
+            }
+        }
+        // Guard against pathological float ties landing past the last cell.
+        if k > 3 { k = 3; }
+
+        for i in (k + 1)..5 {
+            self.n[i] += 1.0;
+        }
+        for i in 0..5 {
+            self.np[i] += self.dn[i];
+        }
+
+        for i in 1..4 {
+            let d = self.np[i] - self.n[i];
+            if (d >= 1.0 && self.n[i + 1] - self.n[i] > 1.0)
+                || (d <= -1.0 && self.n[i - 1] - self.n[i] < -1.0)
+            {
+                let d = if d >= 0.0 { 1.0 } else { -1.0 };
+                let qp = self.parabolic(i, d);
+                if self.q[i - 1] < qp && qp < self.q[i + 1] {
+                    self.q[i] = qp;
+                } else {
+                    self.q[i] = self.linear(i, d);
+                }
+                self.n[i] += d;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+    }
+
+    /// Best current estimate of the tracked quantile.
+    fn estimate(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else if self.init.len() < 5 {
+            // Not enough samples yet to run P²; fall back to an exact
+            // nearest-rank estimate over the buffered values.
+            let mut sorted = self.init.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let idx = ((sorted.len() as f64 - 1.0) * self.p).round() as usize;
+            sorted.get(idx).copied()
+        } else {
+            Some(self.q[2])
+        }
+    }
+}
+
+/// Bounded, O(1)-memory-per-key summary of a metric's samples: a running
+/// count/sum for the average, plus P² estimators for p50/p90/p99 so a
+/// long-running node doesn't need to retain every observation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MetricSummary {
+    count: u64,
+    sum: f64,
+    p50: P2Quantile,
+    p90: P2Quantile,
+    p99: P2Quantile,
+}
+
+impl MetricSummary {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            p50: P2Quantile::new(0.50),
+            p90: P2Quantile::new(0.90),
+            p99: P2Quantile::new(0.99),
+        }
+    }
+
+    fn observe(&mut self, value: u64) {
+        self.count += 1;
+        self.sum += value as f64;
+        let x = value as f64;
+        self.p50.observe(x);
+        self.p90.observe(x);
+        self.p99.observe(x);
+    }
+
+    fn average(&self) -> Option<f64> {
+        if self.count == 0 { None } else { Some(self.sum / self.count as f64) }
+    }
+}
+
+/// Snapshot of a metric's latency/size distribution at a point in time.
+#[derive(Debug, Clone, Copy)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+const METRICS_SNAPSHOT_KEY: &str = "metrics";
+
+#[derive(Clone)]
 pub struct Metrics {
-    metrics: Arc<Mutex<HashMap<String, Vec<u64>>>>,
+    metrics: Arc<Mutex<HashMap<String, MetricSummary>>>,
     timestamps: Arc<Mutex<HashMap<String, Instant>>>,
     thresholds: Arc<Mutex<HashMap<String, u64>>>,
+    persistence: Option<Arc<dyn PersistenceBackend>>,
+    handlers: ThresholdHandlerRegistry,
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics")
+            .field("metrics", &self.metrics)
+            .field("timestamps", &self.timestamps)
+            .field("thresholds", &self.thresholds)
+            .field("persistence", &self.persistence.is_some())
+            .field("handlers", &self.handlers)
+            .finish()
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ThresholdAction {
     TriggerResync,
     ImmediateReconciliation,
@@ -31,15 +221,53 @@ impl Metrics {
             metrics: Arc::new(Mutex::new(HashMap::new())),
             timestamps: Arc::new(Mutex::new(HashMap::new())),
             thresholds: Arc::new(Mutex::new(thresholds)),
+            persistence: None,
+            handlers: ThresholdHandlerRegistry::new(),
         }
     }
 
+    /// Register a handler to run whenever `action` fires, e.g. wiring
+    /// `ReduceParticipantSet` to `CircuitBreaker` or `TriggerResync` to
+    /// `EvolutionManager`.
+    pub fn register_handler(&self, action: ThresholdAction, handler: Arc<dyn ThresholdHandler>) {
+        self.handlers.register(action, handler);
+    }
+
+    /// Build a `Metrics` that persists/restores its summaries through
+    /// `backend` instead of living purely in memory.
+    pub fn with_persistence(backend: Arc<dyn PersistenceBackend>) -> Self {
+        Self { persistence: Some(backend), ..Self::new() }
+    }
+
+    /// Serialize all current metric summaries and write them to the
+    /// configured persistence backend, if any.
+    pub fn persist(&self) -> Result<(), PersistenceError> {
+        let Some(backend) = &self.persistence else { return Ok(()) };
+        let metrics = self.metrics.lock().unwrap();
+        let bytes = serde_json::to_vec(&*metrics)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        backend.save(METRICS_SNAPSHOT_KEY, &bytes)
+    }
+
+    /// Load metric summaries from the configured persistence backend,
+    /// replacing the current in-memory state. No-op if no backend is
+    /// configured or no snapshot has been saved yet.
+    pub fn restore(&self) -> Result<(), PersistenceError> {
+        let Some(backend) = &self.persistence else { return Ok(()) };
+        let Some(bytes) = backend.load(METRICS_SNAPSHOT_KEY)? else { return Ok(()) };
+        let restored: HashMap<String, MetricSummary> = serde_json::from_slice(&bytes)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        *self.metrics.lock().unwrap() = restored;
+        Ok(())
+    }
+
     pub fn record(&self, key: &str, value: u64) {
         let mut metrics = self.metrics.lock().unwrap();
         metrics.entry(key.to_string())
-            .or_insert_with(Vec::new)
-            .push(value);
-        
+            .or_insert_with(MetricSummary::new)
+            .observe(value);
+        drop(metrics);
+
         // Check if threshold is exceeded
         if let Some(threshold) = self.get_threshold(key) {
             if value > threshold {
@@ -60,17 +288,17 @@ impl Metrics {
             self.record(key, duration);
         }
     }
-    
+
     pub fn set_threshold(&self, key: &str, value: u64) {
         let mut thresholds = self.thresholds.lock().unwrap();
         thresholds.insert(key.to_string(), value);
     }
-    
+
     pub fn get_threshold(&self, key: &str) -> Option<u64> {
         let thresholds = self.thresholds.lock().unwrap();
         thresholds.get(key).cloned()
     }
-    
+
     pub fn handle_threshold_exceeded(&self, key: &str, value: u64) {
         let action = match key {
             "neurosynchrony_latency" => ThresholdAction::TriggerResync,
@@ -79,7 +307,7 @@ impl Metrics {
             "federated_aggregation_latency" => ThresholdAction::ReduceParticipantSet,
             _ => return, // No action for unknown metrics
         };
-        
+
         // Handle the action (placeholder implementation)
         match action {
             ThresholdAction::TriggerResync => {
@@ -99,16 +327,25 @@ impl Metrics {
                 eprintln!("Federated aggregation latency exceeded threshold: {}ms. Reducing participant set.", value);
             }
         }
+
+        self.handlers.dispatch(&action, key, value);
     }
-    
+
     pub fn get_average(&self, key: &str) -> Option<f64> {
         let metrics = self.metrics.lock().unwrap();
-        if let Some(values) = metrics.get(key) {
-            if !values.is_empty() {
-                let sum: u64 = values.iter().sum();
-                return Some(sum as f64 / values.len() as f64);
-            }
-        }
-        None
+        metrics.get(key).and_then(|summary| summary.average())
+    }
+
+    /// Online p50/p90/p99 estimate for `key`, accurate to within the P²
+    /// algorithm's approximation error. Returns `None` if `key` has no
+    /// recorded samples yet.
+    pub fn get_percentiles(&self, key: &str) -> Option<Percentiles> {
+        let metrics = self.metrics.lock().unwrap();
+        let summary = metrics.get(key)?;
+        Some(Percentiles {
+            p50: summary.p50.estimate()?,
+            p90: summary.p90.estimate()?,
+            p99: summary.p99.estimate()?,
+        })
     }
 }
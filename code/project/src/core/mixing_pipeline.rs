@@ -0,0 +1,955 @@
+// src/core/mixing_pipeline.rs
+//! Multi-stage sensor fusion on top of `mixing_validation`'s pairwise
+//! checks: a `MixingPattern` is a named transform from a set of input
+//! modality types to an output type, and `plan_pipeline` chains patterns
+//! together into an ordered DAG that turns an available sensor set into a
+//! requested target type — the N-ary generalization of validating one
+//! pair of signals at a time.
+//!
+//! This crate doesn't model real Holochain DHT links between entries
+//! anywhere (see `MigrationPlan` in `sharding::migration`, which stores its
+//! own plain fields rather than walking `get_links`), so the
+//! `PatternComposedWith`/`PatternConflictsWith` relationship between
+//! patterns is likewise modeled as direct `ChunkHash` references on
+//! `MixingPattern` itself rather than as a `LinkTypes` entry/link pair.
+//!
+//! A pattern's provenance is a `Vec<Citation>` rather than free text:
+//! `validate_pattern` checks each citation's `identifier` against the
+//! syntactic shape its `identifier_kind` claims before the pattern is
+//! accepted, and `get_patterns_by_citation` looks patterns up by that same
+//! identifier.
+
+use crate::core::antenna_pattern::AntennaPatternRecord;
+use crate::core::chunked_store::ChunkHash;
+use crate::core::dimensional_analysis::{check_dimensional_consistency, Criterion, Operation};
+use crate::error::ShardError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::RwLock;
+
+/// Bibliographic identifier scheme a [`Citation`]'s `identifier` is claimed
+/// to follow. `MixingPattern::validate_pattern` checks the claim against the
+/// identifier's actual shape, so a citation can't declare e.g. `Doi` while
+/// carrying a bare URL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdentifierKind {
+    Doi,
+    Iso,
+    Astm,
+    ArXiv,
+    Url,
+}
+
+/// A structured bibliographic citation backing a [`MixingPattern`]'s
+/// provenance — replaces a free-text string a pattern could previously
+/// attach without `validate_pattern` ever inspecting it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Citation {
+    pub title: String,
+    pub identifier_kind: IdentifierKind,
+    pub identifier: String,
+    pub authors: Vec<String>,
+    pub year: u32,
+}
+
+/// Splits `s` into its leading run of ASCII digits and whatever follows —
+/// shared by the `Iso`/`Astm` shape checks below, both of which need to
+/// pull a leading number off before looking at an optional `-`/`:` suffix.
+fn split_leading_digits(s: &str) -> (&str, &str) {
+    let digit_len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(digit_len)
+}
+
+/// True if `identifier` has the syntactic shape `identifier_kind` claims.
+/// Checks shape only, not that the identifier resolves to a real record —
+/// the same boundary `ModalityRelationship::validate` draws for its own
+/// free-text `evidence` field.
+fn identifier_matches_kind(identifier: &str, identifier_kind: IdentifierKind) -> bool {
+    match identifier_kind {
+        // "10.<digits>(.<digits>)*/<suffix>" — a registrant code, optionally
+        // with dot-separated sub-prefix elements, then a non-empty suffix.
+        // https://www.doi.org/doi_handbook/2_Numbering.html
+        IdentifierKind::Doi => {
+            let Some(rest) = identifier.strip_prefix("10.") else {
+                return false;
+            };
+            match rest.split_once('/') {
+                Some((registrant, suffix)) => {
+                    !registrant.is_empty()
+                        && registrant.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+                        && !suffix.is_empty()
+                }
+                None => false,
+            }
+        }
+        // "ISO <digits>", optionally with a "-<part>" and/or ":<year>" suffix,
+        // e.g. "ISO 8601" or "ISO 6892-1:2019".
+        IdentifierKind::Iso => match identifier.strip_prefix("ISO ") {
+            Some(rest) => {
+                let (number, rest) = split_leading_digits(rest);
+                if number.is_empty() {
+                    return false;
+                }
+                let rest = match rest.strip_prefix('-') {
+                    Some(after_dash) => {
+                        let (part, remainder) = split_leading_digits(after_dash);
+                        if part.is_empty() {
+                            return false;
+                        }
+                        remainder
+                    }
+                    None => rest,
+                };
+                match rest.strip_prefix(':') {
+                    Some(year) => year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()),
+                    None => rest.is_empty(),
+                }
+            }
+            None => false,
+        },
+        // "ASTM <letter><alphanumerics>", optionally with a "-<year>" suffix,
+        // e.g. "ASTM D123" or "ASTM D256-10".
+        IdentifierKind::Astm => match identifier.strip_prefix("ASTM ") {
+            Some(rest) => {
+                let starts_with_letter = rest.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+                if !starts_with_letter {
+                    return false;
+                }
+                match rest.split_once('-') {
+                    Some((designation, year)) => {
+                        !designation.is_empty()
+                            && designation.chars().all(|c| c.is_ascii_alphanumeric())
+                            && !year.is_empty()
+                            && year.chars().all(|c| c.is_ascii_digit())
+                    }
+                    None => rest.chars().all(|c| c.is_ascii_alphanumeric()),
+                }
+            }
+            None => false,
+        },
+        // "arXiv:YYMM.NNNNN" (new-style identifiers, post-2007).
+        IdentifierKind::ArXiv => match identifier.strip_prefix("arXiv:") {
+            Some(rest) => match rest.split_once('.') {
+                Some((yymm, suffix)) => {
+                    yymm.len() == 4
+                        && yymm.chars().all(|c| c.is_ascii_digit())
+                        && (4..=5).contains(&suffix.len())
+                        && suffix.chars().all(|c| c.is_ascii_digit())
+                }
+                None => false,
+            },
+            None => false,
+        },
+        IdentifierKind::Url => identifier.starts_with("http://") || identifier.starts_with("https://"),
+    }
+}
+
+/// A named sensor-fusion stage: consumes `inputs` (modality/type names)
+/// and yields `produces`. Patterns chain by matching one pattern's
+/// `produces` against another's `inputs`, the same way `plan_pipeline`
+/// builds a multi-stage pipeline out of single-stage patterns.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MixingPattern {
+    pub name: String,
+    pub inputs: Vec<String>,
+    pub produces: Vec<String>,
+    /// Patterns whose output this pattern is known to compose with — the
+    /// `PatternComposedWith` relationship, recorded as a direct reference
+    /// rather than a DHT link (see module doc comment). Informational only:
+    /// `plan_pipeline` derives composability from `inputs`/`produces`
+    /// directly, so this doesn't gate planning, just documents intent.
+    pub composed_with: Vec<ChunkHash>,
+    /// Patterns that must not co-occur in the same pipeline plan — the
+    /// `PatternConflictsWith` relationship. Symmetric: `plan_pipeline`
+    /// excludes a pair if *either* side names the other.
+    pub conflicts_with: Vec<ChunkHash>,
+    /// Bibliographic provenance for this pattern, checked by
+    /// `validate_pattern` rather than taken on faith.
+    pub citations: Vec<Citation>,
+    /// How this pattern's input dimensions combine into its output
+    /// dimension, for `dimensional_analysis::check_dimensional_consistency`
+    /// to check against — same opt-in treatment as `composed_with`/
+    /// `conflicts_with`: a pattern that doesn't set this isn't dimensionally
+    /// checked at all, rather than being forced to declare one. Defaults to
+    /// `None` on deserialization so a `MixingPattern` serialized before this
+    /// field existed still loads instead of failing on a missing key.
+    #[serde(default)]
+    pub operation: Option<Operation>,
+    /// Who or what proposed this pattern — a human author's name, or an
+    /// automated extractor's identifier (e.g. `"keyphrase_extractor"`) for
+    /// a pattern `propose_pattern_from_text` drafted rather than a person
+    /// hand-writing it. Empty for patterns constructed directly by `new`
+    /// that never set it, same as `operation` staying `None` until a
+    /// caller opts in. Defaults to an empty string on deserialization so a
+    /// `MixingPattern` serialized before this field existed still loads.
+    #[serde(default)]
+    pub contributed_by: String,
+    /// The reference radiation pattern a pattern whose matching logic is
+    /// `antenna_pattern::correlate_antenna_patterns` compares a measured
+    /// pattern against, rather than `dimensional_analysis`'s
+    /// operation-combinator checks. `None` for every pattern that isn't an
+    /// antenna-pattern-correlation pattern — same opt-in treatment as
+    /// `operation`. Defaults to `None` on deserialization so a
+    /// `MixingPattern` serialized before this field existed still loads.
+    #[serde(default)]
+    pub reference_pattern: Option<AntennaPatternRecord>,
+}
+
+impl MixingPattern {
+    pub fn new(name: String, inputs: Vec<String>, produces: Vec<String>) -> Self {
+        Self {
+            name,
+            inputs,
+            produces,
+            composed_with: Vec::new(),
+            conflicts_with: Vec::new(),
+            citations: Vec::new(),
+            operation: None,
+            contributed_by: String::new(),
+            reference_pattern: None,
+        }
+    }
+
+    /// Reject a pattern whose provenance is missing or malformed: at least
+    /// one citation must be present, and every citation's `identifier` must
+    /// match the syntactic shape its own `identifier_kind` claims. Doesn't
+    /// check `title`/`authors`/`year` beyond what `Citation`'s shape already
+    /// requires — the identifier is the one field another lookup
+    /// (`get_patterns_by_citation`) depends on being well-formed, so that's
+    /// the one this validates. Unlike `composed_with`/`conflicts_with`, this
+    /// doesn't gate `plan_pipeline` itself — it's the commit-time check a
+    /// caller runs before accepting a pattern into whatever set it later
+    /// hands `plan_pipeline`.
+    ///
+    /// No citations-count cap here unlike `ModalityRelationship::validate`'s
+    /// `MAX_CITATIONS` — this function validates one caller-built
+    /// `MixingPattern` in isolation, and nothing about calling it directly
+    /// grows a pattern's `citations` over repeated calls. The actual
+    /// unsupervised growth vector is `MixingPatternStore::add_pattern`
+    /// merging citations from repeated contributions into one stored entry,
+    /// so that's where `MAX_CITATIONS_PER_PATTERN` is enforced instead.
+    pub fn validate_pattern(&self) -> Result<(), ShardError> {
+        if self.citations.is_empty() {
+            return Err(ShardError::MigrationFailed {
+                context: format!("pattern \"{}\" requires at least one citation", self.name),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing citation")),
+            });
+        }
+        for citation in &self.citations {
+            if !identifier_matches_kind(&citation.identifier, citation.identifier_kind) {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "pattern \"{}\" citation \"{}\" identifier \"{}\" doesn't match the declared {:?} shape",
+                        self.name, citation.title, citation.identifier, citation.identifier_kind
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed citation identifier")),
+                });
+            }
+        }
+        if self.operation.is_some() {
+            let criterion = check_dimensional_consistency(self)?;
+            if !criterion.applies {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "pattern \"{}\" fails dimensional consistency: its declared operation can't turn {:?} into {:?}",
+                        self.name, self.inputs, self.produces
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "dimensionally inconsistent pattern")),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Content hash identifying this pattern — its identity in a
+    /// `plan_pipeline` result, and what `composed_with`/`conflicts_with`
+    /// entries on other patterns reference. Two patterns with the same
+    /// name/inputs/produces hash identically, same as `ChunkHash::of`
+    /// anywhere else in this crate: it's a content hash, not a random id.
+    ///
+    /// Every string is length-prefixed (as a little-endian `u64`) rather
+    /// than separated by a sentinel byte, so a crafted string embedding the
+    /// sentinel can't make two structurally different patterns collide —
+    /// the field boundaries come from the prefixed lengths, not from
+    /// scanning the bytes for a separator.
+    pub fn pattern_hash(&self) -> ChunkHash {
+        let mut bytes = Vec::new();
+        write_field(&mut bytes, self.name.as_bytes());
+        bytes.extend_from_slice(&(self.inputs.len() as u64).to_le_bytes());
+        for input in &self.inputs {
+            write_field(&mut bytes, input.as_bytes());
+        }
+        bytes.extend_from_slice(&(self.produces.len() as u64).to_le_bytes());
+        for output in &self.produces {
+            write_field(&mut bytes, output.as_bytes());
+        }
+        ChunkHash::of(&bytes)
+    }
+
+    /// This pattern's own `dimensional_analysis::check_dimensional_consistency`
+    /// result, as `Criterion` evidence a `pattern_matcher::PatternMatcher`
+    /// can fuse alongside runtime observations — a thin wrapper so a
+    /// caller assembling evidence for a pattern doesn't have to import
+    /// `dimensional_analysis` directly just to reach this one check.
+    pub fn dimensional_consistency_criterion(&self) -> Result<Criterion, ShardError> {
+        check_dimensional_consistency(self)
+    }
+}
+
+/// Append `field`, length-prefixed as a little-endian `u64`, to `bytes` —
+/// shared by `MixingPattern::pattern_hash`'s name/inputs/produces encoding
+/// so every string's boundary comes from its prefixed length rather than a
+/// separator byte a crafted string could embed.
+fn write_field(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(field);
+}
+
+fn conflicts(a: &MixingPattern, a_hash: &ChunkHash, b: &MixingPattern, b_hash: &ChunkHash) -> bool {
+    a.conflicts_with.contains(b_hash) || b.conflicts_with.contains(a_hash)
+}
+
+/// Upper bound on `plan_pipeline`'s `patterns` — the search explores up to
+/// `2^patterns.len()` chosen subsets, so (consistent with
+/// `mixing_validation`'s `MAX_HISTOGRAM_BINS`/`MAX_LAG_SEARCH`/
+/// `MAX_SAMPLES`) an unbounded pattern count is a CPU-exhaustion vector,
+/// not just a correctness concern. Kept small enough that even the
+/// worst case (no pattern ever reaches the target, so every one of
+/// `2^MAX_PATTERNS` subsets gets enumerated) finishes in well under a
+/// second.
+const MAX_PATTERNS: usize = 16;
+
+/// Plan a pipeline of `patterns` that turns `available_types` into
+/// `target_type`, returning the chosen patterns' hashes in application
+/// order (each entry's `inputs` are satisfied by `available_types` plus
+/// every earlier entry's `produces`).
+///
+/// This is a breadth-first forward-chaining search over which *subsets of
+/// patterns* have been tried: starting from no patterns chosen, repeatedly
+/// apply any not-yet-chosen pattern whose `inputs` are all reachable from
+/// `available_types` plus the chosen subset's combined `produces`, and that
+/// doesn't conflict with anything already chosen, until `target_type`
+/// becomes reachable. BFS order means the first plan found uses the fewest
+/// stages.
+///
+/// Dedup is keyed on the chosen subset (as a sorted index set), not on the
+/// reachable-type set it produces — two different subsets can reach the
+/// same types, and only one of them might be conflict-free, so both need
+/// exploring. This still terminates: each step adds exactly one pattern to
+/// the chosen subset, so there are at most `2^patterns.len()` subsets to
+/// visit — fine for realistic pattern-graph sizes, but a combinatorial
+/// search rather than a polynomial one.
+pub fn plan_pipeline(
+    available_types: Vec<String>,
+    target_type: String,
+    patterns: &[MixingPattern],
+) -> Result<Vec<ChunkHash>, ShardError> {
+    if patterns.len() > MAX_PATTERNS {
+        return Err(ShardError::MigrationFailed {
+            context: format!("pipeline planning supports at most {MAX_PATTERNS} patterns per call"),
+            source: Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "too many patterns",
+            )),
+        });
+    }
+
+    let initial: BTreeSet<String> = available_types.into_iter().collect();
+    if initial.contains(&target_type) {
+        return Ok(Vec::new());
+    }
+
+    let hashes: Vec<ChunkHash> = patterns.iter().map(MixingPattern::pattern_hash).collect();
+
+    let mut seen_subsets: HashSet<BTreeSet<usize>> = HashSet::new();
+    seen_subsets.insert(BTreeSet::new());
+
+    let mut queue: VecDeque<(BTreeSet<String>, Vec<usize>)> = VecDeque::new();
+    queue.push_back((initial, Vec::new()));
+
+    while let Some((reachable, chosen)) = queue.pop_front() {
+        for (i, pattern) in patterns.iter().enumerate() {
+            if chosen.contains(&i) {
+                continue;
+            }
+            if !pattern.inputs.iter().all(|t| reachable.contains(t)) {
+                continue;
+            }
+            if chosen
+                .iter()
+                .any(|&j| conflicts(pattern, &hashes[i], &patterns[j], &hashes[j]))
+            {
+                continue;
+            }
+
+            let mut next_chosen = chosen.clone();
+            next_chosen.push(i);
+            let next_subset: BTreeSet<usize> = next_chosen.iter().copied().collect();
+            if !seen_subsets.insert(next_subset) {
+                continue;
+            }
+
+            let mut next_reachable = reachable.clone();
+            next_reachable.extend(pattern.produces.iter().cloned());
+
+            if next_reachable.contains(&target_type) {
+                return Ok(next_chosen.into_iter().map(|idx| hashes[idx]).collect());
+            }
+
+            queue.push_back((next_reachable, next_chosen));
+        }
+    }
+
+    Err(ShardError::MigrationFailed {
+        context: format!("no pipeline of the given patterns reaches target type \"{target_type}\""),
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "target type unreachable from available types",
+        )),
+    })
+}
+
+/// Every pattern in `patterns` that cites `identifier` — e.g. finding every
+/// pattern whose provenance traces back to a given DOI. Linear scan rather
+/// than a maintained index, matching `plan_pipeline`'s own treatment of
+/// `patterns` as a caller-supplied slice rather than state this module
+/// owns.
+pub fn get_patterns_by_citation<'a>(patterns: &'a [MixingPattern], identifier: &str) -> Vec<&'a MixingPattern> {
+    patterns
+        .iter()
+        .filter(|pattern| pattern.citations.iter().any(|citation| citation.identifier == identifier))
+        .collect()
+}
+
+/// Whether a stored `MixingPattern` has passed `validate_pattern` (and, for
+/// a pattern with a declared `operation`, dimensional consistency) or is
+/// still an unreviewed draft.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternStatus {
+    /// Freshly added — e.g. a draft `propose_pattern_from_text` produced —
+    /// and not yet promoted. Not excluded from lookup, just not vouched
+    /// for: a caller building a `plan_pipeline` input set should filter on
+    /// this status if it only wants vetted patterns.
+    Unvalidated,
+    /// Passed `validate_pattern` (and dimensional consistency, if it
+    /// declares an `operation`) at the time it was promoted.
+    Validated,
+}
+
+/// Upper bound on how many patterns a single `MixingPatternStore` will
+/// index. Same reasoning as `ModalityRelationshipStore`'s
+/// `MAX_RELATIONSHIPS_PER_PAIR`: this store is meant to take contributions
+/// from an automated extractor as well as hand-authored patterns, so an
+/// unbounded count is a memory-exhaustion vector, not just clutter.
+const MAX_STORED_PATTERNS: usize = 10_000;
+
+/// Upper bound on how many citations `MixingPatternStore::add_pattern` will
+/// merge onto a single stored entry. Same reasoning as
+/// `ModalityRelationship::validate`'s `MAX_CITATIONS`: `MAX_STORED_PATTERNS`
+/// only caps how many distinct patterns the store holds, not how large any
+/// one of their `citations` lists grows, so repeated contributions that all
+/// hash to the same entry (e.g. an extractor re-running over many source
+/// passages for the same underlying transform) would otherwise be an
+/// unbounded growth vector.
+const MAX_CITATIONS_PER_PATTERN: usize = 32;
+
+/// Upper bound on how many hashes `MixingPatternStore::add_pattern` will
+/// merge onto a single stored entry's `composed_with` or `conflicts_with`
+/// — each capped independently. Same unbounded-growth concern
+/// `MAX_CITATIONS_PER_PATTERN` addresses for `citations`: these two lists
+/// grow by merge too, and `plan_pipeline` scans `conflicts_with` for every
+/// candidate pair it considers, so an unbounded list is a per-call cost
+/// blowup on top of the memory one.
+const MAX_RELATED_HASHES_PER_PATTERN: usize = 32;
+
+/// Content-addressed store of `MixingPattern`s, keyed by
+/// `MixingPattern::pattern_hash` the same way `ChunkStore` keys chunk bytes
+/// by their hash. Every pattern lands as `PatternStatus::Unvalidated` —
+/// `add_pattern` never accepts a status from its caller — and `promote` is
+/// the only way to reach `Validated`, by actually running `validate_pattern`
+/// (which itself runs `check_dimensional_consistency` for a pattern that
+/// declares an `operation`) rather than trusting the source on ingestion.
+#[derive(Default)]
+pub struct MixingPatternStore {
+    patterns: RwLock<HashMap<ChunkHash, (MixingPattern, PatternStatus)>>,
+}
+
+impl MixingPatternStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index `pattern` under its content hash as `PatternStatus::Unvalidated`,
+    /// returning the hash. `pattern_hash` only covers `name`/`inputs`/
+    /// `produces` (see its doc comment), so a hash match doesn't mean
+    /// `pattern`'s citations or declared `operation` match an already-stored
+    /// entry too — two extractions of the same underlying transform from
+    /// different source passages are exactly this case. Rather than
+    /// silently discarding whichever arrives second, an existing entry's
+    /// `citations` gain any new ones (deduped by identifier — both against
+    /// the existing entry and within `pattern`'s own list — capped at
+    /// `MAX_CITATIONS_PER_PATTERN`), and `composed_with`/`conflicts_with`
+    /// gain any new hashes (deduped by equality) the same way — dropping
+    /// either silently would mean a hand-authored `conflicts_with` safety
+    /// constraint could vanish just because an unrelated contribution
+    /// happened to land on the same hash first. Its `operation` is filled
+    /// in if it didn't already have one; either kind of change also adopts
+    /// `pattern.contributed_by` (when non-empty) as the entry's new
+    /// `contributed_by`, since it's now carrying content that contributor
+    /// supplied. Filling in a previously-absent `operation` resets the
+    /// entry's status back to `Unvalidated` even if it had been
+    /// `Validated`, since `Validated` is only ever a claim about the
+    /// `operation` `promote` actually checked, and this pattern now
+    /// declares one it hasn't. `MAX_CITATIONS_PER_PATTERN` is enforced on
+    /// every insertion, not just merges, so a single call can't plant an
+    /// oversized list straight into a brand-new entry either. Errs once the
+    /// store already holds `MAX_STORED_PATTERNS` distinct patterns and
+    /// `pattern`'s hash isn't one of them, once `pattern` itself already
+    /// exceeds `MAX_CITATIONS_PER_PATTERN`, or once merging `pattern`'s
+    /// citations would push an existing entry past it.
+    pub fn add_pattern(&self, pattern: MixingPattern) -> Result<ChunkHash, ShardError> {
+        let hash = pattern.pattern_hash();
+        let mut deduped_citations: Vec<Citation> = Vec::with_capacity(pattern.citations.len());
+        for c in pattern.citations {
+            if !deduped_citations.iter().any(|d| d.identifier == c.identifier) {
+                deduped_citations.push(c);
+            }
+        }
+
+        let mut patterns = self.patterns.write().expect("mixing pattern store lock poisoned");
+
+        if let Some((existing, status)) = patterns.get_mut(&hash) {
+            let new_citations: Vec<Citation> =
+                deduped_citations.into_iter().filter(|c| !existing.citations.iter().any(|e| e.identifier == c.identifier)).collect();
+            if existing.citations.len() + new_citations.len() > MAX_CITATIONS_PER_PATTERN {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "pattern \"{}\" already has {} citation(s), merging {} more would exceed the maximum of {MAX_CITATIONS_PER_PATTERN}",
+                        existing.name,
+                        existing.citations.len(),
+                        new_citations.len()
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many citations")),
+                });
+            }
+            let new_composed_with: Vec<ChunkHash> =
+                pattern.composed_with.into_iter().filter(|h| !existing.composed_with.contains(h)).collect();
+            let new_conflicts_with: Vec<ChunkHash> =
+                pattern.conflicts_with.into_iter().filter(|h| !existing.conflicts_with.contains(h)).collect();
+            if existing.composed_with.len() + new_composed_with.len() > MAX_RELATED_HASHES_PER_PATTERN {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "pattern \"{}\" already has {} composed_with hash(es), merging {} more would exceed the maximum of {MAX_RELATED_HASHES_PER_PATTERN}",
+                        existing.name,
+                        existing.composed_with.len(),
+                        new_composed_with.len()
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many composed_with hashes")),
+                });
+            }
+            if existing.conflicts_with.len() + new_conflicts_with.len() > MAX_RELATED_HASHES_PER_PATTERN {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "pattern \"{}\" already has {} conflicts_with hash(es), merging {} more would exceed the maximum of {MAX_RELATED_HASHES_PER_PATTERN}",
+                        existing.name,
+                        existing.conflicts_with.len(),
+                        new_conflicts_with.len()
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many conflicts_with hashes")),
+                });
+            }
+
+            let gained_citations = !new_citations.is_empty();
+            existing.citations.extend(new_citations);
+            existing.composed_with.extend(new_composed_with);
+            existing.conflicts_with.extend(new_conflicts_with);
+            let gained_operation = existing.operation.is_none() && pattern.operation.is_some();
+            if gained_operation {
+                existing.operation = pattern.operation;
+                *status = PatternStatus::Unvalidated;
+            }
+            if (gained_citations || gained_operation) && !pattern.contributed_by.is_empty() {
+                existing.contributed_by = pattern.contributed_by;
+            }
+            return Ok(hash);
+        }
+
+        if deduped_citations.len() > MAX_CITATIONS_PER_PATTERN {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "pattern \"{}\" has {} citation(s), exceeding the maximum of {MAX_CITATIONS_PER_PATTERN}",
+                    pattern.name,
+                    deduped_citations.len()
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many citations")),
+            });
+        }
+        if patterns.len() >= MAX_STORED_PATTERNS {
+            return Err(ShardError::MigrationFailed {
+                context: format!("mixing pattern store already holds the maximum of {MAX_STORED_PATTERNS} patterns"),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many stored patterns")),
+            });
+        }
+        patterns.insert(hash, (MixingPattern { citations: deduped_citations, ..pattern }, PatternStatus::Unvalidated));
+        Ok(hash)
+    }
+
+    /// Promote the pattern stored under `hash` to `PatternStatus::Validated`
+    /// by running `validate_pattern` against it. Errs (leaving its status
+    /// unchanged) if no pattern is stored under `hash`, or if
+    /// `validate_pattern` rejects it — a draft that doesn't yet pass stays
+    /// `Unvalidated` rather than being silently promoted or discarded.
+    pub fn promote(&self, hash: &ChunkHash) -> Result<(), ShardError> {
+        let mut patterns = self.patterns.write().expect("mixing pattern store lock poisoned");
+        let Some((pattern, status)) = patterns.get_mut(hash) else {
+            return Err(ShardError::MigrationFailed {
+                context: format!("mixing pattern store has no pattern with hash {}", hash.to_hex()),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "unknown pattern hash")),
+            });
+        };
+        pattern.validate_pattern()?;
+        *status = PatternStatus::Validated;
+        Ok(())
+    }
+
+    /// The pattern stored under `hash`, if any.
+    pub fn get(&self, hash: &ChunkHash) -> Option<MixingPattern> {
+        self.patterns
+            .read()
+            .expect("mixing pattern store lock poisoned")
+            .get(hash)
+            .map(|(pattern, _)| pattern.clone())
+    }
+
+    /// `hash`'s stored status, if any.
+    pub fn status(&self, hash: &ChunkHash) -> Option<PatternStatus> {
+        self.patterns.read().expect("mixing pattern store lock poisoned").get(hash).map(|(_, status)| *status)
+    }
+
+    /// Every stored pattern with `PatternStatus::Validated`, for a caller
+    /// assembling `plan_pipeline`'s input set out of only vetted patterns.
+    pub fn validated_patterns(&self) -> Vec<MixingPattern> {
+        self.patterns
+            .read()
+            .expect("mixing pattern store lock poisoned")
+            .values()
+            .filter(|(_, status)| *status == PatternStatus::Validated)
+            .map(|(pattern, _)| pattern.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(name: &str, inputs: &[&str], produces: &[&str]) -> MixingPattern {
+        MixingPattern::new(
+            name.to_string(),
+            inputs.iter().map(|s| s.to_string()).collect(),
+            produces.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn already_available_target_needs_no_patterns() {
+        let patterns = vec![pattern("p1", &[], &["x"])];
+        let plan = plan_pipeline(vec!["target".to_string()], "target".to_string(), &patterns).unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn chains_two_patterns_to_reach_target() {
+        let accel_to_motion = pattern("accel_to_motion", &["accelerometer"], &["motion"]);
+        let motion_to_gesture = pattern("motion_to_gesture", &["motion"], &["gesture"]);
+        let patterns = vec![accel_to_motion.clone(), motion_to_gesture.clone()];
+
+        let plan = plan_pipeline(
+            vec!["accelerometer".to_string()],
+            "gesture".to_string(),
+            &patterns,
+        )
+        .unwrap();
+
+        assert_eq!(plan, vec![accel_to_motion.pattern_hash(), motion_to_gesture.pattern_hash()]);
+    }
+
+    #[test]
+    fn unreachable_target_errors() {
+        let patterns = vec![pattern("p1", &["a"], &["b"])];
+        let result = plan_pipeline(vec!["x".to_string()], "target".to_string(), &patterns);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn avoids_a_conflicting_pair_by_taking_an_alternate_route() {
+        // Two independent patterns both produce "x"; one of them conflicts
+        // with the pattern that turns "x" into the target, so the planner
+        // must route through the other one instead of failing outright.
+        let direct = pattern("direct", &[], &["x"]);
+        let mut alternate = pattern("alternate", &[], &["x"]);
+        let mut consume = pattern("consume", &["x"], &["target"]);
+        consume.conflicts_with.push(direct.pattern_hash());
+        alternate.conflicts_with.push(ChunkHash::of(b"unrelated"));
+
+        let patterns = vec![direct.clone(), alternate.clone(), consume.clone()];
+        let plan = plan_pipeline(vec![], "target".to_string(), &patterns).unwrap();
+
+        assert!(plan.contains(&alternate.pattern_hash()));
+        assert!(!plan.contains(&direct.pattern_hash()));
+        assert!(plan.contains(&consume.pattern_hash()));
+    }
+
+    #[test]
+    fn pattern_hash_is_stable_for_identical_patterns() {
+        let a = pattern("p", &["x"], &["y"]);
+        let b = pattern("p", &["x"], &["y"]);
+        assert_eq!(a.pattern_hash(), b.pattern_hash());
+    }
+
+    fn citation(identifier_kind: IdentifierKind, identifier: &str) -> Citation {
+        Citation {
+            title: "a paper".to_string(),
+            identifier_kind,
+            identifier: identifier.to_string(),
+            authors: vec!["A. Researcher".to_string()],
+            year: 2020,
+        }
+    }
+
+    #[test]
+    fn validate_pattern_rejects_missing_citations() {
+        let p = pattern("p", &["x"], &["y"]);
+        assert!(p.validate_pattern().is_err());
+    }
+
+    #[test]
+    fn validate_pattern_accepts_well_formed_identifiers() {
+        for (kind, identifier) in [
+            (IdentifierKind::Doi, "10.1000/xyz123"),
+            (IdentifierKind::Iso, "ISO 8601"),
+            (IdentifierKind::Iso, "ISO 6892-1:2019"),
+            (IdentifierKind::Astm, "ASTM D123"),
+            (IdentifierKind::Astm, "ASTM D256-10"),
+            (IdentifierKind::ArXiv, "arXiv:2007.12345"),
+            (IdentifierKind::Url, "https://example.org/paper"),
+        ] {
+            let mut p = pattern("p", &["x"], &["y"]);
+            p.citations.push(citation(kind, identifier));
+            assert!(p.validate_pattern().is_ok(), "{identifier:?} should be valid for {kind:?}");
+        }
+    }
+
+    #[test]
+    fn validate_pattern_rejects_identifier_that_does_not_match_its_declared_kind() {
+        let mut p = pattern("p", &["x"], &["y"]);
+        p.citations.push(citation(IdentifierKind::Doi, "not-a-doi"));
+        assert!(p.validate_pattern().is_err());
+    }
+
+    #[test]
+    fn validate_pattern_rejects_iso_part_number_with_no_digits_after_the_dash() {
+        let mut p = pattern("p", &["x"], &["y"]);
+        p.citations.push(citation(IdentifierKind::Iso, "ISO 1-"));
+        assert!(p.validate_pattern().is_err());
+    }
+
+    #[test]
+    fn validate_pattern_accepts_doi_with_a_registrant_sub_prefix() {
+        let mut p = pattern("p", &["x"], &["y"]);
+        p.citations.push(citation(IdentifierKind::Doi, "10.1000.1/206"));
+        assert!(p.validate_pattern().is_ok());
+    }
+
+    #[test]
+    fn get_patterns_by_citation_finds_matching_patterns_only() {
+        let mut cited = pattern("cited", &["x"], &["y"]);
+        cited.citations.push(citation(IdentifierKind::Doi, "10.1000/xyz123"));
+        let uncited = pattern("uncited", &["x"], &["y"]);
+
+        let patterns = vec![cited.clone(), uncited];
+        let found = get_patterns_by_citation(&patterns, "10.1000/xyz123");
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "cited");
+    }
+
+    #[test]
+    fn validate_pattern_rejects_a_dimensionally_inconsistent_operation() {
+        let mut p = pattern("bogus", &["temperature"], &["voltage"]);
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        p.operation = Some(Operation::TimeDerivative);
+
+        assert!(p.validate_pattern().is_err());
+    }
+
+    #[test]
+    fn validate_pattern_accepts_a_dimensionally_consistent_operation() {
+        let mut p = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        p.operation = Some(Operation::TimeDerivative);
+
+        assert!(p.validate_pattern().is_ok());
+    }
+
+    #[test]
+    fn add_pattern_is_idempotent_on_the_same_content() {
+        let store = MixingPatternStore::new();
+        let p = pattern("p", &["x"], &["y"]);
+        let first = store.add_pattern(p.clone()).unwrap();
+        let second = store.add_pattern(p).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn add_pattern_always_lands_as_unvalidated() {
+        let store = MixingPatternStore::new();
+        let mut p = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        p.operation = Some(Operation::TimeDerivative);
+
+        let hash = store.add_pattern(p).unwrap();
+
+        assert_eq!(store.status(&hash), Some(PatternStatus::Unvalidated));
+    }
+
+    #[test]
+    fn add_pattern_merges_citations_on_a_hash_collision() {
+        let store = MixingPatternStore::new();
+        let mut first = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        first.citations.push(citation(IdentifierKind::Url, "https://example.org/paper-a"));
+        first.operation = Some(Operation::TimeDerivative);
+
+        let mut second = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        second.citations.push(citation(IdentifierKind::Url, "https://example.org/paper-b"));
+        second.contributed_by = "extractor".to_string();
+
+        let first_hash = store.add_pattern(first).unwrap();
+        let second_hash = store.add_pattern(second).unwrap();
+        assert_eq!(first_hash, second_hash);
+
+        let stored = store.get(&first_hash).unwrap();
+        assert_eq!(stored.citations.len(), 2);
+        assert!(stored.citations.iter().any(|c| c.identifier == "https://example.org/paper-a"));
+        assert!(stored.citations.iter().any(|c| c.identifier == "https://example.org/paper-b"));
+        assert_eq!(stored.operation, Some(Operation::TimeDerivative));
+        assert_eq!(stored.contributed_by, "extractor");
+    }
+
+    #[test]
+    fn add_pattern_merges_conflicts_with_on_a_hash_collision_instead_of_discarding_it() {
+        let store = MixingPatternStore::new();
+        let conflicting = ChunkHash::of(b"some other pattern");
+
+        let first = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+
+        let mut second = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        second.conflicts_with.push(conflicting);
+
+        let first_hash = store.add_pattern(first).unwrap();
+        store.add_pattern(second).unwrap();
+
+        assert_eq!(store.get(&first_hash).unwrap().conflicts_with, vec![conflicting]);
+    }
+
+    #[test]
+    fn add_pattern_dedups_repeated_identifiers_within_a_single_contribution() {
+        let store = MixingPatternStore::new();
+        let mut p = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+
+        let hash = store.add_pattern(p).unwrap();
+
+        assert_eq!(store.get(&hash).unwrap().citations.len(), 1);
+    }
+
+    #[test]
+    fn add_pattern_rejects_a_fresh_pattern_that_already_exceeds_the_citation_cap() {
+        let store = MixingPatternStore::new();
+        let mut p = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        for i in 0..=MAX_CITATIONS_PER_PATTERN {
+            p.citations.push(citation(IdentifierKind::Url, &format!("https://example.org/paper-{i}")));
+        }
+
+        assert!(store.add_pattern(p).is_err());
+    }
+
+    #[test]
+    fn add_pattern_rejects_a_merge_that_would_exceed_the_per_pattern_citation_cap() {
+        let store = MixingPatternStore::new();
+        let mut first = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        for i in 0..MAX_CITATIONS_PER_PATTERN {
+            first.citations.push(citation(IdentifierKind::Url, &format!("https://example.org/paper-{i}")));
+        }
+        let hash = store.add_pattern(first).unwrap();
+
+        let mut overflowing = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        overflowing.citations.push(citation(IdentifierKind::Url, "https://example.org/one-too-many"));
+
+        assert!(store.add_pattern(overflowing).is_err());
+        assert_eq!(store.get(&hash).unwrap().citations.len(), MAX_CITATIONS_PER_PATTERN);
+    }
+
+    #[test]
+    fn filling_in_a_missing_operation_on_merge_resets_status_to_unvalidated() {
+        let store = MixingPatternStore::new();
+        let mut first = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        first.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        let hash = store.add_pattern(first).unwrap();
+        store.promote(&hash).unwrap();
+        assert_eq!(store.status(&hash), Some(PatternStatus::Validated));
+
+        let mut second = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        second.operation = Some(Operation::TimeDerivative);
+        store.add_pattern(second).unwrap();
+
+        assert_eq!(store.status(&hash), Some(PatternStatus::Unvalidated));
+        assert_eq!(store.get(&hash).unwrap().operation, Some(Operation::TimeDerivative));
+    }
+
+    #[test]
+    fn merging_citations_alone_does_not_demote_a_validated_pattern() {
+        let store = MixingPatternStore::new();
+        let mut first = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        first.citations.push(citation(IdentifierKind::Url, "https://example.org/paper-a"));
+        first.operation = Some(Operation::TimeDerivative);
+        let hash = store.add_pattern(first).unwrap();
+        store.promote(&hash).unwrap();
+
+        let mut second = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        second.citations.push(citation(IdentifierKind::Url, "https://example.org/paper-b"));
+        store.add_pattern(second).unwrap();
+
+        assert_eq!(store.status(&hash), Some(PatternStatus::Validated));
+    }
+
+    #[test]
+    fn promote_rejects_a_pattern_missing_citations() {
+        let store = MixingPatternStore::new();
+        let p = pattern("uncited", &["x"], &["y"]);
+        let hash = store.add_pattern(p).unwrap();
+
+        assert!(store.promote(&hash).is_err());
+        assert_eq!(store.status(&hash), Some(PatternStatus::Unvalidated));
+    }
+
+    #[test]
+    fn promote_accepts_a_well_formed_pattern_and_updates_its_status() {
+        let store = MixingPatternStore::new();
+        let mut p = pattern("induction_coupling", &["magnetic_flux"], &["voltage"]);
+        p.citations.push(citation(IdentifierKind::Url, "https://example.org/paper"));
+        let hash = store.add_pattern(p).unwrap();
+
+        store.promote(&hash).unwrap();
+
+        assert_eq!(store.status(&hash), Some(PatternStatus::Validated));
+        assert_eq!(store.validated_patterns().len(), 1);
+    }
+
+    #[test]
+    fn promote_errs_on_an_unknown_hash() {
+        let store = MixingPatternStore::new();
+        assert!(store.promote(&ChunkHash::of(b"nothing stored here")).is_err());
+    }
+}
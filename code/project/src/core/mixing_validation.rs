@@ -0,0 +1,770 @@
+// src/core/mixing_validation.rs
+//! Data-driven criteria for deciding whether two sensor/modality signals are
+//! worth fusing together. Each criterion is computed directly from a pair
+//! of aligned sample streams (`MixingRequest::samples_a`/`samples_b`)
+//! instead of a hardcoded modality-name lookup, so the numeric score behind
+//! a pass/fail actually measures what its name claims: mutual information
+//! for information gain, joint compressibility for compressibility, and so
+//! on. `validate_mixing_empirical` runs all five and returns every
+//! criterion's score, not just a boolean — plus a `Vec<ValidationDiagnostic>`
+//! explaining, per failing criterion, a stable code and severity a caller
+//! can branch on instead of parsing a message string. The one criterion
+//! that still leans on curated prior knowledge, `check_physical_causation`,
+//! gets that knowledge from a `ModalityRelationshipStore` rather than a
+//! compiled-in table, so it grows through peer contribution.
+
+use crate::core::modality_relationship::{ModalityRelationshipStore, RelationshipKind};
+use crate::error::ShardError;
+
+/// Upper bound on `MixingRequest::histogram_bins`: `joint_histogram`
+/// allocates a `bins x bins` table, so an unbounded caller-supplied value
+/// could otherwise force an arbitrarily large allocation.
+const MAX_HISTOGRAM_BINS: usize = 256;
+
+/// Upper bound on `MixingRequest::max_lag`: `peak_lagged_correlation` does
+/// `2 * max_lag + 1` full-length correlation passes, so an unbounded
+/// caller-supplied value could otherwise turn one validation call into
+/// unbounded CPU work.
+const MAX_LAG_SEARCH: usize = 10_000;
+
+/// Upper bound on `MixingRequest::stability_windows`, applied before any
+/// arithmetic involving it so a huge caller-supplied value can't overflow
+/// the bounds check in `check_temporal_stability`.
+const MAX_STABILITY_WINDOWS: usize = 1_000;
+
+/// Upper bound on `samples_a`/`samples_b`'s length. `check_predictive_power`
+/// and `check_temporal_stability` are O(max_lag * n), and
+/// `check_compressibility` is O(n * window) over an `8n`-byte quantized
+/// buffer, so — same reasoning as `MAX_HISTOGRAM_BINS`/`MAX_LAG_SEARCH` — an
+/// unbounded sample count is as much a CPU-exhaustion vector as an
+/// unbounded bin or lag count, just harder to notice since it comes from
+/// the caller's data rather than a tuning knob.
+const MAX_SAMPLES: usize = 100_000;
+
+/// Two aligned sample streams to validate for mixing, plus the thresholds
+/// each criterion checks against. `samples_a` and `samples_b` must be the
+/// same length — the same sequence of timestamped observations on both
+/// signals, in order.
+#[derive(Clone, Debug)]
+pub struct MixingRequest {
+    pub modality_a: String,
+    pub modality_b: String,
+    pub samples_a: Vec<f64>,
+    pub samples_b: Vec<f64>,
+    /// Histogram bin count for `check_information_gain`'s discretization.
+    pub histogram_bins: usize,
+    pub information_gain_threshold: f64,
+    pub compressibility_threshold: f64,
+    pub predictive_power_threshold: f64,
+    /// Largest lag (in samples, either direction) `check_predictive_power`
+    /// searches before giving up.
+    pub max_lag: usize,
+    /// Number of equal windows `check_temporal_stability` splits the
+    /// streams into.
+    pub stability_windows: usize,
+    pub stability_variance_threshold: f64,
+}
+
+/// One criterion's numeric score plus the pass/fail it implies against the
+/// request's threshold for that criterion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CriterionResult {
+    pub score: f64,
+    pub passed: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct MixingValidationResult {
+    /// Normalized mutual information (NMI) in `[0, 1]`.
+    pub information_gain: CriterionResult,
+    /// Normalized Compression Distance — lower means more redundant (the
+    /// signals compress better together), so this criterion passes when
+    /// the score is *below* `compressibility_threshold`.
+    pub compressibility: CriterionResult,
+    /// Peak absolute lagged cross-correlation over `[-max_lag, max_lag]`.
+    pub predictive_power: CriterionResult,
+    /// The lag at which `predictive_power`'s peak correlation occurred:
+    /// positive means `samples_a` leads `samples_b`, negative the reverse.
+    pub predictive_power_lag: isize,
+    /// Variance of the per-window predictive-power score across
+    /// `stability_windows` windows — passes when that variance is low,
+    /// i.e. the relationship between the signals doesn't drift over time.
+    pub temporal_stability: CriterionResult,
+    /// 1.0 if either the curated modality-pair table or the Granger-style
+    /// residual-reduction test says `modality_b` helps predict
+    /// `modality_a`, else 0.0.
+    pub physical_causation: CriterionResult,
+    /// One entry per criterion that didn't pass, explaining why. Built by
+    /// `validate_mixing_empirical` from the criterion results above — use
+    /// `is_valid`/`criteria_met` rather than re-deriving a summary from this
+    /// list by hand.
+    pub diagnostics: Vec<ValidationDiagnostic>,
+}
+
+impl MixingValidationResult {
+    /// No `Severity::Error` diagnostic was raised, i.e. both criteria this
+    /// module treats as a hard requirement for fusing two signals —
+    /// information gain and physical causation — passed.
+    /// `Severity::Warning` diagnostics (compressibility, predictive power,
+    /// temporal stability) are softer signals and don't affect this.
+    pub fn is_valid(&self) -> bool {
+        !self.diagnostics.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    /// Count of the five criteria that passed. Derived from the
+    /// already-stored per-criterion results rather than kept as a separate
+    /// counter that could drift out of sync with them.
+    pub fn criteria_met(&self) -> usize {
+        [
+            self.information_gain,
+            self.compressibility,
+            self.predictive_power,
+            self.temporal_stability,
+            self.physical_causation,
+        ]
+        .iter()
+        .filter(|c| c.passed)
+        .count()
+    }
+}
+
+/// Stable identifier for why a criterion didn't pass — the part of a
+/// [`ValidationDiagnostic`] a caller matches on to react programmatically,
+/// as opposed to `message`, which is free text meant for display/logging
+/// only and may change wording over time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticCode {
+    /// `check_information_gain`'s NMI score was below
+    /// `information_gain_threshold`.
+    LowMutualInformation,
+    /// `check_compressibility`'s NCD score was at or above
+    /// `compressibility_threshold` — the two signals don't compress
+    /// together any better than they compress apart, i.e. they're *not*
+    /// redundant enough to be worth fusing.
+    InsufficientCompressibility,
+    /// `check_predictive_power`'s peak lagged correlation was below
+    /// `predictive_power_threshold`.
+    WeakPredictivePower,
+    /// `check_temporal_stability`'s windowed-variance score was at or above
+    /// `stability_variance_threshold`.
+    UnstableOverTime,
+    /// `check_physical_causation` found neither a curated modality pair nor
+    /// a Granger-style signal in either direction.
+    CausationUnverified,
+}
+
+/// How seriously a [`ValidationDiagnostic`] should be taken.
+/// `Severity::Error` is what `MixingValidationResult::is_valid` checks for;
+/// `Warning`/`Info` are for callers that want to react to softer signals
+/// (e.g. surfacing a caution in a UI) without hard-rejecting the mixing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// One criterion's explanation for why it didn't pass: a stable `code` to
+/// match on, a `severity` deciding whether it should block mixing outright,
+/// and a human-readable `message` carrying the specific numbers involved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationDiagnostic {
+    pub code: DiagnosticCode,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Mean of `values`, or `0.0` for an empty slice.
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / values.len() as f64
+}
+
+/// Bin index of `value` within `[min, max]` split into `bins` equal-width
+/// buckets; clamped so floating-point error at the upper edge can't produce
+/// an out-of-range index.
+fn bin_index(value: f64, min: f64, max: f64, bins: usize) -> usize {
+    if max <= min {
+        return 0;
+    }
+    let width = (max - min) / bins as f64;
+    (((value - min) / width) as usize).min(bins - 1)
+}
+
+/// Discretize `a`/`b` into a `bins x bins` joint histogram over their own
+/// observed ranges, returning raw counts (not yet normalized to
+/// probabilities) for the joint and each marginal.
+fn joint_histogram(a: &[f64], b: &[f64], bins: usize) -> (Vec<Vec<u64>>, Vec<u64>, Vec<u64>) {
+    let (a_min, a_max) = min_max(a);
+    let (b_min, b_max) = min_max(b);
+
+    let mut joint = vec![vec![0u64; bins]; bins];
+    let mut marginal_a = vec![0u64; bins];
+    let mut marginal_b = vec![0u64; bins];
+
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        let ix = bin_index(x, a_min, a_max, bins);
+        let iy = bin_index(y, b_min, b_max, bins);
+        joint[ix][iy] += 1;
+        marginal_a[ix] += 1;
+        marginal_b[iy] += 1;
+    }
+
+    (joint, marginal_a, marginal_b)
+}
+
+fn min_max(values: &[f64]) -> (f64, f64) {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for &v in values {
+        if v < min {
+            min = v;
+        }
+        if v > max {
+            max = v;
+        }
+    }
+    (min, max)
+}
+
+fn shannon_entropy(counts: &[u64], total: f64) -> f64 {
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Mutual information between `a` and `b`, discretized into
+/// `request.histogram_bins` bins each, normalized by `min(H(a), H(b))` into
+/// `[0, 1]` (NMI). A constant signal has zero entropy and would make the
+/// normalization divide by zero, so it's treated as carrying no
+/// information about anything and scored `0.0` directly rather than fusing
+/// with it.
+pub fn check_information_gain(request: &MixingRequest) -> CriterionResult {
+    let a = &request.samples_a;
+    let b = &request.samples_b;
+    let bins = request.histogram_bins.clamp(1, MAX_HISTOGRAM_BINS);
+
+    if a.is_empty() || b.is_empty() {
+        return CriterionResult { score: 0.0, passed: false };
+    }
+
+    let (joint, marginal_a, marginal_b) = joint_histogram(a, b, bins);
+    let total = a.len() as f64;
+
+    let h_a = shannon_entropy(&marginal_a, total);
+    let h_b = shannon_entropy(&marginal_b, total);
+    if h_a <= 0.0 || h_b <= 0.0 {
+        return CriterionResult { score: 0.0, passed: false };
+    }
+
+    let mut mi = 0.0;
+    for (ix, row) in joint.iter().enumerate() {
+        for (iy, &count) in row.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let p_xy = count as f64 / total;
+            let p_x = marginal_a[ix] as f64 / total;
+            let p_y = marginal_b[iy] as f64 / total;
+            mi += p_xy * (p_xy / (p_x * p_y)).log2();
+        }
+    }
+
+    let nmi = (mi / h_a.min(h_b)).clamp(0.0, 1.0);
+    CriterionResult {
+        score: nmi,
+        passed: nmi >= request.information_gain_threshold,
+    }
+}
+
+/// Deterministic byte-length estimate standing in for a real general-purpose
+/// compressor (deflate/zstd): a greedy LZ77-style pass over a small sliding
+/// window, encoding each position as either a literal byte or a
+/// (distance, length) back-reference, then counting the encoded bytes. It
+/// captures the same thing NCD needs — repeated structure compresses
+/// shorter — without depending on an external compression crate.
+fn compressed_len(bytes: &[u8]) -> usize {
+    const WINDOW: usize = 255;
+    const MIN_MATCH: usize = 3;
+    const MAX_MATCH: usize = 258;
+
+    if bytes.is_empty() {
+        return 0;
+    }
+
+    let mut encoded = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        let window_start = i.saturating_sub(WINDOW);
+        let mut best_len = 0usize;
+
+        let max_len = (bytes.len() - i).min(MAX_MATCH);
+        if max_len >= MIN_MATCH {
+            for start in window_start..i {
+                let mut len = 0;
+                while len < max_len && bytes[start + len] == bytes[i + len] {
+                    len += 1;
+                }
+                if len > best_len {
+                    best_len = len;
+                }
+            }
+        }
+
+        if best_len >= MIN_MATCH {
+            // One (distance, length) token, modeled as 2 encoded bytes.
+            encoded += 2;
+            i += best_len;
+        } else {
+            // One literal byte.
+            encoded += 1;
+            i += 1;
+        }
+    }
+    encoded
+}
+
+/// Quantize `values` to a fixed-point byte representation so the
+/// compressor in `compressed_len` sees the same bytes for the same values
+/// regardless of floating-point formatting noise.
+fn quantize_to_bytes(values: &[f64]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len() * 8);
+    for &v in values {
+        bytes.extend_from_slice(&v.to_bits().to_le_bytes());
+    }
+    bytes
+}
+
+/// Normalized Compression Distance between `a` and `b`: `(C(ab) -
+/// min(C(a), C(b))) / max(C(a), C(b))`, using `compressed_len` as `C(.)`.
+/// Lower means the joint sequence compresses about as well as the smaller
+/// of the two alone — i.e. `b` adds little beyond what `a` already encodes
+/// — so this criterion passes when the score is *below*
+/// `request.compressibility_threshold`.
+pub fn check_compressibility(request: &MixingRequest) -> CriterionResult {
+    let a_bytes = quantize_to_bytes(&request.samples_a);
+    let b_bytes = quantize_to_bytes(&request.samples_b);
+    let mut ab_bytes = a_bytes.clone();
+    ab_bytes.extend_from_slice(&b_bytes);
+
+    let c_a = compressed_len(&a_bytes) as f64;
+    let c_b = compressed_len(&b_bytes) as f64;
+    let c_ab = compressed_len(&ab_bytes) as f64;
+
+    let denom = c_a.max(c_b);
+    let ncd = if denom <= 0.0 {
+        0.0
+    } else {
+        ((c_ab - c_a.min(c_b)) / denom).clamp(0.0, 1.0)
+    };
+
+    CriterionResult {
+        score: ncd,
+        passed: ncd < request.compressibility_threshold,
+    }
+}
+
+/// Pearson correlation between `a` and `b` (same length, already lagged
+/// into alignment by the caller). `0.0` if either has zero variance.
+fn pearson_correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let mean_a = mean(a);
+    let mean_b = mean(b);
+
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for i in 0..n {
+        let da = a[i] - mean_a;
+        let db = b[i] - mean_b;
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return 0.0;
+    }
+    cov / (var_a.sqrt() * var_b.sqrt())
+}
+
+/// `a` and `b` shifted so `a[i]` lines up with `b[i + lag]` (`lag` may be
+/// negative), truncated to their overlapping range. Borrows into `a`/`b`
+/// rather than copying, since every caller only ever reads the result.
+fn lagged_pair<'a>(a: &'a [f64], b: &'a [f64], lag: isize) -> (&'a [f64], &'a [f64]) {
+    if lag >= 0 {
+        let lag = lag as usize;
+        if lag >= b.len() {
+            return (&[], &[]);
+        }
+        let n = a.len().min(b.len() - lag);
+        (&a[..n], &b[lag..lag + n])
+    } else {
+        let lag = (-lag) as usize;
+        if lag >= a.len() {
+            return (&[], &[]);
+        }
+        let n = b.len().min(a.len() - lag);
+        (&a[lag..lag + n], &b[..n])
+    }
+}
+
+/// Peak absolute lagged cross-correlation between `a` and `b` over
+/// `[-max_lag, max_lag]`, and the lag it occurred at (positive: `a` leads
+/// `b`). Used both directly by `check_predictive_power` and, per window, by
+/// `check_temporal_stability`.
+fn peak_lagged_correlation(a: &[f64], b: &[f64], max_lag: usize) -> (f64, isize) {
+    let max_lag = max_lag.min(MAX_LAG_SEARCH);
+    let mut best_corr = 0.0f64;
+    let mut best_lag = 0isize;
+    for lag in -(max_lag as isize)..=(max_lag as isize) {
+        let (la, lb) = lagged_pair(a, b, lag);
+        if la.len() < 2 {
+            continue;
+        }
+        let corr = pearson_correlation(la, lb);
+        if corr.abs() > best_corr.abs() {
+            best_corr = corr;
+            best_lag = lag;
+        }
+    }
+    (best_corr, best_lag)
+}
+
+/// Peak absolute lagged cross-correlation, passing when it exceeds
+/// `request.predictive_power_threshold`. The winning lag is reported
+/// alongside the score so an "X precedes Y" claim names an actual lag
+/// instead of asserting direction.
+pub fn check_predictive_power(request: &MixingRequest) -> (CriterionResult, isize) {
+    let (corr, lag) = peak_lagged_correlation(&request.samples_a, &request.samples_b, request.max_lag);
+    let score = corr.abs();
+    (
+        CriterionResult {
+            score,
+            passed: score >= request.predictive_power_threshold,
+        },
+        lag,
+    )
+}
+
+/// Splits `a`/`b` into `request.stability_windows` equal-size windows,
+/// recomputes `check_predictive_power`'s peak-correlation score within
+/// each, and passes when that score's variance across windows is below
+/// `request.stability_variance_threshold` — i.e. the relationship between
+/// the two signals holds steady over time rather than only in aggregate.
+pub fn check_temporal_stability(request: &MixingRequest) -> CriterionResult {
+    let windows = request.stability_windows.clamp(1, MAX_STABILITY_WINDOWS);
+    let n = request.samples_a.len().min(request.samples_b.len());
+    if n < windows * 2 {
+        return CriterionResult { score: f64::INFINITY, passed: false };
+    }
+
+    let window_len = n / windows;
+    let mut window_scores = Vec::with_capacity(windows);
+    for w in 0..windows {
+        let start = w * window_len;
+        let end = if w == windows - 1 { n } else { start + window_len };
+        let (corr, _) = peak_lagged_correlation(
+            &request.samples_a[start..end],
+            &request.samples_b[start..end],
+            request.max_lag.min((end - start).saturating_sub(1)),
+        );
+        window_scores.push(corr.abs());
+    }
+
+    let score = variance(&window_scores);
+    CriterionResult {
+        score,
+        passed: score < request.stability_variance_threshold,
+    }
+}
+
+/// Fit a lag-1 AR model (`a[i] ~ c + phi * a[i-1]`) by least squares and
+/// return its residual sum of squares.
+fn ar1_residual_sum_of_squares(a: &[f64]) -> f64 {
+    if a.len() < 2 {
+        return 0.0;
+    }
+    let xs: Vec<f64> = a[..a.len() - 1].to_vec();
+    let ys: Vec<f64> = a[1..].to_vec();
+    let (c, phi) = least_squares_line(&xs, &ys);
+    ys.iter()
+        .zip(xs.iter())
+        .map(|(&y, &x)| (y - (c + phi * x)).powi(2))
+        .sum()
+}
+
+/// Fit `a[i] ~ c + phi_a * a[i-1] + phi_b * b[i-1]` by least squares and
+/// return its residual sum of squares — the augmented model a Granger test
+/// compares against the plain AR(1) model above.
+fn ar1_with_exogenous_residual_sum_of_squares(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return 0.0;
+    }
+    let y: Vec<f64> = a[1..n].to_vec();
+    let x1: Vec<f64> = a[..n - 1].to_vec();
+    let x2: Vec<f64> = b[..n - 1].to_vec();
+    let (c, phi_a, phi_b) = least_squares_plane(&x1, &x2, &y);
+    y.iter()
+        .enumerate()
+        .map(|(i, &yi)| (yi - (c + phi_a * x1[i] + phi_b * x2[i])).powi(2))
+        .sum()
+}
+
+/// Ordinary least squares fit of `y ~ c + slope * x`, returning `(c, slope)`.
+fn least_squares_line(x: &[f64], y: &[f64]) -> (f64, f64) {
+    let n = x.len() as f64;
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean_x = mean(x);
+    let mean_y = mean(y);
+    let mut cov = 0.0;
+    let mut var_x = 0.0;
+    for i in 0..x.len() {
+        cov += (x[i] - mean_x) * (y[i] - mean_y);
+        var_x += (x[i] - mean_x).powi(2);
+    }
+    let slope = if var_x > 0.0 { cov / var_x } else { 0.0 };
+    (mean_y - slope * mean_x, slope)
+}
+
+/// Ordinary least squares fit of `y ~ c + b1 * x1 + b2 * x2` via the normal
+/// equations, solved directly since this is always a 3x3 system.
+fn least_squares_plane(x1: &[f64], x2: &[f64], y: &[f64]) -> (f64, f64, f64) {
+    let n = x1.len() as f64;
+    if n == 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let sum_1 = n;
+    let sum_x1: f64 = x1.iter().sum();
+    let sum_x2: f64 = x2.iter().sum();
+    let sum_x1x1: f64 = x1.iter().map(|v| v * v).sum();
+    let sum_x2x2: f64 = x2.iter().map(|v| v * v).sum();
+    let sum_x1x2: f64 = x1.iter().zip(x2.iter()).map(|(a, b)| a * b).sum();
+    let sum_y: f64 = y.iter().sum();
+    let sum_x1y: f64 = x1.iter().zip(y.iter()).map(|(a, b)| a * b).sum();
+    let sum_x2y: f64 = x2.iter().zip(y.iter()).map(|(a, b)| a * b).sum();
+
+    // Normal equations for [c, b1, b2]^T, solved with Cramer's rule.
+    let m = [
+        [sum_1, sum_x1, sum_x2],
+        [sum_x1, sum_x1x1, sum_x1x2],
+        [sum_x2, sum_x1x2, sum_x2x2],
+    ];
+    let rhs = [sum_y, sum_x1y, sum_x2y];
+
+    match solve_3x3(m, rhs) {
+        Some(solution) => (solution[0], solution[1], solution[2]),
+        None => (mean(y), 0.0, 0.0),
+    }
+}
+
+fn determinant_3x3(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn solve_3x3(m: [[f64; 3]; 3], rhs: [f64; 3]) -> Option<[f64; 3]> {
+    let det = determinant_3x3(m);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+
+    let mut solution = [0.0; 3];
+    for col in 0..3 {
+        let mut replaced = m;
+        for row in 0..3 {
+            replaced[row][col] = rhs[row];
+        }
+        solution[col] = determinant_3x3(replaced) / det;
+    }
+    Some(solution)
+}
+
+/// Granger-style causation test: does adding `b`'s lagged values to an
+/// AR(1) model of `a` reduce its residual sum of squares by at least
+/// `MIN_RESIDUAL_REDUCTION`? A real Granger test reports an F-statistic and
+/// p-value; this uses the same "does the exogenous lag help" comparison
+/// but collapses it to a single fractional-improvement threshold, which is
+/// enough to answer "does `b` add predictive power over `a`'s own past"
+/// without pulling in a full statistical-testing dependency.
+fn granger_like_causation(a: &[f64], b: &[f64]) -> bool {
+    const MIN_RESIDUAL_REDUCTION: f64 = 0.1;
+
+    let baseline = ar1_residual_sum_of_squares(a);
+    if baseline <= 0.0 {
+        return false;
+    }
+    let augmented = ar1_with_exogenous_residual_sum_of_squares(a, b);
+    (baseline - augmented) / baseline >= MIN_RESIDUAL_REDUCTION
+}
+
+/// `1.0` if either `relationships` has a contributed `Causal` entry for
+/// this modality pair or the Granger-style test (checked in both
+/// directions) finds one signal helps predict the other, else `0.0`.
+/// `relationships` was a compiled-in `KNOWN_CAUSAL_PAIRS` table before this
+/// took a `ModalityRelationshipStore`, so the knowledge base can now grow
+/// through peer contribution instead of a code release.
+pub fn check_physical_causation(request: &MixingRequest, relationships: &ModalityRelationshipStore) -> CriterionResult {
+    let known =
+        relationships.has_relationship_kind(&request.modality_a, &request.modality_b, RelationshipKind::Causal);
+    let granger = granger_like_causation(&request.samples_a, &request.samples_b)
+        || granger_like_causation(&request.samples_b, &request.samples_a);
+
+    let passed = known || granger;
+    CriterionResult {
+        score: if passed { 1.0 } else { 0.0 },
+        passed,
+    }
+}
+
+/// Build a `ValidationDiagnostic` for a failed criterion, or `None` if it
+/// passed. Shared by every criterion below so that adding a sixth criterion,
+/// or changing `ValidationDiagnostic`'s shape, only needs changing in one
+/// place rather than in five near-identical copies. `message` is lazy so a
+/// passing criterion doesn't pay for a `format!` it never uses.
+fn diagnostic_if_failed(
+    result: CriterionResult,
+    code: DiagnosticCode,
+    severity: Severity,
+    message: impl FnOnce() -> String,
+) -> Option<ValidationDiagnostic> {
+    if result.passed {
+        return None;
+    }
+    Some(ValidationDiagnostic { code, severity, message: message() })
+}
+
+/// Run all five criteria against `request` and return their scores, along
+/// with a `ValidationDiagnostic` for each criterion that didn't pass.
+/// `relationships` backs `check_physical_causation`'s curated-pair lookup —
+/// `ModalityRelationshipStore::new` already seeds the crate's previous
+/// built-in pairs, so a freshly constructed store still carries them. Errs
+/// if `samples_a`/`samples_b` aren't the same length, since every
+/// criterion below assumes index `i` in one lines up with index `i` in the
+/// other.
+pub fn validate_mixing_empirical(
+    request: MixingRequest,
+    relationships: &ModalityRelationshipStore,
+) -> Result<MixingValidationResult, ShardError> {
+    if request.samples_a.len() != request.samples_b.len() {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "mixing validation for {}/{} requires aligned sample vectors",
+                request.modality_a, request.modality_b
+            ),
+            source: Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "samples_a and samples_b have different lengths",
+            )),
+        });
+    }
+    if request.samples_a.len() > MAX_SAMPLES {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "mixing validation for {}/{} exceeds the maximum sample count ({MAX_SAMPLES})",
+                request.modality_a, request.modality_b
+            ),
+            source: Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "sample vectors too large",
+            )),
+        });
+    }
+
+    let information_gain = check_information_gain(&request);
+    let compressibility = check_compressibility(&request);
+    let (predictive_power, predictive_power_lag) = check_predictive_power(&request);
+    let temporal_stability = check_temporal_stability(&request);
+    let physical_causation = check_physical_causation(&request, relationships);
+
+    let mut diagnostics = Vec::new();
+    diagnostics.extend(diagnostic_if_failed(
+        information_gain,
+        DiagnosticCode::LowMutualInformation,
+        Severity::Error,
+        || {
+            format!(
+                "normalized mutual information {:.4} is below the {:.4} threshold for {}/{}",
+                information_gain.score, request.information_gain_threshold, request.modality_a, request.modality_b
+            )
+        },
+    ));
+    diagnostics.extend(diagnostic_if_failed(
+        compressibility,
+        DiagnosticCode::InsufficientCompressibility,
+        Severity::Warning,
+        || {
+            format!(
+                "normalized compression distance {:.4} is at or above the {:.4} threshold for {}/{}",
+                compressibility.score, request.compressibility_threshold, request.modality_a, request.modality_b
+            )
+        },
+    ));
+    diagnostics.extend(diagnostic_if_failed(
+        predictive_power,
+        DiagnosticCode::WeakPredictivePower,
+        Severity::Warning,
+        || {
+            format!(
+                "peak lagged correlation {:.4} at lag {predictive_power_lag} is below the {:.4} threshold for {}/{}",
+                predictive_power.score, request.predictive_power_threshold, request.modality_a, request.modality_b
+            )
+        },
+    ));
+    diagnostics.extend(diagnostic_if_failed(
+        temporal_stability,
+        DiagnosticCode::UnstableOverTime,
+        Severity::Warning,
+        || {
+            format!(
+                "predictive-power variance {:.4} across windows is at or above the {:.4} threshold for {}/{}",
+                temporal_stability.score, request.stability_variance_threshold, request.modality_a, request.modality_b
+            )
+        },
+    ));
+    diagnostics.extend(diagnostic_if_failed(
+        physical_causation,
+        DiagnosticCode::CausationUnverified,
+        Severity::Error,
+        || {
+            format!(
+                "neither a curated modality pair nor a Granger-style test found {} to help predict {} or vice versa",
+                request.modality_b, request.modality_a
+            )
+        },
+    ));
+
+    Ok(MixingValidationResult {
+        information_gain,
+        compressibility,
+        predictive_power,
+        predictive_power_lag,
+        temporal_stability,
+        physical_causation,
+        diagnostics,
+    })
+}
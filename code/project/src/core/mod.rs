@@ -0,0 +1,61 @@
+// src/core/mod.rs
+#[path = "centroidCRDT.rs"]
+mod centroid_crdt;
+mod metrics;
+pub mod antenna_pattern;
+pub mod array_synthesis;
+pub mod checkpoint;
+pub mod chunked_store;
+pub mod cooperative;
+pub mod dimensional_analysis;
+pub mod emitter_association;
+pub mod grib_packing;
+pub mod mixing_pipeline;
+pub mod mixing_validation;
+pub mod modality_relationship;
+pub mod pattern_extraction;
+pub mod pattern_matcher;
+pub mod persistence;
+pub mod propagation_calibration;
+pub mod spectral_band;
+pub mod spectral_coherence;
+pub mod threshold_handlers;
+mod vector;
+
+pub use antenna_pattern::{correlate_antenna_patterns, AntennaLocation, AntennaPatternRecord, Polarization};
+pub use array_synthesis::{
+    synthesize_array_pattern, ArraySynthesisInput, ArraySynthesisResult, ComplexSample, ElementExcitation,
+};
+pub use centroid_crdt::{CentroidCRDT, TimeError, VersionVector};
+pub use checkpoint::CheckpointManager;
+pub use chunked_store::{ChunkHash, ChunkStore, ChunkedHistory, ChunkedVersion};
+pub use cooperative::CooperativeBudget;
+pub use dimensional_analysis::{check_dimensional_consistency, Criterion, Dimension, Operation, QuantityKind};
+pub use emitter_association::{
+    associate_emitters, platform_pattern_to_mixing_pattern, EmitterAssociationResult, FeatureScale, Interception,
+    PlatformPattern,
+};
+pub use grib_packing::{check_compressibility_criterion, pack, unpack, PackOptions, PackedSegment};
+pub use metrics::{Metrics, Percentiles, ThresholdAction, ThresholdHandler};
+pub use mixing_pipeline::{
+    get_patterns_by_citation, plan_pipeline, Citation, IdentifierKind, MixingPattern, MixingPatternStore,
+    PatternStatus,
+};
+pub use mixing_validation::{
+    validate_mixing_empirical, CriterionResult, DiagnosticCode, MixingRequest, MixingValidationResult,
+    Severity, ValidationDiagnostic,
+};
+pub use modality_relationship::{ModalityRelationship, ModalityRelationshipStore, RelationshipKind};
+pub use pattern_extraction::propose_pattern_from_text;
+pub use pattern_matcher::{CriterionContribution, PatternMatcher};
+pub use persistence::{EmbeddedOrderedStore, KeyValueStore, KvOp, LmdbStore, SqliteStore};
+pub use propagation_calibration::{
+    calibrate, dispersive_group_delay_s, estimate_tec_from_dual_frequency, tropospheric_delay_s, ConditionedSignal,
+    PropagationConfig, PropagationObservation, RemovedDelays, TroposphericDelayInput,
+};
+pub use spectral_band::{
+    band_for_type, classify_spectral_relationship, spectrally_reachable_types, synthesize_spectral_pattern,
+    SpectralBand, SpectralRelationship,
+};
+pub use spectral_coherence::{check_spectral_coherence, welch_coherence, CoherenceSpectrum, WelchOptions};
+pub use vector::{Vector, VectorMetadata};
@@ -0,0 +1,363 @@
+// src/core/modality_relationship.rs
+//! Peer-contributable knowledge base backing `mixing_validation`'s
+//! physical-causation check. Previously that check's "well-known pairs"
+//! table (`KNOWN_CAUSAL_PAIRS`) was compiled into the binary, so improving
+//! it meant a code release; a `ModalityRelationship` is instead a
+//! DHT-storable entry any agent can submit via `add_relationship`, and
+//! `ModalityRelationshipStore` indexes them by modality pair the way
+//! `ChunkStore` indexes chunks by hash — the in-process "discovery link" a
+//! real DHT's `get_links` would otherwise provide (see `mixing_pipeline`'s
+//! module doc comment for why this crate models DHT relationships as
+//! direct references/indexes rather than `LinkTypes` entry/link pairs).
+
+use hdk::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::ShardError;
+
+/// What kind of empirical relationship a `ModalityRelationship` claims
+/// between its two modalities — matches the five criteria in
+/// `mixing_validation` a contributed entry could back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelationshipKind {
+    Causal,
+    Complementary,
+    Predictive,
+    Stable,
+    Compressible,
+}
+
+/// A peer-contributed claim that two modalities have an empirical
+/// relationship worth taking into account when validating whether to fuse
+/// them. `evidence` and `citations` let a reviewer judge plausibility
+/// without the crate having to adjudicate truth itself — `validate` only
+/// rejects entries that are malformed, not ones that are merely wrong.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModalityRelationship {
+    pub modality_a: String,
+    pub modality_b: String,
+    pub relationship_kind: RelationshipKind,
+    /// Free-text summary of the observation backing this claim (e.g. "N=…,
+    /// correlation=…"). Not a substitute for `citations` — just enough for
+    /// a reviewer to judge plausibility without following a link out.
+    pub evidence: String,
+    pub citations: Vec<String>,
+    pub contributed_by: AgentPubKey,
+    /// Caller-supplied rather than stamped internally with `sys_time()` —
+    /// matches `ModelMetadata::timestamp` in `nerv::replication`, whose
+    /// caller likewise computes the timestamp itself, so the HDK host call
+    /// happens at the zome boundary rather than inside a constructor that
+    /// plain unit tests would otherwise have to go through too.
+    pub created_at: u64,
+}
+
+impl ModalityRelationship {
+    pub fn new(
+        modality_a: String,
+        modality_b: String,
+        relationship_kind: RelationshipKind,
+        evidence: String,
+        citations: Vec<String>,
+        contributed_by: AgentPubKey,
+        created_at: u64,
+    ) -> Self {
+        Self {
+            modality_a,
+            modality_b,
+            relationship_kind,
+            evidence,
+            citations,
+            contributed_by,
+            created_at,
+        }
+    }
+
+    /// `modality_a`/`modality_b`, lowercased and ordered, independent of
+    /// which side a contributor named first — a relationship between "a"
+    /// and "b" is the same claim as one between "b" and "a", and
+    /// `ModalityRelationshipStore` indexes on this pair so a lookup finds
+    /// it regardless of argument order.
+    fn pair_key(modality_a: &str, modality_b: &str) -> (String, String) {
+        let a = modality_a.to_ascii_lowercase();
+        let b = modality_b.to_ascii_lowercase();
+        if a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Reject an obviously malformed contribution before it's stored or
+    /// folded into validation — the `validate_pattern`-style integrity
+    /// check this entry type gets in place of a Holochain validation
+    /// callback (this crate has none; see `validate_model_update` in
+    /// `nerv::replication` for the equivalent pattern elsewhere).
+    pub fn validate(&self) -> Result<(), ShardError> {
+        if self.modality_a.trim().is_empty() || self.modality_b.trim().is_empty() {
+            return Err(ShardError::MigrationFailed {
+                context: "modality relationship requires non-empty modality names".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty modality name")),
+            });
+        }
+        if self.modality_a.eq_ignore_ascii_case(&self.modality_b) {
+            return Err(ShardError::MigrationFailed {
+                context: format!("modality relationship cannot relate \"{}\" to itself", self.modality_a),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "self-relationship")),
+            });
+        }
+        if self.evidence.trim().is_empty() {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "modality relationship {}/{} requires non-empty evidence",
+                    self.modality_a, self.modality_b
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing evidence")),
+            });
+        }
+        if self.evidence.len() > MAX_EVIDENCE_LEN {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "modality relationship {}/{} evidence exceeds the maximum length of {MAX_EVIDENCE_LEN} bytes",
+                    self.modality_a, self.modality_b
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "evidence too large")),
+            });
+        }
+        if self.citations.len() > MAX_CITATIONS {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "modality relationship {}/{} has more than the maximum of {MAX_CITATIONS} citations",
+                    self.modality_a, self.modality_b
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many citations")),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn to_entry(&self) -> ExternResult<Entry> {
+        let entry = Entry::App(self.try_into()?);
+        Ok(entry)
+    }
+
+    pub fn create_entry(&self) -> ExternResult<HeaderHash> {
+        create_entry(self.to_entry()?)
+    }
+}
+
+/// Upper bound on a single `ModalityRelationship::evidence` string, and on
+/// its `citations` list below: `MAX_RELATIONSHIPS_PER_PAIR` only caps how
+/// many relationships a pair can hold, not how large any one of them is, so
+/// without these a single contribution's free-text fields could still grow
+/// without bound.
+const MAX_EVIDENCE_LEN: usize = 2_000;
+const MAX_CITATIONS: usize = 32;
+
+/// Well-known direct physical relationships this crate shipped as a
+/// hardcoded table before this module existed — seeded into every fresh
+/// `ModalityRelationshipStore` so moving the knowledge base from
+/// compiled-in constants to contributable entries doesn't regress existing
+/// validation behavior.
+const DEFAULT_CAUSAL_PAIRS: &[(&str, &str)] = &[
+    ("temperature", "humidity"),
+    ("accelerometer", "gyroscope"),
+    ("pressure", "altitude"),
+    ("vibration", "acoustic"),
+];
+
+/// Upper bound on how many relationships `add_relationship` will index for
+/// a single modality pair. This store takes contributions from any agent,
+/// so — same reasoning as `mixing_validation`'s `MAX_SAMPLES`/
+/// `mixing_pipeline`'s `MAX_PATTERNS` — an unbounded contribution count per
+/// pair is a memory-exhaustion vector, not just clutter.
+const MAX_RELATIONSHIPS_PER_PAIR: usize = 64;
+
+/// All-zero placeholder attribution for the relationships `new()` seeds
+/// itself, as opposed to ones a real agent submits via `add_relationship` —
+/// an advisory marker only, not a security boundary: this crate has no
+/// zome-level check anywhere (see `MigrationPlan`/`ModelUpdate`) that a
+/// caller's claimed `AgentPubKey` is actually theirs, so a peer naming this
+/// same all-zero key in a real contribution is indistinguishable from a
+/// seeded default. Fine for what this constant is used for today (seeding
+/// `new()`'s own entries); a caller that needs to tell "genuinely a crate
+/// default" from "claims to be" apart would need real agent authentication,
+/// which is out of scope for this crate.
+fn seeded_default_agent() -> AgentPubKey {
+    AgentPubKey::from_raw_32(vec![0u8; 32].try_into().unwrap())
+}
+
+/// In-process index of `ModalityRelationship` entries by modality pair —
+/// the "discovery links" a real DHT would provide via `get_links`, kept
+/// in-memory the same way `ChunkStore` indexes chunk bytes by hash rather
+/// than walking the DHT for every lookup. Guarded by an `RwLock` rather
+/// than a `Mutex`: `has_relationship_kind` is on `check_physical_causation`'s
+/// hot path and only reads, while `add_relationship` (a peer contribution)
+/// is comparatively rare, so concurrent lookups shouldn't serialize on each
+/// other the way they would behind a plain `Mutex`.
+pub struct ModalityRelationshipStore {
+    by_pair: RwLock<HashMap<(String, String), Vec<ModalityRelationship>>>,
+}
+
+impl ModalityRelationshipStore {
+    /// A store seeded with `DEFAULT_CAUSAL_PAIRS` as `Causal` relationships.
+    /// Seeding by default (rather than requiring a caller to opt in) means
+    /// `check_physical_causation` doesn't silently regress relative to the
+    /// compiled-in table it replaces just because a caller reached for the
+    /// obvious constructor instead of remembering a separate "with
+    /// defaults" one.
+    pub fn new() -> Self {
+        let store = Self {
+            by_pair: RwLock::new(HashMap::new()),
+        };
+        for &(a, b) in DEFAULT_CAUSAL_PAIRS {
+            // `created_at: 0` — these aren't attributed to a real moment in
+            // time, just compiled-in defaults present from the store's
+            // creation.
+            let relationship = ModalityRelationship::new(
+                a.to_string(),
+                b.to_string(),
+                RelationshipKind::Causal,
+                "seeded default: well-known direct physical relationship".to_string(),
+                Vec::new(),
+                seeded_default_agent(),
+                0,
+            );
+            store
+                .add_relationship(relationship)
+                .expect("seeded default relationship is always well-formed");
+        }
+        store
+    }
+
+    /// Validate and index `relationship`. Every contribution goes through
+    /// this one path — including the defaults `new` seeds — so the default
+    /// table and peer-contributed entries can't diverge in how they're
+    /// checked. Errs once a pair already holds `MAX_RELATIONSHIPS_PER_PAIR`
+    /// entries: this store accepts contributions from any agent, and
+    /// without a cap a single misbehaving peer could grow one pair's
+    /// `Vec` — and the memory behind it — without bound.
+    pub fn add_relationship(&self, relationship: ModalityRelationship) -> Result<(), ShardError> {
+        relationship.validate()?;
+        let key = ModalityRelationship::pair_key(&relationship.modality_a, &relationship.modality_b);
+        let mut by_pair = self.by_pair.write().expect("modality relationship store lock poisoned");
+        let relationships = by_pair.entry(key).or_default();
+        if relationships.len() >= MAX_RELATIONSHIPS_PER_PAIR {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "modality relationship {}/{} already has the maximum of {MAX_RELATIONSHIPS_PER_PAIR} contributed relationships",
+                    relationship.modality_a, relationship.modality_b
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many contributed relationships")),
+            });
+        }
+        relationships.push(relationship);
+        Ok(())
+    }
+
+    /// Every relationship contributed for this modality pair, in either
+    /// argument order.
+    pub fn relationships_for(&self, modality_a: &str, modality_b: &str) -> Vec<ModalityRelationship> {
+        let key = ModalityRelationship::pair_key(modality_a, modality_b);
+        self.by_pair
+            .read()
+            .expect("modality relationship store lock poisoned")
+            .get(&key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// True if any contributed relationship for this pair names `kind` —
+    /// what `check_physical_causation` queries in place of the old
+    /// `KNOWN_CAUSAL_PAIRS` table lookup. Checks under the lock directly
+    /// rather than going through `relationships_for`, which would clone
+    /// every contributed relationship (including its `evidence`/`citations`
+    /// strings) just to answer a yes/no question.
+    pub fn has_relationship_kind(&self, modality_a: &str, modality_b: &str, kind: RelationshipKind) -> bool {
+        let key = ModalityRelationship::pair_key(modality_a, modality_b);
+        self.by_pair
+            .read()
+            .expect("modality relationship store lock poisoned")
+            .get(&key)
+            .is_some_and(|relationships| relationships.iter().any(|r| r.relationship_kind == kind))
+    }
+}
+
+impl Default for ModalityRelationshipStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_agent() -> AgentPubKey {
+        AgentPubKey::from_raw_32(vec![7; 32].try_into().unwrap())
+    }
+
+    #[test]
+    fn new_recovers_the_previous_known_causal_pairs() {
+        let store = ModalityRelationshipStore::new();
+        assert!(store.has_relationship_kind("temperature", "humidity", RelationshipKind::Causal));
+        assert!(store.has_relationship_kind("accelerometer", "gyroscope", RelationshipKind::Causal));
+        assert!(!store.has_relationship_kind("temperature", "gyroscope", RelationshipKind::Causal));
+    }
+
+    #[test]
+    fn relationship_lookup_is_order_and_case_insensitive() {
+        let store = ModalityRelationshipStore::new();
+        assert!(store.has_relationship_kind("HUMIDITY", "Temperature", RelationshipKind::Causal));
+    }
+
+    #[test]
+    fn add_relationship_rejects_self_relationship() {
+        let store = ModalityRelationshipStore::new();
+        let relationship = ModalityRelationship::new(
+            "temperature".to_string(),
+            "temperature".to_string(),
+            RelationshipKind::Causal,
+            "duplicate sensor readings".to_string(),
+            Vec::new(),
+            test_agent(),
+            1_700_000_000_000,
+        );
+        assert!(store.add_relationship(relationship).is_err());
+    }
+
+    #[test]
+    fn add_relationship_rejects_missing_evidence() {
+        let store = ModalityRelationshipStore::new();
+        let relationship = ModalityRelationship::new(
+            "pressure".to_string(),
+            "altitude".to_string(),
+            RelationshipKind::Causal,
+            String::new(),
+            Vec::new(),
+            test_agent(),
+            1_700_000_000_000,
+        );
+        assert!(store.add_relationship(relationship).is_err());
+    }
+
+    #[test]
+    fn contributed_relationship_is_discoverable_by_kind() {
+        let store = ModalityRelationshipStore::new();
+        let relationship = ModalityRelationship::new(
+            "vibration".to_string(),
+            "temperature".to_string(),
+            RelationshipKind::Complementary,
+            "observed across 40 field deployments".to_string(),
+            vec!["internal-report-2026-03".to_string()],
+            test_agent(),
+            1_700_000_000_000,
+        );
+        store.add_relationship(relationship).unwrap();
+
+        assert!(store.has_relationship_kind("vibration", "temperature", RelationshipKind::Complementary));
+        assert!(!store.has_relationship_kind("vibration", "temperature", RelationshipKind::Causal));
+        assert_eq!(store.relationships_for("temperature", "vibration").len(), 1);
+    }
+}
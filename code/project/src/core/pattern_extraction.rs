@@ -0,0 +1,224 @@
+// src/core/pattern_extraction.rs
+//! Drafts candidate `MixingPattern`s from free text instead of requiring
+//! every pattern to be hand-coded. Modeled on scientific keyphrase-
+//! extraction annotation schemes (e.g. SciERC) that tag spans as
+//! `Material`, `Process`, or `Task` and relate them: a `Material`/
+//! measurable-quantity mention becomes a candidate input or output type,
+//! and a `Process` mention becomes a candidate `Operation`.
+//!
+//! There's no NLP model anywhere in this crate (same boundary
+//! `mixing_validation::check_compressibility`'s stand-in compressor and
+//! `grib_packing`'s hand-rolled packing draw — a real implementation of
+//! the thing exists elsewhere, a bounded approximation lives here), so
+//! extraction here is a fixed-vocabulary keyword match rather than a
+//! trained tagger: [`MATERIAL_TERMS`] draws from the modality/quantity
+//! names this crate already recognizes elsewhere — both
+//! `quantity_kind_for_type`'s dimensionally-typed names and
+//! `ModalityRelationshipStore`'s seeded causal-pair modalities, which
+//! don't all overlap — and [`PROCESS_TERMS`] covers the `Operation`
+//! variants `dimensional_analysis` already checks against. A draft this
+//! produces is never trusted outright — `MixingPatternStore::add_pattern`
+//! always indexes it as `PatternStatus::Unvalidated`, and `promote` still
+//! has to pass `validate_pattern` before it's treated as vetted; a draft
+//! that names a material span `quantity_kind_for_type` doesn't recognize
+//! (e.g. `"vibration"`) alongside a guessed `operation` is expected to
+//! fail promotion for exactly that reason, the same as a hand-authored
+//! pattern would.
+
+use crate::core::dimensional_analysis::Operation;
+use crate::core::mixing_pipeline::{Citation, MixingPattern};
+use crate::error::ShardError;
+
+/// Upper bound on the input text `propose_pattern_from_text` scans.
+/// Extraction checks every one of `MATERIAL_TERMS`/`PROCESS_TERMS` against
+/// the text with `str::find`, so an unbounded text length would make one
+/// call's cost unbounded too.
+const MAX_TEXT_LEN: usize = 20_000;
+
+/// Recognized `Material`/measurable-quantity mentions, as
+/// `(lowercase phrase, MixingPattern input/output type name)` pairs.
+/// Ordered longest-phrase-first so a more specific mention (e.g.
+/// `"magnetic flux density"`) is matched before a shorter one it contains
+/// (`"magnetic flux"`) is mistaken for a second, overlapping mention of
+/// the same span — see `find_material_spans`.
+const MATERIAL_TERMS: &[(&str, &str)] = &[
+    ("magnetic flux density", "magnetic_flux_density"),
+    ("magnetic flux", "magnetic_flux"),
+    ("voltage", "voltage"),
+    ("temperature", "temperature"),
+    ("pressure", "pressure"),
+    ("altitude", "altitude"),
+    ("accelerometer", "accelerometer"),
+    ("gyroscope", "gyroscope"),
+    ("humidity", "humidity"),
+    ("vibration", "vibration"),
+    ("acoustic", "acoustic"),
+];
+
+/// Recognized `Process` mentions, as `(lowercase phrase, Operation)`
+/// pairs, ordered longest-phrase-first for the same reason as
+/// `MATERIAL_TERMS` (`"time derivative"` before `"derivative"`).
+const PROCESS_TERMS: &[(&str, Operation)] = &[
+    ("time derivative", Operation::TimeDerivative),
+    ("derivative", Operation::TimeDerivative),
+    ("time integral", Operation::TimeIntegral),
+    ("integral", Operation::TimeIntegral),
+    ("ratio", Operation::Ratio),
+    ("product", Operation::Product),
+];
+
+/// The first occurrence of each distinct `MATERIAL_TERMS` phrase found in
+/// `text`, as `(type_name, start_byte_offset)`, in the order those
+/// occurrences appear. Each phrase contributes at most one span — this
+/// locates *which* material types a text mentions, not every place it
+/// mentions them — and a shorter phrase whose first occurrence starts
+/// inside an already-matched longer one (e.g. `"magnetic flux"` inside an
+/// already-matched `"magnetic flux density"`) is skipped rather than
+/// counted as a second, distinct type.
+fn find_material_spans(normalized: &str) -> Vec<(&'static str, usize)> {
+    let mut spans: Vec<(usize, usize, &'static str)> = Vec::new();
+    for &(phrase, type_name) in MATERIAL_TERMS {
+        let Some(start) = normalized.find(phrase) else {
+            continue;
+        };
+        let end = start + phrase.len();
+        let overlaps = spans.iter().any(|&(existing_start, existing_end, _)| start < existing_end && existing_start < end);
+        if !overlaps {
+            spans.push((start, end, type_name));
+        }
+    }
+    spans.sort_by_key(|&(start, _, _)| start);
+    spans.into_iter().map(|(start, _, type_name)| (type_name, start)).collect()
+}
+
+/// The highest-priority `PROCESS_TERMS` phrase present in `text`, if any —
+/// "highest-priority" meaning earliest in `PROCESS_TERMS`'s declaration
+/// order, so a more specific phrase wins over a shorter one it contains
+/// (`"time derivative"` over `"derivative"`) regardless of which appears
+/// first in the text. A pattern only ever declares one `operation`, so
+/// unlike `find_material_spans` there's no need to locate every mention.
+fn find_operation(normalized: &str) -> Option<Operation> {
+    PROCESS_TERMS.iter().find(|(phrase, _)| normalized.contains(phrase)).map(|(_, operation)| operation.clone())
+}
+
+/// Draft a candidate `MixingPattern` from `text`: the earliest-mentioned
+/// recognized material spans become `inputs`, the last-mentioned one
+/// becomes the sole `produces` entry, and the highest-priority recognized
+/// process phrase (if any) becomes the declared `operation`. `citation`
+/// (the source passage's provenance) and `contributed_by` (the extractor's
+/// own identifier, e.g. `"keyphrase_extractor"`) are recorded directly on
+/// the result, same as a caller would set them on a hand-built
+/// `MixingPattern`.
+///
+/// Errs if `text` exceeds `MAX_TEXT_LEN`, or if fewer than two distinct
+/// material spans are found — one input and one output type are the
+/// minimum a `MixingPattern` needs, and a single mention can't be split
+/// into both.
+///
+/// The returned pattern is a draft, not a vetted one: it's this
+/// function's caller's job to run it through
+/// `MixingPatternStore::add_pattern` as `PatternStatus::Unvalidated` and
+/// `promote` it only once `validate_pattern` (dimensional consistency
+/// included, for the `operation` this function may have guessed) actually
+/// passes.
+pub fn propose_pattern_from_text(text: &str, citation: Citation, contributed_by: String) -> Result<MixingPattern, ShardError> {
+    if text.len() > MAX_TEXT_LEN {
+        return Err(ShardError::MigrationFailed {
+            context: format!("propose_pattern_from_text: text length {} exceeds the maximum of {MAX_TEXT_LEN} bytes", text.len()),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "text too long")),
+        });
+    }
+
+    let normalized = text.to_ascii_lowercase();
+    let mut material_spans = find_material_spans(&normalized);
+    if material_spans.len() < 2 {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "propose_pattern_from_text: found {} recognized material mention(s), need at least 2 (one input, one output)",
+                material_spans.len()
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "not enough material mentions")),
+        });
+    }
+
+    let (output_type, _) = material_spans.pop().expect("checked len >= 2 above");
+    let inputs: Vec<String> = material_spans.into_iter().map(|(type_name, _)| type_name.to_string()).collect();
+    let produces = vec![output_type.to_string()];
+
+    let mut pattern = MixingPattern::new(format!("{}_to_{output_type}", inputs.join("_and_")), inputs, produces);
+    pattern.citations.push(citation);
+    pattern.contributed_by = contributed_by;
+    pattern.operation = find_operation(&normalized);
+
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mixing_pipeline::IdentifierKind;
+
+    fn source_citation() -> Citation {
+        Citation {
+            title: "Induced EMF in a Coil".to_string(),
+            identifier_kind: IdentifierKind::ArXiv,
+            identifier: "arXiv:2007.12345".to_string(),
+            authors: vec!["A. Researcher".to_string()],
+            year: 2020,
+        }
+    }
+
+    #[test]
+    fn extracts_induction_coupling_from_text() {
+        let pattern = propose_pattern_from_text(
+            "The time derivative of magnetic flux through a coil induces a voltage across its terminals.",
+            source_citation(),
+            "keyphrase_extractor".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(pattern.inputs, vec!["magnetic_flux".to_string()]);
+        assert_eq!(pattern.produces, vec!["voltage".to_string()]);
+        assert_eq!(pattern.operation, Some(Operation::TimeDerivative));
+        assert_eq!(pattern.contributed_by, "keyphrase_extractor");
+        assert_eq!(pattern.citations.len(), 1);
+        assert_eq!(pattern.citations[0].identifier, "arXiv:2007.12345");
+    }
+
+    #[test]
+    fn rejects_text_with_fewer_than_two_material_mentions() {
+        let result = propose_pattern_from_text("The voltage was measured carefully.", source_citation(), "extractor".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn leaves_operation_unset_when_no_process_phrase_is_present() {
+        let pattern = propose_pattern_from_text(
+            "Ambient temperature and pressure were logged together at each site.",
+            source_citation(),
+            "extractor".to_string(),
+        )
+        .unwrap();
+        assert!(pattern.operation.is_none());
+    }
+
+    #[test]
+    fn does_not_double_count_an_overlapping_longer_phrase() {
+        let pattern = propose_pattern_from_text(
+            "Magnetic flux density readings were compared against the induced voltage.",
+            source_citation(),
+            "extractor".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(pattern.inputs, vec!["magnetic_flux_density".to_string()]);
+        assert_eq!(pattern.produces, vec!["voltage".to_string()]);
+    }
+
+    #[test]
+    fn rejects_text_beyond_the_maximum_length() {
+        let text = "voltage ".repeat(MAX_TEXT_LEN / 8 + 1);
+        let result = propose_pattern_from_text(&text, source_citation(), "extractor".to_string());
+        assert!(result.is_err());
+    }
+}
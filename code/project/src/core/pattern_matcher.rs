@@ -0,0 +1,243 @@
+// src/core/pattern_matcher.rs
+//! Recursive Bayesian confidence scoring for the hypothesis "signal_a and
+//! signal_b are the same underlying physical phenomenon," fusing evidence
+//! from `dimensional_analysis::Criterion`s the way multistatic-radar target
+//! recognition fuses returns from several receivers into one track
+//! confidence — no single observation gets a binary up-or-down verdict,
+//! and a pattern's confidence grows or shrinks as more evidence arrives.
+//!
+//! `PatternMatcher` holds a posterior probability for `H` and updates it
+//! one round of observations at a time: `posterior(H) ∝ prior(H) ×
+//! L(z|H)`, renormalized over `{H, ¬H}`. A round can fuse evidence from N
+//! distinct criteria at once by multiplying their individual likelihoods
+//! together first (assuming conditional independence, the same assumption
+//! `mixing_validation::check_information_gain` and friends already make
+//! about `samples_a`/`samples_b` being independently observed per-sample),
+//! and the posterior from one round becomes the prior for the next —
+//! recursive Bayesian filtering, not a one-shot combination.
+
+use crate::core::dimensional_analysis::Criterion;
+use crate::error::ShardError;
+
+/// Upper bound on how many `Criterion`s a single `PatternMatcher::observe`
+/// call will fuse. Each round multiplies one likelihood per criterion, so
+/// this bounds that round's cost the same way `mixing_validation`'s
+/// `MAX_HISTOGRAM_BINS`/`MAX_LAG_SEARCH` bound theirs.
+const MAX_CRITERIA_PER_OBSERVATION: usize = 64;
+
+/// Upper bound on how many rounds of `PatternMatcher::observe`'s
+/// per-criterion contributions are retained in `contribution_history`. Past
+/// this, the oldest round is dropped — same unbounded-growth concern
+/// `metrics::Metrics`' capped sample history addresses, just for rounds
+/// instead of raw samples.
+const MAX_RETAINED_ROUNDS: usize = 1_000;
+
+/// One criterion's contribution to one `PatternMatcher::observe` round:
+/// its name, the `applies` fact it observed, and the posterior it alone
+/// would have produced had it been the only evidence fused that round —
+/// the per-criterion breakdown `MixingValidationResult::diagnostics`
+/// already gives per-criterion reasons, applied here to confidence instead
+/// of pass/fail.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CriterionContribution {
+    pub name: String,
+    pub applies: bool,
+    /// `criterion.prior × L(applies|H) / (criterion.prior × L(applies|H) +
+    /// (1 - criterion.prior) × L(applies|¬H))` — this criterion's own
+    /// single-observation posterior, independent of every other criterion
+    /// fused in the same round.
+    pub standalone_posterior: f64,
+}
+
+/// Recursive Bayesian posterior over "signal_a and signal_b are the same
+/// physical phenomenon," fused from `Criterion` evidence one observation
+/// round at a time.
+#[derive(Clone, Debug)]
+pub struct PatternMatcher {
+    posterior_h: f64,
+    contribution_history: Vec<Vec<CriterionContribution>>,
+}
+
+impl PatternMatcher {
+    /// Start a new matcher with `initial_prior` as `P(H)` before any
+    /// evidence is observed. Errs if `initial_prior` isn't in `(0.0, 1.0)`
+    /// — `0.0`/`1.0` would make the posterior immovable (multiplying zero
+    /// by any likelihood stays zero), the same reasoning
+    /// `Criterion::likelihood_given_h`/`likelihood_given_not_h` never being
+    /// exactly `0.0`/`1.0` is built on.
+    pub fn new(initial_prior: f64) -> Result<Self, ShardError> {
+        if !(initial_prior > 0.0 && initial_prior < 1.0) {
+            return Err(ShardError::MigrationFailed {
+                context: format!("PatternMatcher::new: initial_prior {initial_prior} must be strictly between 0.0 and 1.0"),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "prior out of range")),
+            });
+        }
+        Ok(Self { posterior_h: initial_prior, contribution_history: Vec::new() })
+    }
+
+    /// This matcher's current confidence in `H`, in `[0.0, 1.0]`.
+    pub fn confidence(&self) -> f64 {
+        self.posterior_h
+    }
+
+    /// Per-criterion contributions from every `observe` round so far, in
+    /// call order, capped at `MAX_RETAINED_ROUNDS` (oldest dropped first).
+    pub fn contribution_history(&self) -> &[Vec<CriterionContribution>] {
+        &self.contribution_history
+    }
+
+    /// Fuse one round of evidence from `criteria` — observations from as
+    /// many distinct sensors/checks as `criteria` has entries — into this
+    /// matcher's posterior, then make that posterior the prior for the
+    /// next call. Returns the updated confidence.
+    ///
+    /// Errs if `criteria` is empty (nothing to fuse), if it exceeds
+    /// `MAX_CRITERIA_PER_OBSERVATION`, or if any entry's `prior`,
+    /// `likelihood_given_h`, or `likelihood_given_not_h` isn't in
+    /// `(0.0, 1.0)` — same reasoning as `new`'s bound on `initial_prior`.
+    pub fn observe(&mut self, criteria: &[Criterion]) -> Result<f64, ShardError> {
+        if criteria.is_empty() {
+            return Err(ShardError::MigrationFailed {
+                context: "PatternMatcher::observe: at least one criterion is required".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "no criteria")),
+            });
+        }
+        if criteria.len() > MAX_CRITERIA_PER_OBSERVATION {
+            return Err(ShardError::MigrationFailed {
+                context: format!(
+                    "PatternMatcher::observe: {} criteria exceeds the maximum of {MAX_CRITERIA_PER_OBSERVATION} per observation",
+                    criteria.len()
+                ),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many criteria")),
+            });
+        }
+        for criterion in criteria {
+            for (field_name, value) in [
+                ("prior", criterion.prior),
+                ("likelihood_given_h", criterion.likelihood_given_h),
+                ("likelihood_given_not_h", criterion.likelihood_given_not_h),
+            ] {
+                if !(value > 0.0 && value < 1.0) {
+                    return Err(ShardError::MigrationFailed {
+                        context: format!(
+                            "PatternMatcher::observe: criterion \"{}\"'s {field_name} {value} must be strictly between 0.0 and 1.0",
+                            criterion.name
+                        ),
+                        source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "likelihood out of range")),
+                    });
+                }
+            }
+        }
+
+        let mut contributions = Vec::with_capacity(criteria.len());
+        let mut fused_likelihood_h = 1.0;
+        let mut fused_likelihood_not_h = 1.0;
+        for criterion in criteria {
+            fused_likelihood_h *= criterion.likelihood_given_h;
+            fused_likelihood_not_h *= criterion.likelihood_given_not_h;
+
+            let standalone_unnormalized_h = criterion.prior * criterion.likelihood_given_h;
+            let standalone_unnormalized_not_h = (1.0 - criterion.prior) * criterion.likelihood_given_not_h;
+            let standalone_posterior = standalone_unnormalized_h / (standalone_unnormalized_h + standalone_unnormalized_not_h);
+
+            contributions.push(CriterionContribution {
+                name: criterion.name.clone(),
+                applies: criterion.applies,
+                standalone_posterior,
+            });
+        }
+
+        let unnormalized_h = self.posterior_h * fused_likelihood_h;
+        let unnormalized_not_h = (1.0 - self.posterior_h) * fused_likelihood_not_h;
+        self.posterior_h = unnormalized_h / (unnormalized_h + unnormalized_not_h);
+
+        self.contribution_history.push(contributions);
+        if self.contribution_history.len() > MAX_RETAINED_ROUNDS {
+            self.contribution_history.remove(0);
+        }
+
+        Ok(self.posterior_h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn criterion(name: &str, applies: bool, prior: f64, likelihood_given_h: f64, likelihood_given_not_h: f64) -> Criterion {
+        Criterion { name: name.to_string(), applies, prior, likelihood_given_h, likelihood_given_not_h }
+    }
+
+    #[test]
+    fn new_rejects_a_prior_outside_the_open_unit_interval() {
+        assert!(PatternMatcher::new(0.0).is_err());
+        assert!(PatternMatcher::new(1.0).is_err());
+        assert!(PatternMatcher::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn confirming_evidence_raises_confidence_above_the_prior() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        let confidence = matcher.observe(&[criterion("dimensional_consistency", true, 0.5, 0.95, 0.3)]).unwrap();
+        assert!(confidence > 0.5);
+    }
+
+    #[test]
+    fn disconfirming_evidence_lowers_confidence_below_the_prior() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        let confidence = matcher.observe(&[criterion("dimensional_consistency", false, 0.5, 0.05, 0.7)]).unwrap();
+        assert!(confidence < 0.5);
+    }
+
+    #[test]
+    fn recursive_filtering_accumulates_confirming_evidence_across_rounds() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        let first = matcher.observe(&[criterion("dimensional_consistency", true, 0.5, 0.95, 0.3)]).unwrap();
+        let second = matcher.observe(&[criterion("dimensional_consistency", true, 0.5, 0.95, 0.3)]).unwrap();
+        assert!(second > first);
+    }
+
+    #[test]
+    fn fusing_multiple_criteria_in_one_round_compounds_confirming_evidence() {
+        let mut single = PatternMatcher::new(0.5).unwrap();
+        let one_criterion_confidence = single.observe(&[criterion("a", true, 0.5, 0.95, 0.3)]).unwrap();
+
+        let mut double = PatternMatcher::new(0.5).unwrap();
+        let two_criteria_confidence =
+            double.observe(&[criterion("a", true, 0.5, 0.95, 0.3), criterion("b", true, 0.5, 0.9, 0.4)]).unwrap();
+
+        assert!(two_criteria_confidence > one_criterion_confidence);
+    }
+
+    #[test]
+    fn observe_rejects_an_empty_criteria_slice() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        assert!(matcher.observe(&[]).is_err());
+    }
+
+    #[test]
+    fn observe_rejects_a_likelihood_outside_the_open_unit_interval() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        assert!(matcher.observe(&[criterion("bad", true, 0.5, 1.0, 0.3)]).is_err());
+    }
+
+    #[test]
+    fn contribution_history_records_each_rounds_per_criterion_breakdown() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        matcher.observe(&[criterion("a", true, 0.5, 0.95, 0.3)]).unwrap();
+        matcher.observe(&[criterion("b", false, 0.5, 0.05, 0.7)]).unwrap();
+
+        assert_eq!(matcher.contribution_history().len(), 2);
+        assert_eq!(matcher.contribution_history()[0][0].name, "a");
+        assert_eq!(matcher.contribution_history()[1][0].name, "b");
+    }
+
+    #[test]
+    fn contribution_history_is_capped_at_the_retained_round_limit() {
+        let mut matcher = PatternMatcher::new(0.5).unwrap();
+        for _ in 0..MAX_RETAINED_ROUNDS + 5 {
+            matcher.observe(&[criterion("a", true, 0.5, 0.95, 0.3)]).unwrap();
+        }
+        assert_eq!(matcher.contribution_history().len(), MAX_RETAINED_ROUNDS);
+    }
+}
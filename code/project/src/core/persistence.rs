@@ -0,0 +1,279 @@
+// src/core/persistence.rs
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(thiserror::Error, Debug)]
+pub enum PersistenceError {
+    #[error("persistence backend io error: {0}")]
+    Io(String),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error("no snapshot found for key '{0}'")]
+    NotFound(String),
+}
+
+/// Pluggable durable-storage backend for core state (`Metrics` summaries,
+/// `CentroidCRDT` snapshots). Keeps the in-memory types decoupled from any
+/// particular storage technology so a node can run fully in-memory for
+/// tests and swap in a real store (filesystem, DHT entry, object store) in
+/// production without touching `Metrics`/`CentroidCRDT` themselves.
+pub trait PersistenceBackend: Send + Sync {
+    fn save(&self, key: &str, bytes: &[u8]) -> Result<(), PersistenceError>;
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError>;
+}
+
+/// Default backend: keeps snapshots in memory only. Useful for tests and as
+/// the fallback when no durable store is configured.
+#[derive(Default)]
+pub struct InMemoryBackend {
+    store: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl PersistenceBackend for InMemoryBackend {
+    fn save(&self, key: &str, bytes: &[u8]) -> Result<(), PersistenceError> {
+        self.store.lock().unwrap().insert(key.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError> {
+        Ok(self.store.lock().unwrap().get(key).cloned())
+    }
+}
+
+/// A single write in a `KeyValueStore::transaction` batch.
+pub enum KvOp {
+    Put(String, Vec<u8>),
+    Delete(String),
+}
+
+/// Narrow ordered key-value store for durable shard/agent state
+/// (`ShardManager` shard status, per-agent `CentroidCRDT`), so
+/// `sync_state` can reload its working set after a restart instead of
+/// replaying the whole DHT. Kept narrow (get/put/range-scan/transaction) so
+/// alternative backends (LMDB, RocksDB, a DHT-backed store) can be dropped
+/// in without touching callers.
+pub trait KeyValueStore: Send + Sync {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError>;
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), PersistenceError>;
+    /// Keys in `[start, end)`, in ascending order.
+    fn range_scan(&self, start: &str, end: &str) -> Result<Vec<(String, Vec<u8>)>, PersistenceError>;
+    /// Apply every op atomically relative to concurrent readers of this store.
+    fn transaction(&self, ops: Vec<KvOp>) -> Result<(), PersistenceError>;
+}
+
+/// In-memory ordered store, backed by a `BTreeMap` so `range_scan` returns
+/// keys in sorted order the way an LMDB-style embedded store would. Default
+/// backend for tests and for nodes with no durable store configured.
+#[derive(Default)]
+pub struct EmbeddedOrderedStore {
+    store: Mutex<std::collections::BTreeMap<String, Vec<u8>>>,
+}
+
+impl KeyValueStore for EmbeddedOrderedStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError> {
+        Ok(self.store.lock().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), PersistenceError> {
+        self.store.lock().unwrap().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn range_scan(&self, start: &str, end: &str) -> Result<Vec<(String, Vec<u8>)>, PersistenceError> {
+        Ok(self
+            .store
+            .lock()
+            .unwrap()
+            .range(start.to_string()..end.to_string())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect())
+    }
+
+    fn transaction(&self, ops: Vec<KvOp>) -> Result<(), PersistenceError> {
+        let mut guard = self.store.lock().unwrap();
+        for op in ops {
+            match op {
+                KvOp::Put(key, value) => { guard.insert(key, value); }
+                KvOp::Delete(key) => { guard.remove(&key); }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Backend that persists each key as a file under a root directory, one
+/// snapshot per key.
+pub struct FileBackend {
+    root: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{key}.snapshot"))
+    }
+}
+
+impl PersistenceBackend for FileBackend {
+    fn save(&self, key: &str, bytes: &[u8]) -> Result<(), PersistenceError> {
+        std::fs::create_dir_all(&self.root).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        std::fs::write(self.path_for(key), bytes).map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError> {
+        match std::fs::read(self.path_for(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(PersistenceError::Io(e.to_string())),
+        }
+    }
+}
+
+/// `KeyValueStore` backed by a real embedded LMDB environment — same sorted
+/// key ordering `EmbeddedOrderedStore` gives for free from a `BTreeMap`, but
+/// durable across restarts and crash-safe via LMDB's own copy-on-write
+/// transactions. Reads open their own read-only transaction so they never
+/// block a concurrent writer.
+pub struct LmdbStore {
+    env: lmdb::Environment,
+    db: lmdb::Database,
+}
+
+impl LmdbStore {
+    /// Open (creating if absent) an LMDB environment rooted at `path`, with
+    /// one unnamed database holding every key this store is asked to manage.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        std::fs::create_dir_all(&path).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        let env = lmdb::Environment::new()
+            .set_map_size(1024 * 1024 * 1024)
+            .open(path.as_ref())
+            .map_err(|e| PersistenceError::Io(e.to_string()))?;
+        let db = env.open_db(None).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        Ok(Self { env, db })
+    }
+}
+
+impl KeyValueStore for LmdbStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError> {
+        use lmdb::Transaction;
+        let txn = self.env.begin_ro_txn().map_err(|e| PersistenceError::Io(e.to_string()))?;
+        match txn.get(self.db, &key) {
+            Ok(bytes) => Ok(Some(bytes.to_vec())),
+            Err(lmdb::Error::NotFound) => Ok(None),
+            Err(e) => Err(PersistenceError::Io(e.to_string())),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), PersistenceError> {
+        use lmdb::{Transaction, WriteFlags};
+        let mut txn = self.env.begin_rw_txn().map_err(|e| PersistenceError::Io(e.to_string()))?;
+        txn.put(self.db, &key, &value, WriteFlags::empty()).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        txn.commit().map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+
+    fn range_scan(&self, start: &str, end: &str) -> Result<Vec<(String, Vec<u8>)>, PersistenceError> {
+        use lmdb::{Cursor, Transaction};
+        let txn = self.env.begin_ro_txn().map_err(|e| PersistenceError::Io(e.to_string()))?;
+        let mut cursor = txn.open_ro_cursor(self.db).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        let mut rows = Vec::new();
+        for (key, value) in cursor.iter_from(start.as_bytes()) {
+            if key >= end.as_bytes() {
+                break;
+            }
+            let key = std::str::from_utf8(key).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+            rows.push((key.to_string(), value.to_vec()));
+        }
+        Ok(rows)
+    }
+
+    fn transaction(&self, ops: Vec<KvOp>) -> Result<(), PersistenceError> {
+        use lmdb::{Transaction, WriteFlags};
+        let mut txn = self.env.begin_rw_txn().map_err(|e| PersistenceError::Io(e.to_string()))?;
+        for op in ops {
+            match op {
+                KvOp::Put(key, value) => {
+                    txn.put(self.db, &key, &value, WriteFlags::empty()).map_err(|e| PersistenceError::Io(e.to_string()))?;
+                }
+                KvOp::Delete(key) => match txn.del(self.db, &key, None) {
+                    Ok(()) | Err(lmdb::Error::NotFound) => {}
+                    Err(e) => return Err(PersistenceError::Io(e.to_string())),
+                },
+            }
+        }
+        txn.commit().map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+}
+
+/// `KeyValueStore` backed by a single-table SQLite database, for deployments
+/// that already run SQLite for everything else and would rather not add a
+/// second embedded-storage dependency just for this trait. Slower than
+/// `LmdbStore` under concurrent writers (one `Mutex`-guarded `Connection`),
+/// but transactionally just as sound.
+pub struct SqliteStore {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        let conn = rusqlite::Connection::open(path).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        conn.execute("CREATE TABLE IF NOT EXISTS kv_store (key TEXT PRIMARY KEY, value BLOB NOT NULL)", [])
+            .map_err(|e| PersistenceError::Io(e.to_string()))?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+}
+
+impl KeyValueStore for SqliteStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, PersistenceError> {
+        use rusqlite::OptionalExtension;
+        let conn = self.conn.lock().expect("sqlite connection mutex poisoned");
+        conn.query_row("SELECT value FROM kv_store WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), PersistenceError> {
+        let conn = self.conn.lock().expect("sqlite connection mutex poisoned");
+        conn.execute(
+            "INSERT INTO kv_store (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            rusqlite::params![key, value],
+        )
+        .map_err(|e| PersistenceError::Io(e.to_string()))?;
+        Ok(())
+    }
+
+    fn range_scan(&self, start: &str, end: &str) -> Result<Vec<(String, Vec<u8>)>, PersistenceError> {
+        let conn = self.conn.lock().expect("sqlite connection mutex poisoned");
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM kv_store WHERE key >= ?1 AND key < ?2 ORDER BY key ASC")
+            .map_err(|e| PersistenceError::Io(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params![start, end], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| PersistenceError::Io(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+
+    fn transaction(&self, ops: Vec<KvOp>) -> Result<(), PersistenceError> {
+        let mut conn = self.conn.lock().expect("sqlite connection mutex poisoned");
+        let txn = conn.transaction().map_err(|e| PersistenceError::Io(e.to_string()))?;
+        for op in ops {
+            match op {
+                KvOp::Put(key, value) => {
+                    txn.execute(
+                        "INSERT INTO kv_store (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                        rusqlite::params![key, value],
+                    )
+                    .map_err(|e| PersistenceError::Io(e.to_string()))?;
+                }
+                KvOp::Delete(key) => {
+                    txn.execute("DELETE FROM kv_store WHERE key = ?1", rusqlite::params![key])
+                        .map_err(|e| PersistenceError::Io(e.to_string()))?;
+                }
+            }
+        }
+        txn.commit().map_err(|e| PersistenceError::Io(e.to_string()))
+    }
+}
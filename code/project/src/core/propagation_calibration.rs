@@ -0,0 +1,354 @@
+// src/core/propagation_calibration.rs
+//! Calibrates the three standard deep-space/ionospheric propagation
+//! effects out of a raw EM recording before it's handed to
+//! `antenna_pattern::correlate_antenna_patterns` or any other
+//! mixing-pipeline operation: the interplanetary plasma and ionospheric
+//! contributions are both dispersive (their group delay is proportional to
+//! the path's total electron content divided by carrier frequency
+//! squared, `Δt ∝ TEC/f²`), while the tropospheric contribution is
+//! non-dispersive (a zenith delay scaled by an elevation-dependent mapping
+//! function). Uncorrected, these media distortions make two
+//! correctly-paired signals look like they don't correlate at all, or —
+//! worse — make two unrelated signals appear to correlate because their
+//! raw recordings share a coincidental medium-induced lag.
+//!
+//! [`PropagationConfig`]'s three flags turn each correction on or off
+//! independently, and [`calibrate`] returns both the conditioned signal
+//! and the [`RemovedDelays`] it subtracted out, so a caller can see
+//! exactly how much of a measured lag was medium, not signal.
+
+use crate::error::ShardError;
+
+/// Speed of light in vacuum, in meters/second — converts a zenith delay
+/// from meters to seconds.
+const SPEED_OF_LIGHT_M_PER_S: f64 = 299_792_458.0;
+
+/// The ionospheric/plasma dispersion constant (in SI units, such that
+/// `delay_m = IONOSPHERIC_K * tec_el_per_m2 / frequency_hz.powi(2)` comes
+/// out in meters when `tec_el_per_m2` is total electron content in
+/// electrons/m²): the standard value used for both the terrestrial
+/// ionosphere and interplanetary plasma, since both media produce the same
+/// `TEC/f²` dispersion — they differ only in which TEC value applies.
+const IONOSPHERIC_K: f64 = 40.3;
+
+/// Smallest elevation angle (degrees) `tropospheric_delay_s` will compute
+/// a mapping function for. Below this, `1 / sin(elevation)` blows up fast
+/// enough that the delay estimate stops being physically meaningful —
+/// real tropospheric models switch to a more elaborate mapping function
+/// near the horizon; this one just refuses to extrapolate there.
+const MIN_ELEVATION_DEG: f64 = 5.0;
+
+/// Upper bound on `calibrate`'s `signal` length. `shift_by_samples`
+/// touches every sample once, so this is a CPU-cost bound on one call, the
+/// same reasoning as `mixing_validation::MAX_SAMPLES`.
+const MAX_SIGNAL_LEN: usize = 1_000_000;
+
+/// Which of the three propagation corrections `calibrate` applies.
+/// Independent flags: a caller with, say, only ionospheric TEC data (no
+/// tropospheric model) disables the other two rather than supplying
+/// placeholder inputs for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropagationConfig {
+    pub correct_ionospheric: bool,
+    pub correct_interplanetary_plasma: bool,
+    pub correct_tropospheric: bool,
+}
+
+/// Inputs for `tropospheric_delay_s`'s elevation-dependent mapping: the
+/// zenith wet and dry delays, in meters, at the observation site —
+/// typically supplied externally from a GNSS-derived troposphere model —
+/// and the signal path's elevation angle above the local horizon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TroposphericDelayInput {
+    pub elevation_deg: f64,
+    pub zenith_dry_delay_m: f64,
+    pub zenith_wet_delay_m: f64,
+}
+
+/// What `calibrate` needs to compute each enabled correction. A field
+/// stays `None` when its corresponding `PropagationConfig` flag is
+/// disabled (or simply unavailable) — `calibrate` only errs over a missing
+/// field when the matching flag asks for that correction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PropagationObservation {
+    pub carrier_frequency_hz: f64,
+    /// Ionospheric total electron content along the path, in
+    /// electrons/m² — typically estimated with `estimate_tec_from_dual_frequency`.
+    pub ionospheric_tec_el_per_m2: Option<f64>,
+    /// Interplanetary plasma total electron content along the path, in
+    /// electrons/m².
+    pub interplanetary_tec_el_per_m2: Option<f64>,
+    pub troposphere: Option<TroposphericDelayInput>,
+}
+
+/// The group delay, in seconds, each enabled correction removed — `None`
+/// for a correction `PropagationConfig` didn't enable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RemovedDelays {
+    pub ionospheric_delay_s: Option<f64>,
+    pub interplanetary_plasma_delay_s: Option<f64>,
+    pub tropospheric_delay_s: Option<f64>,
+}
+
+impl RemovedDelays {
+    /// The sum of every removed delay that was actually computed — the
+    /// total lag `calibrate` shifted the signal by.
+    pub fn total_s(&self) -> f64 {
+        self.ionospheric_delay_s.unwrap_or(0.0)
+            + self.interplanetary_plasma_delay_s.unwrap_or(0.0)
+            + self.tropospheric_delay_s.unwrap_or(0.0)
+    }
+}
+
+/// `calibrate`'s output: the media-corrected signal plus the delays that
+/// were subtracted out of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionedSignal {
+    pub samples: Vec<f64>,
+    pub removed_delays: RemovedDelays,
+}
+
+/// The dispersive group delay, in seconds, a path with total electron
+/// content `tec_el_per_m2` (electrons/m²) imposes on a signal at
+/// `carrier_frequency_hz` — `Δt = IONOSPHERIC_K × TEC / (c × f²)`. The same
+/// formula applies whether `tec_el_per_m2` comes from the ionosphere or
+/// interplanetary plasma; only the TEC value differs between the two
+/// media.
+pub fn dispersive_group_delay_s(tec_el_per_m2: f64, carrier_frequency_hz: f64) -> f64 {
+    IONOSPHERIC_K * tec_el_per_m2 / (SPEED_OF_LIGHT_M_PER_S * carrier_frequency_hz.powi(2))
+}
+
+/// Estimate the path's total electron content from the group delay
+/// measured at two distinct carrier frequencies — the standard
+/// dual-frequency ionospheric correction technique: since
+/// `delay(f) = IONOSPHERIC_K × TEC / (c × f²)`, the difference
+/// `delay1 - delay2` isolates `TEC` without needing to know the
+/// frequency-independent (geometric) part of the delay.
+///
+/// Errs if `frequency_1_hz` equals `frequency_2_hz` — two measurements at
+/// the same frequency carry no dispersive information to separate from
+/// the shared geometric delay.
+pub fn estimate_tec_from_dual_frequency(
+    delay_1_s: f64,
+    frequency_1_hz: f64,
+    delay_2_s: f64,
+    frequency_2_hz: f64,
+) -> Result<f64, ShardError> {
+    let inverse_f1_sq = 1.0 / frequency_1_hz.powi(2);
+    let inverse_f2_sq = 1.0 / frequency_2_hz.powi(2);
+    if inverse_f1_sq == inverse_f2_sq {
+        return Err(ShardError::MigrationFailed {
+            context: "estimate_tec_from_dual_frequency: frequency_1_hz and frequency_2_hz must differ".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "identical frequencies")),
+        });
+    }
+    Ok((delay_1_s - delay_2_s) * SPEED_OF_LIGHT_M_PER_S / (IONOSPHERIC_K * (inverse_f1_sq - inverse_f2_sq)))
+}
+
+/// The non-dispersive tropospheric group delay, in seconds, along a path
+/// at `input.elevation_deg` above the horizon: the zenith delay
+/// (`zenith_dry_delay_m + zenith_wet_delay_m`) scaled by the simple
+/// cosecant obliquity mapping function `1 / sin(elevation)` and converted
+/// from meters to seconds.
+///
+/// Errs if `input.elevation_deg` is below `MIN_ELEVATION_DEG` — the
+/// mapping function diverges too fast near the horizon for this simple
+/// model to stay meaningful there.
+pub fn tropospheric_delay_s(input: &TroposphericDelayInput) -> Result<f64, ShardError> {
+    if input.elevation_deg < MIN_ELEVATION_DEG {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "tropospheric_delay_s: elevation {:.2} degrees is below the minimum of {MIN_ELEVATION_DEG} degrees this mapping function supports",
+                input.elevation_deg
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "elevation too low")),
+        });
+    }
+    let mapping_function = 1.0 / input.elevation_deg.to_radians().sin();
+    let zenith_delay_m = input.zenith_dry_delay_m + input.zenith_wet_delay_m;
+    Ok(zenith_delay_m * mapping_function / SPEED_OF_LIGHT_M_PER_S)
+}
+
+/// Shift `samples` forward by `shift_samples` (fractional sample
+/// positions), reconstructing the pre-delay signal via linear
+/// interpolation: `output[i] = interpolate(samples, i + shift_samples)`.
+/// A queried position outside `samples`' extent clamps to the nearest
+/// edge value rather than extrapolating — the same bounded-approximation
+/// policy `antenna_pattern::AntennaPatternRecord::gain_at_db` uses for a
+/// query outside its grid.
+fn shift_by_samples(samples: &[f64], shift_samples: f64) -> Vec<f64> {
+    let last_index = samples.len() - 1;
+    (0..samples.len())
+        .map(|i| {
+            let position = (i as f64 + shift_samples).clamp(0.0, last_index as f64);
+            let lo = position.floor() as usize;
+            let hi = (lo + 1).min(last_index);
+            let t = position - lo as f64;
+            samples[lo] + (samples[hi] - samples[lo]) * t
+        })
+        .collect()
+}
+
+/// Remove every correction `config` enables from `signal`, sampled at
+/// `sample_rate_hz`, using `observation`'s inputs: compute each enabled
+/// correction's delay, sum them, and shift `signal` forward by that total
+/// (in samples) to undo it.
+///
+/// Errs if `signal` exceeds `MAX_SIGNAL_LEN`, if a flag in `config` is
+/// enabled but `observation` is missing the input that correction needs
+/// (e.g. `correct_ionospheric` with `ionospheric_tec_el_per_m2: None`), or
+/// if `tropospheric_delay_s` itself errs on too-low an elevation.
+pub fn calibrate(
+    signal: &[f64],
+    sample_rate_hz: f64,
+    config: &PropagationConfig,
+    observation: &PropagationObservation,
+) -> Result<ConditionedSignal, ShardError> {
+    if signal.is_empty() {
+        return Err(ShardError::MigrationFailed {
+            context: "calibrate: signal must have at least one sample".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty signal")),
+        });
+    }
+    if signal.len() > MAX_SIGNAL_LEN {
+        return Err(ShardError::MigrationFailed {
+            context: format!("calibrate: signal length {} exceeds the maximum of {MAX_SIGNAL_LEN} samples", signal.len()),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "signal too long")),
+        });
+    }
+
+    let ionospheric_delay_s = if config.correct_ionospheric {
+        let tec = observation.ionospheric_tec_el_per_m2.ok_or_else(|| ShardError::MigrationFailed {
+            context: "calibrate: correct_ionospheric is enabled but observation has no ionospheric_tec_el_per_m2".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing ionospheric TEC")),
+        })?;
+        Some(dispersive_group_delay_s(tec, observation.carrier_frequency_hz))
+    } else {
+        None
+    };
+
+    let interplanetary_plasma_delay_s = if config.correct_interplanetary_plasma {
+        let tec = observation.interplanetary_tec_el_per_m2.ok_or_else(|| ShardError::MigrationFailed {
+            context: "calibrate: correct_interplanetary_plasma is enabled but observation has no interplanetary_tec_el_per_m2".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing interplanetary TEC")),
+        })?;
+        Some(dispersive_group_delay_s(tec, observation.carrier_frequency_hz))
+    } else {
+        None
+    };
+
+    let tropospheric_delay_s = if config.correct_tropospheric {
+        let input = observation.troposphere.as_ref().ok_or_else(|| ShardError::MigrationFailed {
+            context: "calibrate: correct_tropospheric is enabled but observation has no troposphere input".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing troposphere input")),
+        })?;
+        Some(tropospheric_delay_s(input)?)
+    } else {
+        None
+    };
+
+    let removed_delays = RemovedDelays { ionospheric_delay_s, interplanetary_plasma_delay_s, tropospheric_delay_s };
+    let shift_samples = removed_delays.total_s() * sample_rate_hz;
+
+    Ok(ConditionedSignal { samples: shift_by_samples(signal, shift_samples), removed_delays })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_CORRECTIONS: PropagationConfig =
+        PropagationConfig { correct_ionospheric: false, correct_interplanetary_plasma: false, correct_tropospheric: false };
+
+    fn observation() -> PropagationObservation {
+        PropagationObservation {
+            carrier_frequency_hz: 1.5e9,
+            ionospheric_tec_el_per_m2: Some(1e17),
+            interplanetary_tec_el_per_m2: Some(5e15),
+            troposphere: Some(TroposphericDelayInput { elevation_deg: 45.0, zenith_dry_delay_m: 2.3, zenith_wet_delay_m: 0.1 }),
+        }
+    }
+
+    #[test]
+    fn dispersive_delay_shrinks_with_the_square_of_frequency() {
+        let low_freq = dispersive_group_delay_s(1e17, 1e9);
+        let high_freq = dispersive_group_delay_s(1e17, 2e9);
+        assert!((low_freq / high_freq - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimate_tec_round_trips_through_dispersive_group_delay() {
+        let tec = 8e16;
+        let f1 = 1.2e9;
+        let f2 = 1.6e9;
+        let delay1 = dispersive_group_delay_s(tec, f1);
+        let delay2 = dispersive_group_delay_s(tec, f2);
+
+        let estimated = estimate_tec_from_dual_frequency(delay1, f1, delay2, f2).unwrap();
+        assert!((estimated - tec).abs() / tec < 1e-9);
+    }
+
+    #[test]
+    fn estimate_tec_rejects_identical_frequencies() {
+        assert!(estimate_tec_from_dual_frequency(1e-6, 1e9, 2e-6, 1e9).is_err());
+    }
+
+    #[test]
+    fn tropospheric_delay_grows_toward_the_horizon() {
+        let near_zenith =
+            tropospheric_delay_s(&TroposphericDelayInput { elevation_deg: 89.0, zenith_dry_delay_m: 2.3, zenith_wet_delay_m: 0.1 })
+                .unwrap();
+        let near_horizon =
+            tropospheric_delay_s(&TroposphericDelayInput { elevation_deg: 10.0, zenith_dry_delay_m: 2.3, zenith_wet_delay_m: 0.1 })
+                .unwrap();
+        assert!(near_horizon > near_zenith);
+    }
+
+    #[test]
+    fn tropospheric_delay_rejects_too_low_an_elevation() {
+        assert!(
+            tropospheric_delay_s(&TroposphericDelayInput { elevation_deg: 1.0, zenith_dry_delay_m: 2.3, zenith_wet_delay_m: 0.1 })
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn calibrate_with_every_correction_disabled_leaves_the_signal_unchanged() {
+        let signal = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let conditioned = calibrate(&signal, 1_000.0, &NO_CORRECTIONS, &observation()).unwrap();
+        assert_eq!(conditioned.samples, signal);
+        assert_eq!(conditioned.removed_delays.total_s(), 0.0);
+    }
+
+    #[test]
+    fn calibrate_reports_only_the_enabled_corrections() {
+        let signal = vec![1.0; 10];
+        let config = PropagationConfig { correct_ionospheric: true, correct_interplanetary_plasma: false, correct_tropospheric: false };
+        let conditioned = calibrate(&signal, 1_000.0, &config, &observation()).unwrap();
+        assert!(conditioned.removed_delays.ionospheric_delay_s.is_some());
+        assert!(conditioned.removed_delays.interplanetary_plasma_delay_s.is_none());
+        assert!(conditioned.removed_delays.tropospheric_delay_s.is_none());
+    }
+
+    #[test]
+    fn calibrate_errs_when_an_enabled_correction_is_missing_its_input() {
+        let signal = vec![1.0; 10];
+        let config = PropagationConfig { correct_ionospheric: true, correct_interplanetary_plasma: false, correct_tropospheric: false };
+        let mut incomplete_observation = observation();
+        incomplete_observation.ionospheric_tec_el_per_m2 = None;
+        assert!(calibrate(&signal, 1_000.0, &config, &incomplete_observation).is_err());
+    }
+
+    #[test]
+    fn calibrate_errs_on_a_signal_exceeding_the_maximum_length() {
+        let signal = vec![0.0; MAX_SIGNAL_LEN + 1];
+        assert!(calibrate(&signal, 1_000.0, &NO_CORRECTIONS, &observation()).is_err());
+    }
+
+    #[test]
+    fn shift_by_samples_clamps_at_the_signal_edges() {
+        let signal = vec![1.0, 2.0, 3.0];
+        let shifted = shift_by_samples(&signal, 10.0);
+        assert_eq!(shifted, vec![3.0, 3.0, 3.0]);
+    }
+}
@@ -0,0 +1,232 @@
+// src/core/spectral_band.rs
+//! Structured electromagnetic-spectrum metadata for `MixingPattern` input
+//! types, replacing a pattern library that would otherwise need a
+//! hand-written "X-Y continuity" entry for every pair of adjacent or
+//! overlapping EM bands (e.g. an "Optical-EM Spectrum Continuity" pattern
+//! and an "Infrared-Temperature Pyrometry" pattern encoding, by hand, the
+//! same underlying fact that optical and infrared are neighboring regions
+//! of one continuous spectrum). A [`SpectralBand`] attaches a wavelength
+//! range to an EM-family type name (see [`EM_BANDS`]);
+//! [`classify_spectral_relationship`] compares two bands, and
+//! [`synthesize_spectral_pattern`] turns that comparison directly into a
+//! draft `MixingPattern` the same way a human author would have.
+//!
+//! There's no real spectroscopy database here, same boundary
+//! `pattern_extraction`'s fixed-vocabulary keyword match and
+//! `grib_packing`'s hand-rolled packing draw elsewhere in this module
+//! group: [`EM_BANDS`] is a small, hand-curated table of the regions this
+//! crate's `MixingPattern`s actually reference, not an exhaustive
+//! spectroscopic reference. Adding a new EM band is a one-line addition to
+//! that table rather than a new hand-written continuity pattern.
+
+use crate::core::mixing_pipeline::{Citation, IdentifierKind, MixingPattern};
+
+/// Speed of light in vacuum, in meters/second — relates a [`SpectralBand`]'s
+/// wavelength range to the equivalent frequency range via
+/// `frequency_hz = SPEED_OF_LIGHT_M_PER_S / wavelength_m`.
+pub const SPEED_OF_LIGHT_M_PER_S: f64 = 299_792_458.0;
+
+/// A contiguous wavelength range, in meters, for one named region of the
+/// electromagnetic spectrum. Expressed as wavelength rather than frequency
+/// because that's the convention the regions themselves are named by
+/// (radio/infrared/optical/ultraviolet); `frequency_range_hz` gives the
+/// equivalent frequency range for a caller that wants it that way instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectralBand {
+    /// Shortest wavelength in the band, in meters.
+    pub lambda_min_m: f64,
+    /// Longest wavelength in the band, in meters.
+    pub lambda_max_m: f64,
+}
+
+impl SpectralBand {
+    /// The frequency range, in Hz, equivalent to this band's wavelength
+    /// range — `(lowest_frequency, highest_frequency)`, i.e. swapped from
+    /// `(lambda_min_m, lambda_max_m)` since frequency is inversely
+    /// proportional to wavelength.
+    pub fn frequency_range_hz(&self) -> (f64, f64) {
+        (SPEED_OF_LIGHT_M_PER_S / self.lambda_max_m, SPEED_OF_LIGHT_M_PER_S / self.lambda_min_m)
+    }
+}
+
+/// Recognized EM-family `MixingPattern` input/output type names, as
+/// `(type_name, SpectralBand)` pairs, ordered from longest to shortest
+/// wavelength. Boundaries are shared between neighbors (e.g. `infrared`'s
+/// `lambda_min_m` equals `optical`'s `lambda_max_m`) so adjacent entries
+/// classify as [`SpectralRelationship::Adjacent`] rather than
+/// `Disjoint` — see `classify_spectral_relationship`.
+pub const EM_BANDS: &[(&str, SpectralBand)] = &[
+    ("radio", SpectralBand { lambda_min_m: 1e-1, lambda_max_m: 1e5 }),
+    ("microwave", SpectralBand { lambda_min_m: 1e-3, lambda_max_m: 1e-1 }),
+    ("infrared", SpectralBand { lambda_min_m: 7e-7, lambda_max_m: 1e-3 }),
+    ("optical", SpectralBand { lambda_min_m: 4e-7, lambda_max_m: 7e-7 }),
+    ("ultraviolet", SpectralBand { lambda_min_m: 1e-8, lambda_max_m: 4e-7 }),
+    ("x_ray", SpectralBand { lambda_min_m: 1e-11, lambda_max_m: 1e-8 }),
+    ("gamma_ray", SpectralBand { lambda_min_m: 1e-15, lambda_max_m: 1e-11 }),
+];
+
+/// `EM_BANDS`'s band for `type_name`, if this subsystem recognizes it —
+/// the EM-family counterpart to `dimensional_analysis::quantity_kind_for_type`.
+pub fn band_for_type(type_name: &str) -> Option<SpectralBand> {
+    EM_BANDS.iter().find(|(name, _)| *name == type_name).map(|(_, band)| *band)
+}
+
+/// How two `SpectralBand`s relate to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpectralRelationship {
+    /// The bands share more than a single shared boundary point — the same
+    /// underlying radiation could plausibly be observed through either
+    /// band's sensor.
+    Overlapping,
+    /// The bands don't overlap but one's `lambda_max_m` equals the other's
+    /// `lambda_min_m` — neighboring regions of one continuous spectrum.
+    Adjacent,
+    /// Neither overlapping nor sharing a boundary.
+    Disjoint,
+}
+
+/// Classify how `a` and `b` relate: `Overlapping` if their ranges share
+/// more than a point, `Adjacent` if they share exactly a boundary point,
+/// `Disjoint` otherwise.
+pub fn classify_spectral_relationship(a: SpectralBand, b: SpectralBand) -> SpectralRelationship {
+    if a.lambda_min_m < b.lambda_max_m && b.lambda_min_m < a.lambda_max_m {
+        SpectralRelationship::Overlapping
+    } else if a.lambda_max_m == b.lambda_min_m || b.lambda_max_m == a.lambda_min_m {
+        SpectralRelationship::Adjacent
+    } else {
+        SpectralRelationship::Disjoint
+    }
+}
+
+/// Every `EM_BANDS` type name spectrally reachable from `type_name` — i.e.
+/// every other entry whose band is `Overlapping` or `Adjacent` to
+/// `type_name`'s, for a caller assembling a multi-spectral sensor fusion
+/// candidate set. Empty if `type_name` isn't in `EM_BANDS` at all.
+pub fn spectrally_reachable_types(type_name: &str) -> Vec<&'static str> {
+    let Some(band) = band_for_type(type_name) else {
+        return Vec::new();
+    };
+    EM_BANDS
+        .iter()
+        .filter(|(name, other)| *name != type_name && classify_spectral_relationship(band, *other) != SpectralRelationship::Disjoint)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// The citation `synthesize_spectral_pattern` attaches to every pattern it
+/// drafts — the electromagnetic spectrum's continuity is a single settled
+/// physical fact, not something that varies per band pair, so one
+/// canonical reference backs all of them rather than a per-pair source
+/// text the way `pattern_extraction::propose_pattern_from_text` takes one.
+fn em_continuity_citation() -> Citation {
+    Citation {
+        title: "Space environment (natural and artificial) — Process for determining solar irradiances".to_string(),
+        identifier_kind: IdentifierKind::Iso,
+        identifier: "ISO 21348:2007".to_string(),
+        authors: vec!["ISO/TC 20/SC 14".to_string()],
+        year: 2007,
+    }
+}
+
+/// Draft a `MixingPattern` relating `type_a` and `type_b`, if
+/// `EM_BANDS` recognizes both and their classified relationship isn't
+/// `Disjoint`: an `Overlapping` pair becomes a `multi_wavelength_correlation`
+/// pattern (the same underlying radiation observable through either band),
+/// an `Adjacent` pair becomes a `spectral_continuity` pattern (neighboring
+/// regions of one continuum). Returns `None` if either type isn't in
+/// `EM_BANDS`, or if they're classified `Disjoint`.
+///
+/// Like `pattern_extraction::propose_pattern_from_text`'s drafts, the
+/// result is never trusted outright — it still has to go through
+/// `MixingPatternStore::add_pattern` (landing `Unvalidated`) and `promote`
+/// (running `validate_pattern`) before a caller treats it as vetted.
+pub fn synthesize_spectral_pattern(type_a: &str, type_b: &str) -> Option<MixingPattern> {
+    let band_a = band_for_type(type_a)?;
+    let band_b = band_for_type(type_b)?;
+
+    let relationship = classify_spectral_relationship(band_a, band_b);
+    let name_prefix = match relationship {
+        SpectralRelationship::Overlapping => "multi_wavelength_correlation",
+        SpectralRelationship::Adjacent => "spectral_continuity",
+        SpectralRelationship::Disjoint => return None,
+    };
+
+    let mut pattern =
+        MixingPattern::new(format!("{name_prefix}_{type_a}_{type_b}"), vec![type_a.to_string(), type_b.to_string()], vec![format!(
+            "{type_a}_{type_b}_correlation"
+        )]);
+    pattern.citations.push(em_continuity_citation());
+    pattern.contributed_by = "spectral_band_synthesizer".to_string();
+    Some(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacent_bands_classify_as_adjacent() {
+        let optical = band_for_type("optical").unwrap();
+        let infrared = band_for_type("infrared").unwrap();
+        assert_eq!(classify_spectral_relationship(optical, infrared), SpectralRelationship::Adjacent);
+    }
+
+    #[test]
+    fn identical_bands_classify_as_overlapping() {
+        let radio = band_for_type("radio").unwrap();
+        assert_eq!(classify_spectral_relationship(radio, radio), SpectralRelationship::Overlapping);
+    }
+
+    #[test]
+    fn far_apart_bands_classify_as_disjoint() {
+        let radio = band_for_type("radio").unwrap();
+        let gamma_ray = band_for_type("gamma_ray").unwrap();
+        assert_eq!(classify_spectral_relationship(radio, gamma_ray), SpectralRelationship::Disjoint);
+    }
+
+    #[test]
+    fn spectrally_reachable_types_includes_only_overlapping_and_adjacent_bands() {
+        let reachable = spectrally_reachable_types("optical");
+        assert!(reachable.contains(&"infrared"));
+        assert!(reachable.contains(&"ultraviolet"));
+        assert!(!reachable.contains(&"radio"));
+        assert!(!reachable.contains(&"gamma_ray"));
+    }
+
+    #[test]
+    fn spectrally_reachable_types_is_empty_for_an_unrecognized_type() {
+        assert!(spectrally_reachable_types("magnetic_flux").is_empty());
+    }
+
+    #[test]
+    fn synthesizes_a_spectral_continuity_pattern_for_adjacent_bands() {
+        let pattern = synthesize_spectral_pattern("optical", "infrared").unwrap();
+        assert_eq!(pattern.name, "spectral_continuity_optical_infrared");
+        assert_eq!(pattern.inputs, vec!["optical".to_string(), "infrared".to_string()]);
+        assert_eq!(pattern.contributed_by, "spectral_band_synthesizer");
+        assert_eq!(pattern.citations.len(), 1);
+    }
+
+    #[test]
+    fn synthesizes_a_multi_wavelength_correlation_pattern_for_overlapping_bands() {
+        let pattern = synthesize_spectral_pattern("radio", "radio").unwrap();
+        assert_eq!(pattern.name, "multi_wavelength_correlation_radio_radio");
+    }
+
+    #[test]
+    fn returns_none_for_disjoint_bands() {
+        assert!(synthesize_spectral_pattern("radio", "gamma_ray").is_none());
+    }
+
+    #[test]
+    fn returns_none_for_an_unrecognized_type() {
+        assert!(synthesize_spectral_pattern("optical", "magnetic_flux").is_none());
+        assert!(synthesize_spectral_pattern("magnetic_flux", "optical").is_none());
+    }
+
+    #[test]
+    fn synthesized_pattern_passes_validate_pattern() {
+        let pattern = synthesize_spectral_pattern("ultraviolet", "x_ray").unwrap();
+        assert!(pattern.validate_pattern().is_ok());
+    }
+}
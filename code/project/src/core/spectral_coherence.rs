@@ -0,0 +1,433 @@
+// src/core/spectral_coherence.rs
+//! Welch's method for magnitude-squared coherence between two sampled
+//! signals, so a pattern's claimed physical coupling (induction,
+//! photoacoustic, strain-vibration, seismic-vibration) is checked against
+//! real sampled data instead of asserted. Each signal is split into
+//! overlapping, Hann-windowed segments; a discrete Fourier transform of
+//! each segment feeds averaged auto-/cross-spectra, from which coherence
+//! `gamma^2(f) = |Pxy(f)|^2 / (Pxx(f) * Pyy(f))` is computed per frequency
+//! bin.
+//!
+//! The DFT here is the direct O(n^2) sum rather than a radix-2 FFT: no
+//! external FFT crate exists in this repo, and per-segment length is
+//! capped by `MAX_SEGMENT_LENGTH` for the same CPU-cost reason
+//! `mixing_validation`'s `MAX_*` constants exist.
+
+use crate::core::mixing_validation::CriterionResult;
+use crate::error::ShardError;
+use std::f64::consts::PI;
+
+/// Upper bound on `WelchOptions::segment_length`: the DFT below is O(n^2)
+/// per segment, so an unbounded segment length would make one coherence
+/// call arbitrarily expensive.
+const MAX_SEGMENT_LENGTH: usize = 1024;
+
+/// Upper bound on the common length `welch_coherence` processes after
+/// truncating `x`/`y` to the shorter of the two, bounding the number of
+/// segments alongside `MAX_SEGMENT_LENGTH`.
+const MAX_SIGNAL_LENGTH: usize = 100_000;
+
+/// Upper bound on the number of overlapping segments a single
+/// `welch_coherence` call will process. `MAX_SEGMENT_LENGTH` and
+/// `MAX_SIGNAL_LENGTH` alone don't bound per-call cost: a high
+/// `overlap_fraction` (e.g. `0.9999`) shrinks the step between segments
+/// toward `1`, so a signal at the length cap can still produce tens of
+/// thousands of segments, each paying the full O(segment_length^2) DFT.
+const MAX_SEGMENTS: usize = 1_000;
+
+/// Lower bound on `WelchOptions::segment_length`. Below this, the Hann
+/// window's period no longer spans more than one full cycle over the
+/// segment — at `segment_length == 2` it is `[0.0, 0.0]` for every
+/// sample, zeroing out the segment entirely rather than windowing it.
+const MIN_SEGMENT_LENGTH: usize = 4;
+
+/// Upper bound on `num_segments * segment_length^2`, the dominant term in
+/// the total number of trigonometric evaluations one `welch_coherence`
+/// call performs (each segment's DFT is O(segment_length^2), doubled for
+/// `x` and `y`). `MAX_SEGMENT_LENGTH` and `MAX_SEGMENTS` each bound one
+/// factor individually, but their product still allows on the order of a
+/// billion evaluations when both are near their individual caps at once
+/// — this bounds the two jointly.
+const MAX_TOTAL_DFT_COST: usize = 8_000_000;
+
+/// Segmentation parameters for [`welch_coherence`].
+#[derive(Clone, Copy, Debug)]
+pub struct WelchOptions {
+    pub segment_length: usize,
+    /// Fraction of each segment that overlaps the next, e.g. `0.5` for 50%
+    /// overlap. Must be in `[0.0, 1.0)`.
+    pub overlap_fraction: f64,
+}
+
+/// Magnitude-squared coherence, one value in `[0, 1]` per one-sided
+/// frequency bin from `0` Hz to the Nyquist frequency.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoherenceSpectrum {
+    pub frequencies: Vec<f64>,
+    pub coherence: Vec<f64>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    fn conj(self) -> Complex {
+        Complex { re: self.re, im: -self.im }
+    }
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex { re: self.re * other.re - self.im * other.im, im: self.re * other.im + self.im * other.re }
+    }
+
+    fn add(self, other: Complex) -> Complex {
+        Complex { re: self.re + other.re, im: self.im + other.im }
+    }
+
+    fn scale(self, factor: f64) -> Complex {
+        Complex { re: self.re * factor, im: self.im * factor }
+    }
+}
+
+/// Hann window of length `n`, normalized so its mean squared value is `1`
+/// — "normalize windows to preserve power": applying it to a stationary
+/// signal doesn't change that signal's average power, only its spectral
+/// leakage characteristics.
+fn hann_window(n: usize) -> Vec<f64> {
+    if n <= 1 {
+        return vec![1.0; n];
+    }
+    let raw: Vec<f64> = (0..n).map(|i| 0.5 - 0.5 * (2.0 * PI * i as f64 / (n - 1) as f64).cos()).collect();
+    let mean_square = raw.iter().map(|w| w * w).sum::<f64>() / n as f64;
+    let scale = if mean_square > 0.0 { mean_square.sqrt().recip() } else { 1.0 };
+    raw.iter().map(|w| w * scale).collect()
+}
+
+/// One-sided discrete Fourier transform of `signal` (length `n`): bins `0`
+/// through `n/2` inclusive. Direct O(n^2) sum — see the module doc comment
+/// for why this isn't a radix-2 FFT.
+fn dft_one_sided(signal: &[f64]) -> Vec<Complex> {
+    let n = signal.len();
+    let half = n / 2;
+    (0..=half)
+        .map(|k| {
+            let mut acc = Complex::default();
+            for (i, &x) in signal.iter().enumerate() {
+                let angle = -2.0 * PI * k as f64 * i as f64 / n as f64;
+                acc = acc.add(Complex { re: x * angle.cos(), im: x * angle.sin() });
+            }
+            acc
+        })
+        .collect()
+}
+
+/// Start indices of every `opts.segment_length`-sample segment, stepped by
+/// `opts.overlap_fraction`'s complement, that fits within `n` samples.
+fn segment_starts(n: usize, opts: &WelchOptions) -> Vec<usize> {
+    if opts.segment_length == 0 || n < opts.segment_length {
+        return Vec::new();
+    }
+    let step = (((opts.segment_length as f64) * (1.0 - opts.overlap_fraction)).round() as usize).max(1);
+    let mut starts = Vec::new();
+    let mut start = 0;
+    while start + opts.segment_length <= n {
+        starts.push(start);
+        start += step;
+    }
+    starts
+}
+
+/// Welch's method: magnitude-squared coherence between `x` and `y`,
+/// truncated to their common length, split into `opts.segment_length`-
+/// sample segments with `opts.overlap_fraction` overlap. Errs if
+/// `sample_rate` isn't positive (a zero or negative rate would collapse
+/// every frequency bin's label to a meaningless value), if
+/// `opts.segment_length` is outside `[MIN_SEGMENT_LENGTH,
+/// MAX_SEGMENT_LENGTH]`, if
+/// `opts.overlap_fraction` isn't in `[0, 1)`, if the truncated signal
+/// length exceeds `MAX_SIGNAL_LENGTH`, if fewer than 2 segments fit — a
+/// single segment trivially yields coherence `1.0` everywhere and must be
+/// rejected rather than silently "confirming" a coupling — or if more
+/// than `MAX_SEGMENTS` segments fit, if `num_segments * segment_length^2`
+/// exceeds `MAX_TOTAL_DFT_COST` (the two caps bound each factor
+/// individually, but their product can still be expensive near both
+/// limits at once), or if `x`/`y` (after truncation to their common
+/// length) contain a NaN or infinite sample, which would otherwise
+/// propagate into a coherence value outside this function's documented
+/// `[0, 1]` range.
+pub fn welch_coherence(
+    x: &[f64],
+    y: &[f64],
+    sample_rate: f64,
+    opts: &WelchOptions,
+) -> Result<CoherenceSpectrum, ShardError> {
+    if !(sample_rate > 0.0) {
+        return Err(ShardError::MigrationFailed {
+            context: format!("welch_coherence: sample_rate {sample_rate} must be positive"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "sample_rate not positive")),
+        });
+    }
+    if opts.segment_length > MAX_SEGMENT_LENGTH || opts.segment_length < MIN_SEGMENT_LENGTH {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "welch_coherence: segment_length {} must be between {MIN_SEGMENT_LENGTH} and {MAX_SEGMENT_LENGTH}",
+                opts.segment_length
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "segment_length out of range")),
+        });
+    }
+    if !(0.0..1.0).contains(&opts.overlap_fraction) {
+        return Err(ShardError::MigrationFailed {
+            context: format!("welch_coherence: overlap_fraction {} must be in [0, 1)", opts.overlap_fraction),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "overlap_fraction out of range")),
+        });
+    }
+
+    let n = x.len().min(y.len());
+    if n > MAX_SIGNAL_LENGTH {
+        return Err(ShardError::MigrationFailed {
+            context: format!("welch_coherence: signal length {n} exceeds the maximum of {MAX_SIGNAL_LENGTH}"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "signal too long")),
+        });
+    }
+    let x = &x[..n];
+    let y = &y[..n];
+
+    if x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+        return Err(ShardError::MigrationFailed {
+            context: "welch_coherence: x and y must contain only finite samples".to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "non-finite sample")),
+        });
+    }
+
+    let starts = segment_starts(n, opts);
+    if starts.len() < 2 {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "welch_coherence: only {} segment(s) of length {} fit in {n} samples, need at least 2",
+                starts.len(),
+                opts.segment_length
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too few segments")),
+        });
+    }
+    if starts.len() > MAX_SEGMENTS {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "welch_coherence: {} segments exceeds the maximum of {MAX_SEGMENTS} (segment_length {} with overlap_fraction {} is too fine-grained for {n} samples)",
+                starts.len(),
+                opts.segment_length,
+                opts.overlap_fraction
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "too many segments")),
+        });
+    }
+    let dft_cost = starts.len() as u64 * (opts.segment_length as u64) * (opts.segment_length as u64);
+    if dft_cost > MAX_TOTAL_DFT_COST as u64 {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "welch_coherence: {} segments of length {} would perform {dft_cost} DFT evaluations, exceeding the maximum of {MAX_TOTAL_DFT_COST}",
+                starts.len(),
+                opts.segment_length
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "DFT cost too high")),
+        });
+    }
+
+    let window = hann_window(opts.segment_length);
+    let half = opts.segment_length / 2;
+    let mut pxx_sum = vec![0.0; half + 1];
+    let mut pyy_sum = vec![0.0; half + 1];
+    let mut pxy_sum = vec![Complex::default(); half + 1];
+
+    for &start in &starts {
+        let windowed_x: Vec<f64> = (0..opts.segment_length).map(|i| x[start + i] * window[i]).collect();
+        let windowed_y: Vec<f64> = (0..opts.segment_length).map(|i| y[start + i] * window[i]).collect();
+        let spectrum_x = dft_one_sided(&windowed_x);
+        let spectrum_y = dft_one_sided(&windowed_y);
+        for k in 0..=half {
+            pxx_sum[k] += spectrum_x[k].norm_sqr();
+            pyy_sum[k] += spectrum_y[k].norm_sqr();
+            pxy_sum[k] = pxy_sum[k].add(spectrum_x[k].mul(spectrum_y[k].conj()));
+        }
+    }
+
+    let num_segments = starts.len() as f64;
+    let frequencies: Vec<f64> = (0..=half).map(|k| k as f64 * sample_rate / opts.segment_length as f64).collect();
+    let coherence: Vec<f64> = (0..=half)
+        .map(|k| {
+            let pxx = pxx_sum[k] / num_segments;
+            let pyy = pyy_sum[k] / num_segments;
+            if pxx <= 0.0 || pyy <= 0.0 {
+                return 0.0;
+            }
+            let pxy = pxy_sum[k].scale(1.0 / num_segments);
+            (pxy.norm_sqr() / (pxx * pyy)).clamp(0.0, 1.0)
+        })
+        .collect();
+
+    Ok(CoherenceSpectrum { frequencies, coherence })
+}
+
+/// Check that `x`/`y`'s mean coherence within `band` (inclusive, in Hz)
+/// exceeds `threshold` — i.e. that a pattern's claimed physical coupling
+/// holds in the frequency window it declares. Returns both the
+/// band-limited `CriterionResult` (as `mixing_validation::CriterionResult`,
+/// the same "score against a threshold" shape every other empirically
+/// measured criterion in this crate uses) and the full spectrum, so a
+/// caller can plot and audit what the band-limited score was averaged
+/// from. Errs if `welch_coherence` errs, or if no frequency bin falls
+/// within `band`.
+pub fn check_spectral_coherence(
+    x: &[f64],
+    y: &[f64],
+    sample_rate: f64,
+    band: (f64, f64),
+    threshold: f64,
+    opts: &WelchOptions,
+) -> Result<(CriterionResult, CoherenceSpectrum), ShardError> {
+    let spectrum = welch_coherence(x, y, sample_rate, opts)?;
+    let in_band: Vec<f64> = spectrum
+        .frequencies
+        .iter()
+        .zip(spectrum.coherence.iter())
+        .filter(|(&f, _)| f >= band.0 && f <= band.1)
+        .map(|(_, &c)| c)
+        .collect();
+
+    if in_band.is_empty() {
+        return Err(ShardError::MigrationFailed {
+            context: format!(
+                "check_spectral_coherence: no frequency bin falls within the declared band [{}, {}] Hz",
+                band.0, band.1
+            ),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty frequency band")),
+        });
+    }
+
+    let score = in_band.iter().sum::<f64>() / in_band.len() as f64;
+    Ok((CriterionResult { score, passed: score > threshold }, spectrum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq_hz: f64, sample_rate: f64, n: usize) -> Vec<f64> {
+        (0..n).map(|i| (2.0 * PI * freq_hz * i as f64 / sample_rate).sin()).collect()
+    }
+
+    #[test]
+    fn identical_signal_is_fully_coherent_at_its_own_frequency() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 512);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        let (result, spectrum) =
+            check_spectral_coherence(&signal, &signal, sample_rate, (15.0, 25.0), 0.9, &opts).unwrap();
+        assert!(result.passed, "score was {}", result.score);
+        assert!(result.score > 0.99);
+        assert_eq!(spectrum.frequencies.len(), opts.segment_length / 2 + 1);
+    }
+
+    #[test]
+    fn unrelated_frequencies_are_less_coherent_than_identical_signals() {
+        let sample_rate = 256.0;
+        let x = sine(20.0, sample_rate, 512);
+        let y = sine(90.0, sample_rate, 512);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+
+        let (identical, _) = check_spectral_coherence(&x, &x, sample_rate, (15.0, 25.0), 0.0, &opts).unwrap();
+        let (unrelated, _) = check_spectral_coherence(&x, &y, sample_rate, (15.0, 25.0), 0.0, &opts).unwrap();
+        assert!(unrelated.score < identical.score);
+    }
+
+    #[test]
+    fn single_segment_is_rejected_rather_than_trivially_passing() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 64);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        assert!(welch_coherence(&signal, &signal, sample_rate, &opts).is_err());
+    }
+
+    #[test]
+    fn rejects_a_segment_count_and_length_combination_that_exceeds_the_joint_dft_cost_cap() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 5_000);
+        // Well under MAX_SEGMENTS on its own, but segment_length^2 * num_segments
+        // still exceeds MAX_TOTAL_DFT_COST.
+        let opts = WelchOptions { segment_length: MAX_SEGMENT_LENGTH, overlap_fraction: 0.99 };
+        assert!(welch_coherence(&signal, &signal, sample_rate, &opts).is_err());
+    }
+
+    #[test]
+    fn rejects_an_overlap_fine_grained_enough_to_exceed_the_segment_cap() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, MAX_SIGNAL_LENGTH);
+        let opts = WelchOptions { segment_length: MAX_SEGMENT_LENGTH, overlap_fraction: 0.9999 };
+        assert!(welch_coherence(&signal, &signal, sample_rate, &opts).is_err());
+    }
+
+    #[test]
+    fn unequal_length_signals_are_truncated_rather_than_erroring() {
+        let sample_rate = 256.0;
+        let x = sine(20.0, sample_rate, 512);
+        let y = sine(20.0, sample_rate, 300);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        let spectrum = welch_coherence(&x, &y, sample_rate, &opts).unwrap();
+        assert!(spectrum.coherence.iter().all(|&c| (0.0..=1.0).contains(&c)));
+    }
+
+    #[test]
+    fn rejects_a_band_with_no_frequency_bin_inside_it() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 512);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        assert!(check_spectral_coherence(&signal, &signal, sample_rate, (1_000.0, 2_000.0), 0.5, &opts).is_err());
+    }
+
+    #[test]
+    fn hann_window_preserves_mean_square_power() {
+        let window = hann_window(64);
+        let mean_square = window.iter().map(|w| w * w).sum::<f64>() / window.len() as f64;
+        assert!((mean_square - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_a_non_positive_sample_rate() {
+        let signal = sine(20.0, 256.0, 512);
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        assert!(welch_coherence(&signal, &signal, 0.0, &opts).is_err());
+        assert!(welch_coherence(&signal, &signal, -256.0, &opts).is_err());
+    }
+
+    #[test]
+    fn rejects_a_non_finite_sample() {
+        let sample_rate = 256.0;
+        let mut signal = sine(20.0, sample_rate, 512);
+        signal[10] = f64::NAN;
+        let opts = WelchOptions { segment_length: 64, overlap_fraction: 0.5 };
+        assert!(welch_coherence(&signal, &signal, sample_rate, &opts).is_err());
+    }
+
+    #[test]
+    fn rejects_segment_length_outside_the_allowed_range() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 512);
+        assert!(welch_coherence(&signal, &signal, sample_rate, &WelchOptions { segment_length: MAX_SEGMENT_LENGTH + 1, overlap_fraction: 0.5 }).is_err());
+        assert!(welch_coherence(&signal, &signal, sample_rate, &WelchOptions { segment_length: MIN_SEGMENT_LENGTH - 1, overlap_fraction: 0.5 }).is_err());
+    }
+
+    #[test]
+    fn rejects_overlap_fraction_out_of_range() {
+        let sample_rate = 256.0;
+        let signal = sine(20.0, sample_rate, 512);
+        assert!(welch_coherence(&signal, &signal, sample_rate, &WelchOptions { segment_length: 64, overlap_fraction: 1.0 }).is_err());
+        assert!(welch_coherence(&signal, &signal, sample_rate, &WelchOptions { segment_length: 64, overlap_fraction: -0.1 }).is_err());
+    }
+}
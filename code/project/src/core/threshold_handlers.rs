@@ -0,0 +1,48 @@
+// src/core/threshold_handlers.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::metrics::ThresholdAction;
+
+/// Something that can react to a `Metrics` threshold being exceeded.
+/// `CircuitBreaker` and `EvolutionManager` implement this so a threshold
+/// breach drives real corrective action instead of just being logged.
+pub trait ThresholdHandler: Send + Sync {
+    fn handle(&self, action: &ThresholdAction, key: &str, value: u64);
+}
+
+/// Maps each `ThresholdAction` variant to the handlers that should run when
+/// it fires. Multiple handlers can be registered per action (e.g. tripping
+/// a circuit breaker *and* alerting operators for the same action).
+#[derive(Default, Clone)]
+pub struct ThresholdHandlerRegistry {
+    handlers: Arc<Mutex<HashMap<ThresholdAction, Vec<Arc<dyn ThresholdHandler>>>>>,
+}
+
+impl ThresholdHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, action: ThresholdAction, handler: Arc<dyn ThresholdHandler>) {
+        self.handlers.lock().unwrap().entry(action).or_default().push(handler);
+    }
+
+    pub fn dispatch(&self, action: &ThresholdAction, key: &str, value: u64) {
+        if let Some(handlers) = self.handlers.lock().unwrap().get(action) {
+            for handler in handlers {
+                handler.handle(action, key, value);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ThresholdHandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts: HashMap<_, _> = self.handlers.lock().unwrap()
+            .iter()
+            .map(|(action, handlers)| (action.clone(), handlers.len()))
+            .collect();
+        f.debug_struct("ThresholdHandlerRegistry").field("handlers_per_action", &counts).finish()
+    }
+}
@@ -0,0 +1,71 @@
+// src/core/vector.rs
+use serde::{Serialize, Deserialize};
+use hdk::prelude::*;
+
+/// An embedding vector plus enough bookkeeping to place and re-verify it:
+/// `metadata.owner` ties it to the agent that wrote it, and `timestamp` is
+/// the DHT-visible creation time used for conflict resolution elsewhere in
+/// `core`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vector {
+    pub id: String,
+    pub data: Vec<f32>,
+    pub metadata: VectorMetadata,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VectorMetadata {
+    pub dimension: usize,
+    pub cluster_id: Option<String>,
+    pub owner: AgentPubKey,
+}
+
+impl Vector {
+    pub fn new(data: Vec<f32>, owner: AgentPubKey) -> Self {
+        Self {
+            id: nanoid::nanoid!(),
+            metadata: VectorMetadata {
+                dimension: data.len(),
+                cluster_id: None,
+                owner,
+            },
+            timestamp: sys_time().expect("Could not get system time"),
+            data,
+        }
+    }
+
+    /// First two components scaled into `[0, 1000]` for `HilbertCurve`'s
+    /// legacy 2-D path (`partition`, shard-split planning). Lossy for
+    /// anything beyond 2 dimensions — prefer `to_point_nd` for indexing
+    /// decisions that need to see every component.
+    pub fn to_point_2d(&self) -> [u32; 2] {
+        let scale_factor = 1000.0;
+
+        let x = if !self.data.is_empty() {
+            ((self.data[0] + 1.0) * 0.5 * scale_factor) as u32
+        } else {
+            0
+        };
+
+        let y = if self.data.len() > 1 {
+            ((self.data[1] + 1.0) * 0.5 * scale_factor) as u32
+        } else {
+            0
+        };
+
+        [x, y]
+    }
+
+    /// Every component scaled from its normalized `[-1, 1]` range into a
+    /// `bits`-wide unsigned coordinate, for `HilbertCurve::compute_index_advanced`
+    /// (and anything else that needs the full embedding, not just the
+    /// first two axes, to preserve spatial locality).
+    pub fn to_point_nd(&self, bits: u32) -> Vec<u32> {
+        let scale_factor = ((1u64 << bits) - 1) as f32;
+        self.data
+            .iter()
+            .map(|value| ((value + 1.0) * 0.5 * scale_factor) as u32)
+            .collect()
+    }
+}
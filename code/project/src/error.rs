@@ -0,0 +1,40 @@
+// src/error.rs
+use thiserror::Error;
+use std::time::Duration;
+
+#[derive(Debug, Error)]
+pub enum ShardError {
+    #[error("Shard migration failed: {context}")]
+    MigrationFailed {
+        context: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Circuit breaker is open")]
+    CircuitBreakerOpen,
+
+    #[error("Operation timed out after {duration:?}")]
+    Timeout {
+        duration: Duration,
+        operation: String,
+    },
+
+    #[error("Holochain error: {0}")]
+    Holochain(#[from] hdk::prelude::HdkError),
+
+    #[error("system clock error: {0}")]
+    Time(#[from] crate::core::TimeError),
+
+    #[error("checksum mismatch for {context}: content was corrupted in transit or storage")]
+    ChecksumMismatch {
+        context: String,
+    },
+
+    #[error("{context} was trained against base version {expected}, but version {actual} has since committed — retrain against the latest model")]
+    ConflictingBaseVersion {
+        context: String,
+        expected: u32,
+        actual: u32,
+    },
+}
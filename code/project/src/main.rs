@@ -23,6 +23,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         sync_timeout_ms: 5000,
         merge_interval_ms: 10000,
         merge_threshold: 0.01,
+        sync_work_budget: 32,
+        replicas_per_shard: 3,
+        nodes: vec![],
     };
     
     let shard_manager = Arc::new(ShardManager::new(shard_config, Arc::clone(&metrics)));
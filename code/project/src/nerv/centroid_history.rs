@@ -0,0 +1,87 @@
+// src/nerv/centroid_history.rs
+//! Content-defined-chunked version history for the centroids
+//! `EvolutionManager` tracks, so a long-lived centroid's update history
+//! costs roughly the unique bytes across versions rather than a full clone
+//! per update, and only chunks a durable backend hasn't seen yet get
+//! uploaded to Holochain.
+
+use crate::core::chunked_store::{restore_version, store_version, ChunkStore, ChunkedHistory};
+use crate::core::persistence::PersistenceError;
+use crate::core::{CentroidCRDT, KeyValueStore, KvOp};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const CENTROID_CHUNK_PREFIX: &str = "centroid_chunk.";
+
+/// Number of past versions retained per tracked centroid. Evicted versions'
+/// chunks stay in the `ChunkStore` only as long as a retained version still
+/// references them.
+const DEFAULT_HISTORY_DEPTH: usize = 8;
+
+pub struct CentroidVersionHistory {
+    store: ChunkStore,
+    backend: Arc<dyn KeyValueStore>,
+    history: RwLock<HashMap<String, ChunkedHistory>>,
+    history_depth: usize,
+}
+
+impl CentroidVersionHistory {
+    pub fn new(backend: Arc<dyn KeyValueStore>) -> Self {
+        Self::with_history_depth(backend, DEFAULT_HISTORY_DEPTH)
+    }
+
+    pub fn with_history_depth(backend: Arc<dyn KeyValueStore>, history_depth: usize) -> Self {
+        Self {
+            store: ChunkStore::new(),
+            backend,
+            history: RwLock::new(HashMap::new()),
+            history_depth: history_depth.max(1),
+        }
+    }
+
+    /// Chunk and record `centroid`'s serialized state as the newest version
+    /// tracked under `key` (the same identity `EvolutionManager` keys its
+    /// centroid map by).
+    pub async fn record_update(&self, key: &str, centroid: &CentroidCRDT) -> Result<(), serde_json::Error> {
+        let bytes = serde_json::to_vec(centroid)?;
+        let version = store_version(&self.store, &bytes);
+        let mut history = self.history.write().await;
+        let evicted = history.entry(key.to_string()).or_insert_with(|| ChunkedHistory::new(self.history_depth)).push(version);
+        drop(history);
+        if let Some(evicted) = evicted {
+            self.store.release(&evicted.chunk_hashes);
+        }
+        Ok(())
+    }
+
+    /// Reconstruct every retained version for `key`, oldest first.
+    pub async fn versions(&self, key: &str) -> Vec<CentroidCRDT> {
+        let history = self.history.read().await;
+        let Some(versions) = history.get(key) else { return Vec::new() };
+        versions
+            .versions()
+            .filter_map(|version| restore_version(&self.store, version))
+            .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+            .collect()
+    }
+
+    /// Durably persist every chunk produced since the last commit, keyed by
+    /// its content hash so identical chunks across versions are uploaded
+    /// once. Returns how many chunks were newly written. On a failed write,
+    /// the drained chunks are re-queued so the next call retries them
+    /// instead of losing them.
+    pub fn commit_to_holochain(&self) -> Result<usize, PersistenceError> {
+        let pending = self.store.drain_pending();
+        let count = pending.len();
+        let ops = pending
+            .iter()
+            .map(|(hash, bytes)| KvOp::Put(format!("{CENTROID_CHUNK_PREFIX}{}", hash.to_hex()), bytes.clone()))
+            .collect();
+        if let Err(err) = self.backend.transaction(ops) {
+            self.store.requeue_pending(pending.into_iter().map(|(hash, _)| hash));
+            return Err(err);
+        }
+        Ok(count)
+    }
+}
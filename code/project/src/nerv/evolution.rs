@@ -0,0 +1,207 @@
+// src/nerv/evolution.rs
+use super::CentroidVersionHistory;
+use crate::core::{CooperativeBudget, Metrics, CentroidCRDT, EmbeddedOrderedStore, KeyValueStore, ThresholdAction, ThresholdHandler};
+use crate::error::ShardError;
+use std::sync::Arc;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Work units an evolution sync round may spend merging centroids before it
+/// must yield back to the tokio scheduler.
+const DEFAULT_MERGE_WORK_BUDGET: u32 = 32;
+
+pub struct EvolutionManager {
+    evolution_interval_ms: u64,
+    merge_threshold: f32,
+    merge_work_budget: u32,
+    metrics: Arc<Metrics>,
+    centroids: RwLock<HashMap<String, CentroidCRDT>>,
+    /// Content-defined-chunked history of every tracked centroid's updates,
+    /// deduplicated against near-identical consecutive versions so the
+    /// history costs roughly the unique bytes rather than a full clone per
+    /// update.
+    history: CentroidVersionHistory,
+    running: RwLock<bool>,
+}
+
+impl EvolutionManager {
+    pub fn new(
+        evolution_interval_ms: u64,
+        merge_threshold: f32,
+        metrics: Arc<Metrics>
+    ) -> Self {
+        Self::with_merge_work_budget(evolution_interval_ms, merge_threshold, DEFAULT_MERGE_WORK_BUDGET, metrics)
+    }
+
+    pub fn with_merge_work_budget(
+        evolution_interval_ms: u64,
+        merge_threshold: f32,
+        merge_work_budget: u32,
+        metrics: Arc<Metrics>
+    ) -> Self {
+        Self::with_store(evolution_interval_ms, merge_threshold, merge_work_budget, metrics, Arc::new(EmbeddedOrderedStore::default()))
+    }
+
+    /// Construct an `EvolutionManager` whose centroid version history
+    /// durably persists new chunks to `store` instead of keeping them in
+    /// memory only.
+    pub fn with_store(
+        evolution_interval_ms: u64,
+        merge_threshold: f32,
+        merge_work_budget: u32,
+        metrics: Arc<Metrics>,
+        store: Arc<dyn KeyValueStore>,
+    ) -> Self {
+        Self {
+            evolution_interval_ms,
+            merge_threshold,
+            merge_work_budget,
+            metrics,
+            centroids: RwLock::new(HashMap::new()),
+            history: CentroidVersionHistory::new(store),
+            running: RwLock::new(false),
+        }
+    }
+
+    pub async fn start(&self) -> Result<(), ShardError> {
+        let mut running = self.running.write().await;
+        *running = true;
+
+        self.metrics.record("evolution_manager_start", 1);
+
+        // In a real implementation, this would start a background task to
+        // periodically merge centroids based on the evolution interval
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<(), ShardError> {
+        let mut running = self.running.write().await;
+        *running = false;
+
+        self.metrics.record("evolution_manager_stop", 1);
+
+        // In a real implementation, this would stop the background task
+
+        Ok(())
+    }
+
+    pub async fn process_centroid_update(&self, centroid: &CentroidCRDT) -> Result<(), ShardError> {
+        self.metrics.start_operation("evolution_process_update");
+
+        let key = format!("{:?}", centroid.centroid);
+        let mut centroids = self.centroids.write().await;
+
+        // Check if we already have this centroid
+        let recorded = if let Some(existing) = centroids.get_mut(&key) {
+            // Merge the centroids
+            let before_merge = existing.clone();
+            let merged = existing.merge(centroid);
+
+            if merged {
+                // Calculate displacement
+                let displacement = existing.calculate_displacement(&before_merge);
+                self.metrics.record("crdt_merge_divergence", (displacement * 1000.0) as u64);
+
+                // Check if displacement exceeds threshold
+                if displacement > self.merge_threshold {
+                    // In a real implementation, this would trigger immediate reconciliation
+                    self.metrics.record("evolution_threshold_exceeded", 1);
+                }
+            }
+            existing.clone()
+        } else {
+            // Add the new centroid
+            centroids.insert(key.clone(), centroid.clone());
+            centroid.clone()
+        };
+
+        // Record the history entry while still holding the centroids lock,
+        // so concurrent updates to the same key can't race and append their
+        // versions out of merge order.
+        if self.history.record_update(&key, &recorded).await.is_err() {
+            self.metrics.record("evolution_history_record_error", 1);
+        }
+        drop(centroids);
+
+        self.metrics.end_operation("evolution_process_update");
+
+        Ok(())
+    }
+
+    pub async fn sync_state(&self) -> Result<(), ShardError> {
+        self.metrics.start_operation("evolution_sync_state");
+
+        // In a real implementation this would fetch the latest centroids
+        // from other nodes; we merge whatever is already tracked locally so
+        // a large local set still can't starve the rest of the executor.
+        let incoming: Vec<CentroidCRDT> = self.centroids.read().await.values().cloned().collect();
+        self.merge_incoming_centroids(incoming).await?;
+
+        self.metrics.end_operation("evolution_sync_state");
+
+        Ok(())
+    }
+
+    /// Merge a batch of centroids received from other nodes, yielding back
+    /// to the tokio scheduler every `merge_work_budget` merges so a large
+    /// sync round can't monopolize the runtime.
+    async fn merge_incoming_centroids(&self, incoming: Vec<CentroidCRDT>) -> Result<(), ShardError> {
+        let mut budget = CooperativeBudget::new(self.merge_work_budget);
+        let mut centroids = self.centroids.write().await;
+
+        for centroid in incoming {
+            let key = format!("{:?}", centroid.centroid);
+            let merged = centroids
+                .entry(key.clone())
+                .and_modify(|existing| { existing.merge(&centroid); })
+                .or_insert(centroid)
+                .clone();
+
+            if self.history.record_update(&key, &merged).await.is_err() {
+                self.metrics.record("evolution_history_record_error", 1);
+            }
+
+            if budget.charge() {
+                tokio::task::yield_now().await;
+                budget.refill();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_centroids(&self) -> Result<Vec<CentroidCRDT>, ShardError> {
+        let centroids = self.centroids.read().await;
+        Ok(centroids.values().cloned().collect())
+    }
+
+    /// Every retained version of the centroid tracked under `centroid`'s
+    /// identity, oldest first, reconstructed from its content-defined
+    /// chunks.
+    pub async fn centroid_history(&self, centroid: &CentroidCRDT) -> Vec<CentroidCRDT> {
+        self.history.versions(&format!("{:?}", centroid.centroid)).await
+    }
+
+    /// Durably persist every history chunk produced since the last commit,
+    /// uploading only chunks the backend hasn't already stored.
+    pub fn commit_history_to_holochain(&self) -> Result<usize, crate::core::persistence::PersistenceError> {
+        self.history.commit_to_holochain()
+    }
+}
+
+/// Registered against `Metrics` for `ThresholdAction::TriggerResync` and
+/// `ThresholdAction::ImmediateReconciliation`: a neurosynchrony-latency or
+/// CRDT-divergence breach forces an immediate accounting of tracked
+/// centroids instead of only logging the breach.
+impl ThresholdHandler for EvolutionManager {
+    fn handle(&self, action: &ThresholdAction, key: &str, value: u64) {
+        if !matches!(action, ThresholdAction::TriggerResync | ThresholdAction::ImmediateReconciliation) {
+            return;
+        }
+        if let Ok(centroids) = self.centroids.try_read() {
+            self.metrics.record("evolution_forced_resync_count", centroids.len() as u64);
+        }
+        eprintln!("EvolutionManager: forcing centroid resync from '{key}' threshold breach (value={value})");
+    }
+}
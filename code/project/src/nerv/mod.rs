@@ -0,0 +1,16 @@
+// src/nerv/mod.rs
+mod centroid_history;
+mod replication;
+mod evolution;
+mod participant_selection;
+mod proof;
+mod secure_agg;
+mod worker;
+
+pub use centroid_history::CentroidVersionHistory;
+pub use replication::{mask_contribution, AggregationResult, AggregationStrategy, ChecksumAlgorithm, ModelChecksum, ModelMetadata, ModelMetrics, ModelUpdate, ReplicationManager};
+pub use evolution::EvolutionManager;
+pub use participant_selection::{ParticipantSelector, SampleWeightedSelector, SelectedParticipant, StalenessDecayedSelector, UniformRandomSelector};
+pub use proof::{ProofManager, RoundProof};
+pub use secure_agg::{majority_threshold, PairwiseMaskedAggregator, PlaintextAggregator, SecureAggregator};
+pub use worker::{RoundMetrics, Worker, WorkerManager, WorkerState, WorkerStatus};
@@ -0,0 +1,126 @@
+// src/nerv/participant_selection.rs
+//! Which of a round's pending `ModelUpdate`s actually get folded into
+//! `ReplicationManager::aggregate_model_updates`'s result, and what weight
+//! each chosen one carries. `max_participants_per_round` only ever bounded
+//! a round's *enrollment* (see `ReplicationManager::enroll`) — nothing kept
+//! aggregation itself from combining an unbounded number of submissions
+//! once they landed. Exposed as a trait so a deployment can swap in
+//! whichever of `UniformRandomSelector`, `SampleWeightedSelector`, or
+//! `StalenessDecayedSelector` fits its data distribution and staleness
+//! tolerance — see `ReplicationManager::set_participant_selector`.
+
+use super::replication::ModelUpdate;
+use crate::core::chunked_store::splitmix64;
+
+/// One update `ParticipantSelector::select` chose to fold in, and the
+/// weight it should carry relative to the rest of the selection — not
+/// necessarily normalized to sum to 1; a caller that needs a proper
+/// weighted average renormalizes over just the chosen set, the same way
+/// `aggregate_model_updates` already renormalizes FedAvg shares over a
+/// round's actual survivors.
+#[derive(Clone, Copy, Debug)]
+pub struct SelectedParticipant {
+    /// Position of the chosen update in the slice `select` was called
+    /// with.
+    pub index: usize,
+    pub weight: f32,
+}
+
+/// Chooses up to `max_participants` of `updates` to fold into a round's
+/// global model. Implementations may return fewer than `max_participants`
+/// (e.g. `updates.len() < max_participants`) but never more.
+pub trait ParticipantSelector: Send + Sync {
+    fn select(&self, updates: &[ModelUpdate], max_participants: usize, current_version: u32) -> Vec<SelectedParticipant>;
+}
+
+/// Deterministic weighted sampling without replacement (the "A-ES"
+/// algorithm): draw each candidate a uniform key in `(0, 1]`, raise it to
+/// the power `1 / weight`, and keep the `max_participants` updates with
+/// the largest resulting key. A candidate with a larger `weight` is more
+/// likely to survive the cut, but — unlike a plain top-`max_participants`
+/// sort by weight — never guaranteed to, so one or two outsized
+/// submissions can't permanently starve every smaller one out of ever
+/// being selected. Each draw is seeded from the candidate's own `checksum`
+/// folded with its position, so the sample is reproducible from `updates`
+/// alone (no external RNG — the same `splitmix64`-based determinism
+/// `pairwise_mask` relies on elsewhere in `nerv`) without two candidates
+/// ever drawing the same key merely for sharing a round.
+fn weighted_sample(updates: &[ModelUpdate], weights: &[f32], max_participants: usize) -> Vec<SelectedParticipant> {
+    if updates.len() <= max_participants {
+        return weights.iter().enumerate().map(|(index, &weight)| SelectedParticipant { index, weight }).collect();
+    }
+
+    let mut keyed: Vec<(f64, usize)> = updates
+        .iter()
+        .zip(weights)
+        .enumerate()
+        .map(|(index, (update, &weight))| {
+            let seed = u64::from_le_bytes(update.checksum.as_bytes()[0..8].try_into().unwrap()) ^ splitmix64(index as u64);
+            let draw = ((splitmix64(seed) >> 11) as f64 / (1u64 << 53) as f64).clamp(f64::MIN_POSITIVE, 1.0);
+            let key = draw.powf(1.0 / (weight.max(f32::EPSILON) as f64));
+            (key, index)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keyed.truncate(max_participants);
+    keyed.into_iter().map(|(_, index)| SelectedParticipant { index, weight: weights[index] }).collect()
+}
+
+/// Every candidate weighted equally — `weighted_sample` with uniform
+/// weights reduces to plain reservoir sampling, picking `max_participants`
+/// submissions without regard to `samples_count` or staleness.
+pub struct UniformRandomSelector;
+
+impl ParticipantSelector for UniformRandomSelector {
+    fn select(&self, updates: &[ModelUpdate], max_participants: usize, _current_version: u32) -> Vec<SelectedParticipant> {
+        let weights = vec![1.0f32; updates.len()];
+        weighted_sample(updates, &weights, max_participants)
+    }
+}
+
+/// Weight each candidate by its declared `samples_count`, mirroring
+/// FedAvg's own weighting: an agent that trained on more data is
+/// proportionally more likely to survive the cut when a round has more
+/// submissions than `max_participants_per_round` allows.
+pub struct SampleWeightedSelector;
+
+impl ParticipantSelector for SampleWeightedSelector {
+    fn select(&self, updates: &[ModelUpdate], max_participants: usize, _current_version: u32) -> Vec<SelectedParticipant> {
+        let weights: Vec<f32> = updates.iter().map(|u| u.metadata.metrics.samples_count as f32).collect();
+        weighted_sample(updates, &weights, max_participants)
+    }
+}
+
+/// Like `SampleWeightedSelector`, but additionally decays each candidate's
+/// weight by how far its `base_version` lags `current_version` — an
+/// update trained against a version several rounds stale is less likely to
+/// still reflect where the model actually is, so it's down-weighted
+/// (not rejected outright; `ReplicationManager::reconcile_base_version`
+/// already rejects anything too stale to rebase onto at all) relative to
+/// a fresher submission with the same sample count. The default
+/// `ReplicationManager::with_store` selects with.
+pub struct StalenessDecayedSelector {
+    /// Weight multiplier per version of lag — `0.5` halves a candidate's
+    /// weight for every version `base_version` trails `current_version`.
+    pub decay_per_version: f32,
+}
+
+impl Default for StalenessDecayedSelector {
+    fn default() -> Self {
+        Self { decay_per_version: 0.5 }
+    }
+}
+
+impl ParticipantSelector for StalenessDecayedSelector {
+    fn select(&self, updates: &[ModelUpdate], max_participants: usize, current_version: u32) -> Vec<SelectedParticipant> {
+        let weights: Vec<f32> = updates
+            .iter()
+            .map(|u| {
+                let lag = current_version.saturating_sub(u.base_version);
+                let decay = self.decay_per_version.clamp(0.0, 1.0).powi(lag as i32);
+                u.metadata.metrics.samples_count as f32 * decay
+            })
+            .collect();
+        weighted_sample(updates, &weights, max_participants)
+    }
+}
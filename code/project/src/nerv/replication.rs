@@ -1,46 +1,672 @@
 // src/nerv/replication.rs
-use crate::core::Metrics;
+use crate::core::chunked_store::{restore_version, splitmix64, store_version, ChunkHash, ChunkStore, ChunkedVersion};
+use crate::core::persistence::PersistenceError;
+use crate::core::{EmbeddedOrderedStore, KeyValueStore, KvOp, Metrics};
 use crate::error::ShardError;
+use crate::nerv::participant_selection::{ParticipantSelector, SelectedParticipant, StalenessDecayedSelector};
+use crate::nerv::proof::{ProofManager, RoundProof};
+use crate::nerv::secure_agg::{majority_threshold, PairwiseMaskedAggregator, SecureAggregator};
+use crate::nerv::worker::{RoundMetrics, Worker, WorkerManager, WorkerStatus};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Instant;
+use tokio::sync::{Mutex, RwLock};
 use hdk::prelude::*;
 
+/// Default throttle passed to `WorkerManager::new` — see
+/// `ReplicationManager::with_tranquility` to override. `1.0` sleeps as long
+/// as the previous round took, which keeps aggregation from monopolizing
+/// CPU without needing a separate fixed-interval scheduler.
+const DEFAULT_TRANQUILITY: f64 = 1.0;
+
+/// Id `ReplicationManager::start` registers its round-driving task under.
+/// There is exactly one per manager today, but `WorkerManager` is keyed by
+/// id so a future manager could drive several named workers side by side.
+const FEDERATED_ROUND_WORKER_ID: &str = "federated_round";
+
+/// How long a round stays open (from its first enrollment or submission)
+/// waiting for stragglers before `start_federated_round` forces it closed
+/// via mask recovery. See `ReplicationManager::with_round_timeout` to
+/// override.
+const DEFAULT_ROUND_TIMEOUT_MS: u64 = 30_000;
+
+/// `KeyValueStore` key the current global model is persisted under — see
+/// `ReplicationManager::with_store`.
+const CURRENT_MODEL_KEY: &str = "replication.current_model";
+/// Pending per-round updates are persisted one key per submitting agent, so
+/// a crash mid-round restores exactly the submissions that had landed.
+const PENDING_UPDATE_PREFIX: &str = "replication.pending.";
+const PENDING_UPDATE_PREFIX_END: &str = "replication.pending/"; // '/' > '.' in byte order, bounds the prefix scan
+/// Every accepted global model is additionally kept under its own version,
+/// so `get_model_at_version` can serve an audit or rollback query without
+/// disturbing `CURRENT_MODEL_KEY`.
+const HISTORY_PREFIX: &str = "replication.history.";
+
+/// How many past committed versions `recent_committed_versions` retains.
+/// A submission whose `base_version` still appears in this window is
+/// recent enough to rebase onto the current version; one that's aged out
+/// is rejected outright, since there's no bound on how far its weights
+/// have since drifted from the model they'd be folded into. See
+/// `ReplicationManager::reconcile_base_version`.
+const CONFLICT_WINDOW: usize = 8;
+
+/// Which digest `ReplicationManager::checksum_algorithm` computes
+/// `ModelUpdate::integrity` (and each chunk's checksum, when chunking is in
+/// play — see `ChunkedModelRecord`) under. `Crc32c` is a cheap,
+/// non-cryptographic transport check — fast, and enough to catch accidental
+/// corruption; `Sha256` is a slower cryptographic digest for deployments
+/// that need tamper resistance rather than just bit-rot detection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Crc32c,
+    Sha256,
+}
+
+impl Default for ChecksumAlgorithm {
+    fn default() -> Self {
+        ChecksumAlgorithm::Crc32c
+    }
+}
+
+/// A digest computed under one `ChecksumAlgorithm`. Kept as two differently
+/// sized variants, rather than always storing a 32-byte buffer, so a
+/// `Crc32c`-configured deployment isn't paying for bytes it never asked for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ModelChecksum {
+    Crc32c(u32),
+    Sha256([u8; 32]),
+}
+
+impl ModelChecksum {
+    fn of(algorithm: ChecksumAlgorithm, bytes: &[u8]) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Crc32c => ModelChecksum::Crc32c(crc32c(bytes)),
+            ChecksumAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(bytes);
+                let digest = hasher.finalize();
+                let mut out = [0u8; 32];
+                out.copy_from_slice(&digest);
+                ModelChecksum::Sha256(out)
+            }
+        }
+    }
+
+    /// Combine `per_chunk` (each chunk's own `ModelChecksum`, in the same
+    /// order as the chunks themselves) into one composite digest, by
+    /// hashing their concatenated bytes under the same algorithm. A
+    /// receiver holding only some of a transfer's chunks so far can still
+    /// verify each one it has against `per_chunk` directly — the composite
+    /// only needs to match once every chunk has landed.
+    fn composite(algorithm: ChecksumAlgorithm, per_chunk: &[ModelChecksum]) -> Self {
+        let mut bytes = Vec::with_capacity(per_chunk.len() * 32);
+        for checksum in per_chunk {
+            bytes.extend_from_slice(&checksum.to_bytes());
+        }
+        Self::of(algorithm, &bytes)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            ModelChecksum::Crc32c(v) => v.to_le_bytes().to_vec(),
+            ModelChecksum::Sha256(v) => v.to_vec(),
+        }
+    }
+}
+
+/// Per-byte-value CRC32C (Castagnoli) remainder table, generated at compile
+/// time the same way `chunked_store::GEAR_TABLE` is — avoids pulling in a
+/// whole crate for one polynomial.
+const fn build_crc32c_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0x82F6_3B78 } else { crc >> 1 };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CRC32C_TABLE: [u32; 256] = build_crc32c_table();
+
+fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        let index = ((crc ^ byte as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC32C_TABLE[index];
+    }
+    !crc
+}
+
 // Define model types for federated learning
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ModelUpdate {
     pub weights: Vec<f32>,
     pub bias: f32,
     pub version: u32,
+    /// The global model version this update was trained against — the
+    /// `current_model.version` the submitting agent last observed before
+    /// training, not necessarily `version - 1` if a round has since
+    /// rebased it (see `ReplicationManager::reconcile_base_version`). This
+    /// is the snapshot-isolation "read version" a transactional replicator
+    /// would track: `receive_model_update` compares it against whatever
+    /// has committed most recently to tell an ordinary submission apart
+    /// from one that's racing a concurrent round.
+    pub base_version: u32,
     pub metadata: ModelMetadata,
+    /// Content checksum over `weights` + `bias` + `version` + `base_version`
+    /// + `metadata`'s canonical bytes, computed by `ModelUpdate::new`.
+    /// Doubles as this update's commitment in `ProofManager`'s folding
+    /// proof (see `aggregate_model_updates`), which is why it stays a fixed
+    /// `ChunkHash` (BLAKE2s) rather than following `integrity`'s pluggable
+    /// algorithm — a folding proof's verifier needs every prover
+    /// committing under the same hash.
+    pub checksum: ChunkHash,
+    /// Transport-integrity digest over the same canonical bytes as
+    /// `checksum` minus `metadata` (just `weights` + `bias` + `version` +
+    /// `base_version`), computed under whichever `ChecksumAlgorithm` the
+    /// submitting `ReplicationManager` is configured with. Distinct from
+    /// `checksum`: this exists purely so `receive_model_update`/
+    /// `validate_model_update` can cheaply catch a corrupted or truncated
+    /// payload, without tying a deployment's choice of transport checksum
+    /// to the proof system's fixed commitment hash.
+    pub integrity: ModelChecksum,
 }
 
-#[derive(Clone, Debug)]
+impl ModelUpdate {
+    pub fn new(weights: Vec<f32>, bias: f32, version: u32, base_version: u32, metadata: ModelMetadata, algorithm: ChecksumAlgorithm) -> Self {
+        let checksum = Self::compute_checksum(&weights, bias, version, base_version, &metadata);
+        let integrity = Self::compute_integrity(&weights, bias, version, base_version, algorithm);
+        Self { weights, bias, version, base_version, metadata, checksum, integrity }
+    }
+
+    fn compute_checksum(weights: &[f32], bias: f32, version: u32, base_version: u32, metadata: &ModelMetadata) -> ChunkHash {
+        let mut bytes = Vec::with_capacity(weights.len() * 4 + 12 + 16 + 39);
+        for weight in weights {
+            bytes.extend_from_slice(&weight.to_le_bytes());
+        }
+        bytes.extend_from_slice(&bias.to_le_bytes());
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes.extend_from_slice(&base_version.to_le_bytes());
+        bytes.extend_from_slice(&metadata.timestamp.to_le_bytes());
+        bytes.extend_from_slice(&metadata.metrics.loss.to_le_bytes());
+        bytes.extend_from_slice(&metadata.metrics.accuracy.to_le_bytes());
+        bytes.extend_from_slice(&metadata.metrics.samples_count.to_le_bytes());
+        bytes.extend_from_slice(metadata.agent_id.get_raw_39());
+        ChunkHash::of(&bytes)
+    }
+
+    fn compute_integrity(weights: &[f32], bias: f32, version: u32, base_version: u32, algorithm: ChecksumAlgorithm) -> ModelChecksum {
+        let mut bytes = Vec::with_capacity(weights.len() * 4 + 12);
+        for weight in weights {
+            bytes.extend_from_slice(&weight.to_le_bytes());
+        }
+        bytes.extend_from_slice(&bias.to_le_bytes());
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes.extend_from_slice(&base_version.to_le_bytes());
+        ModelChecksum::of(algorithm, &bytes)
+    }
+
+    /// `false` means `weights`/`bias`/`version`/`base_version`/`metadata`
+    /// were altered since this update's checksum was computed — corrupted
+    /// in transit or storage.
+    pub fn verify_checksum(&self) -> bool {
+        self.checksum == Self::compute_checksum(&self.weights, self.bias, self.version, self.base_version, &self.metadata)
+    }
+
+    /// `false` means `weights`/`bias`/`version`/`base_version` were altered
+    /// since this update's `integrity` digest was computed under
+    /// `algorithm` — or `algorithm` doesn't match the one the sender used.
+    pub fn verify_integrity(&self, algorithm: ChecksumAlgorithm) -> bool {
+        self.integrity == Self::compute_integrity(&self.weights, self.bias, self.version, self.base_version, algorithm)
+    }
+}
+
+/// Flatten `weights` to little-endian bytes for content-defined chunking
+/// (see `ReplicationManager`'s `weight_chunks`) — the inverse of
+/// `weights_from_bytes`.
+fn weights_to_bytes(weights: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(weights.len() * 4);
+    for weight in weights {
+        bytes.extend_from_slice(&weight.to_le_bytes());
+    }
+    bytes
+}
+
+/// Inverse of `weights_to_bytes`. Trailing bytes that don't fill a whole
+/// `f32` are dropped rather than erroring — `bytes` only ever comes from
+/// `restore_version` reassembling chunks this same function produced, so a
+/// short tail would mean store corruption, not a legitimately truncated
+/// weight vector.
+fn weights_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes.chunks_exact(4).map(|chunk| f32::from_le_bytes(chunk.try_into().expect("chunks_exact(4) yields 4-byte slices"))).collect()
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ModelMetadata {
     pub timestamp: u64,
     pub metrics: ModelMetrics,
     pub agent_id: AgentPubKey,
+    /// The round's folding proof over its accepted updates (see
+    /// `ProofManager`), checkable via `ReplicationManager::verify_round`
+    /// without re-running aggregation. `None` for a model that didn't come
+    /// out of a federated round (e.g. `get_latest_global_model`'s stub).
+    pub round_proof: Option<RoundProof>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ModelMetrics {
     pub loss: f32,
     pub accuracy: f32,
     pub samples_count: u32,
 }
 
+/// How `aggregate_model_updates` combines a round's submitted contributions
+/// into the global model. `Mean` (the default) sums every submission, which
+/// is also what the SecAgg masking path in this file requires — masks only
+/// telescope to zero when *every* roster member's term is included in the
+/// sum, so the robust strategies below only make sense for a round run
+/// without masking (agents submit plaintext `weights`/`bias` and rely on
+/// the strategy itself, rather than secure aggregation, to keep a corrupted
+/// contribution from poisoning the result).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AggregationStrategy {
+    Mean,
+    /// Per-coordinate: sort the submitted values and take the middle one
+    /// (or the mean of the two middle values for an even submission
+    /// count) — unlike `Mean`, a single outlying coordinate in one
+    /// malicious update can't drag the result, since the median only
+    /// looks at rank, not magnitude.
+    Median,
+    /// Per-coordinate: sort the submitted values, drop the top and bottom
+    /// `beta` fraction, average what's left.
+    TrimmedMean { beta: f32 },
+    /// Score every update by the sum of squared L2 distances to its
+    /// `n - f - 2` closest other updates (`f` = assumed Byzantine count)
+    /// and keep the single lowest-scoring one.
+    Krum { f: usize },
+    /// Like `Krum`, but averages the `m` lowest-scoring updates instead of
+    /// keeping only one.
+    MultiKrum { f: usize, m: usize },
+}
+
+impl Default for AggregationStrategy {
+    fn default() -> Self {
+        AggregationStrategy::Mean
+    }
+}
+
+fn squared_l2_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Flatten each update's `weights` followed by `bias` into a single vector,
+/// the common shape the robust strategies below score and combine.
+fn updates_to_vectors(updates: &[ModelUpdate]) -> Vec<Vec<f32>> {
+    updates
+        .iter()
+        .map(|u| {
+            let mut v = u.weights.clone();
+            v.push(u.bias);
+            v
+        })
+        .collect()
+}
+
+/// Coordinate-wise median over `vectors` (each the same length): for
+/// every coordinate, sort the contributed values and take the middle one,
+/// or the mean of the two middle values when `vectors.len()` is even.
+fn coordinate_median(vectors: &[Vec<f32>]) -> Vec<f32> {
+    let n = vectors.len();
+    let len = vectors[0].len();
+
+    (0..len)
+        .map(|coord| {
+            let mut values: Vec<f32> = vectors.iter().map(|v| v[coord]).collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            if n % 2 == 1 {
+                values[n / 2]
+            } else {
+                (values[n / 2 - 1] + values[n / 2]) / 2.0
+            }
+        })
+        .collect()
+}
+
+/// Coordinate-wise trimmed mean over `vectors` (each the same length):
+/// for every coordinate, sort the contributed values, drop the top and
+/// bottom `beta` fraction, and average the rest. Falls back to averaging
+/// everything for a coordinate where trimming would drop every value (too
+/// few contributors for the requested `beta`).
+fn coordinate_trimmed_mean(vectors: &[Vec<f32>], beta: f32) -> Vec<f32> {
+    let n = vectors.len();
+    let len = vectors[0].len();
+    let trim = ((n as f32 * beta.clamp(0.0, 0.5)).floor() as usize).min(n.saturating_sub(1) / 2);
+
+    (0..len)
+        .map(|coord| {
+            let mut values: Vec<f32> = vectors.iter().map(|v| v[coord]).collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            let kept = &values[trim..n - trim];
+            kept.iter().sum::<f32>() / kept.len() as f32
+        })
+        .collect()
+}
+
+/// Krum score for each of `vectors`: the sum of squared L2 distances to
+/// its `n - f - 2` closest other updates (clamped to at least 1 neighbor
+/// so a roster too small for the declared `f` still produces a score
+/// instead of summing zero distances).
+fn krum_scores(vectors: &[Vec<f32>], f: usize) -> Vec<f32> {
+    let n = vectors.len();
+    let neighbors = n.saturating_sub(f + 2).max(1).min(n.saturating_sub(1).max(1));
+
+    vectors
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let mut distances: Vec<f32> = vectors
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| squared_l2_distance(v, other))
+                .collect();
+            distances.sort_by(|a, b| a.total_cmp(b));
+            distances.iter().take(neighbors).sum()
+        })
+        .collect()
+}
+
+/// Index of `vectors`' single lowest Krum score, alongside how many
+/// candidates that leaves rejected (every other submission).
+fn krum_select(vectors: &[Vec<f32>], f: usize) -> (usize, usize) {
+    let scores = krum_scores(vectors, f);
+    let selected = scores
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    (selected, vectors.len().saturating_sub(1))
+}
+
+/// Indices of `vectors`' `m` lowest Krum scores, alongside how many
+/// candidates that leaves rejected.
+fn multi_krum_select(vectors: &[Vec<f32>], f: usize, m: usize) -> (Vec<usize>, usize) {
+    let scores = krum_scores(vectors, f);
+    let mut ranked: Vec<usize> = (0..vectors.len()).collect();
+    ranked.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+    let m = m.min(ranked.len()).max(1);
+    ranked.truncate(m);
+    let rejected = vectors.len().saturating_sub(ranked.len());
+    (ranked, rejected)
+}
+
 #[derive(Debug)]
 pub struct AggregationResult {
     pub global_model: ModelUpdate,
+    /// The round's full enrolled roster, including any agent that never
+    /// submitted and had to be reconstructed out via
+    /// `reconstruct_missing_contribution`.
     pub participating_agents: Vec<AgentPubKey>,
+    /// Number of agents whose masked contribution was actually summed into
+    /// `global_model` — i.e. `participating_agents.len()` minus any
+    /// stragglers recovered out of the round.
+    pub effective_participant_count: usize,
+    /// Which `AggregationStrategy` combined this round's updates, so a
+    /// validator auditing `global_model` can tell a Krum-selected single
+    /// update apart from a full `Mean`/`Median`/`TrimmedMean` blend.
+    pub strategy: AggregationStrategy,
+    /// Checksums of the submitted updates that actually fed
+    /// `global_model` — every submission for `Mean`/`Median`/
+    /// `TrimmedMean` (they blend all of them), or just the survivors
+    /// `Krum`/`MultiKrum` selected, so an auditor can tell which
+    /// contributions a robust strategy discarded as (likely) Byzantine.
+    pub surviving_checksums: Vec<ChunkHash>,
+    /// Each update `ParticipantSelector::select` actually admitted into
+    /// this round (before any further `Krum`/`MultiKrum` culling), paired
+    /// with the weight it was chosen under — lets an auditor tell a
+    /// submission dropped for exceeding `max_participants_per_round` apart
+    /// from one a robust strategy rejected as (likely) Byzantine, and
+    /// recover why the selector favored one submission over another.
+    pub participant_weights: Vec<(AgentPubKey, f32)>,
+}
+
+/// One agent's enrollment in the current round: the sample count it
+/// declared up front, used by every enrolled agent to derive the same
+/// FedAvg `weight_share` (`samples_count / total_declared_samples`)
+/// before masking and submitting its contribution.
+#[derive(Clone, Debug)]
+struct RoundEnrollment {
+    agent_id: AgentPubKey,
+    declared_samples_count: u32,
+}
+
+/// The pairwise mask `a` and `b` agree on for this round, standing in for
+/// the output of a real pairwise key-agreement (e.g. Diffie-Hellman)
+/// between the two agents. Deriving it deterministically from the agents'
+/// public keys and the round salt keeps this simulation self-contained,
+/// the same way the rest of this file simulates its Holochain calls; it
+/// is symmetric (the ordering of `a`/`b` doesn't matter) so both sides
+/// compute the same mask independently.
+fn pairwise_mask(round_salt: u64, a: &AgentPubKey, b: &AgentPubKey, len: usize) -> Vec<f32> {
+    let (lo, hi) = if a.get_raw_39() <= b.get_raw_39() { (a, b) } else { (b, a) };
+    let mut seed_bytes = Vec::with_capacity(lo.get_raw_39().len() + hi.get_raw_39().len() + 8);
+    seed_bytes.extend_from_slice(lo.get_raw_39());
+    seed_bytes.extend_from_slice(hi.get_raw_39());
+    seed_bytes.extend_from_slice(&round_salt.to_le_bytes());
+    let digest = ChunkHash::of(&seed_bytes);
+    let mut seed = u64::from_le_bytes(digest.as_bytes()[0..8].try_into().unwrap());
+    (0..len)
+        .map(|_| {
+            seed = splitmix64(seed);
+            // Fold the top bits into a mask centered on zero so it doesn't
+            // bias the sum it's meant to cancel out of.
+            ((seed >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+        })
+        .collect()
+}
+
+/// Additive mask `agent_id` should fold into its FedAvg-weighted
+/// contribution before calling `receive_model_update`, so the coordinator
+/// never sees a raw per-agent weight vector. `weight_share` must already be
+/// `agent_id`'s `declared_samples_count / total_declared_samples` over
+/// `roster` (every enrolled agent computes the same shares from the same
+/// roster, so the coordinator never needs to hand out weights itself).
+/// Every ordered pair in `roster` agrees a pairwise mask; this agent adds
+/// it for peers ordered after it and subtracts it for peers ordered
+/// before it, so the masks telescope to zero once every participant's
+/// masked contribution is summed. `self_mask_seed` additionally folds in
+/// this agent's own random mask (see `secure_agg::expand_self_mask`),
+/// which doesn't cancel against any peer — it's removed by the
+/// aggregator reconstructing it from `threshold`-of-`n` Shamir shares via
+/// `ReplicationManager::deposit_self_mask_shares` instead, so no partial
+/// sum is ever meaningful before every roster member's term (submitted or
+/// reconstructed) has landed.
+pub fn mask_contribution(
+    round_salt: u64,
+    roster: &[AgentPubKey],
+    agent_id: &AgentPubKey,
+    weight_share: f32,
+    weights: &[f32],
+    bias: f32,
+    self_mask_seed: u64,
+) -> (Vec<f32>, f32) {
+    let self_pos = roster
+        .iter()
+        .position(|a| a == agent_id)
+        .expect("agent_id must be a member of roster");
+
+    let mut masked: Vec<f32> = weights.iter().map(|w| w * weight_share).collect();
+    masked.push(bias * weight_share);
+
+    for (peer_pos, peer) in roster.iter().enumerate() {
+        if peer_pos == self_pos {
+            continue;
+        }
+        let mask = pairwise_mask(round_salt, agent_id, peer, masked.len());
+        if self_pos < peer_pos {
+            for (slot, m) in masked.iter_mut().zip(mask.iter()) {
+                *slot += m;
+            }
+        } else {
+            for (slot, m) in masked.iter_mut().zip(mask.iter()) {
+                *slot -= m;
+            }
+        }
+    }
+
+    let self_mask = crate::nerv::secure_agg::expand_self_mask(self_mask_seed, masked.len());
+    for (slot, m) in masked.iter_mut().zip(self_mask.iter()) {
+        *slot += m;
+    }
+
+    let masked_bias = masked.pop().expect("masked always has a trailing bias slot");
+    (masked, masked_bias)
+}
+
+/// What's left to subtract out of a round's masked sum once `missing` is
+/// known to have enrolled but never submitted: the sum, over every agent
+/// that *did* submit, of the pairwise mask it agreed with `missing` —
+/// signed the same way `mask_contribution` would have signed it from each
+/// submitter's side. In a real deployment this would come from secret
+/// shares the surviving agents reveal for recovery rather than be
+/// recomputed directly; since `pairwise_mask` is itself a deterministic
+/// stand-in for that secret, recomputing it here reaches the same result.
+pub(crate) fn reconstruct_missing_contribution(
+    round_salt: u64,
+    roster: &[AgentPubKey],
+    missing: &AgentPubKey,
+    submitted: &[AgentPubKey],
+    len: usize,
+) -> Vec<f32> {
+    let missing_pos = roster
+        .iter()
+        .position(|a| a == missing)
+        .expect("missing must be a member of roster");
+
+    let mut delta = vec![0.0f32; len];
+    for submitter in submitted {
+        let submitter_pos = roster
+            .iter()
+            .position(|a| a == submitter)
+            .expect("submitter must be a member of roster");
+        let mask = pairwise_mask(round_salt, submitter, missing, len);
+        if submitter_pos < missing_pos {
+            for (d, m) in delta.iter_mut().zip(mask.iter()) {
+                *d += m;
+            }
+        } else {
+            for (d, m) in delta.iter_mut().zip(mask.iter()) {
+                *d -= m;
+            }
+        }
+    }
+    delta
 }
 
 pub struct ReplicationManager {
     replication_interval_ms: u64,
     max_participants_per_round: u32,
     metrics: Arc<Metrics>,
-    running: RwLock<bool>,
     current_model: RwLock<Option<ModelUpdate>>,
     pending_updates: RwLock<Vec<ModelUpdate>>,
+    /// Agents that have called `enroll` for the round currently being
+    /// assembled. Fixes the masking roster and each agent's FedAvg
+    /// `weight_share` before any masked contribution is submitted.
+    enrolled: RwLock<Vec<RoundEnrollment>>,
+    /// Salt for the round currently being assembled, minted the first time
+    /// an agent enrolls. `None` when no round is open.
+    round_salt: RwLock<Option<u64>>,
+    round_opened_at_ms: RwLock<Option<u64>>,
+    round_timeout_ms: u64,
+    aggregation_strategy: RwLock<AggregationStrategy>,
+    proof_manager: ProofManager,
+    /// Shamir shares of each submitter's self-mask seed, deposited by
+    /// roster peers via `deposit_self_mask_shares` standing in for the
+    /// gossip/relay layer a real deployment would use. Keyed by the agent
+    /// whose seed the shares reconstruct, cleared when the round closes.
+    self_mask_shares: RwLock<HashMap<AgentPubKey, Vec<(u8, u64)>>>,
+    /// Unmasking strategy for `Mean`-aggregated rounds. Defaults to the
+    /// real protocol; tests that never mask in the first place can swap in
+    /// `PlaintextAggregator` via `set_aggregator`.
+    aggregator: RwLock<Box<dyn SecureAggregator>>,
+    /// Picks which of a round's pending updates actually feed
+    /// `aggregate_model_updates` when there are more than
+    /// `max_participants_per_round` of them, and the weight each chosen
+    /// one carries. Defaults to `StalenessDecayedSelector`; swap via
+    /// `set_participant_selector`.
+    participant_selector: RwLock<Box<dyn ParticipantSelector>>,
+    /// Drives the actual round-ticking background task `start` spawns. See
+    /// `FEDERATED_ROUND_WORKER_ID` for the single worker it currently runs.
+    worker_manager: WorkerManager,
+    /// Content-addressed store backing `latest_committed`'s weight vectors —
+    /// see `submit_model_update`/`get_latest_global_model`. Successive
+    /// rounds' (and different agents') weight vectors usually share most of
+    /// their bytes, so chunking them here means only the chunks a real DHT
+    /// hasn't already seen ever need to cross the wire.
+    weight_chunks: ChunkStore,
+    /// The most recently `submit_model_update`-committed model, with its
+    /// weights stored as a `ChunkedVersion` rather than inline. `None` until
+    /// the first successful commit, in which case `get_latest_global_model`
+    /// falls back to a bootstrap stub.
+    latest_committed: RwLock<Option<ChunkedModelRecord>>,
+    /// Digest algorithm `ModelUpdate::integrity` is computed and verified
+    /// under. See `set_checksum_algorithm` to change it at runtime.
+    checksum_algorithm: RwLock<ChecksumAlgorithm>,
+    /// Durable backend for `current_model`, `pending_updates`, and the
+    /// accepted-model history `get_model_at_version` queries — an in-memory
+    /// `EmbeddedOrderedStore` by default (see `new`), or a real embedded
+    /// store (`LmdbStore`, `SqliteStore`) via `with_store`, so a restart
+    /// reloads exactly the federated state it had instead of losing
+    /// in-flight rounds and every past model version.
+    store: Arc<dyn KeyValueStore>,
+    /// Serializes `receive_model_update`'s `reconcile_base_version` check
+    /// and enqueue against `start_federated_round`'s commit step, so a
+    /// submission can't be accepted against a `base_version` that a
+    /// concurrent round commit has already invalidated by the time it
+    /// actually lands in `pending_updates`.
+    round_commit_lock: Mutex<()>,
+    /// Count of rounds that have committed a new global model, independent
+    /// of `ModelUpdate` version numbers — purely a diagnostic counter, kept
+    /// monotonic so an operator can tell two nodes' commit histories apart
+    /// even if their version numbering has diverged (e.g. after a rebase).
+    commit_counter: RwLock<u64>,
+    /// The last `CONFLICT_WINDOW` committed global-model versions, oldest
+    /// first. See `reconcile_base_version`.
+    recent_committed_versions: RwLock<VecDeque<u32>>,
+}
+
+/// A committed `ModelUpdate` as `submit_model_update` actually stores it:
+/// `weights` chunked into `weight_chunks` instead of kept inline, so
+/// `get_latest_global_model` has to reassemble the full vector on read (see
+/// `restore_version`) in exchange for only ever storing — and, in a real
+/// deployment, transferring — each distinct chunk once.
+#[derive(Clone, Debug)]
+struct ChunkedModelRecord {
+    chunked_weights: ChunkedVersion,
+    /// `integrity` digest of each chunk in `chunked_weights`, same order —
+    /// lets a receiver holding only some of a transfer's chunks so far
+    /// verify each one it has without needing the rest.
+    chunk_checksums: Vec<ModelChecksum>,
+    /// Digest of `chunk_checksums`' concatenated bytes, letting a receiver
+    /// that already has every chunk confirm the whole transfer in one
+    /// comparison instead of walking `chunk_checksums` one at a time.
+    composite_integrity: ModelChecksum,
+    bias: f32,
+    version: u32,
+    base_version: u32,
+    metadata: ModelMetadata,
+    checksum: ChunkHash,
+    integrity: ModelChecksum,
 }
 
 impl ReplicationManager {
@@ -48,120 +674,623 @@ impl ReplicationManager {
         replication_interval_ms: u64,
         max_participants_per_round: u32,
         metrics: Arc<Metrics>
+    ) -> Self {
+        Self::with_round_timeout(replication_interval_ms, max_participants_per_round, metrics, DEFAULT_ROUND_TIMEOUT_MS)
+    }
+
+    pub fn with_round_timeout(
+        replication_interval_ms: u64,
+        max_participants_per_round: u32,
+        metrics: Arc<Metrics>,
+        round_timeout_ms: u64,
+    ) -> Self {
+        Self::with_tranquility(replication_interval_ms, max_participants_per_round, metrics, round_timeout_ms, DEFAULT_TRANQUILITY)
+    }
+
+    /// Like `with_round_timeout`, but also overrides `tranquility` — the
+    /// fraction of each round's measured duration that `start`'s spawned
+    /// task sleeps afterward (`0.0` never sleeps, `1.0` sleeps as long as
+    /// the round took). See `WorkerManager::new`.
+    pub fn with_tranquility(
+        replication_interval_ms: u64,
+        max_participants_per_round: u32,
+        metrics: Arc<Metrics>,
+        round_timeout_ms: u64,
+        tranquility: f64,
+    ) -> Self {
+        Self::with_checksum_algorithm(
+            replication_interval_ms,
+            max_participants_per_round,
+            metrics,
+            round_timeout_ms,
+            tranquility,
+            ChecksumAlgorithm::default(),
+        )
+    }
+
+    /// Like `with_tranquility`, but also overrides `checksum_algorithm` —
+    /// see `ChecksumAlgorithm` for the `Crc32c`/`Sha256` tradeoff.
+    pub fn with_checksum_algorithm(
+        replication_interval_ms: u64,
+        max_participants_per_round: u32,
+        metrics: Arc<Metrics>,
+        round_timeout_ms: u64,
+        tranquility: f64,
+        checksum_algorithm: ChecksumAlgorithm,
+    ) -> Self {
+        Self::with_store(
+            replication_interval_ms,
+            max_participants_per_round,
+            metrics,
+            round_timeout_ms,
+            tranquility,
+            checksum_algorithm,
+            Arc::new(EmbeddedOrderedStore::default()),
+        )
+    }
+
+    /// Like `with_checksum_algorithm`, but also overrides the durable
+    /// backend for `current_model`, `pending_updates`, and accepted-model
+    /// history — pass an `Arc<LmdbStore>`/`Arc<SqliteStore>` in production
+    /// so `start` reloads this state after a restart instead of only
+    /// fetching the latest committed model from Holochain.
+    pub fn with_store(
+        replication_interval_ms: u64,
+        max_participants_per_round: u32,
+        metrics: Arc<Metrics>,
+        round_timeout_ms: u64,
+        tranquility: f64,
+        checksum_algorithm: ChecksumAlgorithm,
+        store: Arc<dyn KeyValueStore>,
     ) -> Self {
         Self {
             replication_interval_ms,
             max_participants_per_round,
             metrics,
-            running: RwLock::new(false),
             current_model: RwLock::new(None),
             pending_updates: RwLock::new(Vec::new()),
+            enrolled: RwLock::new(Vec::new()),
+            round_salt: RwLock::new(None),
+            round_opened_at_ms: RwLock::new(None),
+            round_timeout_ms,
+            aggregation_strategy: RwLock::new(AggregationStrategy::default()),
+            proof_manager: ProofManager::new(),
+            self_mask_shares: RwLock::new(HashMap::new()),
+            aggregator: RwLock::new(Box::new(PairwiseMaskedAggregator)),
+            participant_selector: RwLock::new(Box::new(StalenessDecayedSelector::default())),
+            worker_manager: WorkerManager::new(tranquility),
+            weight_chunks: ChunkStore::new(),
+            latest_committed: RwLock::new(None),
+            checksum_algorithm: RwLock::new(checksum_algorithm),
+            store,
+            round_commit_lock: Mutex::new(()),
+            commit_counter: RwLock::new(0),
+            recent_committed_versions: RwLock::new(VecDeque::with_capacity(CONFLICT_WINDOW)),
         }
     }
-    
-    pub async fn start(&self) -> Result<(), ShardError> {
-        let mut running = self.running.write().await;
-        *running = true;
-        
+
+    /// Digest algorithm this manager computes and verifies `ModelUpdate`
+    /// integrity checksums with. See `set_checksum_algorithm` to change it.
+    pub async fn checksum_algorithm(&self) -> ChecksumAlgorithm {
+        *self.checksum_algorithm.read().await
+    }
+
+    /// Swap the algorithm future `ModelUpdate`s are computed and verified
+    /// under — e.g. move a deployment from `Crc32c`'s cheap transport check
+    /// up to `Sha256`'s cryptographic one. Does not retroactively reverify
+    /// anything already committed.
+    pub async fn set_checksum_algorithm(&self, algorithm: ChecksumAlgorithm) {
+        *self.checksum_algorithm.write().await = algorithm;
+    }
+
+    /// Select the strategy `start_federated_learning_round` uses to combine
+    /// the next round's submissions. See `AggregationStrategy` for the
+    /// tradeoff between `Mean`'s SecAgg-compatible masked sum and the
+    /// robust strategies, which require plaintext submissions.
+    pub async fn set_aggregation_strategy(&self, strategy: AggregationStrategy) {
+        *self.aggregation_strategy.write().await = strategy;
+    }
+
+    /// Swap the `Mean`-round unmasking strategy — the real
+    /// `PairwiseMaskedAggregator` by default, or `PlaintextAggregator` for
+    /// tests that submit unmasked contributions directly.
+    pub async fn set_aggregator(&self, aggregator: Box<dyn SecureAggregator>) {
+        *self.aggregator.write().await = aggregator;
+    }
+
+    /// Swap which `ParticipantSelector` caps a round's submissions down to
+    /// `max_participants_per_round` — e.g. move a deployment from the
+    /// default `StalenessDecayedSelector` to `UniformRandomSelector` for a
+    /// round where sample count and recency shouldn't factor into who gets
+    /// dropped.
+    pub async fn set_participant_selector(&self, selector: Box<dyn ParticipantSelector>) {
+        *self.participant_selector.write().await = selector;
+    }
+
+    /// Deposit `shares` of `owner`'s self-mask seed for the round masked
+    /// under `round_salt`, standing in for the gossip/relay layer a real
+    /// deployment's roster peers would use. Stale deposits (a round that's
+    /// already closed) are silently accepted and simply never read, since
+    /// `self_mask_shares` is cleared whenever a round closes.
+    pub async fn deposit_self_mask_shares(&self, round_salt: u64, owner: AgentPubKey, shares: Vec<(u8, u64)>) -> Result<(), ShardError> {
+        if *self.round_salt.read().await != Some(round_salt) {
+            return Err(ShardError::MigrationFailed {
+                context: "self-mask shares deposited against a round that is no longer current".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "stale round_salt")),
+            });
+        }
+        self.self_mask_shares.write().await.insert(owner, shares);
+        Ok(())
+    }
+
+    /// Start the background task that drives `start_federated_round` on a
+    /// loop, one call per tick (throttled by `tranquility`; see
+    /// `with_tranquility`). Takes `Arc<Self>` rather than `&self` because
+    /// the spawned task needs its own owned handle into the manager —
+    /// callers hold the manager behind an `Arc` from construction for
+    /// exactly this reason.
+    pub async fn start(self: Arc<Self>) -> Result<(), ShardError> {
         self.metrics.record("replication_manager_start", 1);
-        
-        // Initialize the current model by fetching the latest global model from Holochain
-        let latest_model = self.get_latest_global_model().await?;
-        if let Some(model) = latest_model {
-            let mut current_model = self.current_model.write().await;
-            *current_model = Some(model);
+
+        // Reload durable state from `store` first, so a restart resumes
+        // with exactly the current model and in-flight submissions it had
+        // rather than replaying only whatever Holochain currently reports.
+        // Only fall back to the Holochain fetch when `store` has nothing
+        // persisted yet (a fresh deployment, or the in-memory default after
+        // every prior process's state was lost anyway).
+        let persisted_model = self.load_current_model_from_store().map_err(|e| ShardError::MigrationFailed {
+            context: "reloading persisted current model on start".to_string(),
+            source: Box::new(e),
+        })?;
+        let current_model = match persisted_model {
+            Some(model) => Some(model),
+            None => self.get_latest_global_model().await?,
+        };
+        if let Some(model) = current_model {
+            *self.current_model.write().await = Some(model);
         }
-        
-        // In a real implementation, this would start a background task to
-        // periodically run federated learning rounds
-        
+
+        let pending = self.load_pending_from_store().map_err(|e| ShardError::MigrationFailed {
+            context: "reloading persisted pending updates on start".to_string(),
+            source: Box::new(e),
+        })?;
+        *self.pending_updates.write().await = pending;
+
+        let worker: Arc<dyn Worker> = Arc::clone(&self) as Arc<dyn Worker>;
+        self.worker_manager.spawn(FEDERATED_ROUND_WORKER_ID, worker).await;
+
         Ok(())
     }
-    
+
+    /// Cancel and cleanly join the background round-ticking task started by
+    /// `start`. A no-op (not an error) if the task was never started.
     pub async fn stop(&self) -> Result<(), ShardError> {
-        let mut running = self.running.write().await;
-        *running = false;
-        
+        self.worker_manager.stop().await;
+
         self.metrics.record("replication_manager_stop", 1);
-        
-        // In a real implementation, this would stop the background task
-        
+
         Ok(())
     }
-    
+
+    /// Pause the background round-ticking task between ticks, without
+    /// tearing it down — `resume` picks the loop back up.
+    pub async fn pause(&self) -> Result<(), ShardError> {
+        self.worker_manager.pause(FEDERATED_ROUND_WORKER_ID).await
+    }
+
+    /// Resume a task previously `pause`d.
+    pub async fn resume(&self) -> Result<(), ShardError> {
+        self.worker_manager.resume(FEDERATED_ROUND_WORKER_ID).await
+    }
+
+    /// Lifecycle state and last-round metrics of the background
+    /// round-ticking task, keyed by worker id (there is currently only
+    /// `FEDERATED_ROUND_WORKER_ID`). Empty before `start` is called.
+    pub async fn worker_status(&self) -> HashMap<String, WorkerStatus> {
+        self.worker_manager.worker_status().await
+    }
+
     pub async fn sync_state(&self) -> Result<(), ShardError> {
         self.metrics.start_operation("replication_sync_state");
-        
+
         // Fetch the latest global model from Holochain
         let latest_model = self.get_latest_global_model().await?;
         if let Some(model) = latest_model {
             let mut current_model = self.current_model.write().await;
             *current_model = Some(model);
         }
-        
+
         self.metrics.end_operation("replication_sync_state");
-        
+
         Ok(())
     }
-    
-    pub async fn start_federated_round(&self) -> Result<(), ShardError> {
+
+    /// Join the round currently being assembled, declaring `samples_count`
+    /// samples to contribute. Returns the round's salt (stable for the
+    /// round's whole lifetime, usable immediately against `mask_contribution`
+    /// once paired with a roster) but, deliberately, not a roster or
+    /// `weight_share` — those depend on `total_declared` across the *final*
+    /// roster, which isn't settled until the round closes (see
+    /// `round_roster`). Rejected once the round has already reached
+    /// `max_participants_per_round` or timed out, since at that point the
+    /// roster other agents are masking against is already fixed.
+    pub async fn enroll(&self, agent_id: AgentPubKey, samples_count: u32) -> Result<u64, ShardError> {
+        let now = sys_time().map_err(|e| ShardError::Holochain(e))?.as_millis() as u64;
+
+        let mut round_salt = self.round_salt.write().await;
+        let salt = *round_salt.get_or_insert_with(|| now);
+        drop(round_salt);
+
+        let mut opened_at = self.round_opened_at_ms.write().await;
+        let opened_at_ms = *opened_at.get_or_insert(now);
+        drop(opened_at);
+
+        let mut enrolled = self.enrolled.write().await;
+        let already_enrolled = enrolled.iter().any(|e| e.agent_id == agent_id);
+        if !already_enrolled {
+            let cap_reached = enrolled.len() >= self.max_participants_per_round as usize;
+            let timed_out = now.saturating_sub(opened_at_ms) >= self.round_timeout_ms;
+            if cap_reached || timed_out {
+                return Err(ShardError::MigrationFailed {
+                    context: "round roster is already closing".to_string(),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, "enrollment closed for current round")),
+                });
+            }
+            enrolled.push(RoundEnrollment { agent_id, declared_samples_count: samples_count });
+        }
+
+        Ok(salt)
+    }
+
+    /// The round's finalized masking roster — `None` until `round_ready`'s
+    /// gate (`max_participants_per_round` enrolled, or the round timeout)
+    /// has fired and `enroll` stops admitting new agents. Callers must wait
+    /// for `Some` before computing `mask_contribution`'s `weight_share`,
+    /// since that share is `declared_samples_count / total_declared` over
+    /// this exact roster, not however many agents had enrolled earlier.
+    pub async fn round_roster(&self) -> Result<Option<Vec<(AgentPubKey, u32)>>, ShardError> {
+        if !self.round_ready().await? {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.enrolled
+                .read()
+                .await
+                .iter()
+                .map(|e| (e.agent_id.clone(), e.declared_samples_count))
+                .collect(),
+        ))
+    }
+
+    /// Run one federated round to completion: aggregate and submit if the
+    /// round is ready to close, or a cheap no-op tick otherwise. This is
+    /// `ReplicationManager`'s `Worker::run_round` implementation — `start`'s
+    /// background task calls it once per tick.
+    pub async fn start_federated_round(&self) -> Result<RoundMetrics, ShardError> {
         self.metrics.start_operation("federated_round");
-        
-        // In a real implementation, this would:
-        // 1. Select participants for the round (up to max_participants_per_round)
-        // 2. Distribute the current model to participants
-        // 3. Collect model updates from participants
-        // 4. Aggregate updates using secure multi-party computation (SMPC)
-        // 5. Apply the aggregated update to the global model
-        
+        let started = Instant::now();
+
+        if !self.round_ready().await? {
+            self.metrics.record("federated_round_not_ready", 1);
+            self.metrics.end_operation("federated_round");
+            return Ok(RoundMetrics { participants: 0, duration_ms: started.elapsed().as_millis() as u64 });
+        }
+
         // Get all pending updates
         let pending_updates = {
             let updates = self.pending_updates.read().await;
             updates.clone()
         };
-        
-        if !pending_updates.is_empty() {
-            // Aggregate the updates
-            let aggregation_result = self.aggregate_model_updates(pending_updates.clone()).await?;
-            
-            // Submit the aggregated model update to Holochain
-            self.submit_model_update(aggregation_result.global_model.clone()).await?;
-            
+
+        let mut effective_participant_count = 0usize;
+        if pending_updates.is_empty() {
+            // Every enrolled agent dropped out before submitting (e.g. they
+            // crashed after `enroll`); nothing to aggregate, but the round
+            // still needs to close so a fresh one can open instead of
+            // reporting `federated_round_not_ready` forever.
+            self.enrolled.write().await.clear();
+            *self.round_salt.write().await = None;
+            *self.round_opened_at_ms.write().await = None;
+            self.self_mask_shares.write().await.clear();
+        } else {
+            let roster = {
+                self.enrolled
+                    .read()
+                    .await
+                    .iter()
+                    .map(|e| (e.agent_id.clone(), e.declared_samples_count))
+                    .collect::<Vec<_>>()
+            };
+            let round_salt = self.round_salt.read().await.unwrap_or(0);
+
+            // Held from here through the durable commit and the in-memory
+            // `current_model`/`recent_committed_versions` updates below, so
+            // a `receive_model_update` call can't reconcile its
+            // `base_version` against a version this round is about to
+            // retire out from under it. See `round_commit_lock`.
+            let _commit_guard = self.round_commit_lock.lock().await;
+
+            // Aggregate and submit, but close the round out below regardless
+            // of the outcome: a bad pending update (failed checksum, rejected
+            // submission) must not wedge the round open forever, since once
+            // it's capped `enroll` refuses every new agent and every retry
+            // would just hit the same poisoned `pending_updates` again.
+            let outcome = match self.aggregate_model_updates(pending_updates.clone(), &roster, round_salt).await {
+                Ok(aggregation_result) => self
+                    .submit_model_update(aggregation_result.global_model.clone())
+                    .await
+                    .map(|()| aggregation_result),
+                Err(e) => Err(e),
+            };
+
+            // Clear the pending updates and close the round out
+            let mut updates = self.pending_updates.write().await;
+            updates.clear();
+            let mut enrolled = self.enrolled.write().await;
+            enrolled.clear();
+            let mut round_salt = self.round_salt.write().await;
+            *round_salt = None;
+            let mut opened_at = self.round_opened_at_ms.write().await;
+            *opened_at = None;
+            self.self_mask_shares.write().await.clear();
+
+            let aggregation_result = outcome?;
+            effective_participant_count = aggregation_result.effective_participant_count;
+
+            // Atomically retire this round's applied pending updates and
+            // persist the new global model — both as the current model and
+            // into version history — before updating in-memory state, so a
+            // crash right after this point still leaves the durable store at
+            // a consistent round boundary.
+            self.commit_round_to_store(&pending_updates, &aggregation_result.global_model).map_err(|e| ShardError::MigrationFailed {
+                context: "durably committing federated round outcome".to_string(),
+                source: Box::new(e),
+            })?;
+
             // Update the current model
+            let committed_version = aggregation_result.global_model.version;
             let mut current_model = self.current_model.write().await;
             *current_model = Some(aggregation_result.global_model);
-            
-            // Clear the pending updates
-            let mut updates = self.pending_updates.write().await;
-            updates.clear();
+            drop(current_model);
+
+            // Track this round in `recent_committed_versions` for
+            // `reconcile_base_version`, trimming the window's oldest entry
+            // once it's full, and bump the diagnostic commit counter.
+            let mut recent_committed = self.recent_committed_versions.write().await;
+            recent_committed.push_back(committed_version);
+            if recent_committed.len() > CONFLICT_WINDOW {
+                recent_committed.pop_front();
+            }
+            drop(recent_committed);
+            *self.commit_counter.write().await += 1;
+
+            self.metrics.record("federated_round_participants", effective_participant_count as u64);
         }
-        
-        // Simulate federated round duration
-        let round_duration = 5000; // 5 seconds simulated duration
-        self.metrics.record("federated_aggregation_latency", round_duration);
-        
+
+        let duration_ms = started.elapsed().as_millis() as u64;
+        self.metrics.record("federated_aggregation_latency", duration_ms);
+
         self.metrics.end_operation("federated_round");
-        
-        Ok(())
+
+        Ok(RoundMetrics { participants: effective_participant_count as u32, duration_ms })
     }
-    
-    pub async fn receive_model_update(&self, update: ModelUpdate) -> Result<(), ShardError> {
+
+    /// Gate on `max_participants_per_round` enrolled agents or the round
+    /// timeout elapsing, whichever comes first, so `start_federated_round`
+    /// doesn't aggregate (and unmask) a round that's still filling up.
+    async fn round_ready(&self) -> Result<bool, ShardError> {
+        let opened_at_ms = *self.round_opened_at_ms.read().await;
+        let Some(opened_at_ms) = opened_at_ms else {
+            return Ok(false);
+        };
+
+        let enrolled_count = self.enrolled.read().await.len() as u32;
+        if enrolled_count >= self.max_participants_per_round {
+            return Ok(true);
+        }
+
+        let now = sys_time().map_err(|e| ShardError::Holochain(e))?.as_millis() as u64;
+        Ok(now.saturating_sub(opened_at_ms) >= self.round_timeout_ms)
+    }
+
+    /// `round_salt` must be the value `enroll` returned, so a contribution
+    /// masked against a round that has since closed (e.g. delivered late,
+    /// after the coordinator already opened the next round under a new
+    /// salt) is rejected instead of summed against the wrong pairwise masks.
+    pub async fn receive_model_update(&self, update: ModelUpdate, round_salt: u64) -> Result<(), ShardError> {
         // Validate the update
         self.validate_model_update(update.clone()).await?;
-        
+
+        // Held for the rest of this function, across the base-version
+        // reconciliation check and the durable enqueue below, so a
+        // concurrent `start_federated_round` commit can't age
+        // `update.base_version` out of `CONFLICT_WINDOW` in the gap between
+        // `reconcile_base_version` passing and `update` actually landing in
+        // `pending_updates`. See `round_commit_lock`.
+        let _commit_guard = self.round_commit_lock.lock().await;
+
+        self.reconcile_base_version(&update).await?;
+
+        if *self.round_salt.read().await != Some(round_salt) {
+            return Err(ShardError::MigrationFailed {
+                context: "model update masked against a round that is no longer current".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "stale round_salt")),
+            });
+        }
+
+        // Masked contributions only cancel out across the roster they were
+        // masked against, and `reconstruct_missing_contribution` assumes
+        // every submitter is a roster member — so reject anything from an
+        // agent that never called `enroll` for this round.
+        let is_enrolled = self
+            .enrolled
+            .read()
+            .await
+            .iter()
+            .any(|e| e.agent_id == update.metadata.agent_id);
+        if !is_enrolled {
+            return Err(ShardError::MigrationFailed {
+                context: "model update from an agent that never enrolled in the current round".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "not enrolled")),
+            });
+        }
+
         // Add the update to the pending updates
         let mut updates = self.pending_updates.write().await;
+
+        // A second submission from an agent that already landed one this
+        // round would double its masked contribution in the sum: its
+        // pairwise masks against every peer get counted twice while the
+        // peers' matching mask terms are only counted once, so the masks no
+        // longer telescope to zero and its FedAvg share is doubled besides.
+        let already_submitted = updates.iter().any(|u| u.metadata.agent_id == update.metadata.agent_id);
+        if already_submitted {
+            return Err(ShardError::MigrationFailed {
+                context: "model update from an agent that already submitted this round".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "duplicate submission")),
+            });
+        }
+
+        // Durably enqueue before accepting the submission in memory, so a
+        // crash between the two never loses an already-acknowledged update —
+        // keyed per agent so a retried submission overwrites rather than
+        // accumulates, mirroring the in-memory duplicate check above.
+        let bytes = serde_json::to_vec(&update).map_err(|e| ShardError::MigrationFailed {
+            context: "serializing model update for the durable pending queue".to_string(),
+            source: Box::new(e),
+        })?;
+        self.store.put(&Self::pending_key(&update.metadata.agent_id), &bytes).map_err(|e| ShardError::MigrationFailed {
+            context: format!("durably enqueuing model update from {:?}", update.metadata.agent_id),
+            source: Box::new(e),
+        })?;
+
         updates.push(update);
-        
+
         Ok(())
     }
-    
+
+    /// Key `update`'s submitting agent's pending entry is persisted under —
+    /// see `PENDING_UPDATE_PREFIX`.
+    fn pending_key(agent_id: &AgentPubKey) -> String {
+        format!("{PENDING_UPDATE_PREFIX}{agent_id:?}")
+    }
+
+    /// Key `version`'s accepted global model is persisted under in history —
+    /// see `HISTORY_PREFIX`.
+    fn history_key(version: u32) -> String {
+        format!("{HISTORY_PREFIX}{version}")
+    }
+
+    /// Reload every durably enqueued pending update. Order doesn't matter:
+    /// the round they were submitted against is long closed by the time a
+    /// restart replays them, so `start` just needs them back in
+    /// `pending_updates` rather than lost.
+    fn load_pending_from_store(&self) -> Result<Vec<ModelUpdate>, PersistenceError> {
+        self.store
+            .range_scan(PENDING_UPDATE_PREFIX, PENDING_UPDATE_PREFIX_END)?
+            .into_iter()
+            .map(|(_, bytes)| serde_json::from_slice(&bytes).map_err(|e| PersistenceError::Serialization(e.to_string())))
+            .collect()
+    }
+
+    /// Reload the persisted current global model, if `commit_round_to_store`
+    /// has ever run against `store`.
+    fn load_current_model_from_store(&self) -> Result<Option<ModelUpdate>, PersistenceError> {
+        let Some(bytes) = self.store.get(CURRENT_MODEL_KEY)? else { return Ok(None) };
+        serde_json::from_slice(&bytes).map(Some).map_err(|e| PersistenceError::Serialization(e.to_string()))
+    }
+
+    /// Atomically clear `applied`'s persisted pending entries, persist
+    /// `global_model` as the new current model, and append it to history
+    /// keyed by its version — one `KeyValueStore::transaction`, so a crash
+    /// mid-round can never leave the pending queue, current-model pointer,
+    /// and history out of sync with each other.
+    fn commit_round_to_store(&self, applied: &[ModelUpdate], global_model: &ModelUpdate) -> Result<(), PersistenceError> {
+        let model_bytes = serde_json::to_vec(global_model).map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        let mut ops = Vec::with_capacity(applied.len() + 2);
+        for update in applied {
+            ops.push(KvOp::Delete(Self::pending_key(&update.metadata.agent_id)));
+        }
+        ops.push(KvOp::Put(Self::history_key(global_model.version), model_bytes.clone()));
+        ops.push(KvOp::Put(CURRENT_MODEL_KEY.to_string(), model_bytes));
+        self.store.transaction(ops)
+    }
+
+    /// Query the accepted-model history for the exact version that was
+    /// current immediately after some past federated round — e.g. to audit
+    /// what shipped at a point in time, or roll a deployment back to it.
+    /// `None` if no round ever produced that version, or `store` doesn't
+    /// retain history (the in-memory default after a restart).
+    pub async fn get_model_at_version(&self, version: u32) -> Result<Option<ModelUpdate>, ShardError> {
+        let Some(bytes) = self.store.get(&Self::history_key(version)).map_err(|e| ShardError::MigrationFailed {
+            context: format!("loading model history for version {version}"),
+            source: Box::new(e),
+        })?
+        else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes).map(Some).map_err(|e| ShardError::MigrationFailed {
+            context: format!("deserializing model history for version {version}"),
+            source: Box::new(e),
+        })
+    }
+
     // Holochain DNA integration functions
-    
+
     async fn get_latest_global_model(&self) -> Result<Option<ModelUpdate>, ShardError> {
-        // In a real implementation, this would call the Holochain function to get the latest global model
-        // For now, we'll create a stub implementation that simulates Holochain API calls
-        
+        // A real implementation would pull whichever chunks it's missing
+        // from the DHT and reassemble them here; since `submit_model_update`
+        // already chunked and stored the last committed model locally, that
+        // reassembly is just `restore_version`.
+        if let Some(record) = self.latest_committed.read().await.clone() {
+            let algorithm = self.checksum_algorithm().await;
+
+            // Cheap path: recomputing each chunk's digest and folding them
+            // into one composite comparison confirms every chunk is still
+            // intact in a single check. Only on mismatch do we walk
+            // `chunk_checksums` one at a time, so the error can point at
+            // the specific corrupted chunk instead of just "something in
+            // this transfer is wrong" — exactly what a receiver validating
+            // a partial transfer incrementally needs to do per chunk.
+            let mut chunk_bytes = Vec::with_capacity(record.chunked_weights.chunk_hashes.len());
+            for hash in &record.chunked_weights.chunk_hashes {
+                let bytes = self.weight_chunks.get_chunk(hash).ok_or_else(|| ShardError::MigrationFailed {
+                    context: "latest committed model's weight chunks are missing from the store".to_string(),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "missing weight chunk")),
+                })?;
+                chunk_bytes.push(bytes);
+            }
+            let actual_checksums: Vec<ModelChecksum> = chunk_bytes.iter().map(|bytes| ModelChecksum::of(algorithm, bytes)).collect();
+            if ModelChecksum::composite(algorithm, &actual_checksums) != record.composite_integrity {
+                let corrupt_index = actual_checksums.iter().zip(record.chunk_checksums.iter()).position(|(a, b)| a != b);
+                return Err(ShardError::ChecksumMismatch {
+                    context: match corrupt_index {
+                        Some(i) => format!("committed model weight chunk {i}"),
+                        None => "committed model weight chunks (composite)".to_string(),
+                    },
+                });
+            }
+
+            let bytes = restore_version(&self.weight_chunks, &record.chunked_weights).ok_or_else(|| {
+                ShardError::MigrationFailed {
+                    context: "latest committed model's weight chunks are missing from the store".to_string(),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "missing weight chunk")),
+                }
+            })?;
+            return Ok(Some(ModelUpdate {
+                weights: weights_from_bytes(&bytes),
+                bias: record.bias,
+                version: record.version,
+                base_version: record.base_version,
+                metadata: record.metadata,
+                checksum: record.checksum,
+                integrity: record.integrity,
+            }));
+        }
+
+        // Nothing committed yet (fresh deployment) — stub a bootstrap model
+        // the same way a freshly-initialized DHT would seed one.
+
         // Create a simulated agent public key
         let agent_id = AgentPubKey::from_raw_39(vec![0; 39]).map_err(|e| {
             ShardError::MigrationFailed {
@@ -169,13 +1298,16 @@ impl ReplicationManager {
                 source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{:?}", e))),
             }
         })?;
-        
+
         // Create a simulated model update for testing purposes
-        let model = ModelUpdate {
-            weights: vec![0.1, 0.2, 0.3, 0.4, 0.5],
-            bias: 0.01,
-            version: 1,
-            metadata: ModelMetadata {
+        let model = ModelUpdate::new(
+            vec![0.1, 0.2, 0.3, 0.4, 0.5],
+            0.01,
+            1,
+            // Bootstrapping: there is no prior version to have trained
+            // against.
+            0,
+            ModelMetadata {
                 timestamp: sys_time()
                     .map_err(|e| ShardError::Holochain(e))?
                     .as_millis() as u64,
@@ -185,12 +1317,14 @@ impl ReplicationManager {
                     samples_count: 1000,
                 },
                 agent_id,
+                round_proof: None,
             },
-        };
-        
+            self.checksum_algorithm().await,
+        );
+
         Ok(Some(model))
     }
-    
+
     async fn validate_model_update(&self, update: ModelUpdate) -> Result<(), ShardError> {
         // Basic validation
         if update.weights.is_empty() {
@@ -199,21 +1333,105 @@ impl ReplicationManager {
                 source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Empty weights")),
             });
         }
-        
-        // Version validation against current model
+
+        // A zero `samples_count` would either divide by zero in the
+        // sample-weighted loss/accuracy blend below or, worse, silently
+        // contribute a `weight_share` of zero to `total_declared` while
+        // still occupying a roster slot — neither is a contribution this
+        // round should accept.
+        if update.metadata.metrics.samples_count == 0 {
+            return Err(ShardError::MigrationFailed {
+                context: "model update declares zero samples_count".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "zero samples_count")),
+            });
+        }
+
+        if !update.verify_checksum() {
+            return Err(ShardError::ChecksumMismatch {
+                context: format!("model update from {:?}", update.metadata.agent_id),
+            });
+        }
+
+        if !update.verify_integrity(self.checksum_algorithm().await) {
+            return Err(ShardError::ChecksumMismatch {
+                context: format!("model update from {:?} (integrity digest)", update.metadata.agent_id),
+            });
+        }
+
+        // Cheap sanity check only: an update can't have been trained against
+        // a base version later than the current one, since that version
+        // can't have existed yet when training started. This does not reject
+        // a `base_version` that's merely stale (behind `current_model` by
+        // one or more committed rounds) — whether a stale base is still
+        // close enough to accept is `reconcile_base_version`'s call, made
+        // under `round_commit_lock` in `receive_model_update` so it can't
+        // race a concurrent round's commit.
         if let Some(current_model) = self.current_model.read().await.clone() {
-            if update.version <= current_model.version {
+            if update.base_version > current_model.version {
                 return Err(ShardError::MigrationFailed {
                     context: "Invalid version number".to_string(),
                     source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid version")),
                 });
             }
         }
-        
+
         Ok(())
     }
-    
-    async fn aggregate_model_updates(&self, updates: Vec<ModelUpdate>) -> Result<AggregationResult, ShardError> {
+
+    /// Serializable-snapshot conflict check: `update.base_version` must
+    /// still be within `CONFLICT_WINDOW` of the latest committed version,
+    /// i.e. recent enough that FedAvg's renormalization in
+    /// `aggregate_model_updates` is still a reasonable stand-in for
+    /// rebasing `update`'s weights onto whatever has committed since. An
+    /// update trained against a version that's aged out of the window is
+    /// rejected outright rather than silently folded in against a base
+    /// it's drifted too far from.
+    ///
+    /// Must be called with `round_commit_lock` held, so the check and the
+    /// caller's subsequent enqueue happen atomically with respect to
+    /// `start_federated_round`'s commit step — otherwise a round could
+    /// commit (aging `update.base_version` out of the window) in the gap
+    /// between this check passing and `update` actually landing in
+    /// `pending_updates`.
+    async fn reconcile_base_version(&self, update: &ModelUpdate) -> Result<(), ShardError> {
+        let recent = self.recent_committed_versions.read().await;
+        // `recent_committed_versions` only grows once a round actually
+        // commits through `start_federated_round`; before the first round
+        // since a restart, fall back to whatever `current_model` was
+        // bootstrapped to (the stub model or the last persisted model), so
+        // a submission trained against it isn't rejected as stale just
+        // because no round has committed yet in this process's lifetime.
+        let latest = match recent.back() {
+            Some(&version) => version,
+            None => self.current_model.read().await.as_ref().map(|m| m.version).unwrap_or(0),
+        };
+        if update.base_version == latest {
+            return Ok(());
+        }
+        if recent.contains(&update.base_version) {
+            return Ok(());
+        }
+        Err(ShardError::ConflictingBaseVersion {
+            context: format!("model update from {:?}", update.metadata.agent_id),
+            expected: update.base_version,
+            actual: latest,
+        })
+    }
+
+    /// Combine `updates` into the round's global model using whichever
+    /// `AggregationStrategy` is currently selected (see
+    /// `set_aggregation_strategy`). Under the default `Mean`, `updates`
+    /// each already hold a FedAvg-weighted, masked contribution from
+    /// `mask_contribution`; because the masks were chosen to cancel across
+    /// `roster`, summing every submitted contribution directly recovers
+    /// the weighted mean with no further per-agent weighting needed. Any
+    /// `roster` member that enrolled but isn't in `updates` has its
+    /// dangling mask term subtracted back out via
+    /// `reconstruct_missing_contribution`, and the survivors' total is
+    /// renormalized over their own weight shares so the result stays a
+    /// proper weighted average. The other strategies instead score and
+    /// select among `updates` directly (see `AggregationStrategy`'s docs).
+    async fn aggregate_model_updates(&self, updates: Vec<ModelUpdate>, roster: &[(AgentPubKey, u32)], round_salt: u64) -> Result<AggregationResult, ShardError> {
         // Validate inputs
         if updates.is_empty() {
             return Err(ShardError::MigrationFailed {
@@ -221,68 +1439,275 @@ impl ReplicationManager {
                 source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Empty updates")),
             });
         }
-        
-        // Calculate the new global model through averaging
-        let weights_len = updates[0].weights.len();
-        let mut aggregated_weights = vec![0.0; weights_len];
-        let mut aggregated_bias = 0.0;
-        
-        // Sum all weights and biases
+
+        // The version `global_model` is rebased onto, so a submitter who
+        // trains against it can cite it as their own `base_version` on the
+        // next round. `reconcile_base_version` is what actually lets a
+        // *stale* `base_version` through (within `CONFLICT_WINDOW`); this is
+        // only where the newly published one gets set.
+        let committed_version = self.current_model.read().await.as_ref().map(|m| m.version).unwrap_or(0);
+
+        let checksum_algorithm = self.checksum_algorithm().await;
         for update in &updates {
-            for (i, weight) in update.weights.iter().enumerate() {
-                aggregated_weights[i] += weight;
+            if !update.verify_checksum() {
+                return Err(ShardError::ChecksumMismatch {
+                    context: format!("model update from {:?} entering aggregation", update.metadata.agent_id),
+                });
+            }
+            if !update.verify_integrity(checksum_algorithm) {
+                return Err(ShardError::ChecksumMismatch {
+                    context: format!("model update from {:?} entering aggregation (integrity digest)", update.metadata.agent_id),
+                });
             }
-            aggregated_bias += update.bias;
         }
-        
-        // Average weights and bias
-        let update_count = updates.len() as f32;
-        for weight in &mut aggregated_weights {
-            *weight /= update_count;
+
+        let weights_len = updates[0].weights.len();
+        if updates.iter().any(|u| u.weights.len() != weights_len) {
+            return Err(ShardError::MigrationFailed {
+                context: "model updates in this round have mismatched weight vector lengths".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "weights length mismatch")),
+            });
         }
-        aggregated_bias /= update_count;
-        
-        // Create the new global model
+        let strategy = *self.aggregation_strategy.read().await;
+
+        // Cap this round down to `max_participants_per_round`, if there are
+        // more verified submissions than that — `enroll` only bounds a
+        // round's roster, not how many of the roster's eventual
+        // submissions land here. Every update not selected is treated
+        // exactly like a roster member who never submitted at all: for
+        // `Mean`, `missing` below reconstructs its dangling pairwise-mask
+        // term out of the sum the same way it would for a true dropout.
+        let selection: Vec<SelectedParticipant> = self
+            .participant_selector
+            .read()
+            .await
+            .select(&updates, self.max_participants_per_round as usize, committed_version);
+        let participant_weights: Vec<(AgentPubKey, f32)> =
+            selection.iter().map(|s| (updates[s.index].metadata.agent_id.clone(), s.weight)).collect();
+        let updates: Vec<ModelUpdate> = selection.iter().map(|s| updates[s.index].clone()).collect();
+        if updates.is_empty() {
+            return Err(ShardError::MigrationFailed {
+                context: "participant selection left no updates to aggregate".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty selection")),
+            });
+        }
+
+        let roster_ids: Vec<AgentPubKey> = roster.iter().map(|(a, _)| a.clone()).collect();
+        let submitted: Vec<AgentPubKey> = updates.iter().map(|u| u.metadata.agent_id.clone()).collect();
+
+        // Which submitted updates' self-reported `loss`/`accuracy` feed the
+        // global model's published metrics: every submission for `Mean`/
+        // `TrimmedMean` (they blend all of them), but only the
+        // survivors `Krum`/`MultiKrum` actually kept — a rejected update's
+        // weights never make it into `global_model`, so its self-reported
+        // metrics shouldn't skew the published ones either.
+        let mut metrics_indices: Vec<usize> = (0..updates.len()).collect();
+
+        let (weighted_weights, weighted_bias, rejected_count) = match strategy {
+            AggregationStrategy::Mean => {
+                let mut weighted_weights = vec![0.0; weights_len];
+                let mut weighted_bias = 0.0;
+
+                for update in &updates {
+                    for (i, weight) in update.weights.iter().enumerate() {
+                        weighted_weights[i] += weight;
+                    }
+                    weighted_bias += update.bias;
+                }
+
+                let submitted_set: HashSet<&AgentPubKey> = submitted.iter().collect();
+                let missing: Vec<(AgentPubKey, u32)> = roster.iter().filter(|(a, _)| !submitted_set.contains(a)).cloned().collect();
+
+                // Unwind both mask layers: each submitter's self-mask
+                // (reconstructed from its deposited Shamir shares) and
+                // each dropout's dangling pairwise-mask term, via whichever
+                // `SecureAggregator` is configured (the real protocol by
+                // default, a no-op for tests that never masked).
+                let self_mask_shares = self.self_mask_shares.read().await.clone();
+                let threshold = majority_threshold(roster_ids.len());
+                let (mut weighted_weights, mut weighted_bias) = self
+                    .aggregator
+                    .read()
+                    .await
+                    .unmask_round(weighted_weights, weighted_bias, round_salt, &roster_ids, &submitted, &self_mask_shares, threshold)?;
+
+                // Each submitted weight was scaled by its declared weight_share
+                // over the full enrolled roster (see `enroll`); renormalize over
+                // the roster members that actually contributed so the
+                // survivors' declared shares sum back to 1 instead of silently
+                // understating the model by the missing agents' share.
+                let total_declared: u32 = roster.iter().map(|(_, count)| count).sum();
+                let missing_share: f32 = missing
+                    .iter()
+                    .map(|(_, count)| *count as f32 / total_declared.max(1) as f32)
+                    .sum();
+                let survivor_share = (1.0 - missing_share).max(f32::EPSILON);
+                let renorm = 1.0 / survivor_share;
+                for w in &mut weighted_weights {
+                    *w *= renorm;
+                }
+                weighted_bias *= renorm;
+
+                (weighted_weights, weighted_bias, 0usize)
+            }
+            // The robust strategies below compare updates against each
+            // other, which only means something over plaintext
+            // contributions — they run directly on `updates`' `weights`/
+            // `bias` rather than unwinding SecAgg masks, so a round using
+            // one of them must have its agents submit unmasked updates.
+            AggregationStrategy::Median => {
+                let vectors = updates_to_vectors(&updates);
+                let combined = coordinate_median(&vectors);
+                (combined[..weights_len].to_vec(), combined[weights_len], 0)
+            }
+            AggregationStrategy::TrimmedMean { beta } => {
+                let vectors = updates_to_vectors(&updates);
+                let combined = coordinate_trimmed_mean(&vectors, beta);
+                (combined[..weights_len].to_vec(), combined[weights_len], 0)
+            }
+            AggregationStrategy::Krum { f } => {
+                let vectors = updates_to_vectors(&updates);
+                let (selected, rejected) = krum_select(&vectors, f);
+                let combined = &vectors[selected];
+                let result = (combined[..weights_len].to_vec(), combined[weights_len], rejected);
+                metrics_indices = vec![selected];
+                result
+            }
+            AggregationStrategy::MultiKrum { f, m } => {
+                let vectors = updates_to_vectors(&updates);
+                let (selected, rejected) = multi_krum_select(&vectors, f, m);
+                let count = selected.len() as f32;
+                let mut combined = vec![0.0; weights_len + 1];
+                for &idx in &selected {
+                    for (c, v) in combined.iter_mut().zip(vectors[idx].iter()) {
+                        *c += v / count;
+                    }
+                }
+                let result = (combined[..weights_len].to_vec(), combined[weights_len], rejected);
+                metrics_indices = selected;
+                result
+            }
+        };
+
+        self.metrics.record("federated_round_rejected_participants", rejected_count as u64);
+
+        // Fold only the updates that actually fed `weighted_weights`/
+        // `weighted_bias` into this round's proof — under `Krum`/`MultiKrum`
+        // that's the subset `metrics_indices` names, not every submission —
+        // and bind the fold to the published weights/bias themselves, so a
+        // downstream node can call `verify_round` against the round's result
+        // without re-running this function.
+        let accepted_checksums: Vec<ChunkHash> = metrics_indices.iter().map(|&i| updates[i].checksum).collect();
+        let round_proof = self.proof_manager.prove_round(&accepted_checksums, &weighted_weights, weighted_bias);
+
+        let total_samples: u32 = metrics_indices.iter().map(|&i| updates[i].metadata.metrics.samples_count).sum();
+        // FedAvg-weighted blend: each surviving update's loss/accuracy
+        // counts in proportion to how many samples it was trained on
+        // (`validate_model_update` already rejects `samples_count == 0`,
+        // so `total_samples` is positive here), rather than every
+        // surviving update counting equally regardless of how much data
+        // backed it.
+        let (loss, accuracy) = metrics_indices.iter().fold((0.0f32, 0.0f32), |(loss, accuracy), &i| {
+            let share = updates[i].metadata.metrics.samples_count as f32 / total_samples as f32;
+            (loss + updates[i].metadata.metrics.loss * share, accuracy + updates[i].metadata.metrics.accuracy * share)
+        });
         let agent_id = AgentPubKey::from_raw_39(vec![0; 39]).map_err(|e| {
             ShardError::MigrationFailed {
                 context: "Failed to create agent key".to_string(),
                 source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{:?}", e))),
             }
         })?;
-        
-        let global_model = ModelUpdate {
-            weights: aggregated_weights,
-            bias: aggregated_bias,
-            version: updates.iter().map(|u| u.version).max().unwrap() + 1,
-            metadata: ModelMetadata {
+
+        let global_model = ModelUpdate::new(
+            weighted_weights,
+            weighted_bias,
+            updates.iter().map(|u| u.version).max().unwrap() + 1,
+            committed_version,
+            ModelMetadata {
                 timestamp: sys_time()
                     .map_err(|e| ShardError::Holochain(e))?
                     .as_millis() as u64,
-                metrics: ModelMetrics {
-                    loss: updates.iter().map(|u| u.metadata.metrics.loss).sum::<f32>() / update_count,
-                    accuracy: updates.iter().map(|u| u.metadata.metrics.accuracy).sum::<f32>() / update_count,
-                    samples_count: updates.iter().map(|u| u.metadata.metrics.samples_count).sum::<u32>(),
-                },
+                metrics: ModelMetrics { loss, accuracy, samples_count: total_samples },
                 agent_id,
+                round_proof: Some(round_proof),
             },
-        };
-        
-        // Collect participating agents
-        let participating_agents = updates.iter()
-            .map(|u| u.metadata.agent_id.clone())
-            .collect::<Vec<_>>();
-        
+            checksum_algorithm,
+        );
+
         Ok(AggregationResult {
             global_model,
-            participating_agents,
+            participating_agents: roster_ids,
+            effective_participant_count: metrics_indices.len(),
+            strategy,
+            surviving_checksums: accepted_checksums,
+            participant_weights,
         })
     }
-    
-    async fn submit_model_update(&self, _update: ModelUpdate) -> Result<(), ShardError> {
-        // In a real implementation, this would call the Holochain function to submit the model update
-        // For now, this is a stub implementation
-        
-        // Simulate successful submission
+
+    /// Re-check `result.global_model`'s folding proof without re-running
+    /// `aggregate_model_updates` — cheap and independent of the round's
+    /// model size, since `ProofManager::verify_round` only re-derives the
+    /// fold from the proof's own committed checksums.
+    pub async fn verify_round(&self, result: &AggregationResult) -> Result<bool, ShardError> {
+        match &result.global_model.metadata.round_proof {
+            Some(proof) => Ok(self.proof_manager.verify_round(
+                proof,
+                &result.global_model.weights,
+                result.global_model.bias,
+            )),
+            None => Err(ShardError::MigrationFailed {
+                context: "global model has no round proof attached".to_string(),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing round proof")),
+            }),
+        }
+    }
+
+    async fn submit_model_update(&self, update: ModelUpdate) -> Result<(), ShardError> {
+        // A real implementation would only need to ship the DHT the chunks
+        // it doesn't already hold; `drain_pending` is exactly those, so this
+        // is also where the bandwidth saving actually happens.
+        let algorithm = self.checksum_algorithm().await;
+        let chunked_weights = store_version(&self.weight_chunks, &weights_to_bytes(&update.weights));
+
+        // Per-chunk integrity digests, plus their composite — see
+        // `ChunkedModelRecord` for why chunking calls for more than the
+        // single whole-vector `integrity` check `update` already carries.
+        let chunk_checksums: Vec<ModelChecksum> = chunked_weights
+            .chunk_hashes
+            .iter()
+            .map(|hash| {
+                let chunk_bytes = self.weight_chunks.get_chunk(hash).expect("chunk just stored by store_version must be present");
+                ModelChecksum::of(algorithm, &chunk_bytes)
+            })
+            .collect();
+        let composite_integrity = ModelChecksum::composite(algorithm, &chunk_checksums);
+
+        let transferred = self.weight_chunks.drain_pending().len();
+        self.metrics.record("model_update_chunks_total", chunked_weights.chunk_hashes.len() as u64);
+        self.metrics.record("model_update_chunks_transferred", transferred as u64);
+
+        *self.latest_committed.write().await = Some(ChunkedModelRecord {
+            chunked_weights,
+            chunk_checksums,
+            composite_integrity,
+            bias: update.bias,
+            version: update.version,
+            base_version: update.base_version,
+            metadata: update.metadata,
+            checksum: update.checksum,
+            integrity: update.integrity,
+        });
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Lets `start`'s spawned task drive rounds through the same `WorkerManager`
+/// that any other background loop would use, rather than hand-rolling its
+/// own tokio task and control plumbing.
+#[async_trait]
+impl Worker for ReplicationManager {
+    async fn run_round(&self) -> Result<RoundMetrics, ShardError> {
+        self.start_federated_round().await
+    }
+}
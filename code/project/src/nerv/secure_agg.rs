@@ -0,0 +1,328 @@
+// src/nerv/secure_agg.rs
+//! Self-masking and Shamir secret sharing on top of the pairwise-masking
+//! scheme `replication.rs` already runs (`pairwise_mask`/`mask_contribution`/
+//! `reconstruct_missing_contribution`): every submitting agent additionally
+//! folds in a random self-mask before its contribution ever leaves the
+//! client, so the aggregator never sees a meaningful partial sum until
+//! every roster member's term (submitted or reconstructed) has landed.
+//!
+//! Recovering each self-mask needs `threshold`-of-`n` Shamir shares, which
+//! this simulation has roster peers deposit with `ReplicationManager`
+//! (`deposit_self_mask_shares`) standing in for the gossip/relay layer a
+//! real deployment would use — the same category of simplification
+//! `pairwise_mask`'s doc comment already makes for its own key agreement.
+//! The invariant the real Bonawitz et al. protocol relies on still holds
+//! here: for any one roster member, the aggregator reconstructs *either*
+//! its self-mask shares (if it submitted, via [`SecureAggregator::unmask_round`]
+//! below) *or* its dangling pairwise-mask term (if it didn't, via the
+//! unchanged `reconstruct_missing_contribution`) — never both.
+
+use super::replication::reconstruct_missing_contribution;
+use crate::core::chunked_store::splitmix64;
+use crate::error::ShardError;
+use hdk::prelude::AgentPubKey;
+use std::collections::HashMap;
+
+/// A prime just under 2^61 (`2^61 - 1`, itself Mersenne-prime), large
+/// enough that a random `u64` self-mask seed reduced mod it still carries
+/// effectively full entropy, while staying inside `u128` multiplication
+/// headroom (`p^2 < 2^128`) so every Shamir operation below can use plain
+/// `u128` arithmetic without a bignum crate.
+const SHAMIR_PRIME: u128 = 2_305_843_009_213_693_951;
+
+/// Split `secret` into `holder_ids.len()` Shamir shares over `GF(p)`
+/// (`p` = [`SHAMIR_PRIME`]), any `threshold` of which reconstruct it via
+/// [`reconstruct`]. `holder_ids` must be distinct and nonzero (`x = 0` is
+/// reserved for the secret itself in Lagrange interpolation).
+pub fn split(secret: u64, threshold: u8, holder_ids: &[u8], rng_seed: u64) -> Vec<(u8, u64)> {
+    assert!(threshold >= 1 && (threshold as usize) <= holder_ids.len(), "threshold must be in [1, holder_ids.len()]");
+    assert!(holder_ids.iter().all(|&id| id != 0), "holder id 0 is reserved for the secret's own x-coordinate");
+
+    let mut coeffs: Vec<u128> = Vec::with_capacity(threshold as usize);
+    coeffs.push(secret as u128 % SHAMIR_PRIME);
+    let mut seed = rng_seed;
+    for _ in 1..threshold {
+        seed = splitmix64(seed);
+        coeffs.push(seed as u128 % SHAMIR_PRIME);
+    }
+
+    holder_ids
+        .iter()
+        .map(|&id| {
+            let x = id as u128;
+            let mut value = 0u128;
+            let mut power = 1u128;
+            for &c in &coeffs {
+                value = (value + c * power) % SHAMIR_PRIME;
+                power = (power * x) % SHAMIR_PRIME;
+            }
+            (id, value as u64)
+        })
+        .collect()
+}
+
+/// Reconstruct the secret `split` encoded, via Lagrange interpolation at
+/// `x = 0`, from any `threshold`-sized (or larger) subset of its shares.
+pub fn reconstruct(shares: &[(u8, u64)]) -> u64 {
+    let p = SHAMIR_PRIME;
+    let mut secret = 0u128;
+    for (i, &(xi, yi)) in shares.iter().enumerate() {
+        let xi = xi as u128;
+        let mut numerator = 1u128;
+        let mut denominator = 1u128;
+        for (j, &(xj, _)) in shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let xj = xj as u128;
+            numerator = numerator * ((p - xj) % p) % p;
+            denominator = denominator * ((xi + p - xj) % p) % p;
+        }
+        let term = (yi as u128) % p * numerator % p * mod_inverse(denominator, p) % p;
+        secret = (secret + term) % p;
+    }
+    secret as u64
+}
+
+fn mod_pow(mut base: u128, mut exp: u128, modulus: u128) -> u128 {
+    let mut result = 1u128;
+    base %= modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        exp >>= 1;
+        base = base * base % modulus;
+    }
+    result
+}
+
+/// Modular inverse via Fermat's little theorem (`SHAMIR_PRIME` is prime):
+/// `a^-1 = a^(p-2) mod p`.
+fn mod_inverse(a: u128, p: u128) -> u128 {
+    mod_pow(a % p, p - 2, p)
+}
+
+/// Expand a self-mask `seed` into a `len`-element additive mask, the same
+/// splitmix64-and-fold-top-bits shape `pairwise_mask` uses, so a
+/// self-masked and pairwise-masked contribution compose without either
+/// biasing the sum they're meant to cancel out of.
+pub fn expand_self_mask(seed: u64, len: usize) -> Vec<f32> {
+    let mut seed = seed;
+    (0..len)
+        .map(|_| {
+            seed = splitmix64(seed);
+            ((seed >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+        })
+        .collect()
+}
+
+/// `threshold`-of-`roster.len()` majority: at least half the roster plus
+/// one must reveal shares to reconstruct a seed, so no minority coalition
+/// (including a single peer, or the aggregator alone) can recover an
+/// agent's self-mask or pairwise-mask term on its own.
+pub fn majority_threshold(roster_len: usize) -> u8 {
+    ((roster_len / 2) + 1).min(u8::MAX as usize) as u8
+}
+
+/// Unwinds a round's SecAgg-masked sum into the true weighted sum.
+/// Exposed as a trait so `ReplicationManager` can run the real protocol
+/// for masked rounds (see [`PairwiseMaskedAggregator`]) while strategies
+/// that never mask in the first place (`Median`/`TrimmedMean`/`Krum`/
+/// `MultiKrum` — see `AggregationStrategy`) or tests that skip masking
+/// entirely can use [`PlaintextAggregator`]'s no-op passthrough instead.
+pub trait SecureAggregator: Send + Sync {
+    /// `weighted_sum`/`weighted_bias_sum` is the coordinate-wise sum of
+    /// every submitted (already FedAvg-weighted) masked contribution.
+    /// Returns the unmasked weighted sum; the caller still renormalizes it
+    /// over whichever roster members actually contributed.
+    fn unmask_round(
+        &self,
+        weighted_sum: Vec<f32>,
+        weighted_bias_sum: f32,
+        round_salt: u64,
+        roster: &[AgentPubKey],
+        submitted: &[AgentPubKey],
+        self_mask_shares: &HashMap<AgentPubKey, Vec<(u8, u64)>>,
+        threshold: u8,
+    ) -> Result<(Vec<f32>, f32), ShardError>;
+}
+
+/// The real protocol: reconstructs and subtracts every submitter's
+/// self-mask from `threshold`-or-more deposited shares, then cancels the
+/// dangling pairwise-mask term of every roster member that never
+/// submitted (via `reconstruct_missing_contribution`, unchanged from
+/// before this module existed). Per the module doc, a given agent's term
+/// is recovered through exactly one of those two paths, never both.
+pub struct PairwiseMaskedAggregator;
+
+impl SecureAggregator for PairwiseMaskedAggregator {
+    fn unmask_round(
+        &self,
+        mut weighted_sum: Vec<f32>,
+        mut weighted_bias_sum: f32,
+        round_salt: u64,
+        roster: &[AgentPubKey],
+        submitted: &[AgentPubKey],
+        self_mask_shares: &HashMap<AgentPubKey, Vec<(u8, u64)>>,
+        threshold: u8,
+    ) -> Result<(Vec<f32>, f32), ShardError> {
+        let len = weighted_sum.len() + 1;
+
+        for agent in submitted {
+            let shares = self_mask_shares.get(agent).ok_or_else(|| ShardError::MigrationFailed {
+                context: format!("no self-mask shares deposited for submitter {:?}", agent),
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing self-mask shares")),
+            })?;
+            if (shares.len() as u8) < threshold {
+                return Err(ShardError::MigrationFailed {
+                    context: format!(
+                        "only {} of {} required self-mask shares deposited for {:?}",
+                        shares.len(), threshold, agent
+                    ),
+                    source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "insufficient self-mask shares")),
+                });
+            }
+            let seed = reconstruct(&shares[..threshold as usize]);
+            let mask = expand_self_mask(seed, len);
+            for (w, m) in weighted_sum.iter_mut().zip(mask.iter()) {
+                *w -= m;
+            }
+            weighted_bias_sum -= mask[len - 1];
+        }
+
+        let missing: Vec<AgentPubKey> = roster.iter().filter(|a| !submitted.contains(a)).cloned().collect();
+        for missing_agent in &missing {
+            let delta = reconstruct_missing_contribution(round_salt, roster, missing_agent, submitted, len);
+            for (w, d) in weighted_sum.iter_mut().zip(delta.iter()) {
+                *w -= d;
+            }
+            weighted_bias_sum -= delta[len - 1];
+        }
+
+        Ok((weighted_sum, weighted_bias_sum))
+    }
+}
+
+/// No-op passthrough for rounds that never masked in the first place.
+pub struct PlaintextAggregator;
+
+impl SecureAggregator for PlaintextAggregator {
+    fn unmask_round(
+        &self,
+        weighted_sum: Vec<f32>,
+        weighted_bias_sum: f32,
+        _round_salt: u64,
+        _roster: &[AgentPubKey],
+        _submitted: &[AgentPubKey],
+        _self_mask_shares: &HashMap<AgentPubKey, Vec<(u8, u64)>>,
+        _threshold: u8,
+    ) -> Result<(Vec<f32>, f32), ShardError> {
+        Ok((weighted_sum, weighted_bias_sum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::replication::mask_contribution;
+
+    fn agent(id: u8) -> AgentPubKey {
+        AgentPubKey::from_raw_39(vec![id; 39]).unwrap()
+    }
+
+    #[test]
+    fn split_reconstruct_round_trips_at_threshold() {
+        let secret = 123_456_789_u64;
+        let holder_ids = [1, 2, 3, 4, 5];
+        let shares = split(secret, 3, &holder_ids, 42);
+        assert_eq!(shares.len(), 5);
+
+        // Any threshold-sized subset reconstructs the secret, not just a
+        // fixed prefix.
+        assert_eq!(reconstruct(&shares[0..3]), secret);
+        assert_eq!(reconstruct(&shares[1..4]), secret);
+        assert_eq!(reconstruct(&shares[2..5]), secret);
+        // More than threshold also works.
+        assert_eq!(reconstruct(&shares), secret);
+    }
+
+    #[test]
+    fn fewer_than_threshold_shares_do_not_recover_the_secret() {
+        let secret = 123_456_789_u64;
+        let holder_ids = [1, 2, 3, 4, 5];
+        let shares = split(secret, 3, &holder_ids, 42);
+
+        // `threshold - 1` shares under-determine the degree-2 polynomial;
+        // interpolating through them recovers the wrong constant term.
+        assert_ne!(reconstruct(&shares[0..2]), secret);
+    }
+
+    #[test]
+    fn unmask_round_recovers_the_true_weighted_sum_with_a_dropped_participant() {
+        let round_salt = 999;
+        let a1 = agent(1);
+        let a2 = agent(2);
+        let a3 = agent(3); // enrolled, but never submits
+        let roster = vec![a1.clone(), a2.clone(), a3.clone()];
+
+        let weights1 = vec![1.0_f32, 2.0, 3.0];
+        let bias1 = 0.5_f32;
+        let share1 = 0.6_f32;
+        let secret1 = 111_111_u64;
+
+        let weights2 = vec![4.0_f32, 5.0, 6.0];
+        let bias2 = -1.5_f32;
+        let share2 = 0.4_f32;
+        let secret2 = 222_222_u64;
+
+        let (masked1, masked_bias1) =
+            mask_contribution(round_salt, &roster, &a1, share1, &weights1, bias1, secret1);
+        let (masked2, masked_bias2) =
+            mask_contribution(round_salt, &roster, &a2, share2, &weights2, bias2, secret2);
+
+        let mut weighted_sum = vec![0.0_f32; weights1.len()];
+        for (slot, (m1, m2)) in weighted_sum.iter_mut().zip(masked1.iter().zip(masked2.iter())) {
+            *slot = m1 + m2;
+        }
+        let weighted_bias_sum = masked_bias1 + masked_bias2;
+
+        // Only submitters' self-masks need shares deposited; a3's dangling
+        // pairwise term is recovered via `reconstruct_missing_contribution`
+        // instead, never via Shamir shares.
+        let threshold = majority_threshold(roster.len());
+        let holder_ids = [10, 20, 30, 40, 50];
+        let mut self_mask_shares = HashMap::new();
+        self_mask_shares.insert(a1.clone(), split(secret1, threshold, &holder_ids, 1)[..threshold as usize].to_vec());
+        self_mask_shares.insert(a2.clone(), split(secret2, threshold, &holder_ids, 2)[..threshold as usize].to_vec());
+
+        let (unmasked_sum, unmasked_bias) = PairwiseMaskedAggregator
+            .unmask_round(weighted_sum, weighted_bias_sum, round_salt, &roster, &[a1, a2], &self_mask_shares, threshold)
+            .unwrap();
+
+        let expected_weights: Vec<f32> = weights1.iter().zip(weights2.iter()).map(|(w1, w2)| w1 * share1 + w2 * share2).collect();
+        let expected_bias = bias1 * share1 + bias2 * share2;
+
+        for (actual, expected) in unmasked_sum.iter().zip(expected_weights.iter()) {
+            assert!((actual - expected).abs() < 1e-3, "expected {expected}, got {actual}");
+        }
+        assert!((unmasked_bias - expected_bias).abs() < 1e-3);
+    }
+
+    #[test]
+    fn unmask_round_rejects_too_few_self_mask_shares() {
+        let round_salt = 1;
+        let a1 = agent(1);
+        let a2 = agent(2);
+        let roster = vec![a1.clone(), a2.clone()];
+        let threshold = majority_threshold(roster.len());
+        let holder_ids = [10, 20, 30];
+
+        let mut self_mask_shares = HashMap::new();
+        // One share short of threshold.
+        self_mask_shares.insert(a1.clone(), split(42, threshold, &holder_ids, 7)[..(threshold as usize - 1)].to_vec());
+
+        let result = PairwiseMaskedAggregator.unmask_round(vec![0.0], 0.0, round_salt, &roster, &[a1], &self_mask_shares, threshold);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,203 @@
+// src/nerv/worker.rs
+use crate::error::ShardError;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+/// One unit of recurring background work, ticked once per round by a
+/// `WorkerManager`-driven loop. `ReplicationManager::start_federated_round`
+/// is the motivating implementor: each tick attempts one federated round,
+/// completing as a cheap no-op when the round isn't ready to close yet.
+#[async_trait]
+pub trait Worker: Send + Sync {
+    async fn run_round(&self) -> Result<RoundMetrics, ShardError>;
+}
+
+/// What a `Worker::run_round` call accomplished, surfaced through
+/// `WorkerManager::worker_status` so callers can tell a healthy idle tick
+/// from a round that actually landed work.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoundMetrics {
+    pub participants: u32,
+    pub duration_ms: u64,
+}
+
+/// Lifecycle of a spawned worker loop, reported by `worker_status`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkerState {
+    /// Between ticks, waiting on its tranquility sleep or a control message.
+    Idle,
+    /// Inside a `run_round` call.
+    Busy,
+    /// Cancelled (or its manager was stopped); the loop has exited.
+    Done,
+    /// The most recent `run_round` returned an error, captured here instead
+    /// of being dropped silently. The loop keeps ticking on the next round.
+    Errored(String),
+}
+
+/// A worker's lifecycle state alongside its last completed round's metrics
+/// (`None` until the first round that didn't error).
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkerStatus {
+    pub state: WorkerState,
+    pub last_round: Option<RoundMetrics>,
+}
+
+enum Control {
+    Start,
+    Pause,
+    Cancel,
+}
+
+struct WorkerHandle {
+    control: mpsc::Sender<Control>,
+    status: Arc<RwLock<WorkerStatus>>,
+    join: JoinHandle<()>,
+}
+
+/// Upper bound on `tranquility` so a misconfigured manager can't make a
+/// worker loop sleep for hours between one-off rounds.
+const MAX_TRANQUILITY: f64 = 10.0;
+
+/// Drives one or more `Worker`s, each on its own tokio task, ticking at
+/// `tranquility`-throttled intervals so aggregation doesn't monopolize CPU.
+/// Replaces the old "flip a running bool and leave a comment" stub: `spawn`
+/// actually starts a task, `pause`/`resume`/`cancel` drive it through a
+/// control channel, and `worker_status` reports real per-worker state
+/// instead of nothing.
+pub struct WorkerManager {
+    /// Fraction of each round's measured duration to sleep afterward — 1.0
+    /// sleeps as long as the round took, 0.0 never sleeps between rounds.
+    tranquility: f64,
+    workers: RwLock<HashMap<String, WorkerHandle>>,
+}
+
+impl WorkerManager {
+    pub fn new(tranquility: f64) -> Self {
+        Self {
+            tranquility: tranquility.clamp(0.0, MAX_TRANQUILITY),
+            workers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Spawn `worker` under `id` on its own tokio task, ticking `run_round`
+    /// until `cancel(id)` or `stop` tears it down. Replacing an id that's
+    /// already registered leaves the old task running detached until it
+    /// next checks its (now orphaned) control channel — `cancel` it first
+    /// if that matters to the caller.
+    pub async fn spawn(&self, id: impl Into<String>, worker: Arc<dyn Worker>) {
+        let id = id.into();
+        let (control_tx, mut control_rx) = mpsc::channel(8);
+        let status = Arc::new(RwLock::new(WorkerStatus { state: WorkerState::Idle, last_round: None }));
+        let task_status = Arc::clone(&status);
+        let tranquility = self.tranquility;
+
+        let join = tokio::spawn(async move {
+            let mut paused = false;
+            loop {
+                match control_rx.try_recv() {
+                    Ok(Control::Start) => paused = false,
+                    Ok(Control::Pause) => paused = true,
+                    Ok(Control::Cancel) => break,
+                    Err(mpsc::error::TryRecvError::Empty) => {}
+                    Err(mpsc::error::TryRecvError::Disconnected) => break,
+                }
+
+                if paused {
+                    // Block on the channel instead of busy-polling while paused.
+                    match control_rx.recv().await {
+                        Some(Control::Start) => paused = false,
+                        Some(Control::Pause) => continue,
+                        Some(Control::Cancel) | None => break,
+                    }
+                    continue;
+                }
+
+                task_status.write().await.state = WorkerState::Busy;
+                let started = tokio::time::Instant::now();
+                match worker.run_round().await {
+                    Ok(metrics) => {
+                        let mut status = task_status.write().await;
+                        status.state = WorkerState::Idle;
+                        status.last_round = Some(metrics);
+                    }
+                    Err(e) => {
+                        task_status.write().await.state = WorkerState::Errored(e.to_string());
+                    }
+                }
+
+                let sleep_for = started.elapsed().mul_f64(tranquility);
+                if sleep_for > Duration::ZERO {
+                    tokio::time::sleep(sleep_for).await;
+                }
+            }
+            task_status.write().await.state = WorkerState::Done;
+        });
+
+        self.workers.write().await.insert(id, WorkerHandle { control: control_tx, status, join });
+    }
+
+    pub async fn pause(&self, id: &str) -> Result<(), ShardError> {
+        self.send_control(id, Control::Pause).await
+    }
+
+    pub async fn resume(&self, id: &str) -> Result<(), ShardError> {
+        self.send_control(id, Control::Start).await
+    }
+
+    /// Cancel `id`'s task and join it, removing it from the manager.
+    pub async fn cancel(&self, id: &str) -> Result<(), ShardError> {
+        let handle = self.workers.write().await.remove(id);
+        let Some(handle) = handle else {
+            return Err(Self::unknown_worker(id));
+        };
+        // The task may already have exited on its own (e.g. a prior Cancel
+        // it's still draining), in which case the send is a harmless no-op.
+        let _ = handle.control.send(Control::Cancel).await;
+        let _ = handle.join.await;
+        Ok(())
+    }
+
+    /// Cancel and join every worker, leaving the manager empty. Mirrors
+    /// `ReplicationManager::stop`'s "cleanly join the task" contract at
+    /// the multi-worker level.
+    pub async fn stop(&self) {
+        let ids: Vec<String> = self.workers.read().await.keys().cloned().collect();
+        for id in ids {
+            let _ = self.cancel(&id).await;
+        }
+    }
+
+    /// Current lifecycle state and last-round metrics of every worker
+    /// registered with this manager, keyed by the id passed to `spawn`.
+    pub async fn worker_status(&self) -> HashMap<String, WorkerStatus> {
+        let workers = self.workers.read().await;
+        let mut out = HashMap::with_capacity(workers.len());
+        for (id, handle) in workers.iter() {
+            out.insert(id.clone(), handle.status.read().await.clone());
+        }
+        out
+    }
+
+    async fn send_control(&self, id: &str, msg: Control) -> Result<(), ShardError> {
+        let workers = self.workers.read().await;
+        let Some(handle) = workers.get(id) else {
+            return Err(Self::unknown_worker(id));
+        };
+        handle.control.send(msg).await.map_err(|_| ShardError::MigrationFailed {
+            context: format!("worker '{id}' task has already exited"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, "control channel closed")),
+        })
+    }
+
+    fn unknown_worker(id: &str) -> ShardError {
+        ShardError::MigrationFailed {
+            context: format!("no worker registered under '{id}'"),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "unknown worker id")),
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::time::{Duration, Instant};
 
+use crate::core::{ThresholdAction, ThresholdHandler};
+
 #[derive(Debug)]
 pub struct CircuitBreaker {
     state: Arc<RwLock<CircuitState>>,
@@ -115,4 +117,22 @@ impl CircuitBreaker {
         *state = CircuitState::Closed { failures: 0 };
         Ok(())
     }
+}
+
+/// Registered against `Metrics` for `ThresholdAction::ReduceParticipantSet`:
+/// a sustained federated-aggregation latency breach trips the circuit so
+/// subsequent operations back off until the reset timeout elapses, instead
+/// of only logging the breach.
+impl ThresholdHandler for CircuitBreaker {
+    fn handle(&self, action: &ThresholdAction, key: &str, value: u64) {
+        if !matches!(action, ThresholdAction::ReduceParticipantSet) {
+            return;
+        }
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            let mut state = state.write().await;
+            *state = CircuitState::Open { since: Instant::now() };
+        });
+        eprintln!("CircuitBreaker: tripped open by '{key}' threshold breach (value={value})");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+// src/network/fault_injection.rs
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::error::ShardError;
+
+/// Deterministic fault-injection sink for tests that can't run against a
+/// live Holochain conductor. Wraps the budget- and sync-failure surface
+/// behind an injectable point that fails a named call a scheduled number of
+/// times with a chosen error, then lets it through, while recording every
+/// call that passed through it so a test can assert exactly what was
+/// charged or retried.
+#[derive(Default)]
+pub struct FaultInjector {
+    scheduled: Mutex<HashMap<String, VecDeque<Box<dyn Fn() -> ShardError + Send + Sync>>>>,
+    observed: Mutex<Vec<String>>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the next `guard(op, ..)` call for `op` fail once with the error
+    /// returned by `make_error`, then succeed thereafter (unless more
+    /// failures are scheduled on top).
+    pub fn fail_once(&self, op: &str, make_error: impl Fn() -> ShardError + Send + Sync + 'static) {
+        self.scheduled.lock().unwrap().entry(op.to_string()).or_default().push_back(Box::new(make_error));
+    }
+
+    /// Record that `op` was attempted, and either hand back the next
+    /// scheduled failure for it or pass `outcome` through unchanged.
+    pub fn guard<T>(&self, op: &str, outcome: Result<T, ShardError>) -> Result<T, ShardError> {
+        self.observed.lock().unwrap().push(op.to_string());
+
+        let mut scheduled = self.scheduled.lock().unwrap();
+        if let Some(queue) = scheduled.get_mut(op) {
+            if let Some(make_error) = queue.pop_front() {
+                return Err(make_error());
+            }
+        }
+        outcome
+    }
+
+    /// Every op name pushed through `guard`, in call order, so a test can
+    /// assert exactly which operations were charged and retried.
+    pub fn observed(&self) -> Vec<String> {
+        self.observed.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fails_once_then_succeeds() {
+        let injector = FaultInjector::new();
+        injector.fail_once("sync_shards", || ShardError::CircuitBreakerOpen);
+
+        let first = injector.guard("sync_shards", Ok::<_, ShardError>(()));
+        assert!(matches!(first, Err(ShardError::CircuitBreakerOpen)));
+
+        let second = injector.guard("sync_shards", Ok::<_, ShardError>(()));
+        assert!(second.is_ok());
+
+        assert_eq!(injector.observed(), vec!["sync_shards", "sync_shards"]);
+    }
+
+    #[test]
+    fn unscheduled_ops_pass_through_untouched() {
+        let injector = FaultInjector::new();
+        let result = injector.guard("consume_budget", Ok::<_, ShardError>(42));
+        assert_eq!(result.unwrap(), 42);
+    }
+}
@@ -0,0 +1,8 @@
+// src/network/mod.rs
+mod circuit_breaker;
+mod fault_injection;
+mod retry;
+
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+pub use fault_injection::FaultInjector;
+pub use retry::RetryPolicy;
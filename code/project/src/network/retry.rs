@@ -0,0 +1,66 @@
+// src/network/retry.rs
+use std::time::Duration;
+
+use crate::error::ShardError;
+
+/// Bounded exponential-backoff retry policy for sync operations that can
+/// fail transiently (circuit breaker open, transport timeout) as opposed to
+/// permanently (a malformed migration plan, an exhausted budget) — retrying
+/// a permanent failure just wastes the attempt budget, so callers should
+/// stop at the first one.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff to wait before retry number `attempt` (1-indexed), doubling
+    /// each attempt and capped at `max_backoff`.
+    pub fn backoff_for_attempt(&self, attempt: u32) -> Duration {
+        let multiplier = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.base_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+
+    /// Whether `error` is worth retrying. Circuit-breaker and timeout
+    /// failures are transport-level and transient; everything else (a bad
+    /// migration plan, an exhausted budget, a clock failure) is permanent
+    /// and retrying it would just burn the attempt budget for nothing.
+    pub fn is_transient(error: &ShardError) -> bool {
+        matches!(error, ShardError::CircuitBreakerOpen | ShardError::Timeout { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let policy = RetryPolicy { max_attempts: 10, base_backoff: Duration::from_millis(100), max_backoff: Duration::from_millis(500) };
+        assert_eq!(policy.backoff_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for_attempt(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for_attempt(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff_for_attempt(4), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn classifies_transient_vs_permanent() {
+        assert!(RetryPolicy::is_transient(&ShardError::CircuitBreakerOpen));
+        assert!(RetryPolicy::is_transient(&ShardError::Timeout { duration: Duration::from_secs(1), operation: "sync".into() }));
+        assert!(!RetryPolicy::is_transient(&ShardError::ChecksumMismatch {
+            context: "vector migrating into shard s1".into(),
+        }));
+    }
+}
@@ -0,0 +1,149 @@
+// src/sharding/hilbert.rs
+#[derive(Debug)]
+pub struct HilbertCurve {
+    dimensions: u32,
+    order: u32,
+}
+
+impl HilbertCurve {
+    pub fn new(dimensions: u32, order: u32) -> Self {
+        Self { dimensions, order }
+    }
+
+    pub fn compute_index(&self, point: &[u32]) -> u64 {
+        // Implementation of Hilbert curve index computation
+        // This is a simplified version; real implementation would be more complex
+        let mut index = 0u64;
+        for (i, &p) in point.iter().enumerate() {
+            index |= (p as u64) << (i * self.order as u64);
+        }
+        index
+    }
+
+    pub fn partition<T>(&self, data: &[(T, [u32; 2])]) -> Vec<Vec<T>>
+    where T: Clone {
+        let mut indexed: Vec<_> = data.iter()
+            .map(|(item, point)| (self.compute_index(point), item))
+            .collect();
+
+        indexed.sort_by_key(|(index, _)| *index);
+
+        let chunk_size = (data.len() + self.dimensions as usize - 1) / self.dimensions as usize;
+        indexed.chunks(chunk_size)
+            .map(|chunk| chunk.iter().map(|(_, item)| (*item).clone()).collect())
+            .collect()
+    }
+
+    /// Dimension-generic Hilbert index via Skilling's transpose algorithm:
+    /// undo the axes' inverse-gray encoding against each other, gray-encode
+    /// the result, then interleave each axis's bits into a single integer.
+    /// Unlike `compute_index_advanced`'s old quadrant-rotation method (only
+    /// ever correct for 2 axes), this works for any `point.len()`, which is
+    /// what lets a shard route on an embedding's full set of components
+    /// instead of just its first two.
+    ///
+    /// `point`'s components must already be quantized to `self.order` bits
+    /// each (see `Vector::to_point_nd`). The transpose interleaves bit
+    /// planes from most to least significant, cycling through every axis
+    /// within each plane, so when `point.len() * self.order` exceeds 64
+    /// bits the index only captures the most significant `64 /
+    /// point.len()` or so bit planes: every axis loses the same low-order
+    /// bits, and with more than 64 axes the axes past the 64th do not
+    /// contribute at all. Callers indexing high-dimensional embeddings
+    /// against a `u64` should keep `point.len() * self.order <= 64` (e.g. by
+    /// dimensionality-reducing before quantizing) if every bit needs to
+    /// contribute.
+    pub fn compute_index_advanced(&self, point: &[u32]) -> u64 {
+        let n = point.len();
+        let bits = self.order;
+        if n == 0 || bits == 0 {
+            return 0;
+        }
+
+        let mut x: Vec<u32> = point.to_vec();
+        let m = 1u32 << (bits - 1);
+
+        // Undo the inverse-gray transform, axis against axis.
+        let mut q = m;
+        while q > 1 {
+            let p = q - 1;
+            for i in 0..n {
+                if x[i] & q != 0 {
+                    x[0] ^= p;
+                } else {
+                    let t = (x[0] ^ x[i]) & p;
+                    x[0] ^= t;
+                    x[i] ^= t;
+                }
+            }
+            q >>= 1;
+        }
+
+        // Gray-encode.
+        for i in 1..n {
+            x[i] ^= x[i - 1];
+        }
+        let mut t = 0u32;
+        let mut q = m;
+        while q > 1 {
+            if x[n - 1] & q != 0 {
+                t ^= q - 1;
+            }
+            q >>= 1;
+        }
+        for value in x.iter_mut() {
+            *value ^= t;
+        }
+
+        Self::interleave_bits(&x, bits, n)
+    }
+
+    /// Transpose the per-axis bit columns in `x` (each `bits` wide) into a
+    /// single index, most-significant bit first, axis 0 first within each
+    /// bit position — the standard Hilbert-curve "transpose" layout. Stops
+    /// once 64 bits have been written, so `n * bits > 64` drops the least
+    /// significant interleaved bits rather than overflowing the shift.
+    fn interleave_bits(x: &[u32], bits: u32, n: usize) -> u64 {
+        let mut index = 0u64;
+        let mut written = 0u32;
+        'bits: for b in (0..bits).rev() {
+            for &axis in x.iter().take(n) {
+                if written >= 64 {
+                    break 'bits;
+                }
+                if (axis >> b) & 1 != 0 {
+                    index |= 1u64 << (63 - written);
+                }
+                written += 1;
+            }
+        }
+        index
+    }
+
+    pub fn find_nearest_neighbors<T>(&self, query_point: &[u32], data: &[(T, Vec<u32>)], k: usize) -> Vec<&T>
+    where T: Clone {
+        let query_index = self.compute_index_advanced(query_point);
+
+        // Calculate distances (in Hilbert space)
+        let mut distances: Vec<_> = data.iter()
+            .map(|(item, point)| {
+                let idx = self.compute_index_advanced(point);
+                let distance = if idx > query_index {
+                    idx - query_index
+                } else {
+                    query_index - idx
+                };
+                (distance, item)
+            })
+            .collect();
+
+        // Sort by distance
+        distances.sort_by_key(|(dist, _)| *dist);
+
+        // Return k nearest
+        distances.iter()
+            .take(k)
+            .map(|(_, item)| *item)
+            .collect()
+    }
+}
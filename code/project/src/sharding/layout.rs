@@ -0,0 +1,239 @@
+// src/sharding/layout.rs
+//! Versioned, stageable cluster layout. Tracks the committed node roles
+//! (zone, capacity, tags) plus a set of pending role changes so multiple
+//! operators can edit placement offline and reconcile deterministically,
+//! the way distributed stores reconcile layout versions.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeRole {
+    pub zone: String,
+    pub capacity: u64,
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+struct StagedChange {
+    role: NodeRole,
+    updated_at: u64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LayoutError {
+    #[error("expected version {expected}, current version is {actual}")]
+    VersionMismatch { expected: u64, actual: u64 },
+}
+
+/// Versioned, CRDT-mergeable record of which node holds which role.
+/// `staging` accumulates pending edits (last-writer-wins per node by
+/// `updated_at`) until `apply_staged_changes` commits them under a known
+/// version, preventing two operators from racing a write.
+#[derive(Clone, Debug, Default)]
+pub struct ClusterLayout {
+    pub version: u64,
+    committed: HashMap<String, NodeRole>,
+    staging: HashMap<String, StagedChange>,
+    pub staging_hash: u64,
+}
+
+impl ClusterLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn committed_roles(&self) -> &HashMap<String, NodeRole> {
+        &self.committed
+    }
+
+    /// Stage a role change for `node_id`, to be folded in by a later
+    /// `apply_staged_changes`. If a newer staged change for the same node
+    /// already exists, this is a no-op (last-writer-wins).
+    pub fn stage_change(&mut self, node_id: String, role: NodeRole, updated_at: u64) {
+        let should_replace = match self.staging.get(&node_id) {
+            Some(existing) => updated_at >= existing.updated_at,
+            None => true,
+        };
+        if should_replace {
+            self.staging.insert(node_id, StagedChange { role, updated_at });
+        }
+        self.recompute_staging_hash();
+    }
+
+    fn recompute_staging_hash(&mut self) {
+        let mut entries: Vec<_> = self.staging.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let mut hasher = DefaultHasher::new();
+        for (node_id, change) in entries {
+            node_id.hash(&mut hasher);
+            change.role.zone.hash(&mut hasher);
+            change.role.capacity.hash(&mut hasher);
+            change.role.tags.hash(&mut hasher);
+            change.updated_at.hash(&mut hasher);
+        }
+        self.staging_hash = hasher.finish();
+    }
+
+    /// CRDT merge against `other`. If `other` is strictly ahead, replace
+    /// wholesale. If versions tie, merge staging maps last-writer-wins per
+    /// node. If `other` is behind, this is a no-op. Returns whether `self`
+    /// changed.
+    pub fn merge(&mut self, other: &ClusterLayout) -> bool {
+        if other.version > self.version {
+            *self = other.clone();
+            return true;
+        }
+
+        if other.version < self.version {
+            return false;
+        }
+
+        let mut changed = false;
+        for (node_id, change) in &other.staging {
+            let should_adopt = match self.staging.get(node_id) {
+                Some(existing) => change.updated_at > existing.updated_at,
+                None => true,
+            };
+            if should_adopt {
+                self.staging.insert(node_id.clone(), change.clone());
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.recompute_staging_hash();
+        }
+        changed
+    }
+
+    /// Commit all staged changes into `committed`, bump `version`, and
+    /// clear staging. Refuses unless `expected_version` matches the current
+    /// version, so a racing writer can't silently clobber a concurrent
+    /// commit.
+    pub fn apply_staged_changes(&mut self, expected_version: u64) -> Result<(), LayoutError> {
+        if expected_version != self.version {
+            return Err(LayoutError::VersionMismatch { expected: expected_version, actual: self.version });
+        }
+
+        for (node_id, change) in self.staging.drain() {
+            self.committed.insert(node_id, change.role);
+        }
+        self.version += 1;
+        self.recompute_staging_hash();
+        Ok(())
+    }
+}
+
+/// Bounded history of recent `ClusterLayout` versions. Reads during a
+/// graceful drain resolve against the union of every version still in this
+/// history, so a partition that moved mid-read isn't silently dropped;
+/// once every node has acked past the version that introduced a change
+/// (see `AckTracker`), older entries are garbage-collected.
+pub struct LayoutHistory {
+    max_versions: usize,
+    versions: std::collections::VecDeque<ClusterLayout>,
+}
+
+impl LayoutHistory {
+    pub fn new(max_versions: usize) -> Self {
+        Self { max_versions: max_versions.max(1), versions: std::collections::VecDeque::new() }
+    }
+
+    pub fn push(&mut self, layout: ClusterLayout) {
+        self.versions.push_back(layout);
+        while self.versions.len() > self.max_versions {
+            self.versions.pop_front();
+        }
+    }
+
+    pub fn versions(&self) -> impl Iterator<Item = &ClusterLayout> {
+        self.versions.iter()
+    }
+
+    /// Drop every retained version strictly older than `min_acked_version`
+    /// — every node has confirmed it no longer needs them.
+    pub fn garbage_collect(&mut self, min_acked_version: u64) {
+        self.versions.retain(|layout| layout.version >= min_acked_version);
+    }
+}
+
+/// Tracks the highest layout version each node has fully applied, so the
+/// cluster can compute the minimum acked version and know when it's safe
+/// to garbage-collect an old layout and drop a drained shard.
+#[derive(Default)]
+pub struct AckTracker {
+    acked: HashMap<String, u64>,
+}
+
+impl AckTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `node_id` has fully applied up through `version`.
+    pub fn ack_version(&mut self, node_id: String, version: u64) {
+        self.acked
+            .entry(node_id)
+            .and_modify(|v| *v = (*v).max(version))
+            .or_insert(version);
+    }
+
+    /// The minimum version acked by every node tracked so far, or `None`
+    /// if no node has acked anything yet.
+    pub fn min_acked_version(&self) -> Option<u64> {
+        self.acked.values().copied().min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(zone: &str, capacity: u64) -> NodeRole {
+        NodeRole { zone: zone.into(), capacity, tags: vec![] }
+    }
+
+    #[test]
+    fn ahead_version_replaces_wholesale() {
+        let mut a = ClusterLayout::new();
+        let mut b = ClusterLayout::new();
+        b.stage_change("n1".into(), role("us-east", 10), 1);
+        b.apply_staged_changes(0).unwrap();
+
+        assert!(a.merge(&b));
+        assert_eq!(a.version, 1);
+        assert_eq!(a.committed_roles().get("n1").unwrap().zone, "us-east");
+    }
+
+    #[test]
+    fn equal_version_merges_staging_last_writer_wins() {
+        let mut a = ClusterLayout::new();
+        let mut b = ClusterLayout::new();
+        a.stage_change("n1".into(), role("us-east", 10), 1);
+        b.stage_change("n1".into(), role("us-west", 20), 2);
+
+        assert!(a.merge(&b));
+        a.apply_staged_changes(0).unwrap();
+        assert_eq!(a.committed_roles().get("n1").unwrap().zone, "us-west");
+    }
+
+    #[test]
+    fn behind_version_is_a_no_op() {
+        let mut a = ClusterLayout::new();
+        a.stage_change("n1".into(), role("us-east", 10), 1);
+        a.apply_staged_changes(0).unwrap();
+
+        let b = ClusterLayout::new();
+        assert!(!a.merge(&b));
+        assert_eq!(a.version, 1);
+    }
+
+    #[test]
+    fn apply_rejects_stale_expected_version() {
+        let mut layout = ClusterLayout::new();
+        layout.stage_change("n1".into(), role("us-east", 10), 1);
+        assert!(layout.apply_staged_changes(5).is_err());
+    }
+}
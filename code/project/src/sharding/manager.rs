@@ -1,19 +1,42 @@
 // src/sharding/manager.rs
 use crate::error::ShardError;
-use crate::network::CircuitBreaker;
-use crate::core::{Metrics, Vector, CentroidCRDT};
-use super::migration::{MigrationPlan, ShardStatus};
+use crate::network::{CircuitBreaker, RetryPolicy};
+use crate::core::{CooperativeBudget, Metrics, Vector, CentroidCRDT, CheckpointManager, KeyValueStore, KvOp};
+use crate::core::chunked_store::ChunkHash;
+use super::layout::{AckTracker, ClusterLayout, LayoutError, LayoutHistory, NodeRole};
+use super::migration::{checksum_vector, MigrationPlan, ShardStatus};
+use super::pacing::MigrationPacer;
+use super::placement::{Layout, NodeInfo, ShardPlacer, ShardReplicaRequest};
 use super::hilbert::HilbertCurve;
+use super::merkle_sync::{MerkleSync, DEFAULT_MERKLE_DEPTH};
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use hdk::prelude::*;
 
+const SHARD_STATUS_PREFIX: &str = "shard.";
+const SHARD_STATUS_PREFIX_END: &str = "shard/"; // '/' > '.' in byte order, bounds the prefix scan
+
+/// Number of past `ClusterLayout` versions kept around so reads in flight
+/// during a drain can still resolve against the version that held their
+/// partition before the acknowledgment watermark catches up.
+const LAYOUT_HISTORY_DEPTH: usize = 16;
+
 pub struct ShardManager {
     config: ShardConfig,
     circuit_breaker: CircuitBreaker,
     metrics: Arc<crate::core::Metrics>,
     hilbert_curve: HilbertCurve,
     shard_statuses: HashMap<String, ShardStatus>,
+    store: Arc<dyn KeyValueStore>,
+    checkpoints: CheckpointManager,
+    layout: ClusterLayout,
+    layout_history: LayoutHistory,
+    ack_tracker: AckTracker,
+    /// Layout version in effect when a shard started draining, keyed by
+    /// shard id. A draining shard is dropped once the cluster-wide minimum
+    /// acked version has passed this value.
+    draining_since: HashMap<String, u64>,
 }
 
 #[derive(Clone)]
@@ -25,17 +48,160 @@ pub struct ShardConfig {
     pub sync_timeout_ms: u64,
     pub merge_interval_ms: u64,
     pub merge_threshold: f32,
+    /// Work units `sync_shards` may spend walking stale migrations before it
+    /// must yield back to the tokio scheduler, preventing a large sync
+    /// batch from monopolizing the runtime.
+    pub sync_work_budget: u32,
+    /// Replicas each shard should have, placed across distinct zones when
+    /// possible by `ShardManager::compute_placement`.
+    pub replicas_per_shard: usize,
+    /// Physical nodes available to hold shard replicas, annotated with
+    /// their zone and storage capacity.
+    pub nodes: Vec<super::placement::NodeInfo>,
 }
 
 impl ShardManager {
+    /// Construct a `ShardManager` backed by an in-memory store only — shard
+    /// state does not survive a restart. Prefer `with_store` in production.
     pub fn new(config: ShardConfig, metrics: Arc<crate::core::Metrics>) -> Self {
+        Self::with_store(config, metrics, Arc::new(crate::core::EmbeddedOrderedStore::default()))
+    }
+
+    /// Construct a `ShardManager` backed by `store`, reloading any shard
+    /// statuses persisted under it so `sync_state` resumes from the last
+    /// known layout instead of starting from an empty DHT replay.
+    pub fn with_store(config: ShardConfig, metrics: Arc<crate::core::Metrics>, store: Arc<dyn KeyValueStore>) -> Self {
+        let mut shard_statuses = HashMap::new();
+        if let Ok(rows) = store.range_scan(SHARD_STATUS_PREFIX, SHARD_STATUS_PREFIX_END) {
+            for (_, bytes) in rows {
+                if let Ok(status) = serde_json::from_slice::<ShardStatus>(&bytes) {
+                    shard_statuses.insert(status.id.clone(), status);
+                }
+            }
+        }
+
         Self {
             hilbert_curve: HilbertCurve::new(config.dimensions, config.hilbert_order),
             config,
             circuit_breaker: CircuitBreaker::new(),
             metrics,
-            shard_statuses: HashMap::new(),
+            shard_statuses,
+            checkpoints: CheckpointManager::new(Arc::clone(&store)),
+            store,
+            layout: ClusterLayout::new(),
+            layout_history: LayoutHistory::new(LAYOUT_HISTORY_DEPTH),
+            ack_tracker: AckTracker::new(),
+            draining_since: HashMap::new(),
+        }
+    }
+
+    /// Record that `node_id` has fully applied every layout change up
+    /// through `version`, then recompute the cluster-wide minimum acked
+    /// version and reconcile draining shards / layout history against it.
+    pub fn update_trackers(&mut self, node_id: String, version: u64) {
+        self.ack_tracker.ack_version(node_id, version);
+        self.reconcile_draining();
+    }
+
+    /// Garbage-collect layout history entries and drop drained shards once
+    /// every tracked node has acked past the version that introduced the
+    /// change. Reads against a still-retained old version remain valid
+    /// until then.
+    fn reconcile_draining(&mut self) {
+        let Some(min_acked) = self.ack_tracker.min_acked_version() else { return };
+        self.layout_history.garbage_collect(min_acked);
+
+        let drained: Vec<String> = self
+            .draining_since
+            .iter()
+            .filter(|(_, &since)| since <= min_acked)
+            .map(|(shard_id, _)| shard_id.clone())
+            .collect();
+
+        for shard_id in drained {
+            self.draining_since.remove(&shard_id);
+            self.shard_statuses.remove(&shard_id);
+            let _ = self.store.transaction(vec![KvOp::Delete(format!("{SHARD_STATUS_PREFIX}{shard_id}"))]);
+        }
+    }
+
+    /// Mark `shard_id` as draining against the current layout version,
+    /// snapshotting the layout into history so in-flight reads can still
+    /// resolve against it until `reconcile_draining` confirms every node
+    /// has synced past this point.
+    fn begin_drain(&mut self, shard_id: &str) {
+        self.layout_history.push(self.layout.clone());
+        self.draining_since.insert(shard_id.to_string(), self.layout.version);
+        if let Some(status) = self.shard_statuses.get_mut(shard_id) {
+            status.set_draining(true);
+        }
+    }
+
+    /// Stage a role change (zone/capacity/tags) for `node_id` against the
+    /// current cluster layout. Takes effect once `apply_layout_changes`
+    /// commits it.
+    pub fn stage_layout_change(&mut self, node_id: String, role: NodeRole, updated_at: u64) {
+        self.layout.stage_change(node_id, role, updated_at);
+    }
+
+    pub fn layout_version(&self) -> u64 {
+        self.layout.version
+    }
+
+    /// Merge a layout received from another agent editing placement
+    /// offline, then reconcile: see `ClusterLayout::merge` for the CRDT
+    /// semantics (newer version wins wholesale, tied versions merge
+    /// staging last-writer-wins, older versions are a no-op).
+    pub fn merge_layout(&mut self, other: &ClusterLayout) -> bool {
+        self.layout.merge(other)
+    }
+
+    /// Merge a whole round of gossiped peer layouts in one pass, as part of
+    /// a periodic sync round alongside `sync_shards`/`sync_state_checkpointed`
+    /// — nodes converge on the newest layout version and reconcile any
+    /// concurrent staged edits without a round having to wait for a single
+    /// peer at a time. Snapshots the merged layout into `layout_history`
+    /// once if any peer changed it, then returns whether anything changed.
+    pub fn gossip_layouts(&mut self, peer_layouts: &[ClusterLayout]) -> bool {
+        let mut changed = false;
+        for peer_layout in peer_layouts {
+            changed |= self.layout.merge(peer_layout);
         }
+        if changed {
+            self.layout_history.push(self.layout.clone());
+        }
+        changed
+    }
+
+    /// Commit staged layout changes (refusing unless `expected_version`
+    /// matches, to prevent racing writers), rebuild `self.config.nodes`
+    /// from the newly-committed roles, and recompute shard placement
+    /// against the updated node set.
+    pub async fn apply_layout_changes(&mut self, expected_version: u64) -> Result<HashMap<String, Vec<String>>, ShardError> {
+        self.layout.apply_staged_changes(expected_version).map_err(|e: LayoutError| ShardError::MigrationFailed {
+            context: e.to_string(),
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())),
+        })?;
+
+        self.config.nodes = self
+            .layout
+            .committed_roles()
+            .iter()
+            .map(|(node_id, role)| NodeInfo { id: node_id.clone(), capacity: role.capacity, zone: role.zone.clone(), tags: role.tags.clone() })
+            .collect();
+
+        self.compute_placement().await
+    }
+
+    /// Persist the current state of shard `id`, if tracked, so a restart
+    /// reloads it via `with_store` instead of replaying the whole DHT.
+    fn persist_shard_status(&self, id: &str) -> Result<(), ShardError> {
+        let Some(status) = self.shard_statuses.get(id) else { return Ok(()) };
+        let bytes = serde_json::to_vec(status)
+            .map_err(|e| ShardError::MigrationFailed { context: format!("serialize shard status for {id}"), source: Box::new(e) })?;
+        self.store
+            .transaction(vec![KvOp::Put(format!("{SHARD_STATUS_PREFIX}{id}"), bytes)])
+            .map_err(|e| ShardError::MigrationFailed { context: format!("persist shard status for {id}"), source: Box::new(e) })
     }
 
     pub async fn handle_shard_split(&mut self, shard_id: &str) -> Result<(), ShardError> {
@@ -105,31 +271,50 @@ impl ShardManager {
     async fn execute_migration(&mut self, plan: MigrationPlan) -> Result<(), ShardError> {
         let mut current_plan = plan.clone();
         
-        // Record the migration in source shard status
+        // Record the migration in source shard status and mark it draining
+        // so it's kept around (in a bounded layout history) rather than
+        // dropped until every node has acked past this layout version.
         if let Some(source_status) = self.shard_statuses.get_mut(&plan.source_shard) {
             source_status.add_migration(plan.id.clone(), plan.target_shard.clone());
         }
-        
+        self.begin_drain(&plan.source_shard);
+
         // Create target shard status
         let target_status = ShardStatus::new(plan.target_shard.clone(), plan.centroids.clone());
         self.shard_statuses.insert(plan.target_shard.clone(), target_status);
-        
-        // Start migration process
-        current_plan.update_progress(0);
-        let total = current_plan.vectors.len();
-        
+        self.persist_shard_status(&plan.target_shard)?;
+
         let mut success_count = 0;
         let mut failure_count = 0;
-        
-        // Migrate vectors (in a real implementation this would be done in batches)
-        for (i, vector) in current_plan.vectors.iter().enumerate() {
-            match self.migrate_vector(vector, &plan.target_shard).await {
-                Ok(_) => success_count += 1,
-                Err(_) => failure_count += 1,
-            }
-            
-            if (i + 1) % 10 == 0 {
-                current_plan.update_progress(i + 1);
+        let mut checksum_failure_count = 0;
+
+        // Adapt the batch size to how quickly the target shard acknowledges
+        // each batch, instead of always migrating in fixed groups of 10 and
+        // risking backpressure against a slow target.
+        let mut pacer = MigrationPacer::new(1, 50, 10);
+
+        // Resume from whatever index ranges the target shard hasn't
+        // acknowledged yet, rather than re-sending the whole plan.
+        for range in current_plan.pending_ranges() {
+            let mut batch_start = range.start;
+            while batch_start < range.end {
+                let batch_end = (batch_start + pacer.batch_size()).min(range.end);
+                let sent_at_ms = sys_time().expect("Could not get system time");
+
+                for i in batch_start..batch_end {
+                    let vector = &current_plan.vectors[i];
+                    let expected_checksum = current_plan.checksums[i];
+                    match self.migrate_vector(vector, &plan.target_shard, expected_checksum).await {
+                        Ok(_) => success_count += 1,
+                        Err(ShardError::ChecksumMismatch { .. }) => checksum_failure_count += 1,
+                        Err(_) => failure_count += 1,
+                    }
+                }
+
+                let acked_at_ms = sys_time().expect("Could not get system time");
+                pacer.on_batch_acked(sent_at_ms, acked_at_ms);
+                current_plan.update_progress(batch_start..batch_end);
+                batch_start = batch_end;
             }
         }
         
@@ -137,15 +322,20 @@ impl ShardManager {
         if let Some(source_status) = self.shard_statuses.get_mut(&plan.source_shard) {
             source_status.update_vector_count(source_status.vector_count - success_count);
             source_status.remove_migration(&plan.id);
+            source_status.set_capacity(self.config.max_shard_size - source_status.vector_count, self.config.max_shard_size);
         }
-        
+
         if let Some(target_status) = self.shard_statuses.get_mut(&plan.target_shard) {
             target_status.update_vector_count(success_count);
+            target_status.set_capacity(self.config.max_shard_size - success_count, self.config.max_shard_size);
         }
-        
+
         // Complete the migration plan
-        current_plan.complete(success_count, failure_count);
-        
+        current_plan.complete(success_count, failure_count, checksum_failure_count);
+
+        self.persist_shard_status(&plan.source_shard)?;
+        self.persist_shard_status(&plan.target_shard)?;
+
         Ok(())
     }
     
@@ -159,21 +349,60 @@ impl ShardManager {
         ])
     }
     
+    /// Build this node's Merkle anti-entropy tree over `shard_id`'s
+    /// vectors, keyed by each vector's `HilbertCurve::compute_index_advanced`
+    /// index (over every component, not just the first two — see
+    /// `Vector::to_point_nd`) and content-hashed via `checksum_vector`. Two
+    /// nodes' trees compare equal iff their vectors agree at every
+    /// Hilbert-index range, so `diff_shard_against_peer` only needs to walk
+    /// the subtrees whose hashes actually differ.
+    async fn build_merkle_sync(&self, shard_id: &str) -> Result<MerkleSync, ShardError> {
+        let vectors = self.fetch_vectors_for_shard(shard_id).await?;
+        let entries: Vec<(u64, ChunkHash)> = vectors
+            .iter()
+            .map(|v| {
+                let point = v.to_point_nd(self.config.hilbert_order);
+                let key = self.hilbert_curve.compute_index_advanced(&point);
+                (key, checksum_vector(v))
+            })
+            .collect();
+        Ok(MerkleSync::build(&entries, DEFAULT_MERKLE_DEPTH))
+    }
+
+    /// Hilbert-index ranges where `shard_id`'s local vectors diverge from
+    /// `peer_tree` (a `MerkleSync` the peer built the same way on its
+    /// side). A sync round only needs to fetch and compare vectors inside
+    /// these ranges instead of the whole shard.
+    pub async fn diff_shard_against_peer(&self, shard_id: &str, peer_tree: &MerkleSync) -> Result<Vec<RangeInclusive<u64>>, ShardError> {
+        let local_tree = self.build_merkle_sync(shard_id).await?;
+        Ok(local_tree.diff(peer_tree))
+    }
+
     async fn generate_centroids(&self, vectors: &[Vector]) -> Result<Vec<CentroidCRDT>, ShardError> {
         if vectors.is_empty() {
             return Ok(vec![]);
         }
         
         // Simple centroid generation - just use the first vector as initial centroid
-        let initial_centroid = CentroidCRDT::new(vectors[0].data.clone());
-        
+        let initial_centroid = CentroidCRDT::new(vectors[0].data.clone())?;
+
         Ok(vec![initial_centroid])
     }
     
-    async fn migrate_vector(&self, vector: &Vector, target_shard: &str) -> Result<(), ShardError> {
+    /// Transfer `vector` into `target_shard`. Recomputes its checksum on
+    /// arrival and rejects it with `ShardError::ChecksumMismatch` if it
+    /// doesn't match `expected_checksum`, so `execute_migration` can count
+    /// corruption separately from transport failure.
+    async fn migrate_vector(&self, vector: &Vector, target_shard: &str, expected_checksum: ChunkHash) -> Result<(), ShardError> {
+        if checksum_vector(vector) != expected_checksum {
+            return Err(ShardError::ChecksumMismatch {
+                context: format!("vector migrating into shard {target_shard}"),
+            });
+        }
+
         // In a real implementation, this would update the vector's metadata and commit to the DHT
         // For now, just simulate the operation
-        
+
         Ok(())
     }
     
@@ -184,30 +413,148 @@ impl ShardManager {
             return Err(ShardError::CircuitBreakerOpen);
         }
         
-        // Check for stale migrations
+        // Check for stale migrations. This walks every active migration
+        // across every shard, which can be large, so it spends a bounded
+        // cooperative work budget and yields back to the scheduler rather
+        // than monopolizing the runtime for the whole scan.
+        let mut budget = CooperativeBudget::new(self.config.sync_work_budget);
         for (shard_id, status) in self.shard_statuses.iter() {
             for (migration_id, _) in status.active_migrations.iter() {
                 // In a real implementation, we would query the migration plan from the DHT
                 // and check if it's stale
-                
+
                 // For now, just simulate the check
                 let is_stale = true; // Placeholder
-                
+
                 if is_stale {
                     // Handle stale migration
                     eprintln!("Found stale migration {} for shard {}", migration_id, shard_id);
                     // Recovery logic would go here
                 }
+
+                if budget.charge() {
+                    tokio::task::yield_now().await;
+                    budget.refill();
+                }
             }
         }
         
+        // Merkle anti-entropy: once a peer round delivers the other side's
+        // tree, `diff_shard_against_peer` turns what would otherwise be a
+        // full-shard resync below into a diff over just the Hilbert-index
+        // ranges that actually came back differing. There's no real peer
+        // transport wired up yet (like the rest of this sync round), so
+        // there's nothing to diff against here; building a tree with
+        // nothing to compare it to would just be wasted work every round.
+
         // Sync centroids
         self.sync_centroids().await?;
-        
+
         self.metrics.end_operation("sync_shards");
         Ok(())
     }
     
+    /// Run `sync_shards` under `retry_policy`, retrying only transient
+    /// failures with exponential backoff, and advance each shard's
+    /// checkpoint only once the round fully commits. The checkpoint is
+    /// readable through `last_checkpoint`; `sync_shards` itself does not
+    /// read it, so a round after a crash or a failed round runs in full
+    /// rather than starting from the last checkpoint.
+    pub async fn sync_state_checkpointed(&mut self, retry_policy: &RetryPolicy) -> Result<(), ShardError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.sync_shards().await {
+                Ok(()) => {
+                    for status in self.shard_statuses.values() {
+                        if let Some(centroid) = status.centroids.first() {
+                            let _ = self.checkpoints.advance(&status.id, &centroid.version_vector);
+                        }
+                    }
+                    return Ok(());
+                }
+                Err(err) if RetryPolicy::is_transient(&err) && attempt < retry_policy.max_attempts => {
+                    tokio::time::sleep(retry_policy.backoff_for_attempt(attempt)).await;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Last checkpointed `VersionVector` for `shard_id`, used to resume a
+    /// sync round after a crash instead of replaying from scratch.
+    pub fn last_checkpoint(&self, shard_id: &str) -> Option<crate::core::VersionVector> {
+        self.checkpoints.load(shard_id).ok().flatten()
+    }
+
+    /// Whether `shard_id` is currently draining (migrated away from, but
+    /// kept until every node has acked past the version that started it).
+    pub fn is_draining(&self, shard_id: &str) -> bool {
+        self.draining_since.contains_key(shard_id)
+    }
+
+    /// Layout versions still retained in history, oldest first — the set a
+    /// read in flight during a drain may need to resolve against.
+    pub fn retained_layout_versions(&self) -> Vec<u64> {
+        self.layout_history.versions().map(|layout| layout.version).collect()
+    }
+
+    /// Recompute zone-aware, capacity-weighted replica placement for every
+    /// tracked shard against `self.config.nodes`, using each shard's current
+    /// `replica_nodes` as the previous assignment so the result perturbs
+    /// the existing layout as little as possible. Updates and persists
+    /// each shard's `replica_nodes` to the new assignment.
+    pub async fn compute_placement(&mut self) -> Result<HashMap<String, Vec<String>>, ShardError> {
+        let (requests, previous) = self.placement_inputs(self.config.replicas_per_shard);
+        let assignment = ShardPlacer::place(&requests, &self.config.nodes, &previous);
+
+        for (shard_id, nodes) in &assignment {
+            if let Some(status) = self.shard_statuses.get_mut(shard_id) {
+                status.update_replica_nodes(nodes.clone());
+            }
+        }
+        for shard_id in assignment.keys() {
+            self.persist_shard_status(shard_id)?;
+        }
+
+        Ok(assignment)
+    }
+
+    /// Preview zone-aware, capacity-weighted placement against a candidate
+    /// node set and replication factor, without touching `self.config` or
+    /// persisting anything — lets an operator compare a few `Layout`s (e.g.
+    /// "what if we add a zone" or "what if we raise replicas_per_shard")
+    /// before committing one via `stage_layout_change`/`apply_layout_changes`.
+    /// Tracked shards and their current `replica_nodes` still seed the
+    /// previous-assignment bias, same as `compute_placement`.
+    pub fn compute_layout(&self, nodes: &[NodeInfo], replication_factor: usize) -> Layout {
+        let (requests, previous) = self.placement_inputs(replication_factor);
+        let assignment = ShardPlacer::place(&requests, nodes, &previous);
+        Layout { assignment, replication_factor }
+    }
+
+    /// Shared `ShardPlacer::place` inputs for every tracked shard: one
+    /// `ShardReplicaRequest` per shard at `replicas` replicas, and each
+    /// shard's current `replica_nodes` as the previous-assignment bias.
+    /// Used by both `compute_placement` (committed, persisting) and
+    /// `compute_layout` (preview, read-only) so they can't silently diverge.
+    fn placement_inputs(&self, replicas: usize) -> (Vec<ShardReplicaRequest>, HashMap<String, Vec<String>>) {
+        let requests = self
+            .shard_statuses
+            .keys()
+            .map(|id| ShardReplicaRequest { shard_id: id.clone(), replicas })
+            .collect();
+
+        let previous = self
+            .shard_statuses
+            .iter()
+            .map(|(id, status)| (id.clone(), status.replica_nodes.clone()))
+            .collect();
+
+        (requests, previous)
+    }
+
     async fn sync_centroids(&self) -> Result<(), ShardError> {
         // In a real implementation, this would fetch and merge centroids from other nodes
         // For now, just simulate the operation
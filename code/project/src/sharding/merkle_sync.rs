@@ -0,0 +1,162 @@
+// src/sharding/merkle_sync.rs
+//! Merkle-tree anti-entropy: lets two peers holding the same key space (a
+//! shard's vectors/centroids, keyed by their Hilbert index) find exactly
+//! the ranges that differ without exchanging full state.
+//!
+//! The key space `0..=u64::MAX` is partitioned into a fixed number of equal
+//! ranges (`2^depth` leaves), independent of how many entries either peer
+//! actually holds, so two peers can always compare trees leaf-for-leaf even
+//! if their entry counts differ. Each leaf hashes the sorted `(key, digest)`
+//! pairs that fall in its range; each internal node hashes its two
+//! children. Comparing root hashes first and only recursing into subtrees
+//! whose hashes differ turns a full-state diff into a logarithmic walk
+//! when the peers are mostly in sync.
+
+use crate::core::chunked_store::ChunkHash;
+use std::ops::RangeInclusive;
+
+/// Tree depth used when no caller-specified depth is given: `2^12` = 4096
+/// leaf ranges, fine-grained enough that a shard's typical working set
+/// diffs down to a handful of small ranges rather than a few huge ones.
+pub const DEFAULT_MERKLE_DEPTH: u32 = 12;
+
+/// A balanced Merkle tree over a fixed partition of the `u64` key space,
+/// stored as a heap-indexed array (`nodes[1]` is the root; node `i`'s
+/// children are `2*i` and `2*i+1`; leaves occupy `nodes[leaf_count..]`).
+#[derive(Clone, Debug)]
+pub struct MerkleSync {
+    depth: u32,
+    leaf_count: usize,
+    nodes: Vec<ChunkHash>,
+}
+
+impl MerkleSync {
+    /// Build a tree with `2^depth` leaves from `entries` (each a Hilbert
+    /// index paired with a content digest, e.g. `checksum_vector`'s
+    /// output). `depth` must match the peer's tree for `diff` to be
+    /// meaningful; callers on both sides of a sync should agree on it up
+    /// front (see `DEFAULT_MERKLE_DEPTH`).
+    pub fn build(entries: &[(u64, ChunkHash)], depth: u32) -> Self {
+        let leaf_count = 1usize << depth;
+        let shift = 64 - depth;
+
+        let mut buckets: Vec<Vec<(u64, ChunkHash)>> = vec![Vec::new(); leaf_count];
+        for &(key, digest) in entries {
+            let leaf_idx = if depth == 0 { 0 } else { (key >> shift) as usize };
+            let leaf_idx = leaf_idx.min(leaf_count - 1);
+            buckets[leaf_idx].push((key, digest));
+        }
+
+        let empty_leaf = ChunkHash::of(&[]);
+        let mut nodes = vec![empty_leaf; 2 * leaf_count];
+        for (leaf_idx, bucket) in buckets.iter_mut().enumerate() {
+            if bucket.is_empty() {
+                continue;
+            }
+            // Break ties on equal keys by digest bytes too, so two peers
+            // whose fetch order differs still hash the same bucket to the
+            // same bytes whenever several entries share a Hilbert index.
+            bucket.sort_by(|(ka, da), (kb, db)| ka.cmp(kb).then_with(|| da.as_bytes().cmp(db.as_bytes())));
+            let mut bytes = Vec::with_capacity(bucket.len() * (8 + 32));
+            for (key, digest) in bucket.iter() {
+                bytes.extend_from_slice(&key.to_le_bytes());
+                bytes.extend_from_slice(digest.as_bytes());
+            }
+            nodes[leaf_count + leaf_idx] = ChunkHash::of(&bytes);
+        }
+
+        for i in (1..leaf_count).rev() {
+            let mut bytes = Vec::with_capacity(64);
+            bytes.extend_from_slice(nodes[2 * i].as_bytes());
+            bytes.extend_from_slice(nodes[2 * i + 1].as_bytes());
+            nodes[i] = ChunkHash::of(&bytes);
+        }
+
+        Self { depth, leaf_count, nodes }
+    }
+
+    pub fn root_hash(&self) -> ChunkHash {
+        self.nodes[1]
+    }
+
+    /// The key ranges whose content differs from `other`'s, found by
+    /// recursing only into subtrees whose hash doesn't match — a peer pair
+    /// with identical state returns empty after a single root comparison.
+    /// Trees built at different `depth`s can't be compared leaf-for-leaf,
+    /// so that case conservatively reports the whole key space as
+    /// differing rather than guessing an alignment.
+    pub fn diff(&self, other: &MerkleSync) -> Vec<RangeInclusive<u64>> {
+        if self.depth != other.depth {
+            return vec![0..=u64::MAX];
+        }
+
+        let mut out = Vec::new();
+        self.diff_subtree(other, 1, &mut out);
+        out
+    }
+
+    fn diff_subtree(&self, other: &MerkleSync, node: usize, out: &mut Vec<RangeInclusive<u64>>) {
+        if self.nodes[node] == other.nodes[node] {
+            return;
+        }
+        if node >= self.leaf_count {
+            out.push(self.leaf_key_range(node - self.leaf_count));
+            return;
+        }
+        self.diff_subtree(other, 2 * node, out);
+        self.diff_subtree(other, 2 * node + 1, out);
+    }
+
+    /// `leaf_idx`'s key range, inclusive of both ends — computed in `u128`
+    /// so the last leaf's end (`u64::MAX`) doesn't require a special case
+    /// that a half-open `Range<u64>` would (and would then silently
+    /// exclude the single key `u64::MAX` from ever matching).
+    fn leaf_key_range(&self, leaf_idx: usize) -> RangeInclusive<u64> {
+        let shift = 64 - self.depth;
+        let width: u128 = 1u128 << shift;
+        let start = (leaf_idx as u128) * width;
+        let end = start + width - 1;
+        (start as u64)..=(end as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(byte: u8) -> ChunkHash {
+        ChunkHash::of(&[byte])
+    }
+
+    #[test]
+    fn identical_trees_have_no_diff() {
+        let entries = vec![(10u64, digest(1)), (1u64 << 60, digest(2))];
+        let a = MerkleSync::build(&entries, 4);
+        let b = MerkleSync::build(&entries, 4);
+        assert_eq!(a.root_hash(), b.root_hash());
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn diverging_entry_narrows_to_its_leaf_range() {
+        let mut entries_a = vec![(10u64, digest(1)), (1u64 << 60, digest(2))];
+        let entries_b = entries_a.clone();
+        entries_a[0] = (10u64, digest(9));
+
+        let a = MerkleSync::build(&entries_a, 4);
+        let b = MerkleSync::build(&entries_b, 4);
+
+        assert_ne!(a.root_hash(), b.root_hash());
+        let diff = a.diff(&b);
+        assert_eq!(diff.len(), 1, "only the one diverging leaf's range should be reported");
+        assert!(diff[0].contains(&10u64));
+        assert!(!diff[0].contains(&(1u64 << 60)), "the untouched entry's leaf should not show up");
+    }
+
+    #[test]
+    fn empty_trees_at_same_depth_match() {
+        let a = MerkleSync::build(&[], 6);
+        let b = MerkleSync::build(&[], 6);
+        assert!(a.diff(&b).is_empty());
+    }
+}
@@ -1,9 +1,12 @@
 // src/sharding/migration.rs
 use crate::core::Vector;
 use crate::core::CentroidCRDT;
+use crate::core::chunked_store::ChunkHash;
+use super::range_set::RangeSet;
 use serde::{Serialize, Deserialize};
 use hdk::prelude::*;
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MigrationPlan {
@@ -12,7 +15,16 @@ pub struct MigrationPlan {
     pub target_shard: String,
     pub vectors: Vec<Vector>,
     pub centroids: Vec<CentroidCRDT>,
+    /// Per-vector content checksum, computed when the plan is built and
+    /// indexed the same as `vectors`. The receiving side recomputes each
+    /// one in `ShardManager::migrate_vector` to catch corruption in transit
+    /// rather than silently migrating a damaged vector.
+    pub checksums: Vec<ChunkHash>,
     pub status: MigrationStatus,
+    /// Vector index ranges acknowledged by the target shard so far. Persisted
+    /// alongside the plan so a restarted migration can resume from
+    /// `pending_ranges()` instead of re-sending every vector.
+    pub acknowledged: RangeSet,
     pub created_at: u64,
     pub updated_at: u64,
 }
@@ -21,37 +33,65 @@ pub struct MigrationPlan {
 pub enum MigrationStatus {
     Pending,
     InProgress { completed: usize, total: usize },
-    Completed { success_count: usize, failure_count: usize },
+    Completed { success_count: usize, failure_count: usize, checksum_failure_count: usize },
     Failed { error: String },
 }
 
+/// Canonical content checksum for a migrated vector's data, recomputed on
+/// the receiving side to distinguish corruption from transport failure.
+pub fn checksum_vector(vector: &Vector) -> ChunkHash {
+    let mut bytes = Vec::with_capacity(vector.data.len() * 4);
+    for value in &vector.data {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    ChunkHash::of(&bytes)
+}
+
 impl MigrationPlan {
     pub fn new(source_shard: String, target_shard: String, vectors: Vec<Vector>, centroids: Vec<CentroidCRDT>) -> Self {
         let now = sys_time().expect("Could not get system time");
+        let checksums = vectors.iter().map(checksum_vector).collect();
         Self {
             id: nanoid::nanoid!(),
             source_shard,
             target_shard,
             vectors,
             centroids,
+            checksums,
             status: MigrationStatus::Pending,
+            acknowledged: RangeSet::new(),
             created_at: now,
             updated_at: now,
         }
     }
 
-    pub fn update_progress(&mut self, completed: usize) {
-        self.status = MigrationStatus::InProgress {
-            completed,
-            total: self.vectors.len(),
+    /// Fold a confirmed range of acknowledged vector indices into the
+    /// migration's progress, marking the plan `Completed` once the
+    /// acknowledged set covers the full `0..vectors.len()` range.
+    pub fn update_progress(&mut self, confirmed: Range<usize>) {
+        self.acknowledged.add(confirmed);
+        let total = self.vectors.len();
+        let completed = self.acknowledged.len();
+
+        self.status = if self.pending_ranges().is_empty() {
+            MigrationStatus::Completed { success_count: completed, failure_count: 0, checksum_failure_count: 0 }
+        } else {
+            MigrationStatus::InProgress { completed, total }
         };
         self.updated_at = sys_time().expect("Could not get system time");
     }
 
-    pub fn complete(&mut self, success_count: usize, failure_count: usize) {
+    /// The vector index ranges not yet acknowledged by the target shard —
+    /// what a resumed migration still needs to (re-)transmit.
+    pub fn pending_ranges(&self) -> Vec<Range<usize>> {
+        self.acknowledged.complement(0..self.vectors.len())
+    }
+
+    pub fn complete(&mut self, success_count: usize, failure_count: usize, checksum_failure_count: usize) {
         self.status = MigrationStatus::Completed {
             success_count,
             failure_count,
+            checksum_failure_count,
         };
         self.updated_at = sys_time().expect("Could not get system time");
     }
@@ -87,6 +127,17 @@ pub struct ShardStatus {
     pub centroids: Vec<CentroidCRDT>,
     pub last_update: u64,
     pub active_migrations: HashMap<String, String>, // migration_id -> target_shard
+    /// Node ids currently holding a replica of this shard, as last computed
+    /// by `ShardPlacer::place`. Feeds back in as the previous assignment so
+    /// the next placement run minimizes data movement.
+    pub replica_nodes: Vec<String>,
+    /// True while this shard is being migrated away from. A draining shard
+    /// is kept (not dropped) until the cluster-wide minimum acked layout
+    /// version has passed the version that introduced the move, so reads
+    /// in flight can still resolve against it.
+    pub draining: bool,
+    pub available_capacity: usize,
+    pub total_capacity: usize,
 }
 
 impl ShardStatus {
@@ -97,9 +148,34 @@ impl ShardStatus {
             centroids,
             last_update: sys_time().expect("Could not get system time"),
             active_migrations: HashMap::new(),
+            replica_nodes: Vec::new(),
+            draining: false,
+            available_capacity: 0,
+            total_capacity: 0,
         }
     }
-    
+
+    pub fn update_replica_nodes(&mut self, replica_nodes: Vec<String>) {
+        self.replica_nodes = replica_nodes;
+        self.last_update = sys_time().expect("Could not get system time");
+    }
+
+    /// Mark this shard as draining (or clear the flag once the cluster has
+    /// fully acked past the layout version that drained it).
+    pub fn set_draining(&mut self, draining: bool) {
+        self.draining = draining;
+        self.last_update = sys_time().expect("Could not get system time");
+    }
+
+    /// Record the operator-visible capacity figures for this shard so
+    /// progress (e.g. how much of a draining shard's capacity has been
+    /// reclaimed) can be monitored externally.
+    pub fn set_capacity(&mut self, available: usize, total: usize) {
+        self.available_capacity = available;
+        self.total_capacity = total;
+        self.last_update = sys_time().expect("Could not get system time");
+    }
+
     pub fn add_migration(&mut self, migration_id: String, target_shard: String) {
         self.active_migrations.insert(migration_id, target_shard);
         self.last_update = sys_time().expect("Could not get system time");
@@ -0,0 +1,18 @@
+// src/sharding/mod.rs
+mod hilbert;
+mod layout;
+pub mod manager;
+mod merkle_sync;
+mod migration;
+mod pacing;
+mod placement;
+mod range_set;
+
+pub use hilbert::HilbertCurve;
+pub use layout::{AckTracker, ClusterLayout, LayoutError, LayoutHistory, NodeRole};
+pub use manager::ShardManager;
+pub use merkle_sync::{MerkleSync, DEFAULT_MERKLE_DEPTH};
+pub use migration::{checksum_vector, MigrationPlan, MigrationStatus, ShardStatus};
+pub use pacing::MigrationPacer;
+pub use placement::{Layout, NodeInfo, ShardPlacer, ShardReplicaRequest};
+pub use range_set::RangeSet;
@@ -0,0 +1,386 @@
+// src/sharding/placement.rs
+//! Zone-aware, capacity-weighted shard placement.
+//!
+//! Given a set of shards (each needing `replicas` copies) and a set of
+//! nodes annotated with `capacity` and `zone`, computes shard -> Vec<node>
+//! assignments that place each shard's replicas in distinct zones when
+//! possible, fill nodes proportionally to capacity, and minimize data
+//! movement relative to the previous assignment.
+//!
+//! Implemented as a two-phase flow computation: phase 1 is a plain
+//! max-flow feasibility check (Edmonds-Karp BFS augmenting) over a graph
+//! that forbids a shard from using the same zone twice, relaxing that
+//! constraint and retrying if full flow isn't achievable; phase 2 reruns
+//! the same flow value with per-edge cost (0 to keep a shard on its
+//! current node, 1 to move it) via successive shortest augmenting paths
+//! (Bellman-Ford/SPFA, which tolerates the negative reduced-cost residual
+//! edges augmenting paths leave behind), so among all maximum assignments
+//! the one found perturbs the existing layout as little as possible.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Clone, Debug)]
+pub struct NodeInfo {
+    pub id: String,
+    pub capacity: u64,
+    pub zone: String,
+    /// Operator-assigned labels (e.g. hardware class, rack), carried through
+    /// for callers that want to inspect a computed `Layout` without looking
+    /// the node back up elsewhere. `ShardPlacer` doesn't constrain on these.
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ShardReplicaRequest {
+    pub shard_id: String,
+    pub replicas: usize,
+}
+
+/// Result of a one-shot placement computation: the shard -> replica-node-id
+/// assignment, alongside the replication factor it was computed for so a
+/// caller comparing several candidate `Layout`s doesn't have to thread that
+/// separately.
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    pub assignment: HashMap<String, Vec<String>>,
+    pub replication_factor: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    to: usize,
+    cap: i64,
+    cost: i64,
+}
+
+/// Minimal residual-graph max-flow / min-cost-flow engine. Edges are stored
+/// as a flat list with paired forward/reverse entries so augmenting a path
+/// only needs the edge index, the repo's usual preference for simple,
+/// explicit data structures over a graph crate dependency.
+struct FlowGraph {
+    edges: Vec<Edge>,
+    adj: Vec<Vec<usize>>, // node -> edge indices
+}
+
+impl FlowGraph {
+    fn new(n: usize) -> Self {
+        Self { edges: Vec::new(), adj: vec![Vec::new(); n] }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) {
+        let forward = self.edges.len();
+        self.edges.push(Edge { to, cap, cost });
+        self.adj[from].push(forward);
+
+        let backward = self.edges.len();
+        self.edges.push(Edge { to: from, cap: 0, cost: -cost });
+        self.adj[to].push(backward);
+    }
+
+    /// Plain BFS augmenting-path max-flow (Edmonds-Karp), ignoring cost.
+    /// Used for phase 1 feasibility: does a max assignment even exist under
+    /// the current zone constraints?
+    fn max_flow(&mut self, s: usize, t: usize) -> i64 {
+        let mut total = 0i64;
+        loop {
+            let mut parent_edge = vec![None; self.adj.len()];
+            let mut visited = vec![false; self.adj.len()];
+            visited[s] = true;
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+
+            while let Some(u) = queue.pop_front() {
+                if u == t { break; }
+                for &edge_idx in &self.adj[u] {
+                    let edge = self.edges[edge_idx];
+                    if edge.cap > 0 && !visited[edge.to] {
+                        visited[edge.to] = true;
+                        parent_edge[edge.to] = Some(edge_idx);
+                        queue.push_back(edge.to);
+                    }
+                }
+            }
+
+            if !visited[t] { break; }
+
+            // Find bottleneck capacity along the discovered path.
+            let mut bottleneck = i64::MAX;
+            let mut v = t;
+            while v != s {
+                let edge_idx = parent_edge[v].expect("path exists to t");
+                bottleneck = bottleneck.min(self.edges[edge_idx].cap);
+                v = self.edges[edge_idx ^ 1].to;
+            }
+
+            let mut v = t;
+            while v != s {
+                let edge_idx = parent_edge[v].expect("path exists to t");
+                self.edges[edge_idx].cap -= bottleneck;
+                self.edges[edge_idx ^ 1].cap += bottleneck;
+                v = self.edges[edge_idx ^ 1].to;
+            }
+
+            total += bottleneck;
+        }
+        total
+    }
+
+    /// Successive shortest augmenting paths by cost (Bellman-Ford/SPFA,
+    /// which handles the negative-cost reverse edges left behind by prior
+    /// augmentations), up to `target_flow` units. Used for phase 2: among
+    /// all assignments achieving the phase-1 max flow, find the one with
+    /// least total movement cost.
+    fn min_cost_flow(&mut self, s: usize, t: usize, target_flow: i64) -> i64 {
+        let mut total_cost = 0i64;
+        let mut remaining = target_flow;
+
+        while remaining > 0 {
+            let n = self.adj.len();
+            let mut dist = vec![i64::MAX; n];
+            let mut parent_edge = vec![None; n];
+            let mut in_queue = vec![false; n];
+            dist[s] = 0;
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            in_queue[s] = true;
+
+            while let Some(u) = queue.pop_front() {
+                in_queue[u] = false;
+                for &edge_idx in &self.adj[u] {
+                    let edge = self.edges[edge_idx];
+                    if edge.cap > 0 && dist[u] != i64::MAX && dist[u] + edge.cost < dist[edge.to] {
+                        dist[edge.to] = dist[u] + edge.cost;
+                        parent_edge[edge.to] = Some(edge_idx);
+                        if !in_queue[edge.to] {
+                            queue.push_back(edge.to);
+                            in_queue[edge.to] = true;
+                        }
+                    }
+                }
+            }
+
+            if dist[t] == i64::MAX {
+                break; // no more augmenting paths; target_flow was infeasible (shouldn't happen post phase 1)
+            }
+
+            let mut bottleneck = remaining;
+            let mut v = t;
+            while v != s {
+                let edge_idx = parent_edge[v].expect("path exists to t");
+                bottleneck = bottleneck.min(self.edges[edge_idx].cap);
+                v = self.edges[edge_idx ^ 1].to;
+            }
+
+            let mut v = t;
+            while v != s {
+                let edge_idx = parent_edge[v].expect("path exists to t");
+                self.edges[edge_idx].cap -= bottleneck;
+                self.edges[edge_idx ^ 1].cap += bottleneck;
+                v = self.edges[edge_idx ^ 1].to;
+            }
+
+            total_cost += bottleneck * dist[t];
+            remaining -= bottleneck;
+        }
+
+        total_cost
+    }
+}
+
+/// Computes shard -> Vec<node-id> replica assignments.
+pub struct ShardPlacer;
+
+impl ShardPlacer {
+    pub fn place(
+        shards: &[ShardReplicaRequest],
+        nodes: &[NodeInfo],
+        previous_assignment: &HashMap<String, Vec<String>>,
+    ) -> HashMap<String, Vec<String>> {
+        if shards.is_empty() || nodes.is_empty() {
+            return HashMap::new();
+        }
+
+        let zones: Vec<String> = {
+            let mut seen = HashSet::new();
+            nodes.iter().map(|n| n.zone.clone()).filter(|z| seen.insert(z.clone())).collect()
+        };
+
+        let total_capacity: u64 = nodes.iter().map(|n| n.capacity).sum::<u64>().max(1);
+        let total_demand: i64 = shards.iter().map(|s| s.replicas as i64).sum();
+
+        // Node slot capacity proportional to its share of total capacity,
+        // scaled against total replica demand so the graph can actually
+        // carry `total_demand` units when capacity allows it.
+        let node_slots: Vec<i64> = nodes
+            .iter()
+            .map(|n| (((n.capacity as u128) * (total_demand as u128) / (total_capacity as u128)) as i64).max(1))
+            .collect();
+
+        let (flow, assignment) = Self::try_assign(shards, nodes, &zones, &node_slots, previous_assignment, true);
+        if flow >= total_demand {
+            return assignment;
+        }
+
+        // Distinct-zone constraint couldn't be fully satisfied (e.g. fewer
+        // zones than replicas needed) — relax it and accept same-zone
+        // replicas rather than silently under-replicating.
+        Self::try_assign(shards, nodes, &zones, &node_slots, previous_assignment, false).1
+    }
+
+    fn try_assign(
+        shards: &[ShardReplicaRequest],
+        nodes: &[NodeInfo],
+        zones: &[String],
+        node_slots: &[i64],
+        previous_assignment: &HashMap<String, Vec<String>>,
+        distinct_zones: bool,
+    ) -> (i64, HashMap<String, Vec<String>>) {
+        // Vertex layout: 0 = source, then one vertex per shard, then (if
+        // distinct_zones) one per (shard, zone) pair, then one per node,
+        // then sink.
+        let source = 0usize;
+        let shard_base = 1usize;
+        let shard_zone_base = shard_base + shards.len();
+        let shard_zone_count = if distinct_zones { shards.len() * zones.len() } else { 0 };
+        let node_base = shard_zone_base + shard_zone_count;
+        let sink = node_base + nodes.len();
+        let n_vertices = sink + 1;
+
+        let mut graph = FlowGraph::new(n_vertices);
+
+        for (si, shard) in shards.iter().enumerate() {
+            graph.add_edge(source, shard_base + si, shard.replicas as i64, 0);
+
+            if distinct_zones {
+                for (zi, zone) in zones.iter().enumerate() {
+                    let shard_zone_vertex = shard_zone_base + si * zones.len() + zi;
+                    graph.add_edge(shard_base + si, shard_zone_vertex, 1, 0);
+                    for (ni, node) in nodes.iter().enumerate() {
+                        if &node.zone == zone {
+                            let prior_here = previous_assignment.get(&shard.shard_id).map(|v| v.iter().any(|id| id == &node.id)).unwrap_or(false);
+                            let cost = if prior_here { 0 } else { 1 };
+                            graph.add_edge(shard_zone_vertex, node_base + ni, 1, cost);
+                        }
+                    }
+                }
+            } else {
+                for (ni, node) in nodes.iter().enumerate() {
+                    let prior_here = previous_assignment.get(&shard.shard_id).map(|v| v.iter().any(|id| id == &node.id)).unwrap_or(false);
+                    let cost = if prior_here { 0 } else { 1 };
+                    graph.add_edge(shard_base + si, node_base + ni, 1, cost);
+                }
+            }
+        }
+
+        for (ni, &slots) in node_slots.iter().enumerate() {
+            graph.add_edge(node_base + ni, sink, slots, 0);
+        }
+
+        let max_flow = graph.max_flow(source, sink);
+
+        // Rebuild a fresh graph for the min-cost pass: the max_flow() run
+        // above consumed the residual capacities, so we need a clean graph
+        // with the same topology to find the minimum-cost flow of that value.
+        let mut cost_graph = FlowGraph::new(n_vertices);
+        for (si, shard) in shards.iter().enumerate() {
+            cost_graph.add_edge(source, shard_base + si, shard.replicas as i64, 0);
+            if distinct_zones {
+                for (zi, zone) in zones.iter().enumerate() {
+                    let shard_zone_vertex = shard_zone_base + si * zones.len() + zi;
+                    cost_graph.add_edge(shard_base + si, shard_zone_vertex, 1, 0);
+                    for (ni, node) in nodes.iter().enumerate() {
+                        if &node.zone == zone {
+                            let prior_here = previous_assignment.get(&shard.shard_id).map(|v| v.iter().any(|id| id == &node.id)).unwrap_or(false);
+                            let cost = if prior_here { 0 } else { 1 };
+                            cost_graph.add_edge(shard_zone_vertex, node_base + ni, 1, cost);
+                        }
+                    }
+                }
+            } else {
+                for (ni, node) in nodes.iter().enumerate() {
+                    let prior_here = previous_assignment.get(&shard.shard_id).map(|v| v.iter().any(|id| id == &node.id)).unwrap_or(false);
+                    let cost = if prior_here { 0 } else { 1 };
+                    cost_graph.add_edge(shard_base + si, node_base + ni, 1, cost);
+                }
+            }
+        }
+        for (ni, &slots) in node_slots.iter().enumerate() {
+            cost_graph.add_edge(node_base + ni, sink, slots, 0);
+        }
+
+        cost_graph.min_cost_flow(source, sink, max_flow);
+
+        // Read back the assignment from the saturated forward edges whose
+        // destination is a node vertex.
+        let mut assignment: HashMap<String, Vec<String>> = HashMap::new();
+        for (si, shard) in shards.iter().enumerate() {
+            let mut assigned = Vec::new();
+            if distinct_zones {
+                for (zi, _zone) in zones.iter().enumerate() {
+                    let shard_zone_vertex = shard_zone_base + si * zones.len() + zi;
+                    for &edge_idx in &cost_graph.adj[shard_zone_vertex] {
+                        let edge = cost_graph.edges[edge_idx];
+                        if edge.to >= node_base && edge.to < sink && edge.cap == 0 {
+                            assigned.push(nodes[edge.to - node_base].id.clone());
+                        }
+                    }
+                }
+            } else {
+                for &edge_idx in &cost_graph.adj[shard_base + si] {
+                    let edge = cost_graph.edges[edge_idx];
+                    if edge.to >= node_base && edge.to < sink && edge.cap == 0 {
+                        assigned.push(nodes[edge.to - node_base].id.clone());
+                    }
+                }
+            }
+            assignment.insert(shard.shard_id.clone(), assigned);
+        }
+
+        (max_flow, assignment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, capacity: u64, zone: &str) -> NodeInfo {
+        NodeInfo { id: id.into(), capacity, zone: zone.into(), tags: vec![] }
+    }
+
+    #[test]
+    fn places_replicas_in_distinct_zones_when_possible() {
+        let shards = vec![ShardReplicaRequest { shard_id: "shard-a".into(), replicas: 2 }];
+        let nodes = vec![
+            node("n1", 10, "us-east"),
+            node("n2", 10, "us-west"),
+            node("n3", 10, "us-east"),
+        ];
+        let assignment = ShardPlacer::place(&shards, &nodes, &HashMap::new());
+        let placed = &assignment["shard-a"];
+        assert_eq!(placed.len(), 2);
+
+        let zone_of = |id: &str| nodes.iter().find(|n| n.id == id).unwrap().zone.clone();
+        let zones_used: HashSet<_> = placed.iter().map(|id| zone_of(id)).collect();
+        assert_eq!(zones_used.len(), 2, "replicas should land in distinct zones");
+    }
+
+    #[test]
+    fn keeps_existing_placement_when_it_still_satisfies_constraints() {
+        let shards = vec![ShardReplicaRequest { shard_id: "shard-a".into(), replicas: 1 }];
+        let nodes = vec![node("n1", 10, "us-east"), node("n2", 10, "us-west")];
+        let mut previous = HashMap::new();
+        previous.insert("shard-a".to_string(), vec!["n2".to_string()]);
+
+        let assignment = ShardPlacer::place(&shards, &nodes, &previous);
+        assert_eq!(assignment["shard-a"], vec!["n2".to_string()]);
+    }
+
+    #[test]
+    fn relaxes_distinct_zone_when_zones_are_scarce() {
+        let shards = vec![ShardReplicaRequest { shard_id: "shard-a".into(), replicas: 2 }];
+        let nodes = vec![node("n1", 10, "only-zone"), node("n2", 10, "only-zone")];
+        let assignment = ShardPlacer::place(&shards, &nodes, &HashMap::new());
+        assert_eq!(assignment["shard-a"].len(), 2, "should still place both replicas despite only one zone");
+    }
+}
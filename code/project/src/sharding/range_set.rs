@@ -0,0 +1,141 @@
+// src/sharding/range_set.rs
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// A sorted set of non-overlapping half-open `[start, end)` index ranges.
+/// Used to track which vector indices of a shard migration have been
+/// acknowledged by the target shard, so a crashed/restarted migration can
+/// resume from `pending_ranges()` instead of re-sending everything.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RangeSet {
+    ranges: Vec<Range<usize>>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Insert `range`, coalescing with any adjacent or overlapping ranges.
+    pub fn add(&mut self, range: Range<usize>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let mut merged = range;
+        let mut result = Vec::with_capacity(self.ranges.len() + 1);
+        let mut inserted = false;
+
+        for existing in self.ranges.drain(..) {
+            if existing.end < merged.start {
+                result.push(existing);
+            } else if merged.end < existing.start {
+                if !inserted {
+                    result.push(merged.clone());
+                    inserted = true;
+                }
+                result.push(existing);
+            } else {
+                // Overlapping or adjacent: coalesce into `merged`.
+                merged.start = merged.start.min(existing.start);
+                merged.end = merged.end.max(existing.end);
+            }
+        }
+        if !inserted {
+            result.push(merged);
+        }
+
+        self.ranges = result;
+    }
+
+    /// Remove `range` from the set, splitting any range it cuts through.
+    pub fn subtract(&mut self, range: Range<usize>) {
+        if range.start >= range.end {
+            return;
+        }
+        let mut result = Vec::with_capacity(self.ranges.len() + 1);
+        for existing in self.ranges.drain(..) {
+            if existing.end <= range.start || existing.start >= range.end {
+                result.push(existing);
+                continue;
+            }
+            if existing.start < range.start {
+                result.push(existing.start..range.start);
+            }
+            if existing.end > range.end {
+                result.push(range.end..existing.end);
+            }
+        }
+        self.ranges = result;
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.ranges.iter().any(|r| r.contains(&idx))
+    }
+
+    /// Total count of covered indices across all ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges within `universe` not covered by this set, i.e. the work
+    /// still left to do.
+    pub fn complement(&self, universe: Range<usize>) -> Vec<Range<usize>> {
+        let mut gaps = Vec::new();
+        let mut cursor = universe.start;
+        for r in &self.ranges {
+            let start = r.start.max(universe.start);
+            let end = r.end.min(universe.end);
+            if start >= end {
+                continue;
+            }
+            if cursor < start {
+                gaps.push(cursor..start);
+            }
+            cursor = cursor.max(end);
+        }
+        if cursor < universe.end {
+            gaps.push(cursor..universe.end);
+        }
+        gaps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_coalesces_overlapping_and_adjacent_ranges() {
+        let mut set = RangeSet::new();
+        set.add(0..5);
+        set.add(5..10);
+        set.add(20..25);
+        set.add(8..22);
+        assert_eq!(set.ranges, vec![0..25]);
+        assert_eq!(set.len(), 25);
+    }
+
+    #[test]
+    fn subtract_splits_ranges() {
+        let mut set = RangeSet::new();
+        set.add(0..10);
+        set.subtract(3..6);
+        assert_eq!(set.ranges, vec![0..3, 6..10]);
+        assert!(!set.contains(4));
+        assert!(set.contains(7));
+    }
+
+    #[test]
+    fn complement_yields_pending_ranges() {
+        let mut set = RangeSet::new();
+        set.add(0..3);
+        set.add(7..10);
+        assert_eq!(set.complement(0..10), vec![3..7]);
+        assert_eq!(set.complement(0..3), Vec::<Range<usize>>::new());
+    }
+}
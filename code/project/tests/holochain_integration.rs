@@ -21,6 +21,9 @@ async fn test_model_update_lifecycle() -> Result<(), Box<dyn std::error::Error>>
         sync_timeout_ms: 5000,
         merge_interval_ms: 10000,
         merge_threshold: 0.01,
+        sync_work_budget: 32,
+        replicas_per_shard: 3,
+        nodes: vec![],
     };
     
     let shard_manager = Arc::new(ShardManager::new(shard_config, Arc::clone(&metrics)));
@@ -49,6 +52,7 @@ async fn test_model_update_lifecycle() -> Result<(), Box<dyn std::error::Error>>
                 accuracy: 0.95,
                 samples_count: 1000,
             },
+            round_proof: None,
             agent_id: agent_pubkey,
         },
     };